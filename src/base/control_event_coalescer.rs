@@ -0,0 +1,151 @@
+use crate::{AbstractTimestamp, ControlEvent, ControlValue, DiscreteIncrement, UnitIncrement};
+use std::time::Duration;
+
+/// Merges bursts of control events coming from one source within a time window.
+///
+/// - Absolute values: Only the latest one survives.
+/// - Relative increments: They are summed up, so no movement gets lost.
+///
+/// Events of different kinds are never merged. Use one coalescer per source.
+#[derive(Clone, Debug)]
+pub struct ControlEventCoalescer<S: AbstractTimestamp> {
+    window: Duration,
+    window_start: Option<S>,
+    pending_event: Option<ControlEvent<ControlValue, S>>,
+}
+
+impl<S: AbstractTimestamp> ControlEventCoalescer<S> {
+    /// Creates a coalescer with the given time window. A zero window disables coalescing.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            window_start: None,
+            pending_event: None,
+        }
+    }
+
+    /// Feeds the given event into the coalescer.
+    ///
+    /// Returns an event which is ready for processing, if there is one. That's the case if the
+    /// given event opens a new window or can't be merged with the pending one.
+    pub fn push(
+        &mut self,
+        event: ControlEvent<ControlValue, S>,
+    ) -> Option<ControlEvent<ControlValue, S>> {
+        if self.window.is_zero() {
+            return Some(event);
+        }
+        let window_is_open = self
+            .window_start
+            .is_some_and(|start| event.timestamp() - start < self.window);
+        if window_is_open {
+            let Some(pending_event) = self.pending_event else {
+                // Increments in this window have cancelled each other out
+                self.pending_event = Some(event);
+                return None;
+            };
+            match merge(pending_event.payload(), event.payload()) {
+                Merge::Merged(value) => {
+                    self.pending_event = value.map(|v| event.with_payload(v));
+                    None
+                }
+                Merge::Incompatible => {
+                    self.start_window(event);
+                    Some(pending_event)
+                }
+            }
+        } else {
+            let ready_event = self.pending_event.take();
+            self.start_window(event);
+            ready_event
+        }
+    }
+
+    /// Returns the pending event if its window has elapsed.
+    ///
+    /// Should be called regularly.
+    pub fn poll(&mut self, now: S) -> Option<ControlEvent<ControlValue, S>> {
+        let start = self.window_start?;
+        if now - start < self.window {
+            return None;
+        }
+        self.window_start = None;
+        self.pending_event.take()
+    }
+
+    /// Returns the pending event immediately, no matter if its window has elapsed.
+    pub fn flush(&mut self) -> Option<ControlEvent<ControlValue, S>> {
+        self.window_start = None;
+        self.pending_event.take()
+    }
+
+    fn start_window(&mut self, event: ControlEvent<ControlValue, S>) {
+        self.window_start = Some(event.timestamp());
+        self.pending_event = Some(event);
+    }
+}
+
+enum Merge {
+    /// `None` means that increments cancelled each other out.
+    Merged(Option<ControlValue>),
+    Incompatible,
+}
+
+fn merge(older: ControlValue, newer: ControlValue) -> Merge {
+    use ControlValue::*;
+    match (older, newer) {
+        (AbsoluteContinuous(_), AbsoluteContinuous(_))
+        | (AbsoluteDiscrete(_), AbsoluteDiscrete(_)) => Merge::Merged(Some(newer)),
+        (RelativeDiscrete(a), RelativeDiscrete(b)) => Merge::Merged(
+            DiscreteIncrement::new_checked(a.get().saturating_add(b.get())).map(RelativeDiscrete),
+        ),
+        (RelativeContinuous(a), RelativeContinuous(b)) => Merge::Merged(
+            UnitIncrement::new_clamped_checked(a.get() + b.get()).map(RelativeContinuous),
+        ),
+        _ => Merge::Incompatible,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UnitValue;
+    use std::time::Instant;
+
+    #[test]
+    fn coalesce() {
+        // Given
+        let mut coalescer = ControlEventCoalescer::new(Duration::from_millis(10));
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let abs = |v, millis| {
+            ControlEvent::new(
+                ControlValue::AbsoluteContinuous(UnitValue::new(v)),
+                at(millis),
+            )
+        };
+        let rel = |i, millis| {
+            ControlEvent::new(
+                ControlValue::RelativeDiscrete(DiscreteIncrement::new(i)),
+                at(millis),
+            )
+        };
+        // When
+        // Then
+        // Absolute values: Latest wins
+        assert_eq!(coalescer.push(abs(0.1, 0)), None);
+        assert_eq!(coalescer.push(abs(0.2, 3)), None);
+        assert_eq!(coalescer.push(abs(0.3, 6)), None);
+        assert_eq!(coalescer.poll(at(8)), None);
+        assert_eq!(coalescer.poll(at(10)), Some(abs(0.3, 6)));
+        assert_eq!(coalescer.poll(at(20)), None);
+        // Relative values: Summed up
+        assert_eq!(coalescer.push(rel(1, 30)), None);
+        assert_eq!(coalescer.push(rel(2, 32)), None);
+        // Different kind: Emit pending one
+        assert_eq!(coalescer.push(abs(0.5, 34)), Some(rel(3, 32)));
+        // New window: Emit pending one
+        assert_eq!(coalescer.push(abs(0.6, 50)), Some(abs(0.5, 34)));
+        assert_eq!(coalescer.flush(), Some(abs(0.6, 50)));
+    }
+}
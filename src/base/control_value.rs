@@ -18,6 +18,77 @@ use std::time::{Duration, Instant};
 /// destination thread.  
 pub trait AbstractTimestamp: Copy + Sub<Output = Duration> + std::fmt::Debug {
     fn duration(&self) -> Duration;
+
+    /// Returns this timestamp shifted by the given offset.
+    ///
+    /// Timestamp types that can't express a shift (e.g. [`NoopTimestamp`]) return themselves.
+    fn shift(&self, offset: TimestampOffset) -> Self {
+        let _ = offset;
+        *self
+    }
+}
+
+/// A signed offset that can be applied to timestamps, e.g. to compensate a known input latency.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct TimestampOffset {
+    duration: Duration,
+    is_negative: bool,
+}
+
+impl TimestampOffset {
+    pub const ZERO: Self = Self {
+        duration: Duration::ZERO,
+        is_negative: false,
+    };
+
+    /// Creates an offset that moves timestamps into the future.
+    pub const fn later(duration: Duration) -> Self {
+        Self {
+            duration,
+            is_negative: false,
+        }
+    }
+
+    /// Creates an offset that moves timestamps into the past.
+    ///
+    /// This is what you want for latency compensation: The event actually happened a bit earlier
+    /// than we noticed it.
+    pub const fn earlier(duration: Duration) -> Self {
+        Self {
+            duration,
+            is_negative: true,
+        }
+    }
+
+    pub fn from_millis(millis: i64) -> Self {
+        let duration = Duration::from_millis(millis.unsigned_abs());
+        if millis < 0 {
+            Self::earlier(duration)
+        } else {
+            Self::later(duration)
+        }
+    }
+
+    pub fn as_millis(&self) -> i64 {
+        let millis = self.duration.as_millis() as i64;
+        if self.is_negative {
+            -millis
+        } else {
+            millis
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.is_negative
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.duration.is_zero()
+    }
 }
 
 /// A timestamp that does nothing and takes no space.
@@ -43,6 +114,15 @@ impl AbstractTimestamp for Instant {
         static INSTANT: LazyLock<Instant> = LazyLock::new(Instant::now);
         self.saturating_duration_since(*INSTANT)
     }
+
+    fn shift(&self, offset: TimestampOffset) -> Self {
+        let shifted = if offset.is_negative() {
+            self.checked_sub(offset.duration())
+        } else {
+            self.checked_add(offset.duration())
+        };
+        shifted.unwrap_or(*self)
+    }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -76,6 +156,11 @@ impl<P, T: AbstractTimestamp> ControlEvent<P, T> {
         self.payload
     }
 
+    /// Returns a reference to the payload of this event.
+    pub fn payload_ref(&self) -> &P {
+        &self.payload
+    }
+
     /// Consumes this event and returns the payload.
     pub fn into_payload(self) -> P {
         self.payload
@@ -89,6 +174,14 @@ impl<P, T: AbstractTimestamp> ControlEvent<P, T> {
         }
     }
 
+    /// Returns this event with its timestamp shifted by the given offset.
+    pub fn shift_timestamp(self, offset: TimestampOffset) -> Self {
+        ControlEvent {
+            timestamp: self.timestamp.shift(offset),
+            payload: self.payload,
+        }
+    }
+
     /// Transforms the payload of this event.
     pub fn map_payload<O>(self, map: impl FnOnce(P) -> O) -> ControlEvent<O, T> {
         let transformed_payload = map(self.payload);
@@ -683,6 +776,21 @@ mod tests {
     use crate::BASE_EPSILON;
     use approx::*;
 
+    #[test]
+    fn timestamp_offset() {
+        // Given
+        let now = Instant::now();
+        let event = ControlEvent::new(ControlValue::absolute_continuous(0.5), now);
+        // When
+        let earlier = event.shift_timestamp(TimestampOffset::from_millis(-20));
+        let later = event.shift_timestamp(TimestampOffset::from_millis(30));
+        // Then
+        assert_eq!(now - earlier.timestamp(), Duration::from_millis(20));
+        assert_eq!(later.timestamp() - now, Duration::from_millis(30));
+        assert_eq!(TimestampOffset::from_millis(-20).as_millis(), -20);
+        assert!(TimestampOffset::default().is_zero());
+    }
+
     #[test]
     fn normalize_comparison() {
         // Given
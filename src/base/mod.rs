@@ -4,6 +4,9 @@ mod regex_util;
 mod control_value;
 pub use control_value::*;
 
+mod control_event_coalescer;
+pub use control_event_coalescer::*;
+
 mod feedback_value;
 pub use feedback_value::*;
 
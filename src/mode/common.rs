@@ -158,6 +158,81 @@ impl EncoderUsage {
     }
 }
 
+/// Which increments are affected by "Reverse" in relative control.
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Hash,
+    Debug,
+    Default,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize,
+    Deserialize,
+)]
+#[repr(usize)]
+pub enum ReverseDirection {
+    #[default]
+    #[serde(rename = "both")]
+    #[display(fmt = "Increments & decrements")]
+    Both,
+    #[serde(rename = "increment-only")]
+    #[display(fmt = "Increments only")]
+    IncrementOnly,
+    #[serde(rename = "decrement-only")]
+    #[display(fmt = "Decrements only")]
+    DecrementOnly,
+}
+
+impl ReverseDirection {
+    pub fn applies_to(&self, is_increment: bool) -> bool {
+        match self {
+            ReverseDirection::Both => true,
+            ReverseDirection::IncrementOnly => is_increment,
+            ReverseDirection::DecrementOnly => !is_increment,
+        }
+    }
+}
+
+/// Decides when performance control takes a new anchor, that is, the last target value which was
+/// not set by performance control.
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Hash,
+    Debug,
+    Default,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize,
+    Deserialize,
+)]
+#[repr(usize)]
+pub enum PerformanceAnchorStrategy {
+    /// Always uses the anchor passed by the caller.
+    #[default]
+    #[serde(rename = "caller")]
+    #[display(fmt = "Always use latest value")]
+    Caller,
+    /// Keeps the anchor until the source returns to its neutral position (zero or, for bipolar
+    /// performance control, center).
+    #[serde(rename = "neutral")]
+    #[display(fmt = "Re-anchor when returning to neutral")]
+    ReanchorAtNeutral,
+    /// Keeps the anchor until no control value arrived for the configured timeout.
+    #[serde(rename = "timeout")]
+    #[display(fmt = "Re-anchor after timeout")]
+    ReanchorAfterTimeout,
+}
+
 #[derive(
     Copy,
     Clone,
@@ -189,6 +264,23 @@ pub enum FireMode {
     #[serde(rename = "double")]
     #[display(fmt = "Fire on double press")]
     OnDoublePress,
+    /// Fires after a configurable number of taps within the multi-press span.
+    #[serde(rename = "multi")]
+    #[display(fmt = "Fire on multi press")]
+    OnMultiPress,
+    /// Continuously fires a value proportional to how long the button has been held.
+    #[serde(rename = "duration")]
+    #[display(fmt = "Fire press duration as value")]
+    PressDurationAsValue,
+    /// Keyboard-style: Fires immediately, then repeatedly after an initial delay while held.
+    #[serde(rename = "typematic")]
+    #[display(fmt = "Fire immediately, repeat after delay (typematic)")]
+    Typematic,
+    /// Fires one value on a short press (on release) and another one on a long press (as soon
+    /// as the press duration minimum is reached).
+    #[serde(rename = "short-or-long")]
+    #[display(fmt = "Fire different values on short or long press")]
+    ShortOrLongPress,
 }
 
 impl Default for FireMode {
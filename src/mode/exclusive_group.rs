@@ -0,0 +1,90 @@
+/// Coordinates several modes which should behave like a bank of radio buttons.
+///
+/// The group doesn't own the modes. It only keeps track of which member turned its target on most
+/// recently. Whenever a member turns its target on, the group tells which other members should
+/// switch their targets off (see [`crate::Mode::exclusive_off_value`]).
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ExclusiveGroup<K> {
+    members: Vec<K>,
+    active_member: Option<K>,
+}
+
+impl<K> Default for ExclusiveGroup<K> {
+    fn default() -> Self {
+        Self {
+            members: vec![],
+            active_member: None,
+        }
+    }
+}
+
+impl<K: Copy + Eq> ExclusiveGroup<K> {
+    pub fn new(members: impl IntoIterator<Item = K>) -> Self {
+        let mut group = Self::default();
+        for member in members {
+            group.add_member(member);
+        }
+        group
+    }
+
+    pub fn add_member(&mut self, member: K) {
+        if !self.members.contains(&member) {
+            self.members.push(member);
+        }
+    }
+
+    pub fn remove_member(&mut self, member: K) {
+        self.members.retain(|m| *m != member);
+        if self.active_member == Some(member) {
+            self.active_member = None;
+        }
+    }
+
+    pub fn members(&self) -> &[K] {
+        &self.members
+    }
+
+    /// Returns the member which turned its target on most recently (if it hasn't been turned off
+    /// since then).
+    pub fn active_member(&self) -> Option<K> {
+        self.active_member
+    }
+
+    /// Lets the group know that the given member switched its target on or off.
+    ///
+    /// Returns the members which should now emit an off value.
+    pub fn notify(&mut self, member: K, is_on: bool) -> Vec<K> {
+        if !is_on {
+            if self.active_member == Some(member) {
+                self.active_member = None;
+            }
+            return vec![];
+        }
+        self.active_member = Some(member);
+        self.members
+            .iter()
+            .copied()
+            .filter(|m| *m != member)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn radio_buttons() {
+        // Given
+        let mut group = ExclusiveGroup::new([1, 2, 3]);
+        // When
+        // Then
+        assert_eq!(group.notify(2, true), vec![1, 3]);
+        assert_eq!(group.active_member(), Some(2));
+        assert_eq!(group.notify(3, true), vec![1, 2]);
+        assert_eq!(group.notify(2, false), vec![]);
+        assert_eq!(group.active_member(), Some(3));
+        assert_eq!(group.notify(3, false), vec![]);
+        assert_eq!(group.active_member(), None);
+    }
+}
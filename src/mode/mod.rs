@@ -4,16 +4,26 @@ mod target;
 pub use target::*;
 mod mode_struct;
 pub use mode_struct::*;
+mod mode_settings_builder;
+pub use mode_settings_builder::*;
 mod mode_applicability;
 pub use mode_applicability::*;
 mod transformation;
 pub use transformation::*;
+mod exclusive_group;
+pub use exclusive_group::*;
 mod press_duration_processor;
 pub use press_duration_processor::*;
 mod value_sequence;
 pub use value_sequence::*;
 mod mode_context;
 pub use mode_context::*;
+mod response_curve;
+pub use response_curve::*;
+mod velocity_curve;
+pub use velocity_curve::*;
+mod textual_feedback_template;
+pub use textual_feedback_template::*;
 
 #[cfg(test)]
 mod test_util;
@@ -1,9 +1,9 @@
-use crate::AbsoluteMode::PerformanceControl;
 use crate::{AbsoluteMode, FireMode, GroupInteraction, OutOfRangeBehavior};
 use derive_more::Display;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
+use strum::{EnumIter, IntoEnumIterator};
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Display, TryFromPrimitive, IntoPrimitive)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Display, EnumIter, TryFromPrimitive, IntoPrimitive)]
 #[repr(isize)]
 pub enum DetailedSourceCharacter {
     /// Feature-wise a superset of `MomentaryOnOffButton` and `PressOnlyButton`.
@@ -242,8 +242,13 @@ pub fn check_mode_applicability(
                                 "If enabled, decreases the target value on press instead of increasing it.",
                             ),
                             crate::AbsoluteMode::ToggleButton => MakesNoSenseUseDefault,
+                            crate::AbsoluteMode::MultiStateToggleButton => MakesNoSenseUseDefault,
+                            crate::AbsoluteMode::HybridToggleButton => MakesNoSenseUseDefault,
+                            crate::AbsoluteMode::SampleAndHold => MakesNoSenseUseDefault,
+                            crate::AbsoluteMode::AbToggleButton => MakesNoSenseUseDefault,
                             crate::AbsoluteMode::MakeRelative => MakesNoSenseUseDefault,
                             crate::AbsoluteMode::PerformanceControl => MakesNoSenseUseDefault,
+                            crate::AbsoluteMode::BipolarPerformanceControl => MakesNoSenseUseDefault,
                         }
                     }
                     RangeControl | Relative => {
@@ -440,10 +445,15 @@ pub fn check_mode_applicability(
             } else {
                 use crate::AbsoluteMode::*;
                 match input.absolute_mode {
+                    MultiStateToggleButton => {
+                        MakesSense("Defines the states to cycle through on each button press. Example: 0, 50, 100 %")
+                    }
                     Normal | IncrementalButton | MakeRelative => {
                         MakesSense("Allows you to step through a sequence of comma-separated user-defined target values and value ranges. When using relative control, duplicate values and direction changes are ignored. Example: 25 - 50 (2), 75, 50, 100 %")
                     }
-                    ToggleButton | PerformanceControl => {
+                    ToggleButton | HybridToggleButton | AbToggleButton | PerformanceControl
+                    | SampleAndHold
+                    | BipolarPerformanceControl => {
                         MakesNoSenseUseDefault
                     }
                 }
@@ -583,6 +593,11 @@ pub fn check_mode_applicability(
                             MakesSense(
                                 "If enabled, jumps from max target value to min target value (or opposite if reverse enabled). Was called \"Rotate\" before.",
                             )
+                        } else if input.absolute_mode == crate::AbsoluteMode::MultiStateToggleButton
+                        {
+                            MakesSense(
+                                "If enabled, jumps from the last sequence entry back to the first one.",
+                            )
                         } else {
                             HasNoEffect
                         }
@@ -654,6 +669,36 @@ pub fn check_mode_applicability(
                 OnDoublePress => {
                     MakesSense("Reacts to double button presses only (like a mouse double-click).")
                 }
+                OnMultiPress => MakesSense(
+                    "Reacts to the specified number of quick button presses only. Fewer presses are ignored.",
+                ),
+                PressDurationAsValue => {
+                    if input.source_character == DetailedSourceCharacter::Trigger {
+                        MakesNoSenseParentTakesCareOfDefault
+                    } else {
+                        MakesSense(
+                            "While the button is held, continuously fires a value proportional to the press duration (0% at min, 100% at max).",
+                        )
+                    }
+                }
+                Typematic => {
+                    if input.source_character == DetailedSourceCharacter::Trigger {
+                        MakesNoSenseParentTakesCareOfDefault
+                    } else {
+                        MakesSense(
+                            "Fires immediately when button pressed. If still held after the specified timeout, keeps firing with the specified rate until button released (like a computer keyboard).",
+                        )
+                    }
+                }
+                ShortOrLongPress => {
+                    if input.source_character == DetailedSourceCharacter::Trigger {
+                        MakesNoSenseParentTakesCareOfDefault
+                    } else {
+                        MakesSense(
+                            "Fires the short-press value when button released before the specified timeout, otherwise fires the long-press value as soon as the timeout is reached.",
+                        )
+                    }
+                }
             }
         }
         ButtonFilter => {
@@ -666,13 +711,16 @@ pub fn check_mode_applicability(
                         if input.absolute_mode == crate::AbsoluteMode::Normal =>
                     {
                         match input.fire_mode {
-                            crate::FireMode::Normal | crate::FireMode::AfterTimeout | crate::FireMode::AfterTimeoutKeepFiring => {
+                            crate::FireMode::Normal | crate::FireMode::AfterTimeout | crate::FireMode::AfterTimeoutKeepFiring | crate::FireMode::Typematic => {
                                 MakesSense(
                                     "Defines whether to process button presses only, releases only or both.",
                                 )
                             }
                             crate::FireMode::OnSinglePress |
-                            crate::FireMode::OnDoublePress => {
+                            crate::FireMode::OnDoublePress |
+                            crate::FireMode::OnMultiPress |
+                            crate::FireMode::PressDurationAsValue |
+                            crate::FireMode::ShortOrLongPress => {
                                 // In this case, we need both press and release as input for implementing the fire mode.
                                 // And the output is only press.
                                 MakesNoSenseUseDefault
@@ -742,7 +790,7 @@ pub fn check_mode_applicability(
         SpecificAbsoluteMode(m) => {
             if input.is_feedback {
                 HasNoEffect
-            } else if input.control_transformation_uses_time && m == PerformanceControl {
+            } else if input.control_transformation_uses_time && m.is_performance_control() {
                 MakesNoSenseUseDefault
             } else {
                 use crate::AbsoluteMode::*;
@@ -775,7 +823,17 @@ pub fn check_mode_applicability(
                             ToggleButton => MakesSense(
                                 "Switches the target value between its minimum and maximum on each button press.",
                             ),
-                            MakeRelative | PerformanceControl => MakesNoSenseUseDefault,
+                            MultiStateToggleButton => MakesSense(
+                                "Switches the target value to the next entry of the target value sequence on each button press.",
+                            ),
+                            HybridToggleButton => MakesSense(
+                                "Switches the target value between its minimum and maximum when tapping the button. When holding the button, switches it back on release.",
+                            ),
+                            AbToggleButton => MakesSense(
+                                "Switches the target value between two defined values on each button press.",
+                            ),
+                            MakeRelative | PerformanceControl | BipolarPerformanceControl
+                            | SampleAndHold => MakesNoSenseUseDefault,
                         }
                     }
                     RangeControl => {
@@ -796,7 +854,17 @@ pub fn check_mode_applicability(
                                     "Changes the target value starting from its last position set within REAPER."
                                 )
                             }
-                            IncrementalButton | ToggleButton => MakesNoSenseParentTakesCareOfDefault
+                            BipolarPerformanceControl => {
+                                MakesSense(
+                                    "Changes the target value starting from its last position set within REAPER. The lower half of the source range decreases it, the upper half increases it."
+                                )
+                            }
+                            SampleAndHold => {
+                                MakesSense(
+                                    "Memorizes the control value and sets the target to it only when sampling is triggered (regularly or on demand)."
+                                )
+                            }
+                            IncrementalButton | ToggleButton | MultiStateToggleButton | HybridToggleButton | AbToggleButton => MakesNoSenseParentTakesCareOfDefault
                         }
                     }
                     Relative => {
@@ -807,7 +875,7 @@ pub fn check_mode_applicability(
                                         NORMAL_ABSOLUTE_MODE_FOR_RANGE_DESC,
                                     )
                                 }
-                                MakeRelative | IncrementalButton | ToggleButton | PerformanceControl => MakesNoSenseParentTakesCareOfDefault
+                                MakeRelative | IncrementalButton | ToggleButton | MultiStateToggleButton | HybridToggleButton | AbToggleButton | PerformanceControl | BipolarPerformanceControl | SampleAndHold => MakesNoSenseParentTakesCareOfDefault
                             }
                         } else {
                             HasNoEffect
@@ -870,3 +938,257 @@ pub fn check_mode_applicability(
         }
     }
 }
+
+/// Why a mode parameter is not relevant in a particular situation.
+///
+/// Each reason names a condition which, if changed, would make the parameter relevant. Useful
+/// for explaining to users why a control is greyed out.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Display)]
+pub enum InapplicabilityReason {
+    #[display(fmt = "Only relevant for control, not for feedback")]
+    OnlyRelevantForControl,
+    #[display(fmt = "Only relevant for feedback, not for control")]
+    OnlyRelevantForFeedback,
+    #[display(fmt = "Not relevant for source character \"{_0}\"")]
+    SourceCharacter(DetailedSourceCharacter),
+    #[display(fmt = "Not relevant for virtual targets")]
+    TargetIsVirtual,
+    #[display(fmt = "Only relevant for targets which support discrete values")]
+    TargetIsNotDiscrete,
+    #[display(fmt = "Not relevant in absolute mode \"{_0}\"")]
+    AbsoluteMode(AbsoluteMode),
+    #[display(fmt = "Not relevant if \"Make absolute\" is enabled")]
+    MakeAbsoluteEnabled,
+    #[display(fmt = "Only relevant if \"Make absolute\" is enabled")]
+    MakeAbsoluteDisabled,
+    #[display(fmt = "Not relevant in fire mode \"{_0}\"")]
+    FireMode(FireMode),
+    #[display(fmt = "Not relevant for textual feedback")]
+    TextualFeedbackEnabled,
+    #[display(fmt = "Only relevant for textual feedback")]
+    TextualFeedbackDisabled,
+    #[display(fmt = "Not relevant if a target value sequence is set")]
+    TargetValueSequenceSet,
+    #[display(fmt = "Not relevant with the current control transformation")]
+    ControlTransformation,
+    #[display(fmt = "Not relevant in this combination of settings")]
+    Other,
+}
+
+/// Result of [`diagnose_mode_applicability`].
+#[derive(Copy, Clone, Debug)]
+pub struct ModeApplicabilityDiagnosis {
+    pub applicability: ModeApplicability,
+    /// Only set if the parameter is not relevant.
+    pub reason: Option<InapplicabilityReason>,
+}
+
+/// Like [`check_mode_applicability`] but additionally explains why a parameter is not relevant.
+///
+/// The reason is determined by checking which single change of the given input would make the
+/// parameter relevant.
+pub fn diagnose_mode_applicability(
+    mode_parameter: ModeParameter,
+    input: ModeApplicabilityCheckInput,
+) -> ModeApplicabilityDiagnosis {
+    let applicability = check_mode_applicability(mode_parameter, input);
+    let reason = if applicability.is_relevant() {
+        None
+    } else {
+        Some(determine_inapplicability_reason(mode_parameter, input))
+    };
+    ModeApplicabilityDiagnosis {
+        applicability,
+        reason,
+    }
+}
+
+fn determine_inapplicability_reason(
+    mode_parameter: ModeParameter,
+    input: ModeApplicabilityCheckInput,
+) -> InapplicabilityReason {
+    use InapplicabilityReason::*;
+    let becomes_relevant = |modify: &dyn Fn(&mut ModeApplicabilityCheckInput)| {
+        let mut modified_input = input;
+        modify(&mut modified_input);
+        check_mode_applicability(mode_parameter, modified_input).is_relevant()
+    };
+    if becomes_relevant(&|i| i.is_feedback = !i.is_feedback) {
+        return if input.is_feedback {
+            OnlyRelevantForControl
+        } else {
+            OnlyRelevantForFeedback
+        };
+    }
+    if DetailedSourceCharacter::iter()
+        .any(|c| c != input.source_character && becomes_relevant(&|i| i.source_character = c))
+    {
+        return SourceCharacter(input.source_character);
+    }
+    if input.target_is_virtual && becomes_relevant(&|i| i.target_is_virtual = false) {
+        return TargetIsVirtual;
+    }
+    if !input.target_supports_discrete_values
+        && becomes_relevant(&|i| i.target_supports_discrete_values = true)
+    {
+        return TargetIsNotDiscrete;
+    }
+    // Checked before the absolute mode because a sequence only makes the target range irrelevant
+    // in absolute modes which support sequences
+    if input.target_value_sequence_is_set
+        && becomes_relevant(&|i| i.target_value_sequence_is_set = false)
+    {
+        return TargetValueSequenceSet;
+    }
+    if crate::AbsoluteMode::iter()
+        .any(|m| m != input.absolute_mode && becomes_relevant(&|i| i.absolute_mode = m))
+    {
+        return AbsoluteMode(input.absolute_mode);
+    }
+    if becomes_relevant(&|i| i.make_absolute = !i.make_absolute) {
+        return if input.make_absolute {
+            MakeAbsoluteEnabled
+        } else {
+            MakeAbsoluteDisabled
+        };
+    }
+    if crate::FireMode::iter()
+        .any(|m| m != input.fire_mode && becomes_relevant(&|i| i.fire_mode = m))
+    {
+        return FireMode(input.fire_mode);
+    }
+    if becomes_relevant(&|i| i.use_textual_feedback = !i.use_textual_feedback) {
+        return if input.use_textual_feedback {
+            TextualFeedbackEnabled
+        } else {
+            TextualFeedbackDisabled
+        };
+    }
+    if becomes_relevant(&|i| {
+        i.control_transformation_uses_time = false;
+        i.control_transformation_produces_relative_values = false;
+    }) {
+        return ControlTransformation;
+    }
+    Other
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inapplicability_reasons() {
+        // Given
+        let input = ModeApplicabilityCheckInput {
+            target_is_virtual: false,
+            target_supports_discrete_values: true,
+            control_transformation_uses_time: false,
+            control_transformation_produces_relative_values: false,
+            is_feedback: false,
+            make_absolute: false,
+            use_textual_feedback: false,
+            source_character: DetailedSourceCharacter::RangeControl,
+            absolute_mode: AbsoluteMode::Normal,
+            target_value_sequence_is_set: false,
+            fire_mode: FireMode::Normal,
+        };
+        let reason = |mode_parameter, modify: &dyn Fn(&mut ModeApplicabilityCheckInput)| {
+            let mut modified_input = input;
+            modify(&mut modified_input);
+            diagnose_mode_applicability(mode_parameter, modified_input).reason
+        };
+        // When
+        // Then
+        assert_eq!(reason(ModeParameter::Reverse, &|_| {}), None);
+        assert_eq!(
+            reason(ModeParameter::ControlTransformation, &|i| i.is_feedback =
+                true),
+            Some(InapplicabilityReason::OnlyRelevantForControl)
+        );
+        assert_eq!(
+            reason(ModeParameter::FeedbackType, &|_| {}),
+            Some(InapplicabilityReason::OnlyRelevantForFeedback)
+        );
+        assert_eq!(
+            reason(ModeParameter::RelativeFilter, &|_| {}),
+            Some(InapplicabilityReason::SourceCharacter(
+                DetailedSourceCharacter::RangeControl
+            ))
+        );
+        assert_eq!(
+            reason(ModeParameter::GroupInteraction, &|i| i.target_is_virtual =
+                true),
+            Some(InapplicabilityReason::TargetIsVirtual)
+        );
+        assert_eq!(
+            reason(ModeParameter::UseDiscreteProcessing, &|i| {
+                i.target_supports_discrete_values = false
+            }),
+            Some(InapplicabilityReason::TargetIsNotDiscrete)
+        );
+        assert_eq!(
+            reason(ModeParameter::JumpMinMax, &|i| {
+                i.absolute_mode = AbsoluteMode::MakeRelative
+            }),
+            Some(InapplicabilityReason::AbsoluteMode(
+                AbsoluteMode::MakeRelative
+            ))
+        );
+        assert_eq!(
+            reason(ModeParameter::StepFactorMin, &|i| {
+                i.source_character = DetailedSourceCharacter::Relative;
+                i.make_absolute = true;
+            }),
+            Some(InapplicabilityReason::MakeAbsoluteEnabled)
+        );
+        assert_eq!(
+            reason(ModeParameter::RoundTargetValue, &|i| {
+                i.source_character = DetailedSourceCharacter::Relative;
+                i.absolute_mode = AbsoluteMode::MakeRelative;
+            }),
+            Some(InapplicabilityReason::MakeAbsoluteDisabled)
+        );
+        assert_eq!(
+            reason(ModeParameter::ButtonFilter, &|i| {
+                i.source_character = DetailedSourceCharacter::MomentaryOnOffButton;
+                i.fire_mode = FireMode::OnDoublePress;
+            }),
+            Some(InapplicabilityReason::FireMode(FireMode::OnDoublePress))
+        );
+        assert_eq!(
+            reason(ModeParameter::FeedbackTransformation, &|i| {
+                i.is_feedback = true;
+                i.use_textual_feedback = true;
+            }),
+            Some(InapplicabilityReason::TextualFeedbackEnabled)
+        );
+        assert_eq!(
+            reason(ModeParameter::TextualFeedbackExpression, &|i| {
+                i.is_feedback = true
+            }),
+            Some(InapplicabilityReason::TextualFeedbackDisabled)
+        );
+        assert_eq!(
+            reason(ModeParameter::TargetMinMax, &|i| {
+                // Makes sure that switching to feedback doesn't make the target range relevant
+                i.use_textual_feedback = true;
+                i.target_value_sequence_is_set = true;
+            }),
+            Some(InapplicabilityReason::TargetValueSequenceSet)
+        );
+        assert_eq!(
+            reason(ModeParameter::JumpMinMax, &|i| {
+                i.control_transformation_uses_time = true
+            }),
+            Some(InapplicabilityReason::ControlTransformation)
+        );
+        assert_eq!(
+            reason(ModeParameter::FireMode, &|i| {
+                i.source_character = DetailedSourceCharacter::Relative;
+                i.target_is_virtual = true;
+            }),
+            Some(InapplicabilityReason::Other)
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{
+    AbsoluteMode, ButtonUsage, DiscreteIncrement, EncoderUsage, FeedbackProcessor, FeedbackScript,
+    FeedbackValueTable, FireMode, IncrementMappingTable, Interval, ModeSettings,
+    OutOfRangeBehavior, PerformanceAnchorStrategy, ResponseCurve, ReverseDirection, TakeoverMode,
+    TimestampOffset, Transformation, UnitValue, ValueSequence, ValueSequenceGenerator,
+    VelocityCurve, VirtualColor,
+};
+use std::time::Duration;
+
+/// A combination of mode settings which doesn't make sense.
+#[derive(Clone, PartialEq, Debug, thiserror::Error)]
+pub enum ModeSettingsError {
+    #[error("jump interval has no effect in absolute mode \"{absolute_mode}\"")]
+    JumpIntervalIgnored { absolute_mode: AbsoluteMode },
+    #[error("multi-press count must be at least 2 but is {count}")]
+    MultiPressCountTooLow { count: u32 },
+    #[error("hold threshold of hybrid toggle button must be greater than zero")]
+    ZeroHoldThreshold,
+    #[error("both values of A/B toggle button are the same")]
+    IdenticalAbToggleValues,
+    #[error("make-relative sensitivity must be a positive number but is {sensitivity}")]
+    InvalidMakeRelativeSensitivity { sensitivity: f64 },
+}
+
+impl<T: Transformation, F: for<'a> FeedbackScript<'a>> ModeSettings<T, F> {
+    /// Returns a builder which validates the settings when building.
+    pub fn builder() -> ModeSettingsBuilder<T, F> {
+        ModeSettingsBuilder::default()
+    }
+
+    /// Checks whether these settings form a sensible combination.
+    ///
+    /// Returns the first problem found.
+    pub fn validate(&self) -> Result<(), ModeSettingsError> {
+        use AbsoluteMode::*;
+        let absolute_mode = self.absolute_mode;
+        let has_jump_restrictions = !self.jump_interval.is_full()
+            || self
+                .downward_jump_interval
+                .as_ref()
+                .is_some_and(|i| !i.is_full());
+        if has_jump_restrictions
+            && matches!(
+                absolute_mode,
+                IncrementalButton
+                    | ToggleButton
+                    | MultiStateToggleButton
+                    | HybridToggleButton
+                    | AbToggleButton
+                    | MakeRelative
+            )
+        {
+            return Err(ModeSettingsError::JumpIntervalIgnored { absolute_mode });
+        }
+        if self.fire_mode == FireMode::OnMultiPress && self.multi_press_count < 2 {
+            return Err(ModeSettingsError::MultiPressCountTooLow {
+                count: self.multi_press_count,
+            });
+        }
+        if absolute_mode == HybridToggleButton && self.hold_threshold.is_zero() {
+            return Err(ModeSettingsError::ZeroHoldThreshold);
+        }
+        if absolute_mode == AbToggleButton && self.ab_toggle_values[0] == self.ab_toggle_values[1] {
+            return Err(ModeSettingsError::IdenticalAbToggleValues);
+        }
+        let sensitivity = self.make_relative_sensitivity;
+        if !sensitivity.is_finite() || sensitivity <= 0.0 {
+            return Err(ModeSettingsError::InvalidMakeRelativeSensitivity { sensitivity });
+        }
+        Ok(())
+    }
+}
+
+/// Builds mode settings, starting from the defaults and validating the result.
+#[derive(Clone, Debug)]
+pub struct ModeSettingsBuilder<T: Transformation, F: for<'a> FeedbackScript<'a>> {
+    settings: ModeSettings<T, F>,
+}
+
+impl<T: Transformation, F: for<'a> FeedbackScript<'a>> Default for ModeSettingsBuilder<T, F> {
+    fn default() -> Self {
+        Self {
+            settings: Default::default(),
+        }
+    }
+}
+
+macro_rules! setters {
+    ($($name:ident: $ty:ty),* $(,)?) => {
+        $(
+            pub fn $name(mut self, value: $ty) -> Self {
+                self.settings.$name = value;
+                self
+            }
+        )*
+    };
+}
+
+impl<T: Transformation, F: for<'a> FeedbackScript<'a>> ModeSettingsBuilder<T, F> {
+    setters! {
+        absolute_mode: AbsoluteMode,
+        source_value_interval: Interval<UnitValue>,
+        discrete_source_value_interval: Interval<u32>,
+        target_value_interval: Interval<UnitValue>,
+        discrete_target_value_interval: Interval<u32>,
+        step_factor_interval: Interval<DiscreteIncrement>,
+        step_size_interval: Interval<UnitValue>,
+        step_size_relative_to_target_interval: bool,
+        decrement_step_factor_interval: Option<Interval<DiscreteIncrement>>,
+        decrement_step_size_interval: Option<Interval<UnitValue>>,
+        increment_mapping_table: Option<IncrementMappingTable>,
+        jump_interval: Interval<UnitValue>,
+        downward_jump_interval: Option<Interval<UnitValue>>,
+        discrete_jump_interval: Interval<u32>,
+        takeover_mode: TakeoverMode,
+        pickup_tolerance: UnitValue,
+        encoder_usage: EncoderUsage,
+        button_usage: ButtonUsage,
+        press_velocity_interval: Interval<UnitValue>,
+        reverse: bool,
+        reverse_direction: ReverseDirection,
+        rotate: bool,
+        round_target_value: bool,
+        quantize_step: Option<UnitValue>,
+        out_of_range_behavior: OutOfRangeBehavior,
+        control_transformation: Option<T>,
+        feedback_transformation: Option<T>,
+        feedback_value_table: Option<FeedbackValueTable>,
+        make_absolute: bool,
+        use_discrete_processing: bool,
+        fire_mode: FireMode,
+        multi_press_count: u32,
+        press_duration_interval: Interval<Duration>,
+        turbo_rate: Duration,
+        turbo_ramp_interval: Interval<Duration>,
+        turbo_end_rate: Duration,
+        short_long_press_values: [UnitValue; 2],
+        target_value_sequence: ValueSequence,
+        target_value_sequence_generator: Option<ValueSequenceGenerator<T>>,
+        snap_to_target_value_sequence: bool,
+        feedback_processor: FeedbackProcessor<F>,
+        feedback_color: Option<VirtualColor>,
+        feedback_background_color: Option<VirtualColor>,
+        timestamp_offset: TimestampOffset,
+        glide_time: Duration,
+        transition_time: Duration,
+        response_curve: ResponseCurve,
+        velocity_sensitive_source: bool,
+        velocity_curve: VelocityCurve,
+        encoder_acceleration: bool,
+        hold_threshold: Duration,
+        hysteresis: UnitValue,
+        center_deadzone: UnitValue,
+        make_relative_sensitivity: f64,
+        accumulate_fractional_steps: bool,
+        min_increment_interval: Duration,
+        max_increment_magnitude: Option<u32>,
+        sample_interval: Duration,
+        schmitt_trigger_interval: Option<Interval<UnitValue>>,
+        ab_toggle_values: [UnitValue; 2],
+        revert_on_release: bool,
+        performance_anchor_strategy: PerformanceAnchorStrategy,
+        performance_anchor_timeout: Duration,
+        use_internal_toggle_state: bool,
+    }
+
+    /// Validates and returns the settings.
+    pub fn build(self) -> Result<ModeSettings<T, F>, ModeSettingsError> {
+        self.settings.validate()?;
+        Ok(self.settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_unit_value_interval;
+    use crate::mode::test_util::{TestFeedbackScript, TestTransformation};
+
+    type TestModeSettings = ModeSettings<TestTransformation, TestFeedbackScript>;
+
+    #[test]
+    fn build_valid() {
+        // Given
+        let builder = TestModeSettings::builder()
+            .absolute_mode(AbsoluteMode::Normal)
+            .jump_interval(create_unit_value_interval(0.0, 0.2))
+            .reverse(true);
+        // When
+        let settings = builder.build().unwrap();
+        // Then
+        assert_eq!(settings.absolute_mode, AbsoluteMode::Normal);
+        assert!(settings.reverse);
+        assert!(TestModeSettings::default().validate().is_ok());
+    }
+
+    #[test]
+    fn build_invalid() {
+        // Given
+        let builder = TestModeSettings::builder()
+            .absolute_mode(AbsoluteMode::ToggleButton)
+            .jump_interval(create_unit_value_interval(0.0, 0.2));
+        // When
+        let result = builder.build();
+        // Then
+        assert_eq!(
+            result.err(),
+            Some(ModeSettingsError::JumpIntervalIgnored {
+                absolute_mode: AbsoluteMode::ToggleButton
+            })
+        );
+        let settings = TestModeSettings {
+            fire_mode: FireMode::OnMultiPress,
+            multi_press_count: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.validate(),
+            Err(ModeSettingsError::MultiPressCountTooLow { count: 1 })
+        );
+    }
+}
@@ -4,18 +4,19 @@ use crate::{
     ControlValue, DiscreteIncrement, DiscreteValue, EncoderUsage, EnhancedTransformationOutput,
     FeedbackScript, FeedbackScriptInput, FeedbackStyle, FeedbackValue, FireMode, Fraction,
     Increment, Interval, MinIsMaxBehavior, ModeContext, NumericFeedbackValue, OutOfRangeBehavior,
-    PressDurationProcessor, PropProvider, TakeoverMode, Target, TextualFeedbackValue,
-    Transformation, TransformationInstruction, UnitIncrement, UnitValue, ValueSequence,
-    BASE_EPSILON,
+    PerformanceAnchorStrategy, PressDurationProcessor, PropProvider, ResponseCurve,
+    ReverseDirection, TakeoverMode, Target, TextualFeedbackTemplate, TextualFeedbackValue,
+    TimestampOffset, Transformation, TransformationInstruction, UnitIncrement, UnitValue,
+    ValueSequence, ValueSequenceGenerator, ValueSequenceInterpolation, VelocityCurve, BASE_EPSILON,
+    DEFAULT_MULTI_PRESS_COUNT,
 };
 use base::hash_util::{NonCryptoHashMap, NonCryptoHashSet};
 use derive_more::Display;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
-use regex::Captures;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::borrow::Cow;
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::time::{Duration, Instant};
 use strum::EnumIter;
@@ -57,6 +58,28 @@ pub struct ModeFeedbackOptions {
     pub max_discrete_source_value: Option<u32>,
 }
 
+/// Maps incoming discrete increment magnitudes to output magnitudes, e.g. 1 → 1, 2 → 5, 3 → 20.
+///
+/// Magnitudes without entry are mapped like the next lower magnitude which has an entry (or stay
+/// as they are if there's none). Mapping to 0 drops the increment.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct IncrementMappingTable(BTreeMap<u32, u32>);
+
+impl IncrementMappingTable {
+    pub fn new(entries: impl IntoIterator<Item = (u32, u32)>) -> Self {
+        Self(entries.into_iter().collect())
+    }
+
+    pub fn map(&self, increment: DiscreteIncrement) -> Option<DiscreteIncrement> {
+        let magnitude = increment.to_value().get();
+        let mapped_magnitude = match self.0.range(..=magnitude).next_back() {
+            None => magnitude,
+            Some((_, output)) => *output,
+        };
+        DiscreteValue::new(mapped_magnitude).to_increment(increment.signum())
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum FeedbackValueTable {
     FromTextToDiscrete(NonCryptoHashMap<String, u32>),
@@ -112,29 +135,156 @@ pub struct ModeSettings<T: Transformation, F: for<'a> FeedbackScript<'a>> {
     /// 2nd time only.
     pub step_factor_interval: Interval<DiscreteIncrement>,
     pub step_size_interval: Interval<UnitValue>,
+    /// If enabled, step sizes are interpreted as fraction of the target value interval instead of
+    /// the full unit range, so that narrowing the target interval doesn't make steps feel larger.
+    pub step_size_relative_to_target_interval: bool,
+    /// If set, this is used instead of `step_factor_interval` for decrements.
+    pub decrement_step_factor_interval: Option<Interval<DiscreteIncrement>>,
+    /// If set, this is used instead of `step_size_interval` for decrements.
+    pub decrement_step_size_interval: Option<Interval<UnitValue>>,
+    /// If set, incoming discrete increments are mapped using this table instead of being clamped
+    /// to the speed interval.
+    pub increment_mapping_table: Option<IncrementMappingTable>,
     pub jump_interval: Interval<UnitValue>,
+    /// If set, this is used instead of `jump_interval` for downward jumps.
+    pub downward_jump_interval: Option<Interval<UnitValue>>,
     pub discrete_jump_interval: Interval<u32>,
     pub takeover_mode: TakeoverMode,
+    /// Takeover mode "Pick up (tolerant)": Control values this close to the current target value
+    /// are considered picked up even if they didn't cross it.
+    ///
+    /// Useful for coarse 7-bit faders which tend to skip over the target value.
+    pub pickup_tolerance: UnitValue,
     pub encoder_usage: EncoderUsage,
     pub button_usage: ButtonUsage,
+    /// Button presses with a velocity outside of this interval are ignored, including their
+    /// releases. Only applies to velocity-sensitive sources (see `velocity_sensitive_source`).
+    ///
+    /// Useful for drum pads which shouldn't trigger anything when merely grazed.
+    pub press_velocity_interval: Interval<UnitValue>,
     pub reverse: bool,
+    /// Relative control: Which increments are reversed if `reverse` is enabled.
+    pub reverse_direction: ReverseDirection,
     pub rotate: bool,
     pub round_target_value: bool,
+    /// If set, absolute continuous target values are snapped to a grid with this interval size
+    /// (e.g. semitones), independent of the rounding provided by the target.
+    pub quantize_step: Option<UnitValue>,
     pub out_of_range_behavior: OutOfRangeBehavior,
     pub control_transformation: Option<T>,
     pub feedback_transformation: Option<T>,
     pub feedback_value_table: Option<FeedbackValueTable>,
     /// Converts incoming relative messages to absolute ones.
     pub make_absolute: bool,
-    /// Not in use at the moment, should always be `false`.
+    /// If enabled, discrete values (e.g. from discrete sources or for discrete targets) are
+    /// processed as step counts instead of percentages, so they arrive losslessly at the other
+    /// end. Source/target/jump intervals are then taken from their discrete counterparts.
     pub use_discrete_processing: bool,
     pub fire_mode: FireMode,
+    /// Number of taps necessary for firing in multi-press fire mode.
+    pub multi_press_count: u32,
     pub press_duration_interval: Interval<Duration>,
     pub turbo_rate: Duration,
+    /// Turbo acceleration: While the button is held, the turbo rate ramps from `turbo_rate` to
+    /// `turbo_end_rate` within this hold duration interval. Zero means no acceleration.
+    pub turbo_ramp_interval: Interval<Duration>,
+    /// Zero means no acceleration, too.
+    pub turbo_end_rate: Duration,
+    /// Short-or-long-press fire mode: The value fired on a short press and the one fired on a
+    /// long press (a press lasting at least the press duration minimum).
+    pub short_long_press_values: [UnitValue; 2],
     pub target_value_sequence: ValueSequence,
+    /// If set, the target value sequence is generated by this instead of being taken from
+    /// `target_value_sequence` (whose interpolation setting still applies).
+    pub target_value_sequence_generator: Option<ValueSequenceGenerator<T>>,
+    /// If enabled, relative control first snaps the current target value to the nearest entry of
+    /// the target value sequence before stepping through it.
+    pub snap_to_target_value_sequence: bool,
     pub feedback_processor: FeedbackProcessor<F>,
     pub feedback_color: Option<VirtualColor>,
     pub feedback_background_color: Option<VirtualColor>,
+    /// Applied to the timestamps of all control events entering this mode.
+    ///
+    /// Useful for compensating a known input latency of a device or network path, so that
+    /// time-dependent features (takeover modes, transitions) see the actual event time.
+    pub timestamp_offset: TimestampOffset,
+    /// If non-zero, absolute continuous target values are not hit immediately but approached
+    /// gradually within this time (driven by `poll()`).
+    pub glide_time: Duration,
+    /// If non-zero, every absolute continuous target value hit is approached gradually within
+    /// this time (driven by `poll()`), starting from the current target value.
+    ///
+    /// In contrast to glide, this also applies to hits which are triggered by the press duration
+    /// processor (e.g. fire after timeout), so it's suited for button-triggered value changes.
+    /// Takes precedence over glide.
+    pub transition_time: Duration,
+    /// Shapes absolute continuous control values (and inversely feedback values).
+    pub response_curve: ResponseCurve,
+    /// Whether the source is a velocity-sensitive button (e.g. a drum pad). Velocity-specific
+    /// processing is skipped for other sources, such as faders.
+    pub velocity_sensitive_source: bool,
+    /// Shapes incoming button velocities (absolute non-zero control values) before any further
+    /// processing. Only applies to velocity-sensitive sources.
+    pub velocity_curve: VelocityCurve,
+    /// If enabled, discrete increments get larger the faster they arrive (based on the control
+    /// event timestamps), scaled between step min and max.
+    ///
+    /// Useful for encoders which don't encode the turning speed in the increment value.
+    pub encoder_acceleration: bool,
+    /// Hybrid toggle button: Presses held at least this long are considered momentary (the
+    /// target value is restored on release), shorter ones latch.
+    pub hold_threshold: Duration,
+    /// Absolute control values which differ from the previously accepted source value by less
+    /// than this threshold are suppressed. Zero means no hysteresis.
+    ///
+    /// Useful for jittery faders and expression pedals.
+    pub hysteresis: UnitValue,
+    /// Source-normalized absolute continuous values which are not further away from the center
+    /// (0.5) than this are snapped exactly to the center. Zero means no deadzone.
+    ///
+    /// Useful for joysticks and crossfaders which never quite return to center.
+    pub center_deadzone: UnitValue,
+    /// "Make relative" mode: Factor by which source value differences are scaled before they
+    /// are applied to the target. 1.0 means that a full sweep results in full target travel.
+    pub make_relative_sensitivity: f64,
+    /// If enabled, continuous increments which are smaller than the atomic step size of a
+    /// discrete target are accumulated until they add up to a whole step (instead of being lost).
+    pub accumulate_fractional_steps: bool,
+    /// Minimum time between two forwarded increments. Increments arriving faster are dropped.
+    /// Zero means no rate limiting.
+    ///
+    /// Useful for targets which misbehave when receiving bursts of increments (e.g. preset
+    /// switching).
+    pub min_increment_interval: Duration,
+    /// If set, incoming discrete increments whose magnitude exceeds this value are dropped.
+    ///
+    /// Guards against encoders which occasionally send garbage jumps (e.g. ±60).
+    pub max_increment_magnitude: Option<u32>,
+    /// Sample-and-hold mode: Interval in which the held control value is forwarded to the target
+    /// (driven by `poll()`). Zero means that sampling happens on demand only (via `sample()`).
+    pub sample_interval: Duration,
+    /// If set, absolute control values are turned into on/off values with separate thresholds:
+    /// On when reaching the interval max, off when reaching the interval min. Values in between
+    /// are ignored.
+    ///
+    /// Useful for analog pedals used as switches.
+    pub schmitt_trigger_interval: Option<Interval<UnitValue>>,
+    /// A/B toggle button mode: The two values to alternate between.
+    pub ab_toggle_values: [UnitValue; 2],
+    /// Normal mode: If enabled, releasing the button restores the target value which was
+    /// captured immediately before the press.
+    pub revert_on_release: bool,
+    /// Performance control: When to replace the anchor with the one passed by the caller.
+    pub performance_anchor_strategy: PerformanceAnchorStrategy,
+    /// Performance control: Inactivity after which the anchor is replaced if the strategy is
+    /// [`PerformanceAnchorStrategy::ReanchorAfterTimeout`].
+    pub performance_anchor_timeout: Duration,
+    /// Toggle button mode: If enabled, the on/off state is kept by the mode itself instead of
+    /// being derived from the current target value. Feedback reflects that internal state.
+    ///
+    /// Makes toggling possible with targets which can't report their current value (e.g. virtual
+    /// or write-only targets).
+    pub use_internal_toggle_state: bool,
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -175,13 +325,22 @@ impl<T: Transformation, F: for<'a> FeedbackScript<'a>> Default for ModeSettings<
             discrete_target_value_interval: full_discrete_interval(),
             step_size_interval: default_step_size_interval(),
             step_factor_interval: default_step_count_interval(),
+            step_size_relative_to_target_interval: false,
+            decrement_step_size_interval: None,
+            decrement_step_factor_interval: None,
+            increment_mapping_table: None,
             jump_interval: full_unit_interval(),
+            downward_jump_interval: None,
             discrete_jump_interval: full_discrete_interval(),
             takeover_mode: Default::default(),
+            pickup_tolerance: UnitValue::MIN,
             button_usage: Default::default(),
+            press_velocity_interval: full_unit_interval(),
             encoder_usage: Default::default(),
             reverse: false,
+            reverse_direction: ReverseDirection::Both,
             round_target_value: false,
+            quantize_step: None,
             out_of_range_behavior: OutOfRangeBehavior::MinOrMax,
             control_transformation: None,
             feedback_transformation: None,
@@ -189,13 +348,95 @@ impl<T: Transformation, F: for<'a> FeedbackScript<'a>> Default for ModeSettings<
             make_absolute: false,
             use_discrete_processing: false,
             fire_mode: FireMode::Normal,
+            multi_press_count: DEFAULT_MULTI_PRESS_COUNT,
             press_duration_interval: Interval::new(ZERO_DURATION, ZERO_DURATION),
             turbo_rate: ZERO_DURATION,
+            turbo_ramp_interval: Interval::new(ZERO_DURATION, ZERO_DURATION),
+            turbo_end_rate: ZERO_DURATION,
+            short_long_press_values: [UnitValue::MIN, UnitValue::MAX],
             target_value_sequence: Default::default(),
+            target_value_sequence_generator: None,
+            snap_to_target_value_sequence: false,
             feedback_processor: FeedbackProcessor::Numeric,
             feedback_color: None,
             feedback_background_color: None,
             feedback_value_table: None,
+            timestamp_offset: TimestampOffset::ZERO,
+            glide_time: ZERO_DURATION,
+            transition_time: ZERO_DURATION,
+            response_curve: ResponseCurve::Linear,
+            velocity_sensitive_source: false,
+            velocity_curve: VelocityCurve::Linear,
+            encoder_acceleration: false,
+            hold_threshold: Duration::from_millis(300),
+            hysteresis: UnitValue::MIN,
+            center_deadzone: UnitValue::MIN,
+            make_relative_sensitivity: 1.0,
+            accumulate_fractional_steps: false,
+            min_increment_interval: ZERO_DURATION,
+            max_increment_magnitude: None,
+            sample_interval: ZERO_DURATION,
+            schmitt_trigger_interval: None,
+            ab_toggle_values: [UnitValue::MIN, UnitValue::MAX],
+            revert_on_release: false,
+            performance_anchor_strategy: Default::default(),
+            performance_anchor_timeout: ZERO_DURATION,
+            use_internal_toggle_state: false,
+        }
+    }
+}
+
+impl<T: Transformation, F: for<'a> FeedbackScript<'a>> ModeSettings<T, F> {
+    /// Returns the step size interval to be used for the given direction.
+    pub fn step_size_interval_for(&self, is_increment: bool) -> &Interval<UnitValue> {
+        if is_increment {
+            &self.step_size_interval
+        } else {
+            self.decrement_step_size_interval
+                .as_ref()
+                .unwrap_or(&self.step_size_interval)
+        }
+    }
+
+    /// Returns the step size interval to be used for the given direction when hitting the target
+    /// directly, taking into account whether step sizes are relative to the target interval.
+    fn effective_step_size_interval_for(&self, is_increment: bool) -> Interval<UnitValue> {
+        let interval = *self.step_size_interval_for(is_increment);
+        if !self.step_size_relative_to_target_interval {
+            return interval;
+        }
+        let span = self.target_value_interval.span();
+        Interval::new(
+            UnitValue::new_clamped(interval.min_val().get() * span),
+            UnitValue::new_clamped(interval.max_val().get() * span),
+        )
+    }
+
+    /// Returns whether increments or decrements (depending on the given direction) should be
+    /// reversed.
+    fn reverses_increment(&self, is_increment: bool) -> bool {
+        self.reverse && self.reverse_direction.applies_to(is_increment)
+    }
+
+    /// Returns the jump interval to be used for the given direction.
+    pub fn jump_interval_for(&self, is_upward: bool) -> &Interval<UnitValue> {
+        if is_upward {
+            &self.jump_interval
+        } else {
+            self.downward_jump_interval
+                .as_ref()
+                .unwrap_or(&self.jump_interval)
+        }
+    }
+
+    /// Returns the step factor interval to be used for the given direction.
+    pub fn step_factor_interval_for(&self, is_increment: bool) -> &Interval<DiscreteIncrement> {
+        if is_increment {
+            &self.step_factor_interval
+        } else {
+            self.decrement_step_factor_interval
+                .as_ref()
+                .unwrap_or(&self.step_factor_interval)
         }
     }
 }
@@ -227,7 +468,7 @@ pub struct Mode<T: Transformation, F: for<'a> FeedbackScript<'a>, S: AbstractTim
 
 #[derive(Clone, Debug)]
 struct ModeState<S: AbstractTimestamp> {
-    press_duration_processor: PressDurationProcessor,
+    press_duration_processor: PressDurationProcessor<S>,
     /// For relative-to-absolute mode
     current_absolute_value: UnitValue,
     #[allow(dead_code)]
@@ -252,11 +493,77 @@ struct ModeState<S: AbstractTimestamp> {
     unpacked_target_value_set: BTreeSet<UnitValue>,
     /// For textual feedback
     feedback_props_in_use: NonCryptoHashSet<String>,
+    /// For textual feedback
+    textual_feedback_template: TextualFeedbackTemplate,
     /// Supposed to contain the final target value after the last control with this mode.
     ///
     /// The mode knows the value that it produced for the consumer, so the consumer sends it
     /// to the target. But the target might end up with another value actually.  
     final_target_value_from_previous_control: Option<AbsoluteValue>,
+    /// For glide (slew limiting) and transitions
+    glide: Option<Glide<S>>,
+    /// For timestamp-based encoder acceleration
+    previous_increment_event: Option<ControlEvent<Increment, S>>,
+    /// For hybrid toggle button
+    hybrid_press: Option<HybridPress<S>>,
+    /// Last absolute source value which passed the hysteresis filter.
+    previous_hysteresis_value: Option<UnitValue>,
+    /// Sum of continuous increments which didn't yet add up to a whole target step.
+    fractional_step_accumulator: f64,
+    /// For discrete "make relative" mode: Part of the sensitivity-scaled differences which didn't
+    /// yet add up to a whole step.
+    make_relative_remainder: f64,
+    /// For increment rate limiting
+    last_forwarded_increment_timestamp: Option<S>,
+    /// For sample-and-hold mode: The latest control value which has not been forwarded yet.
+    held_sample: Option<ControlValue>,
+    /// For sample-and-hold mode
+    last_sample_timestamp: Option<S>,
+    /// Whether the Schmitt trigger is currently switched on.
+    schmitt_trigger_is_on: bool,
+    /// For revert on release: Target value from before the press.
+    captured_target_value: Option<AbsoluteValue>,
+    /// Whether the last press was ignored because of its velocity (so its release is ignored, too).
+    press_ignored_due_to_velocity: bool,
+    /// For performance control: The anchor kept by the mode itself (depending on strategy).
+    performance_anchor: Option<AbsoluteValue>,
+    /// For feedback classification: Why this mode hit the target since the last feedback.
+    pending_feedback_origin: Option<FeedbackOrigin>,
+    /// For feedback classification: Whether feedback has been given since creation or
+    /// deactivation.
+    gave_feedback: bool,
+    /// For performance control with timeout-based anchor strategy
+    last_performance_control_timestamp: Option<S>,
+    /// For toggle button mode with internal toggle state
+    internal_toggle_is_on: bool,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct HybridPress<S: AbstractTimestamp> {
+    time: S,
+    /// Target value before the press, restored on release if the button was held.
+    pre_press_value: AbsoluteValue,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Glide<S: AbstractTimestamp> {
+    from: UnitValue,
+    to: UnitValue,
+    start: S,
+    duration: Duration,
+}
+
+impl<S: AbstractTimestamp> Glide<S> {
+    /// Returns the interpolated value and whether the destination has been reached.
+    fn value_at(&self, timestamp: S) -> (UnitValue, bool) {
+        let elapsed = timestamp - self.start;
+        if elapsed >= self.duration {
+            return (self.to, true);
+        }
+        let progress = elapsed.as_secs_f64() / self.duration.as_secs_f64();
+        let value = self.from.get() + (self.to.get() - self.from.get()) * progress;
+        (UnitValue::new_clamped(value), false)
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -303,7 +610,25 @@ impl<S: AbstractTimestamp> Default for ModeState<S> {
             unpacked_target_value_sequence: vec![],
             unpacked_target_value_set: Default::default(),
             feedback_props_in_use: Default::default(),
+            textual_feedback_template: Default::default(),
             final_target_value_from_previous_control: None,
+            glide: None,
+            previous_increment_event: None,
+            hybrid_press: None,
+            previous_hysteresis_value: None,
+            fractional_step_accumulator: 0.0,
+            make_relative_remainder: 0.0,
+            last_forwarded_increment_timestamp: None,
+            held_sample: None,
+            last_sample_timestamp: None,
+            schmitt_trigger_is_on: false,
+            captured_target_value: None,
+            press_ignored_due_to_velocity: false,
+            performance_anchor: None,
+            pending_feedback_origin: None,
+            gave_feedback: false,
+            last_performance_control_timestamp: None,
+            internal_toggle_is_on: false,
         }
     }
 }
@@ -335,6 +660,27 @@ pub enum AbsoluteMode {
     MakeRelative = 3,
     #[display(fmt = "Performance control")]
     PerformanceControl = 4,
+    #[display(fmt = "Multi-state toggle button")]
+    MultiStateToggleButton = 5,
+    #[display(fmt = "Hybrid toggle button")]
+    HybridToggleButton = 6,
+    #[display(fmt = "Bipolar performance control")]
+    BipolarPerformanceControl = 7,
+    #[display(fmt = "Sample and hold")]
+    SampleAndHold = 8,
+    #[display(fmt = "A/B toggle button")]
+    AbToggleButton = 9,
+}
+
+impl AbsoluteMode {
+    /// Returns whether this mode changes the target value relative to the last value which was
+    /// not set by performance control.
+    pub fn is_performance_control(&self) -> bool {
+        matches!(
+            self,
+            AbsoluteMode::PerformanceControl | AbsoluteMode::BipolarPerformanceControl
+        )
+    }
 }
 
 #[derive(
@@ -518,12 +864,20 @@ where
     S: AbstractTimestamp,
 {
     pub fn new(settings: ModeSettings<T, F>) -> Self {
+        let textual_feedback_template = match &settings.feedback_processor {
+            FeedbackProcessor::Text { expression } => TextualFeedbackTemplate::parse(expression),
+            _ => TextualFeedbackTemplate::default(),
+        };
         let state = ModeState {
             press_duration_processor: PressDurationProcessor::new(
                 settings.fire_mode,
                 settings.press_duration_interval,
                 settings.turbo_rate,
                 settings.button_usage,
+                settings.multi_press_count,
+                settings.turbo_ramp_interval,
+                settings.turbo_end_rate,
+                settings.short_long_press_values,
             ),
             feedback_props_in_use: {
                 let mut set = match &settings.feedback_processor {
@@ -539,9 +893,10 @@ where
                             set.insert(DEFAULT_TEXTUAL_FEEDBACK_PROP_KEY.to_string());
                         } else {
                             set.extend(
-                                textual_feedback_expression_regex()
-                                    .captures_iter(expression)
-                                    .map(|cap| cap[1].to_string()),
+                                textual_feedback_template
+                                    .used_props()
+                                    .into_iter()
+                                    .map(String::from),
                             );
                         }
                         set
@@ -561,6 +916,7 @@ where
                 }
                 set
             },
+            textual_feedback_template,
             ..Default::default()
         };
         Mode { settings, state }
@@ -606,7 +962,8 @@ where
         options: ModeControlOptions,
         last_non_performance_target_value: Option<AbsoluteValue>,
     ) -> Option<ModeControlResult<ControlValue>> {
-        match control_event.payload() {
+        let control_event = control_event.shift_timestamp(self.settings.timestamp_offset);
+        let result = match control_event.payload() {
             ControlValue::AbsoluteContinuous(v) => self.control_absolute(
                 control_event.with_payload(AbsoluteValue::Continuous(v)),
                 target,
@@ -635,7 +992,70 @@ where
                 context,
                 options,
             ),
+        };
+        let glide_duration = if self.settings.transition_time.is_zero() {
+            self.settings.glide_time
+        } else {
+            self.settings.transition_time
+        };
+        let result = self.start_glide_if_necessary(
+            result,
+            glide_duration,
+            control_event.timestamp(),
+            target,
+            context,
+        );
+        self.memorize_feedback_origin(&result, FeedbackOrigin::Control);
+        result
+    }
+
+    fn memorize_feedback_origin(
+        &mut self,
+        result: &Option<ModeControlResult<ControlValue>>,
+        origin: FeedbackOrigin,
+    ) {
+        if let Some(ModeControlResult::HitTarget { .. }) = result {
+            self.state.pending_feedback_origin = Some(origin);
+        }
+    }
+
+    /// If the given duration is non-zero, defers hitting the target with the given absolute value
+    /// and starts gliding from the current target value instead.
+    fn start_glide_if_necessary<'a, C: Copy + Into<TC>, TC>(
+        &mut self,
+        result: Option<ModeControlResult<ControlValue>>,
+        duration: Duration,
+        timestamp: S,
+        target: &impl Target<'a, Context = TC>,
+        context: C,
+    ) -> Option<ModeControlResult<ControlValue>> {
+        if duration.is_zero() {
+            return result;
+        }
+        let Some(ModeControlResult::HitTarget {
+            value: ControlValue::AbsoluteContinuous(to),
+        }) = result
+        else {
+            return result;
+        };
+        let from = match target.current_value(context.into()) {
+            Some(v) => v.to_unit_value(),
+            // Without knowing the current target value, we can't glide.
+            None => return result,
+        };
+        if from == to {
+            self.state.glide = None;
+            return result;
         }
+        self.state.glide = Some(Glide {
+            from,
+            to,
+            start: timestamp,
+            duration,
+        });
+        Some(ModeControlResult::LeaveTargetUntouched(
+            ControlValue::AbsoluteContinuous(from),
+        ))
     }
 
     /// When `true`, one must use methods such as `build_feedback`.
@@ -652,6 +1072,23 @@ where
         prop_provider: &impl PropProvider,
         context: ModeContext<<F as FeedbackScript<'_>>::AdditionalInput>,
     ) -> FeedbackValue {
+        self.build_feedback_with_target_value(prop_provider, None, context)
+    }
+
+    /// Like [`Self::build_feedback`] but also resolves props provided by the mode itself (see
+    /// [`Self::get_prop_value`]) before consulting the given prop provider. They are only
+    /// available if the current target value is given.
+    pub fn build_feedback_with_target_value(
+        &self,
+        prop_provider: &impl PropProvider,
+        target_value: Option<AbsoluteValue>,
+        context: ModeContext<<F as FeedbackScript<'_>>::AdditionalInput>,
+    ) -> FeedbackValue {
+        let prop_provider = &|key: &str| {
+            target_value
+                .and_then(|v| self.get_prop_value(key, v))
+                .or_else(|| prop_provider.get_prop_value(key))
+        };
         match &self.settings.feedback_processor {
             FeedbackProcessor::Numeric => {
                 unreachable!("Numeric feedback processor doesn't need build step");
@@ -664,12 +1101,10 @@ where
                         .unwrap_or_default()
                         .into_textual()
                 } else {
-                    textual_feedback_expression_regex().replace_all(expression, |c: &Captures| {
-                        prop_provider
-                            .get_prop_value(&c[1])
-                            .unwrap_or_default()
-                            .into_textual()
-                    })
+                    self.state
+                        .textual_feedback_template
+                        .render(prop_provider)
+                        .into()
                 };
                 FeedbackValue::Textual(TextualFeedbackValue::new(style, text))
             }
@@ -689,6 +1124,25 @@ where
         }
     }
 
+    /// Returns the value of a prop which is provided by the mode itself (prefixed with `mode.`).
+    pub fn get_prop_value(&self, key: &str, target_value: AbsoluteValue) -> Option<PropValue> {
+        match key {
+            MODE_SEQUENCE_LABEL_PROP_KEY => {
+                // Discrete target values can't hit sequence values exactly
+                let tolerance = match target_value {
+                    AbsoluteValue::Discrete(f) if f.max_val() > 0 => 0.5 / f.max_val() as f64,
+                    _ => BASE_EPSILON,
+                };
+                let label = self
+                    .settings
+                    .target_value_sequence
+                    .label_at(target_value.to_unit_value(), tolerance)?;
+                Some(PropValue::Text(label.to_string().into()))
+            }
+            _ => None,
+        }
+    }
+
     pub fn feedback_style(&self, prop_provider: &impl PropProvider) -> FeedbackStyle {
         FeedbackStyle {
             color: self
@@ -724,6 +1178,41 @@ where
         Some(out_cow.to_numeric()?.value)
     }
 
+    /// Like [`Self::feedback_with_options_detail`] but also classifies the feedback, so hosts with
+    /// limited feedback bandwidth can send urgent feedback first (see
+    /// [`WithFeedbackOrigin::priority`]).
+    ///
+    /// The origin is derived from what this mode did since the last feedback. Hitting the target
+    /// in response to a control event yields [`FeedbackOrigin::Control`], hitting it while being
+    /// polled or gliding yields [`FeedbackOrigin::Poll`]. The first feedback after creation or
+    /// deactivation is [`FeedbackOrigin::Initial`]. Any other feedback must have been caused by
+    /// a [`FeedbackOrigin::PropChange`] (e.g. a meter value or a change made by another mapping).
+    pub fn feedback_with_origin<'a, 'c>(
+        &mut self,
+        target_value: Option<Cow<'a, FeedbackValue<'c>>>,
+        options: ModeFeedbackOptions,
+        additional_transformation_input: T::AdditionalInput,
+    ) -> Option<WithFeedbackOrigin<Cow<'a, FeedbackValue<'c>>>> {
+        let origin = self.take_feedback_origin();
+        let value = self.feedback_with_options_detail(
+            target_value,
+            options,
+            additional_transformation_input,
+        )?;
+        Some(WithFeedbackOrigin { value, origin })
+    }
+
+    fn take_feedback_origin(&mut self) -> FeedbackOrigin {
+        let origin = match self.state.pending_feedback_origin.take() {
+            Some(origin) => origin,
+            None if self.state.glide.is_some() => FeedbackOrigin::Poll,
+            None if self.state.gave_feedback => FeedbackOrigin::PropChange,
+            None => FeedbackOrigin::Initial,
+        };
+        self.state.gave_feedback = true;
+        origin
+    }
+
     /// Takes a target value, interprets and transforms it conforming to mode rules and
     /// maybe returns an appropriate source value that should be sent to the source.
     pub fn feedback_with_options_detail<'a, 'c>(
@@ -732,6 +1221,18 @@ where
         options: ModeFeedbackOptions,
         additional_transformation_input: T::AdditionalInput,
     ) -> Option<Cow<'a, FeedbackValue<'c>>> {
+        let target_value = if self.uses_internal_toggle_state() {
+            let style = match target_value.as_deref() {
+                Some(FeedbackValue::Numeric(v)) => v.style,
+                _ => FeedbackStyle::default(),
+            };
+            let value = AbsoluteValue::Continuous(self.internal_toggle_target_value());
+            Some(Cow::Owned(FeedbackValue::Numeric(
+                NumericFeedbackValue::new(style, value),
+            )))
+        } else {
+            target_value
+        };
         match target_value {
             None => {
                 // Target didn't return any value. Return minimum value and apply at least source min/max.
@@ -785,7 +1286,19 @@ where
         additional_transformation_input: T::AdditionalInput,
     ) -> Option<FeedbackValue<'static>> {
         let v = feedback_value.value;
-        // 4. Filter and Apply target interval (normalize)
+        if self.settings.absolute_mode == AbsoluteMode::AbToggleButton {
+            // Just report which of both values is active (even if the target value was changed
+            // by someone else).
+            let state = if self.ab_toggle_b_is_active(v) {
+                UnitValue::MAX
+            } else {
+                UnitValue::MIN
+            };
+            let v = self.apply_feedback_source_interval(AbsoluteValue::Continuous(state), options);
+            let result = NumericFeedbackValue::new(feedback_value.style, v);
+            return Some(FeedbackValue::Numeric(result));
+        }
+        // 5. Filter and Apply target interval (normalize)
         let interval_match_result = v.matches_tolerant(
             &self.settings.target_value_interval,
             &self.settings.discrete_target_value_interval,
@@ -819,7 +1332,7 @@ where
             self.settings.use_discrete_processing,
             FEEDBACK_EPSILON,
         );
-        // 3. Apply reverse
+        // 4. Apply reverse
         if self.settings.reverse {
             let normalized_max_discrete_source_value = options.max_discrete_source_value.map(|m| {
                 self.settings
@@ -828,7 +1341,7 @@ where
             });
             v = v.inverse(normalized_max_discrete_source_value);
         };
-        // 2. Apply transformation
+        // 3. Apply transformation
         if let Some(transformation) = self.settings.feedback_transformation.as_ref() {
             if let Ok(output) = v.transform(
                 transformation,
@@ -842,6 +1355,10 @@ where
                 v = output.value?.to_absolute_value().ok()?;
             }
         };
+        // 2. Apply inverse response curve
+        if let AbsoluteValue::Continuous(uv) = v {
+            v = AbsoluteValue::Continuous(self.settings.response_curve.apply_inverse(uv));
+        }
         // 1. Apply source interval
         v = self.apply_feedback_source_interval(v, options);
         let result = NumericFeedbackValue::new(feedback_value.style, v);
@@ -906,6 +1423,10 @@ where
     /// If this returns `true`, the `poll` method should be called, on a regular basis.
     pub fn wants_to_be_polled(&self) -> bool {
         self.state.press_duration_processor.wants_to_be_polled()
+            || !self.settings.glide_time.is_zero()
+            || !self.settings.transition_time.is_zero()
+            || (self.settings.absolute_mode == AbsoluteMode::SampleAndHold
+                && !self.settings.sample_interval.is_zero())
             || self
                 .settings
                 .control_transformation
@@ -922,11 +1443,41 @@ where
         context: C,
         timestamp: S,
     ) -> Option<ModeControlResult<ControlValue>> {
+        Some(self.poll_with_origin(target, context, timestamp)?.value)
+    }
+
+    /// Like [`Self::poll`] but also tells whether the returned value is a direct consequence
+    /// of a user interaction (e.g. fire after timeout) or rather an animation (e.g. glide).
+    pub fn poll_with_origin<
+        'a,
+        C: Copy + TransformationInputProvider<T::AdditionalInput> + Into<TC>,
+        TC,
+    >(
+        &mut self,
+        target: &impl Target<'a, Context = TC>,
+        context: C,
+        timestamp: S,
+    ) -> Option<WithFeedbackOrigin<ModeControlResult<ControlValue>>> {
+        let result = self.poll_internal(target, context, timestamp)?;
+        self.memorize_feedback_origin(&Some(result.value), result.origin);
+        Some(result)
+    }
+
+    fn poll_internal<
+        'a,
+        C: Copy + TransformationInputProvider<T::AdditionalInput> + Into<TC>,
+        TC,
+    >(
+        &mut self,
+        target: &impl Target<'a, Context = TC>,
+        context: C,
+        timestamp: S,
+    ) -> Option<WithFeedbackOrigin<ModeControlResult<ControlValue>>> {
         // Let the press duration processor do its job. We do that even if we a transition because
         // the press might restart the transition. We want single press and fire after timeout to
         // still work even when using transitions. It has priority even.
-        if let Some(control_value) = self.state.press_duration_processor.poll() {
-            return self.control_absolute(
+        if let Some(control_value) = self.state.press_duration_processor.poll(timestamp) {
+            let result = self.control_absolute(
                 ControlEvent::new(control_value, timestamp),
                 target,
                 context,
@@ -935,7 +1486,49 @@ where
                 // Polling is only for buttons. "Performance control" mode is only for range elements.
                 None,
             );
+            let result = self.start_glide_if_necessary(
+                result,
+                self.settings.transition_time,
+                timestamp,
+                target,
+                context,
+            );
+            // Fire after timeout and friends are direct consequences of a user interaction.
+            return Some(WithFeedbackOrigin {
+                value: result?,
+                origin: FeedbackOrigin::Control,
+            });
         };
+        // If we are gliding, emit the next intermediate value.
+        if let Some(glide) = self.state.glide {
+            let (value, done) = glide.value_at(timestamp);
+            if done {
+                self.state.glide = None;
+            }
+            return Some(WithFeedbackOrigin {
+                value: ModeControlResult::hit_target(ControlValue::AbsoluteContinuous(value)),
+                origin: FeedbackOrigin::Poll,
+            });
+        }
+        // If we sample regularly, forward the held control value.
+        if self.settings.absolute_mode == AbsoluteMode::SampleAndHold
+            && !self.settings.sample_interval.is_zero()
+        {
+            let sampling_is_due = match self.state.last_sample_timestamp {
+                None => true,
+                Some(last) => timestamp - last >= self.settings.sample_interval,
+            };
+            if sampling_is_due {
+                self.state.last_sample_timestamp = Some(timestamp);
+                if let Some(result) = self.sample() {
+                    // Forwards a value which has been sent by the user
+                    return Some(WithFeedbackOrigin {
+                        value: result,
+                        origin: FeedbackOrigin::Control,
+                    });
+                }
+            }
+        }
         // If we have a transition (a transformation which depends on the current timestamp), we
         // poll this one as well.
         if let Some(transformation) = &self.settings.control_transformation {
@@ -965,7 +1558,10 @@ where
                     // Relative value stay as they are
                     _ => in_cv,
                 };
-                return Some(ModeControlResult::hit_target(out_cv));
+                return Some(WithFeedbackOrigin {
+                    value: ModeControlResult::hit_target(out_cv),
+                    origin: FeedbackOrigin::Poll,
+                });
             }
         }
         None
@@ -979,6 +1575,41 @@ where
         // Clear the previous absolute value so we don't get jumps when using "Make relative"
         // and using this mapping next time it's active again.
         self.state.previous_source_normalized_control_event = None;
+        self.state.make_relative_remainder = 0.0;
+        self.state.glide = None;
+        self.state.held_sample = None;
+        self.state.captured_target_value = None;
+        self.state.pending_feedback_origin = None;
+        self.state.gave_feedback = false;
+    }
+
+    /// Sample-and-hold mode: Forwards the held control value to the target (if there is one).
+    ///
+    /// Call this whenever sampling should be triggered on demand, e.g. by another button.
+    pub fn sample(&mut self) -> Option<ModeControlResult<ControlValue>> {
+        let value = self.state.held_sample.take()?;
+        self.state.pending_feedback_origin = Some(FeedbackOrigin::Control);
+        Some(ModeControlResult::hit_target(value))
+    }
+
+    /// Exclusive groups: Returns whether the given target value counts as "on", that is, whether
+    /// it's above the minimum of the target interval.
+    pub fn target_value_is_on(&self, value: AbsoluteValue) -> bool {
+        value.to_unit_value().get()
+            > self.settings.target_value_interval.min_val().get() + BASE_EPSILON
+    }
+
+    /// Exclusive groups: Returns the value which switches the target off because another member
+    /// of the group has switched its target on.
+    pub fn exclusive_off_value(
+        &mut self,
+        control_type: ControlType,
+    ) -> ModeControlResult<ControlValue> {
+        self.state.internal_toggle_is_on = false;
+        let off_value = AbsoluteValue::Continuous(self.settings.target_value_interval.min_val());
+        let final_value = self.get_final_absolute_value(off_value, control_type);
+        self.state.pending_feedback_origin = Some(FeedbackOrigin::Control);
+        ModeControlResult::hit_target(ControlValue::from_absolute(final_value))
     }
 
     pub fn wants_to_know_final_target_value(&self) -> bool {
@@ -986,7 +1617,15 @@ where
     }
 
     fn has_jump_restrictions(&self) -> bool {
-        self.settings.takeover_mode.prevents_jumps() && !self.settings.jump_interval.is_full()
+        self.settings.takeover_mode.prevents_jumps()
+            && (!self.settings.jump_interval.is_full()
+                || self
+                    .settings
+                    .downward_jump_interval
+                    .as_ref()
+                    .is_some_and(|i| !i.is_full())
+                || (self.settings.use_discrete_processing
+                    && self.settings.discrete_jump_interval != full_discrete_interval()))
     }
 
     pub fn report_final_target_value_of_last_control(&mut self, value: AbsoluteValue) {
@@ -1004,10 +1643,13 @@ where
             .control_type(context.into())
             .step_size()
             .unwrap_or_else(|| UnitValue::new(DEFAULT_STEP_SIZE));
-        let unpacked_sequence = self
-            .settings
-            .target_value_sequence
-            .unpack(default_step_size);
+        let unpacked_sequence = match &self.settings.target_value_sequence_generator {
+            None => self
+                .settings
+                .target_value_sequence
+                .unpack(default_step_size),
+            Some(generator) => generator.generate(),
+        };
         self.state.unpacked_target_value_set = unpacked_sequence.iter().copied().collect();
         self.state.unpacked_target_value_sequence = unpacked_sequence;
         self.state.previous_jump_prevention_state = None;
@@ -1028,6 +1670,17 @@ where
         if !self.settings.encoder_usage.matches(control_event.payload()) {
             return None;
         }
+        if !self.passes_increment_magnitude_filter(control_event.payload()) {
+            return None;
+        }
+        let control_event = if self.settings.encoder_acceleration {
+            self.accelerate_increment(control_event, target.control_type(context.into()))
+        } else {
+            control_event
+        };
+        if !self.passes_increment_rate_limit(control_event.timestamp()) {
+            return None;
+        }
         if self.settings.make_absolute {
             Some(
                 self.control_relative_to_absolute(control_event, target, context, options)?
@@ -1038,6 +1691,206 @@ where
         }
     }
 
+    /// Returns whether the given absolute source value differs enough from the previously accepted
+    /// one. Extreme values always pass, so that the target can reach its minimum and maximum.
+    fn passes_hysteresis(&mut self, v: AbsoluteValue) -> bool {
+        let threshold = self.settings.hysteresis.get();
+        if threshold <= 0.0 {
+            return true;
+        }
+        let v = v.to_unit_value();
+        if let Some(prev) = self.state.previous_hysteresis_value {
+            let is_extreme = v.is_zero() || v.is_one();
+            if !is_extreme && (v.get() - prev.get()).abs() < threshold {
+                return false;
+            }
+        }
+        self.state.previous_hysteresis_value = Some(v);
+        true
+    }
+
+    /// Returns whether the given button press or release should be processed according to the
+    /// press velocity interval. Releases share the fate of their press.
+    fn passes_velocity_filter(&mut self, v: AbsoluteValue) -> bool {
+        if !self.settings.velocity_sensitive_source
+            || self.settings.press_velocity_interval.is_full()
+        {
+            return true;
+        }
+        if v.is_zero() {
+            let press_was_ignored = self.state.press_ignored_due_to_velocity;
+            self.state.press_ignored_due_to_velocity = false;
+            return !press_was_ignored;
+        }
+        let passes = self
+            .settings
+            .press_velocity_interval
+            .contains(v.to_unit_value());
+        self.state.press_ignored_due_to_velocity = !passes;
+        passes
+    }
+
+    /// Returns whether the given increment is not suspiciously large.
+    fn passes_increment_magnitude_filter(&self, increment: Increment) -> bool {
+        match (increment, self.settings.max_increment_magnitude) {
+            (Increment::Discrete(i), Some(max)) => i.get().unsigned_abs() <= max,
+            _ => true,
+        }
+    }
+
+    /// Returns the anchor to be used for performance control, taking the anchor strategy into
+    /// account.
+    fn resolve_performance_anchor(
+        &mut self,
+        control_event: ControlEvent<AbsoluteValue, S>,
+        caller_anchor: Option<AbsoluteValue>,
+    ) -> Option<AbsoluteValue> {
+        use PerformanceAnchorStrategy::*;
+        let strategy = self.settings.performance_anchor_strategy;
+        if strategy == Caller {
+            return caller_anchor;
+        }
+        if strategy == ReanchorAfterTimeout {
+            let timestamp = control_event.timestamp();
+            let timed_out = self
+                .state
+                .last_performance_control_timestamp
+                .is_some_and(|last| timestamp - last >= self.settings.performance_anchor_timeout);
+            if timed_out {
+                self.state.performance_anchor = None;
+            }
+            self.state.last_performance_control_timestamp = Some(timestamp);
+        }
+        let anchor = match self.state.performance_anchor {
+            Some(a) => a,
+            None => *self.state.performance_anchor.insert(caller_anchor?),
+        };
+        if strategy == ReanchorAtNeutral {
+            let x = control_event.payload().to_unit_value().get();
+            let neutral = if self.settings.absolute_mode == AbsoluteMode::BipolarPerformanceControl
+            {
+                0.5
+            } else {
+                0.0
+            };
+            if (x - neutral).abs() < BASE_EPSILON {
+                self.state.performance_anchor = None;
+            }
+        }
+        Some(anchor)
+    }
+
+    /// Returns whether the given control value is close enough to the target value to be picked up
+    /// in takeover mode "Pick up (tolerant)".
+    fn is_within_pickup_tolerance(
+        &self,
+        control_value: UnitValue,
+        target_value: UnitValue,
+    ) -> bool {
+        let tolerance = self.settings.pickup_tolerance.get();
+        self.settings.takeover_mode == TakeoverMode::PickupTolerant
+            && tolerance > 0.0
+            && (control_value.get() - target_value.get()).abs() <= tolerance + BASE_EPSILON
+    }
+
+    /// Returns whether an increment arriving at the given time may be forwarded.
+    fn passes_increment_rate_limit(&mut self, timestamp: S) -> bool {
+        if self.settings.min_increment_interval.is_zero() {
+            return true;
+        }
+        if let Some(last) = self.state.last_forwarded_increment_timestamp {
+            if timestamp - last < self.settings.min_increment_interval {
+                return false;
+            }
+        }
+        self.state.last_forwarded_increment_timestamp = Some(timestamp);
+        true
+    }
+
+    /// Returns an on or off value whenever the corresponding threshold is crossed, otherwise
+    /// `None`.
+    fn apply_schmitt_trigger(
+        &mut self,
+        v: AbsoluteValue,
+        interval: &Interval<UnitValue>,
+    ) -> Option<AbsoluteValue> {
+        let v = v.to_unit_value();
+        if !self.state.schmitt_trigger_is_on && v >= interval.max_val() {
+            self.state.schmitt_trigger_is_on = true;
+            Some(AbsoluteValue::Continuous(UnitValue::MAX))
+        } else if self.state.schmitt_trigger_is_on && v <= interval.min_val() {
+            self.state.schmitt_trigger_is_on = false;
+            Some(AbsoluteValue::Continuous(UnitValue::MIN))
+        } else {
+            None
+        }
+    }
+
+    /// Makes the given discrete increment larger the faster increments arrive.
+    fn accelerate_increment(
+        &mut self,
+        control_event: ControlEvent<Increment, S>,
+        control_type: ControlType,
+    ) -> ControlEvent<Increment, S> {
+        let previous_event = self.state.previous_increment_event.replace(control_event);
+        let Increment::Discrete(inc) = control_event.payload() else {
+            return control_event;
+        };
+        let Some(previous_event) = previous_event else {
+            return control_event;
+        };
+        if previous_event.payload().is_positive() != inc.is_positive() {
+            // Direction change. Start slowly.
+            return control_event;
+        }
+        let elapsed = control_event.timestamp() - previous_event.timestamp();
+        let speed = if elapsed <= ACCELERATION_FAST_INTERVAL {
+            1.0
+        } else if elapsed >= ACCELERATION_SLOW_INTERVAL {
+            0.0
+        } else {
+            let span = ACCELERATION_SLOW_INTERVAL - ACCELERATION_FAST_INTERVAL;
+            (ACCELERATION_SLOW_INTERVAL - elapsed).as_secs_f64() / span.as_secs_f64()
+        };
+        let max_magnitude = self.full_acceleration_magnitude(control_type, inc.is_positive());
+        let magnitude = 1 + (speed * (max_magnitude - 1) as f64).round() as u32;
+        if magnitude <= inc.get().unsigned_abs() {
+            return control_event;
+        }
+        let accelerated_inc = DiscreteIncrement::new(magnitude as i32).with_direction(inc.signum());
+        control_event.with_payload(Increment::Discrete(accelerated_inc))
+    }
+
+    /// Returns the encoder acceleration magnitude which results in the maximum step size or count.
+    fn full_acceleration_magnitude(&self, control_type: ControlType, is_increment: bool) -> u32 {
+        use ControlType::*;
+        let magnitude = match control_type {
+            AbsoluteContinuous
+            | AbsoluteContinuousRoundable { .. }
+            | AbsoluteContinuousRetriggerable => {
+                let interval = self.settings.step_size_interval_for(is_increment);
+                let min = interval.min_val().get();
+                let max = interval.max_val().get();
+                if min == 0.0 {
+                    1
+                } else {
+                    (max / min).round() as u32
+                }
+            }
+            _ => {
+                let interval = self.settings.step_factor_interval_for(is_increment);
+                let min = interval.min_val().get();
+                let max = interval.max_val().get();
+                if min < 0 && max > 0 {
+                    (max - min) as u32
+                } else {
+                    (max - min) as u32 + 1
+                }
+            }
+        };
+        magnitude.max(1)
+    }
+
     fn control_absolute<
         'a,
         C: Copy + TransformationInputProvider<T::AdditionalInput> + Into<TC>,
@@ -1054,6 +1907,24 @@ where
         // Filter presses/releases. Makes sense only for absolute mode "Normal". If this is used
         // a filter is used with another absolute mode, it's considered a usage fault.
         let mut v = control_event.payload();
+        // Velocity filter
+        if consider_press_duration && !self.passes_velocity_filter(v) {
+            return None;
+        }
+        // Velocity curve (values coming from polling have been shaped already)
+        if consider_press_duration && self.settings.velocity_sensitive_source {
+            v = self.apply_velocity_curve(v);
+        }
+        // Hysteresis
+        if consider_press_duration && !self.passes_hysteresis(v) {
+            return None;
+        }
+        // Schmitt trigger
+        if consider_press_duration {
+            if let Some(interval) = self.settings.schmitt_trigger_interval {
+                v = self.apply_schmitt_trigger(v, &interval)?;
+            }
+        }
         // Press duration
         if consider_press_duration {
             // When press duration is considered (in all cases except polling), the press duration processor
@@ -1061,7 +1932,11 @@ where
             v = self
                 .state
                 .press_duration_processor
-                .process_press_or_release(v, self.settings.button_usage)?;
+                .process_press_or_release(
+                    v,
+                    self.settings.button_usage,
+                    control_event.timestamp(),
+                )?;
         } else {
             // When press duration must not be considered (when polling), process the button usage settings right here
             if self.settings.button_usage.should_ignore(v) {
@@ -1072,6 +1947,9 @@ where
         let control_event = control_event.with_payload(v);
         use AbsoluteMode::*;
         match self.settings.absolute_mode {
+            Normal if self.settings.revert_on_release => {
+                self.control_absolute_capture_and_revert(control_event, target, context)
+            }
             Normal => Some(self.control_absolute_normal(control_event, target, context, None)?),
             IncrementalButton => self.control_absolute_incremental_buttons(
                 control_event.with_payload(v.to_unit_value()),
@@ -1081,18 +1959,38 @@ where
             ),
             ToggleButton => Some(
                 self.control_absolute_toggle_buttons(v, target, context)?
+                    .map(ControlValue::from_absolute),
+            ),
+            AbToggleButton => Some(
+                self.control_absolute_ab_toggle_buttons(v, target, context)?
+                    .map(|v| ControlValue::AbsoluteContinuous(v.to_unit_value())),
+            ),
+            MultiStateToggleButton => Some(
+                self.control_absolute_multi_state_toggle_buttons(v, target, context)?
+                    .map(|v| ControlValue::AbsoluteContinuous(v.to_unit_value())),
+            ),
+            HybridToggleButton => Some(
+                self.control_absolute_hybrid_toggle_buttons(control_event, target, context)?
                     .map(|v| ControlValue::AbsoluteContinuous(v.to_unit_value())),
             ),
             MakeRelative => {
                 self.control_absolute_to_relative(control_event, target, context, options)
             }
-            PerformanceControl => Some(self.control_absolute_normal(
-                control_event,
-                target,
-                context,
-                last_non_performance_target_value,
-            )?),
-        }
+            SampleAndHold => {
+                let result = self.control_absolute_normal(control_event, target, context, None)?;
+                let ModeControlResult::HitTarget { value } = result else {
+                    return Some(result);
+                };
+                // Hold instead of hitting the target right now
+                self.state.held_sample = Some(value);
+                Some(ModeControlResult::LeaveTargetUntouched(value))
+            }
+            PerformanceControl | BipolarPerformanceControl => {
+                let anchor = self
+                    .resolve_performance_anchor(control_event, last_non_performance_target_value);
+                Some(self.control_absolute_normal(control_event, target, context, anchor)?)
+            }
+        }
     }
 
     /// Processes the given control value in absolute mode and maybe returns an appropriate target
@@ -1156,6 +2054,36 @@ where
         }
     }
 
+    /// Shapes the given button velocity. Releases (zero) are left alone.
+    fn apply_velocity_curve(&self, v: AbsoluteValue) -> AbsoluteValue {
+        let curve = &self.settings.velocity_curve;
+        if curve.is_linear() || v.is_zero() {
+            return v;
+        }
+        match v {
+            AbsoluteValue::Continuous(uv) => AbsoluteValue::Continuous(curve.apply(uv)),
+            AbsoluteValue::Discrete(f) => {
+                let uv = curve.apply(f.to_unit_value());
+                let actual = (uv.get() * f.max_val() as f64).round() as u32;
+                // A press must stay a press
+                AbsoluteValue::Discrete(f.with_actual(actual.max(1)))
+            }
+        }
+    }
+
+    fn apply_center_deadzone(&self, v: AbsoluteValue) -> AbsoluteValue {
+        let deadzone = self.settings.center_deadzone.get();
+        if deadzone <= 0.0 {
+            return v;
+        }
+        match v {
+            AbsoluteValue::Continuous(uv) if (uv.get() - 0.5).abs() <= deadzone => {
+                AbsoluteValue::Continuous(UnitValue::new(0.5))
+            }
+            _ => v,
+        }
+    }
+
     fn pre_process_absolute_value(
         &mut self,
         control_event: ControlEvent<AbsoluteValue, S>,
@@ -1172,15 +2100,14 @@ where
             (control_value, MinIsMaxBehavior::PreferOne)
         } else {
             // Control value is outside source value interval
-            // TODO-high-discrete Check if the lack of `use_discrete_processing` is a problem here (that
-            //  we use the discrete interval although it's not currently set). It shouldn't
-            //  cause an issue because it only has an effect if source min/max are non-default
-            //  and then this will be normalized to 0.0 or 1.0 anyway in the next step.
-            //  However, we should make this more clear.
-            // TODO-high-discrete Having all the dead code for the discrete processing logic is not good.
-            //  That code needs to grow with the rest. Idea: Unlock discrete processing at first
-            //  with only a few very simple operators. Hide the rest.
-            //  Unlock more complicated ones later if necessary.
+            let control_value = if self.settings.use_discrete_processing {
+                control_value
+            } else {
+                // Without discrete processing, the continuous source interval was decisive for
+                // the match, so it must also be the one providing the min/max. Nothing gets lost
+                // because the value ends up as 0% or 100% anyway.
+                control_value.to_continuous_value()
+            };
             self.settings.out_of_range_behavior.process(
                 control_value,
                 interval_match_result,
@@ -1197,6 +2124,9 @@ where
             self.settings.use_discrete_processing,
             BASE_EPSILON,
         );
+        // 2. Apply center deadzone
+        let source_normalized_control_value =
+            self.apply_center_deadzone(source_normalized_control_value);
         // Memorize as previous value for next control cycle.
         let prev_absolute_control_event = self
             .state
@@ -1222,7 +2152,6 @@ where
         context: C,
         options: ModeControlOptions,
     ) -> Option<ModeControlResult<ControlValue>> {
-        // TODO-high-discrete In discrete processing, don't interpret current target value as percentage!
         if control_event.payload().is_zero()
             || !self
                 .settings
@@ -1240,6 +2169,7 @@ where
             // velocity. Second, using "Incremental buttons" with "Make absolute" is probably
             // something people rarely use.
             let discrete_increment = self.convert_to_discrete_increment(control_event.payload())?;
+            let use_discrete_processing = self.settings.use_discrete_processing;
             Some(
                 self.control_relative_to_absolute(
                     control_event.with_payload(Increment::Discrete(discrete_increment)),
@@ -1247,7 +2177,14 @@ where
                     context,
                     options,
                 )?
-                .map(|v| ControlValue::AbsoluteContinuous(v.to_unit_value())),
+                .map(|v| {
+                    if use_discrete_processing {
+                        // Discrete targets receive a step instead of a percentage
+                        ControlValue::from_absolute(v)
+                    } else {
+                        ControlValue::AbsoluteContinuous(v.to_unit_value())
+                    }
+                }),
             )
         } else {
             // Initiate relative control via incremental buttons
@@ -1292,13 +2229,16 @@ where
                 // - Minimum target step size (enables accurate minimum increment, atomic)
                 // - Maximum target step size (enables accurate maximum increment, clamped)
                 // - Target value interval (absolute, important for rotation only, clamped)
+                let step_size_interval = self
+                    .settings
+                    .effective_step_size_interval_for(!self.settings.reverse);
                 let step_size_value = control_value
                     .normalize(
                         &self.settings.source_value_interval,
                         MinIsMaxBehavior::PreferOne,
                         BASE_EPSILON
                     )
-                    .denormalize(&self.settings.step_size_interval);
+                    .denormalize(&step_size_interval);
                 let step_size_increment =
                     step_size_value.to_increment(negative_if(self.settings.reverse))?;
                 self.hit_target_absolutely_with_unit_increment(
@@ -1356,14 +2296,43 @@ where
         target: &impl Target<'a, Context = TC>,
         context: C,
     ) -> Option<ModeControlResult<AbsoluteValue>> {
-        // TODO-high-discrete In discrete processing, don't interpret current target value as
-        //  percentage!
         if control_value.is_zero() {
             return None;
         }
+        if self.settings.use_internal_toggle_state {
+            self.state.internal_toggle_is_on = !self.state.internal_toggle_is_on;
+            let final_absolute_value = self.get_final_absolute_value(
+                AbsoluteValue::Continuous(self.internal_toggle_target_value()),
+                target.control_type(context.into()),
+            );
+            return Some(ModeControlResult::hit_target(final_absolute_value));
+        }
         // Nothing we can do if we can't get the current target value. This shouldn't happen
         // usually because virtual targets are not supposed to be used with toggle mode.
         let current_target_value = target.current_value(context.into())?;
+        if self.settings.use_discrete_processing {
+            if let AbsoluteValue::Discrete(f) = current_target_value {
+                // Don't interpret current target value as percentage
+                let interval = &self.settings.discrete_target_value_interval;
+                let (min, max) = (interval.min_val(), interval.max_val().min(f.max_val()));
+                let desired_target_value = if min == max {
+                    // Special case #452 (target min == target max)
+                    if f.actual() == min {
+                        0
+                    } else {
+                        max
+                    }
+                } else if f.actual() as u64 * 2 > min as u64 + max as u64 {
+                    // Within the second half of the target range (considered as on)
+                    min
+                } else {
+                    max
+                };
+                return Some(ModeControlResult::hit_target(AbsoluteValue::Discrete(
+                    f.with_actual(desired_target_value),
+                )));
+            }
+        }
         let desired_target_value = if self.settings.target_value_interval.min_is_max(BASE_EPSILON) {
             // Special case #452 (target min == target max).
             // Make it usable for exclusive toggle buttons.
@@ -1401,6 +2370,163 @@ where
         Some(ModeControlResult::hit_target(final_absolute_value))
     }
 
+    /// Returns the target value corresponding to the internal toggle state.
+    fn internal_toggle_target_value(&self) -> UnitValue {
+        let interval = &self.settings.target_value_interval;
+        if self.state.internal_toggle_is_on {
+            interval.max_val()
+        } else {
+            interval.min_val()
+        }
+    }
+
+    /// Returns whether feedback should be derived from the internal toggle state instead of the
+    /// target value.
+    fn uses_internal_toggle_state(&self) -> bool {
+        self.settings.use_internal_toggle_state
+            && self.settings.absolute_mode == AbsoluteMode::ToggleButton
+    }
+
+    /// Switches the target value to value B if it's currently closer to value A and vice versa.
+    fn control_absolute_ab_toggle_buttons<'a, C: Copy + Into<TC>, TC>(
+        &mut self,
+        control_value: AbsoluteValue,
+        target: &impl Target<'a, Context = TC>,
+        context: C,
+    ) -> Option<ModeControlResult<AbsoluteValue>> {
+        if control_value.is_zero() {
+            return None;
+        }
+        let current_target_value = target.current_value(context.into())?;
+        let [a, b] = self.settings.ab_toggle_values;
+        let desired_target_value = if self.ab_toggle_b_is_active(current_target_value) {
+            a
+        } else {
+            b
+        };
+        let final_absolute_value = self.get_final_absolute_value(
+            AbsoluteValue::Continuous(desired_target_value),
+            target.control_type(context.into()),
+        );
+        Some(ModeControlResult::hit_target(final_absolute_value))
+    }
+
+    /// Processes button presses like in normal mode but captures the target value from before the
+    /// press and restores it on release.
+    fn control_absolute_capture_and_revert<
+        'a,
+        C: Copy + TransformationInputProvider<T::AdditionalInput> + Into<TC>,
+        TC,
+    >(
+        &mut self,
+        control_event: ControlEvent<AbsoluteValue, S>,
+        target: &impl Target<'a, Context = TC>,
+        context: C,
+    ) -> Option<ModeControlResult<ControlValue>> {
+        if control_event.payload().is_zero() {
+            // Release
+            let captured_value = self.state.captured_target_value.take()?;
+            return Some(ModeControlResult::hit_target(ControlValue::from_absolute(
+                captured_value,
+            )));
+        }
+        // Press (capture only once, velocity changes might send several presses)
+        if self.state.captured_target_value.is_none() {
+            self.state.captured_target_value = target.current_value(context.into());
+        }
+        self.control_absolute_normal(control_event, target, context, None)
+    }
+
+    /// Returns whether the given target value is closer to value B than to value A.
+    fn ab_toggle_b_is_active(&self, target_value: AbsoluteValue) -> bool {
+        let [a, b] = self.settings.ab_toggle_values;
+        let v = target_value.to_unit_value().get();
+        (v - b.get()).abs() < (v - a.get()).abs()
+    }
+
+    /// Toggles the target value on press. If the button is held at least as long as the hold
+    /// threshold, the target value from before the press is restored on release (momentary
+    /// behavior). Otherwise, it stays toggled (latch behavior).
+    fn control_absolute_hybrid_toggle_buttons<'a, C: Copy + Into<TC>, TC>(
+        &mut self,
+        control_event: ControlEvent<AbsoluteValue, S>,
+        target: &impl Target<'a, Context = TC>,
+        context: C,
+    ) -> Option<ModeControlResult<AbsoluteValue>> {
+        let control_value = control_event.payload();
+        if control_value.is_zero() {
+            // Release
+            let press = self.state.hybrid_press.take()?;
+            let hold_duration = control_event.timestamp() - press.time;
+            if hold_duration < self.settings.hold_threshold {
+                // Tap. Latch.
+                return None;
+            }
+            // Hold. Restore.
+            let final_absolute_value = self.get_final_absolute_value(
+                press.pre_press_value,
+                target.control_type(context.into()),
+            );
+            return Some(ModeControlResult::hit_target(final_absolute_value));
+        }
+        // Press
+        let pre_press_value = target.current_value(context.into())?;
+        let result = self.control_absolute_toggle_buttons(control_value, target, context)?;
+        self.state.hybrid_press = Some(HybridPress {
+            time: control_event.timestamp(),
+            pre_press_value,
+        });
+        Some(result)
+    }
+
+    /// Advances the target value to the next entry of the target value sequence on each button
+    /// press. The current position is derived from the sequence entry which is closest to the
+    /// current target value. At the end of the sequence, jumps back to the first entry if wrap is
+    /// enabled, otherwise stays at the last one.
+    ///
+    /// Falls back to normal toggle behavior if no target value sequence is defined.
+    fn control_absolute_multi_state_toggle_buttons<'a, C: Copy + Into<TC>, TC>(
+        &mut self,
+        control_value: AbsoluteValue,
+        target: &impl Target<'a, Context = TC>,
+        context: C,
+    ) -> Option<ModeControlResult<AbsoluteValue>> {
+        if self.state.unpacked_target_value_sequence.is_empty() {
+            return self.control_absolute_toggle_buttons(control_value, target, context);
+        }
+        if control_value.is_zero() {
+            return None;
+        }
+        let current_target_value = target.current_value(context.into())?.to_unit_value();
+        let sequence = &self.state.unpacked_target_value_sequence;
+        let current_index = sequence
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                let distance_a = (a.get() - current_target_value.get()).abs();
+                let distance_b = (b.get() - current_target_value.get()).abs();
+                distance_a.total_cmp(&distance_b)
+            })
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        let next_index = if current_index + 1 < sequence.len() {
+            current_index + 1
+        } else if self.settings.rotate {
+            0
+        } else {
+            // Reached the end and wrap is disabled
+            return Some(ModeControlResult::LeaveTargetUntouched(
+                AbsoluteValue::Continuous(current_target_value),
+            ));
+        };
+        let desired_target_value = sequence[next_index];
+        let final_absolute_value = self.get_final_absolute_value(
+            AbsoluteValue::Continuous(desired_target_value),
+            target.control_type(context.into()),
+        );
+        Some(ModeControlResult::hit_target(final_absolute_value))
+    }
+
     /// Absolute-to-relative conversion mode.
     fn control_absolute_to_relative<
         'a,
@@ -1426,24 +2552,31 @@ where
         };
         let res = self.pre_process_absolute_value(control_event)?;
         // We can't do anything without having a previous value to relate to.
-        let prev_control_value = res.prev_control_event?;
+        let Some(prev_control_value) = res.prev_control_event else {
+            // Fresh start, so a remainder from earlier movements must not cause a step.
+            self.state.make_relative_remainder = 0.0;
+            return None;
+        };
         let increment = match res.control_event.payload() {
             AbsoluteValue::Continuous(v) => {
                 // This is kind of new: Continuous relative increments.
                 let prev_control_value = prev_control_value.payload().continuous_value()?;
-                let diff = v.get() - prev_control_value.get();
-                let increment = UnitIncrement::try_from(diff).ok()?;
+                let diff =
+                    (v.get() - prev_control_value.get()) * self.settings.make_relative_sensitivity;
+                let increment = UnitIncrement::try_from(diff.clamp(-1.0, 1.0)).ok()?;
                 Increment::Continuous(increment)
             }
             AbsoluteValue::Discrete(f) => {
                 // This is easy and accurate! Conforms to our normal relative control stuff,
                 // which has always been discrete in nature.
-                // Be aware, even if the source emits discrete values, if source min/max is set,
-                // we won't arrive in this match branch because discrete processing is not unlocked
-                // yet!
                 let prev_control_value = prev_control_value.payload().discrete_value()?;
                 let diff = f.actual() as i32 - prev_control_value.actual() as i32;
-                let increment = DiscreteIncrement::try_from(diff).ok()?;
+                // Carry the remainder, otherwise single steps would get lost with low sensitivity
+                let scaled_diff = diff as f64 * self.settings.make_relative_sensitivity
+                    + self.state.make_relative_remainder;
+                let rounded_diff = scaled_diff.round();
+                self.state.make_relative_remainder = scaled_diff - rounded_diff;
+                let increment = DiscreteIncrement::try_from(rounded_diff as i32).ok()?;
                 Increment::Discrete(increment)
             }
         };
@@ -1474,10 +2607,13 @@ where
         options: ModeControlOptions,
     ) -> Option<ModeControlResult<AbsoluteValue>> {
         // Convert to absolute value
+        let step_size_interval = self
+            .settings
+            .step_size_interval_for(control_event.payload().is_positive());
         let mut inc = control_event
             .payload()
-            .to_unit_increment(self.settings.step_size_interval.min_val())?;
-        inc = inc.clamp_to_interval(&self.settings.step_size_interval)?;
+            .to_unit_increment(step_size_interval.min_val())?;
+        inc = inc.clamp_to_interval(step_size_interval)?;
         let full_unit_interval = full_unit_interval();
         let abs_input_value = if options.enforce_rotate || self.settings.rotate {
             self.state
@@ -1565,7 +2701,7 @@ where
                 //
                 // Settings which are necessary in order to support >1-increments:
                 // - Maximum target step size (enables accurate maximum increment, clamped)
-                let potentially_reversed_increment = if self.settings.reverse {
+                let potentially_reversed_increment = if self.settings.reverses_increment(increment.is_positive()) {
                     increment.inverse()
                 } else {
                     increment
@@ -1576,9 +2712,10 @@ where
                         UnitIncrement::try_from(i.get() * target_scale_factor).ok()?
                     },
                     Increment::Discrete(i) => {
-                        let unit_increment = i
-                            .to_unit_increment(self.settings.step_size_interval.min_val())?;
-                        unit_increment.clamp_to_interval(&self.settings.step_size_interval)?
+                        let step_size_interval =
+                            self.settings.effective_step_size_interval_for(increment.is_positive());
+                        let unit_increment = i.to_unit_increment(step_size_interval.min_val())?;
+                        unit_increment.clamp_to_interval(&step_size_interval)?
                     }
                 };
                 self.hit_target_absolutely_with_unit_increment(
@@ -1638,7 +2775,19 @@ where
         let current = target.current_value(context.into())?.to_unit_value();
         let target_value_set = &self.state.unpacked_target_value_set;
         use std::ops::Bound::*;
-        let mut v = current;
+        let mut v = if self.settings.snap_to_target_value_sequence {
+            target_value_set
+                .iter()
+                .copied()
+                .min_by(|a, b| {
+                    let distance_a = (a.get() - current.get()).abs();
+                    let distance_b = (b.get() - current.get()).abs();
+                    distance_a.total_cmp(&distance_b)
+                })
+                .unwrap_or(current)
+        } else {
+            current
+        };
         for _ in 0..discrete_increment.get().abs() {
             let next_value_in_direction = if discrete_increment.is_positive() {
                 target_value_set
@@ -1692,7 +2841,17 @@ where
             let y_last = y_last.to_unit_value().get();
             let target_min = self.settings.target_value_interval.min_val().get();
             let target_max = self.settings.target_value_interval.max_val().get();
-            let y = if self.settings.reverse {
+            let y = if self.settings.absolute_mode == AbsoluteMode::BipolarPerformanceControl {
+                // Lower half cuts, upper half boosts
+                let x = if self.settings.reverse { 1.0 - x } else { x };
+                if x < 0.5 {
+                    let span = (y_last - target_min).max(0.0);
+                    y_last - (0.5 - x) * 2.0 * span
+                } else {
+                    let span = (target_max - y_last).max(0.0);
+                    y_last + (x - 0.5) * 2.0 * span
+                }
+            } else if self.settings.reverse {
                 let span = (y_last - target_min).max(0.0);
                 y_last - x * span
             } else {
@@ -1704,7 +2863,11 @@ where
         } else {
             false
         };
-        // 2. Apply transformation
+        // 2. Apply response curve
+        if let AbsoluteValue::Continuous(uv) = v {
+            v = AbsoluteValue::Continuous(self.settings.response_curve.apply(uv));
+        }
+        // 3. Apply transformation
         if let Some(transformation) = self.settings.control_transformation.as_ref() {
             if let Ok(output) = v.transform(
                 transformation,
@@ -1727,11 +2890,13 @@ where
             if self.settings.round_target_value {
                 v = v.round(control_type);
             };
+            v = self.apply_quantization(v);
+            v = self.apply_discrete_target_rounding(control_type, v);
         } else {
             // No performance control
-            // 3. Apply reverse
+            // 4. Apply reverse
             v = self.apply_reverse(control_type, v);
-            // 4. Apply target interval and rounding OR target value sequence
+            // 5. Apply target interval and rounding OR target value sequence
             v = self.apply_rounded_target_interval_or_target_sequence(control_type, v);
         }
         // Return
@@ -1754,21 +2919,62 @@ where
             if self.settings.round_target_value {
                 v = v.round(control_type);
             };
+            v = self.apply_quantization(v);
+            v = self.apply_discrete_target_rounding(control_type, v);
         } else {
             // We have a target value sequence. Apply it.
-            let max_index = self.state.unpacked_target_value_sequence.len() - 1;
-            let seq_index = (v.to_unit_value().get() * max_index as f64).round() as usize;
-            let unit_value = self
-                .state
-                .unpacked_target_value_sequence
-                .get(seq_index)
-                .copied()
-                .unwrap_or_default();
+            let sequence = &self.state.unpacked_target_value_sequence;
+            let max_index = sequence.len() - 1;
+            let position = v.to_unit_value().get() * max_index as f64;
+            let unit_value = match self.settings.target_value_sequence.interpolation() {
+                ValueSequenceInterpolation::Snap => sequence
+                    .get(position.round() as usize)
+                    .copied()
+                    .unwrap_or_default(),
+                ValueSequenceInterpolation::Linear => {
+                    let lower_index = (position.floor() as usize).min(max_index);
+                    let upper_index = (lower_index + 1).min(max_index);
+                    let fraction = position - lower_index as f64;
+                    let lower = sequence[lower_index].get();
+                    let upper = sequence[upper_index].get();
+                    UnitValue::new_clamped(lower + (upper - lower) * fraction)
+                }
+            };
             v = AbsoluteValue::Continuous(unit_value);
         }
         v
     }
 
+    /// Discrete processing: Rounds continuous values which arrive at a discrete target to the
+    /// nearest target step, so the target receives a step instead of a percentage.
+    fn apply_discrete_target_rounding(
+        &self,
+        control_type: ControlType,
+        v: AbsoluteValue,
+    ) -> AbsoluteValue {
+        if !self.settings.use_discrete_processing {
+            return v;
+        }
+        let (AbsoluteValue::Continuous(uv), ControlType::AbsoluteDiscrete { .. }) =
+            (v, control_type)
+        else {
+            return v;
+        };
+        let Some(discrete_max) = control_type.discrete_max() else {
+            return v;
+        };
+        AbsoluteValue::Discrete(Fraction::new(uv.to_discrete(discrete_max), discrete_max))
+    }
+
+    fn apply_quantization(&self, v: AbsoluteValue) -> AbsoluteValue {
+        match (self.settings.quantize_step, v) {
+            (Some(step), AbsoluteValue::Continuous(uv)) => {
+                AbsoluteValue::Continuous(uv.snap_to_grid_by_interval_size(step))
+            }
+            _ => v,
+        }
+    }
+
     fn apply_reverse(&self, control_type: ControlType, mut v: AbsoluteValue) -> AbsoluteValue {
         if !self.settings.reverse {
             return v;
@@ -1867,26 +3073,55 @@ where
             .to_unit_value();
         let current_prepped_value = prepped_control_value.to_unit_value();
         let current_target_value = current_target_value;
-        let jump_max = self.settings.jump_interval.max_val();
+        let is_upward = current_prepped_value >= current_target_value.to_unit_value();
+        let jump_interval = *self.settings.jump_interval_for(is_upward);
+        let jump_max = jump_interval.max_val();
         let distance_to_target_value = if self.settings.use_discrete_processing {
             prepped_control_value.calc_distance_from(current_target_value)
         } else {
             prepped_control_value.calc_distance_from(current_target_value.to_continuous_value())
         };
+        let discrete_values = if self.settings.use_discrete_processing {
+            DiscreteTakeoverValues::from_absolute_values(
+                prev_control_event.payload(),
+                control_event.payload(),
+                prev_jump_state.prepped_control_event.payload(),
+                prepped_control_value,
+                current_target_value,
+            )
+        } else {
+            None
+        };
         // Check if in sync
-        let takeover_in_sync = takeover_is_in_sync(
-            prev_prepped_value,
+        let takeover_in_sync = self.is_within_pickup_tolerance(
             current_prepped_value,
             current_target_value.to_unit_value(),
-            prev_jump_state.takeover_in_sync,
-            is_new_move,
-            jump_max,
-        );
+        ) || match &discrete_values {
+            None => takeover_is_in_sync(
+                prev_prepped_value.get(),
+                current_prepped_value.get(),
+                current_target_value.to_unit_value().get(),
+                prev_jump_state.takeover_in_sync,
+                is_new_move,
+                (!jump_max.is_one()).then_some(jump_max.get()),
+            ),
+            Some(values) => {
+                let discrete_jump_max = self.settings.discrete_jump_interval.max_val();
+                takeover_is_in_sync(
+                    values.prev_prepped_value.actual() as f64,
+                    values.current_prepped_value.actual() as f64,
+                    values.current_target_value.actual() as f64,
+                    prev_jump_state.takeover_in_sync,
+                    is_new_move,
+                    (discrete_jump_max != u32::MAX).then_some(discrete_jump_max as f64),
+                )
+            }
+        };
         if takeover_in_sync {
             // No parameter jump to be expected (at least no unwanted one).
             // Check if distance too small (only for being backward compatible with old presets).
             if distance_to_target_value.is_lower_than(
-                self.settings.jump_interval.min_val(),
+                jump_interval.min_val(),
                 self.settings.discrete_jump_interval.min_val(),
             ) {
                 return None;
@@ -1903,6 +3138,16 @@ where
             return result;
         }
         // Check for controller jumps
+        if let Some(values) = discrete_values {
+            let result = self.take_over_discretely(values, control_type);
+            self.state.previous_jump_prevention_state = Some(JumpPreventionState::new(
+                prepped_control_event,
+                false,
+                result,
+                &prev_jump_state,
+            ));
+            return result;
+        }
         let result = match self.settings.takeover_mode {
             TakeoverMode::Off => unreachable!(),
             TakeoverMode::Pickup | TakeoverMode::PickupTolerant => {
@@ -1910,7 +3155,6 @@ where
                 None
             }
             TakeoverMode::Parallel => {
-                // TODO-high-discrete Implement advanced takeover modes for discrete values, too
                 // We look at source-normalized values, not pepped up values. Because we are
                 // interested in the relative movement of the fader/knob, not the more
                 // processed values that eventually will hit the target.
@@ -1919,8 +3163,10 @@ where
                     None
                 } else {
                     let relative_increment = UnitIncrement::new_clamped(relative_increment);
-                    let restrained_increment =
-                        relative_increment.clamp_to_interval(&self.settings.jump_interval)?;
+                    let restrained_increment = relative_increment.clamp_to_interval(
+                        self.settings
+                            .jump_interval_for(relative_increment.is_positive()),
+                    )?;
                     let final_target_value = current_target_value.to_unit_value().add_clamping(
                         restrained_increment,
                         &self.settings.target_value_interval,
@@ -1937,7 +3183,7 @@ where
                 // This takeover mode can actually work without a previous value. But let's keep
                 // things simple. The in-sync detection needs a previous value anyway.
                 let approach_distance = distance_to_target_value.denormalize(
-                    &self.settings.jump_interval,
+                    &jump_interval,
                     &self.settings.discrete_jump_interval,
                     self.settings.use_discrete_processing,
                     control_type.discrete_max(),
@@ -1985,8 +3231,8 @@ where
                         let scaled_increment = relative_increment * target_distance_from_bound
                             / source_distance_from_bound;
                         let scaled_increment = UnitIncrement::new_clamped(scaled_increment);
-                        let restrained_increment =
-                            scaled_increment.clamp_to_interval(&self.settings.jump_interval)?;
+                        let restrained_increment = scaled_increment
+                            .clamp_to_interval(self.settings.jump_interval_for(goes_up))?;
                         let final_target_value = current_target_value.add_clamping(
                             restrained_increment,
                             &self.settings.target_value_interval,
@@ -2010,6 +3256,81 @@ where
         result
     }
 
+    /// Discrete counterpart of the advanced takeover modes. Works with step counts instead of
+    /// percentages, so nothing gets lost due to scaling.
+    fn take_over_discretely(
+        &self,
+        values: DiscreteTakeoverValues,
+        control_type: ControlType,
+    ) -> Option<ModeControlResult<AbsoluteValue>> {
+        let jump_interval = &self.settings.discrete_jump_interval;
+        let jump_max = i32::try_from(jump_interval.max_val()).unwrap_or(i32::MAX);
+        let restrain = |increment: i32| -> Option<DiscreteIncrement> {
+            DiscreteIncrement::new_checked(increment.signum() * increment.abs().min(jump_max))
+        };
+        let target_interval = &self.settings.discrete_target_value_interval;
+        let current_target_value = values.current_target_value;
+        let current_target_actual = current_target_value.actual() as i32;
+        let increment = match self.settings.takeover_mode {
+            TakeoverMode::Off => unreachable!(),
+            TakeoverMode::Pickup | TakeoverMode::PickupTolerant => {
+                // Scaling not desired. Do nothing.
+                return None;
+            }
+            TakeoverMode::Parallel => restrain(
+                values.current_control_value.actual() as i32
+                    - values.prev_control_value.actual() as i32,
+            )?,
+            TakeoverMode::LongTimeNoSee => {
+                let diff = values.current_prepped_value.actual() as i32 - current_target_actual;
+                let approach_distance = (jump_interval.min_val() as i32)
+                    .saturating_add(diff.abs())
+                    .min(jump_max);
+                DiscreteIncrement::new_checked(diff.signum() * approach_distance)?
+            }
+            TakeoverMode::CatchUp => {
+                let prev_control_actual = values.prev_control_value.actual() as i32;
+                let relative_increment =
+                    values.current_control_value.actual() as i32 - prev_control_actual;
+                if relative_increment == 0 {
+                    return None;
+                }
+                let goes_up = relative_increment > 0;
+                let source_distance_from_bound = if goes_up {
+                    values.prev_control_value.max_val() as i32 - prev_control_actual
+                } else {
+                    prev_control_actual
+                };
+                let target_max = target_interval
+                    .max_val()
+                    .min(current_target_value.max_val());
+                let target_distance_from_bound = if goes_up {
+                    target_max as i32 - current_target_actual
+                } else {
+                    current_target_actual - target_interval.min_val() as i32
+                };
+                if source_distance_from_bound <= 0 || target_distance_from_bound <= 0 {
+                    return None;
+                }
+                let scaled_increment = relative_increment as f64
+                    * target_distance_from_bound as f64
+                    / source_distance_from_bound as f64;
+                // Round away from zero, otherwise small movements would never catch up
+                let scaled_increment =
+                    scaled_increment.abs().ceil() as i32 * scaled_increment.signum() as i32;
+                restrain(scaled_increment)?
+            }
+        };
+        let final_target_value = current_target_value
+            .add_clamping(increment, target_interval)
+            .with_max(current_target_value.max_val());
+        self.hit_if_changed(
+            AbsoluteValue::Discrete(final_target_value),
+            AbsoluteValue::Discrete(current_target_value),
+            control_type,
+        )
+    }
+
     fn hit_if_changed(
         &self,
         desired_target_value: AbsoluteValue,
@@ -2092,6 +3413,11 @@ where
             }
         } else {
             // Continuous processing although target is discrete. Kept for backward compatibility.
+            let increment = if self.settings.accumulate_fractional_steps {
+                self.accumulate_fractional_steps(increment, target_step_size)?
+            } else {
+                increment
+            };
             self.hit_target_absolutely_with_unit_increment(
                 increment.to_unit_increment(target_step_size)?,
                 // In order to not end up on "in-between" values, we should snap the target
@@ -2104,6 +3430,35 @@ where
         }
     }
 
+    /// Accumulates continuous increments until they add up to at least one whole target step.
+    ///
+    /// Returns the whole steps as discrete increment and keeps the remainder for the next time.
+    /// A direction change discards the remainder.
+    fn accumulate_fractional_steps(
+        &mut self,
+        increment: Increment,
+        target_step_size: UnitValue,
+    ) -> Option<Increment> {
+        let Increment::Continuous(i) = increment else {
+            self.state.fractional_step_accumulator = 0.0;
+            return Some(increment);
+        };
+        let step_size = target_step_size.get();
+        if step_size <= 0.0 {
+            return Some(increment);
+        }
+        let prev_acc = self.state.fractional_step_accumulator;
+        let acc = if prev_acc == 0.0 || prev_acc.signum() == i.get().signum() {
+            prev_acc + i.get()
+        } else {
+            i.get()
+        };
+        let steps = (acc / step_size + acc.signum() * BASE_EPSILON).trunc();
+        self.state.fractional_step_accumulator = acc - steps * step_size;
+        let discrete_increment = DiscreteIncrement::new_checked(steps as i32)?;
+        Some(Increment::Discrete(discrete_increment))
+    }
+
     /// Takes care of:
     /// - Snap target interval to grid if desired (if `grid_interval_size` is given)
     /// - Applying increment
@@ -2199,7 +3554,7 @@ where
     ///
     /// - Reverse
     fn prepare_continuous_increment(&mut self, increment: UnitIncrement) -> Option<UnitIncrement> {
-        let result = if self.settings.reverse {
+        let result = if self.settings.reverses_increment(increment.is_positive()) {
             increment.inverse()
         } else {
             increment
@@ -2216,8 +3571,19 @@ where
         original_inc: DiscreteIncrement,
     ) -> Option<DiscreteIncrement> {
         let mut inc = original_inc;
+        // Process increment mapping table (replaces speed)
+        if let Some(table) = &self.settings.increment_mapping_table {
+            inc = table.map(inc)?;
+            if self.settings.reverses_increment(inc.is_positive()) {
+                inc = inc.inverse();
+            }
+            return Some(inc);
+        }
         // Process speed (step count)
-        let factor = inc.clamp_to_interval(&self.settings.step_factor_interval);
+        let factor = inc.clamp_to_interval(
+            self.settings
+                .step_factor_interval_for(original_inc.is_positive()),
+        );
         inc = if factor.is_positive() {
             factor
         } else {
@@ -2231,7 +3597,7 @@ where
         };
         inc = inc.with_direction(original_inc.signum());
         // Process reverse
-        if self.settings.reverse {
+        if self.settings.reverses_increment(inc.is_positive()) {
             inc = inc.inverse();
         }
         Some(inc)
@@ -2266,7 +3632,10 @@ where
                 MinIsMaxBehavior::PreferOne,
                 BASE_EPSILON,
             )
-            .denormalize_discrete_increment(&self.settings.step_factor_interval);
+            .denormalize_discrete_increment(
+                self.settings
+                    .step_factor_interval_for(!self.settings.reverse),
+            );
         // This mode supports positive increment only.
         let discrete_value = if factor.is_positive() {
             factor.to_value()
@@ -2286,19 +3655,20 @@ where
 /// This is largely based on the PR by mdmayfield: https://github.com/helgoboss/helgoboss-learn/pull/2/files
 /// He in turn took inspiration from `controllers/softtakeover.cpp` in the Mixxx DJ project.
 ///
-/// The values passed here are
+/// The values passed here can be percentages or step counts. A jump max of `None` means there's
+/// no restriction.
 fn takeover_is_in_sync(
-    prev_pep_value: UnitValue,
-    current_pep_value: UnitValue,
-    current_target_value: UnitValue,
+    prev_pep_value: f64,
+    current_pep_value: f64,
+    current_target_value: f64,
     was_in_sync_before: bool,
     is_new_move: bool,
-    jump_max: UnitValue,
+    jump_max: Option<f64>,
 ) -> bool {
     // If jump max is 100%, we are in sync by definition.
-    if jump_max.is_one() {
+    let Some(jump_max) = jump_max else {
         return true;
-    }
+    };
     // If we were in sync before and we are still in the same move, stay in sync.
     if was_in_sync_before && !is_new_move {
         return true;
@@ -2314,7 +3684,46 @@ fn takeover_is_in_sync(
         // Avoid awkward "backwards" jump when approaching the target.
         return false;
     }
-    current_distance_to_target.abs() <= jump_max.get() || (crossed_target && !is_new_move)
+    current_distance_to_target.abs() <= jump_max || (crossed_target && !is_new_move)
+}
+
+/// All values relevant for taking over in the discrete domain.
+#[derive(Copy, Clone, Debug)]
+struct DiscreteTakeoverValues {
+    prev_control_value: Fraction,
+    current_control_value: Fraction,
+    prev_prepped_value: Fraction,
+    current_prepped_value: Fraction,
+    current_target_value: Fraction,
+}
+
+impl DiscreteTakeoverValues {
+    /// Returns `None` if not all of the given values are discrete.
+    fn from_absolute_values(
+        prev_control_value: AbsoluteValue,
+        current_control_value: AbsoluteValue,
+        prev_prepped_value: AbsoluteValue,
+        current_prepped_value: AbsoluteValue,
+        current_target_value: AbsoluteValue,
+    ) -> Option<Self> {
+        use AbsoluteValue::Discrete;
+        match (
+            prev_control_value,
+            current_control_value,
+            prev_prepped_value,
+            current_prepped_value,
+            current_target_value,
+        ) {
+            (Discrete(a), Discrete(b), Discrete(c), Discrete(d), Discrete(e)) => Some(Self {
+                prev_control_value: a,
+                current_control_value: b,
+                prev_prepped_value: c,
+                current_prepped_value: d,
+                current_target_value: e,
+            }),
+            _ => None,
+        }
+    }
 }
 
 /// Time in ms between CC messages to assume they are part of the one motion.
@@ -2323,6 +3732,12 @@ fn takeover_is_in_sync(
 /// we detected change of the target by something else (not us).
 const CONTROL_MOVE_TIMEOUT: Duration = Duration::from_millis(100);
 
+/// Increments arriving with this interval or faster result in the maximum step size or count.
+const ACCELERATION_FAST_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Increments arriving with this interval or slower result in the minimum step size or count.
+const ACCELERATION_SLOW_INTERVAL: Duration = Duration::from_millis(100);
+
 struct AbsolutePreProcessingResult<S: AbstractTimestamp> {
     control_event: ControlEvent<AbsoluteValue, S>,
     prev_control_event: Option<ControlEvent<AbsoluteValue, S>>,
@@ -2342,6 +3757,61 @@ pub fn default_step_count_interval() -> Interval<DiscreteIncrement> {
     create_discrete_increment_interval(1, 1)
 }
 
+/// What caused a feedback-relevant change.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum FeedbackOrigin {
+    /// The target has just been resolved or the source connected, so the source should reflect
+    /// the current state.
+    Initial,
+    /// This mode hit the target in response to a control event (also when sampling or
+    /// switching off exclusive group members).
+    Control,
+    /// This mode hit the target while being polled (e.g. glides and transitions).
+    Poll,
+    /// A target prop changed without this mode being involved (e.g. a meter value or a change
+    /// made by another mapping).
+    PropChange,
+}
+
+impl FeedbackOrigin {
+    /// Feedback resulting from the user hitting the target via this mode is urgent, whereas
+    /// feedback caused by polling (animations) or by prop changes from elsewhere (e.g. meters)
+    /// can be sent in bulk.
+    pub fn priority(self) -> FeedbackPriority {
+        use FeedbackOrigin::*;
+        match self {
+            Initial | Control => FeedbackPriority::Urgent,
+            Poll | PropChange => FeedbackPriority::Bulk,
+        }
+    }
+}
+
+/// A value produced by the mode together with the origin of the change which caused it.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct WithFeedbackOrigin<T> {
+    pub value: T,
+    pub origin: FeedbackOrigin,
+}
+
+impl<T> WithFeedbackOrigin<T> {
+    pub fn priority(&self) -> FeedbackPriority {
+        self.origin.priority()
+    }
+}
+
+/// Priority class of a feedback value.
+///
+/// Ordered by urgency, so sorting puts urgent feedback first.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Display)]
+pub enum FeedbackPriority {
+    /// User-visible state changes that should be sent as soon as possible.
+    #[display(fmt = "Urgent")]
+    Urgent,
+    /// Meter or animation updates that can be delayed or dropped in favor of urgent ones.
+    #[display(fmt = "Bulk")]
+    Bulk,
+}
+
 /// If something like this is returned from the mode, it already means that the source value
 /// was not filtered out (e.g. because of button filter).
 #[derive(Copy, Clone, Debug)]
@@ -2401,12 +3871,11 @@ fn full_discrete_interval() -> Interval<u32> {
     Interval::new(0, u32::MAX)
 }
 
-fn textual_feedback_expression_regex() -> &'static regex::Regex {
-    regex!(r"\{\{ *([A-Za-z0-9._]+) *\}\}")
-}
-
 const DEFAULT_TEXTUAL_FEEDBACK_PROP_KEY: &str = "target.text_value";
 
+/// Label of the target value sequence entry which matches the current target value.
+pub const MODE_SEQUENCE_LABEL_PROP_KEY: &str = "mode.sequence_label";
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3134,33 +4603,83 @@ mod tests {
             }
 
             #[test]
-            fn jump_interval_max_pickup_with_target_interval() {
+            fn pickup_tolerance() {
                 // Given
                 let mut mode: TestMode = Mode::new(ModeSettings {
-                    takeover_mode: TakeoverMode::Pickup,
-                    jump_interval: create_unit_value_interval(0.0, 0.1),
-                    target_value_interval: create_unit_value_interval(0.0, 0.5),
+                    takeover_mode: TakeoverMode::PickupTolerant,
+                    pickup_tolerance: UnitValue::new(0.05),
                     ..Default::default()
                 });
-                let target = TestTarget {
-                    current_value: Some(con_val(0.1)),
+                let mut target = TestTarget {
+                    current_value: Some(con_val(0.5)),
                     control_type: ControlType::AbsoluteContinuous,
                 };
                 // When
                 // Then
                 let mut test = |i, o| {
-                    abs_con_test(&mut mode, &target, i, o);
+                    abs_con_test_cumulative(&mut mode, &mut target, i, o);
                 };
                 test(0.0, None);
-                test(0.0, Some(0.00));
-                test(0.1, Some(0.05));
-                test(0.2, None);
-                test(0.3, Some(0.15));
-                test(0.4, Some(0.20));
-                test(0.5, Some(0.25));
-                test(0.7, Some(0.35));
-                test(1.0, Some(0.5));
-            }
+                test(0.3, None);
+                test(0.44, None);
+                // Close enough without crossing
+                test(0.46, Some(0.46));
+                test(0.4, Some(0.4));
+            }
+
+            #[test]
+            fn jump_interval_asymmetric() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    takeover_mode: TakeoverMode::Pickup,
+                    jump_interval: create_unit_value_interval(0.0, 0.1),
+                    downward_jump_interval: Some(full_unit_interval()),
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.5)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                // When
+                // Then
+                assert!(mode.control(abs_con_evt(0.5), &target, ()).is_none());
+                // Upward jumps are prevented
+                assert!(mode.control(abs_con_evt(0.9), &target, ()).is_none());
+                // Downward jumps are allowed
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.0), &target, ()).unwrap(),
+                    abs_con_val(0.0)
+                );
+            }
+
+            #[test]
+            fn jump_interval_max_pickup_with_target_interval() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    takeover_mode: TakeoverMode::Pickup,
+                    jump_interval: create_unit_value_interval(0.0, 0.1),
+                    target_value_interval: create_unit_value_interval(0.0, 0.5),
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.1)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                // When
+                // Then
+                let mut test = |i, o| {
+                    abs_con_test(&mut mode, &target, i, o);
+                };
+                test(0.0, None);
+                test(0.0, Some(0.00));
+                test(0.1, Some(0.05));
+                test(0.2, None);
+                test(0.3, Some(0.15));
+                test(0.4, Some(0.20));
+                test(0.5, Some(0.25));
+                test(0.7, Some(0.35));
+                test(1.0, Some(0.5));
+            }
 
             #[test]
             fn jump_interval_max_pickup_with_target_interval_out_of_range() {
@@ -3581,37 +5100,682 @@ mod tests {
                 );
                 assert_abs_diff_eq!(
                     mode.control(abs_con_evt(0.5), &target, ()).unwrap(),
-                    abs_con_val(0.5)
+                    abs_con_val(0.5)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(1.0), &target, ()).unwrap(),
+                    abs_con_val(0.0)
+                );
+            }
+
+            #[test]
+            fn feedback_priority() {
+                // Given
+                let mut mode: Mode<TestTransformation, TestFeedbackScript, Instant> =
+                    Mode::new(ModeSettings {
+                        glide_time: Duration::from_millis(100),
+                        ..Default::default()
+                    });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.0)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                let start = Instant::now();
+                let at = |millis| start + Duration::from_millis(millis);
+                let feedback =
+                    |mode: &mut Mode<TestTransformation, TestFeedbackScript, Instant>| {
+                        mode.feedback_with_origin(
+                            Some(Cow::Owned(FeedbackValue::Numeric(
+                                NumericFeedbackValue::new(Default::default(), con_val(0.5)),
+                            ))),
+                            Default::default(),
+                            (),
+                        )
+                        .unwrap()
+                        .origin
+                    };
+                // When
+                // Then
+                assert_eq!(feedback(&mut mode), FeedbackOrigin::Initial);
+                assert_eq!(feedback(&mut mode), FeedbackOrigin::PropChange);
+                // Control starts a glide
+                mode.control(ControlEvent::new(abs_con_val(1.0), at(0)), &target, ());
+                assert_eq!(feedback(&mut mode), FeedbackOrigin::Poll);
+                let glide_step = mode.poll_with_origin(&target, (), at(50)).unwrap();
+                assert_eq!(glide_step.origin, FeedbackOrigin::Poll);
+                assert_eq!(glide_step.priority(), FeedbackPriority::Bulk);
+                assert_eq!(feedback(&mut mode), FeedbackOrigin::Poll);
+                // Glide done
+                mode.poll_with_origin(&target, (), at(150)).unwrap();
+                assert_eq!(feedback(&mut mode), FeedbackOrigin::Poll);
+                assert_eq!(feedback(&mut mode), FeedbackOrigin::PropChange);
+                // Hitting the target directly (without glide)
+                let mut direct_mode: Mode<TestTransformation, TestFeedbackScript, Instant> =
+                    Mode::new(Default::default());
+                direct_mode.control(ControlEvent::new(abs_con_val(1.0), at(200)), &target, ());
+                assert_eq!(feedback(&mut direct_mode), FeedbackOrigin::Control);
+                assert_eq!(FeedbackOrigin::Control.priority(), FeedbackPriority::Urgent);
+                // Reactivation
+                mode.on_deactivate();
+                assert_eq!(feedback(&mut mode), FeedbackOrigin::Initial);
+            }
+
+            #[test]
+            fn glide() {
+                // Given
+                let mut mode: Mode<TestTransformation, TestFeedbackScript, Instant> =
+                    Mode::new(ModeSettings {
+                        glide_time: Duration::from_millis(100),
+                        ..Default::default()
+                    });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.0)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                let start = Instant::now();
+                let at = |millis| start + Duration::from_millis(millis);
+                // When
+                let result = mode.control(ControlEvent::new(abs_con_val(1.0), start), &target, ());
+                // Then
+                assert!(mode.wants_to_be_polled());
+                assert_eq!(result, None);
+                assert_abs_diff_eq!(
+                    mode.poll(&target, (), at(50)).unwrap().value(),
+                    abs_con_val(0.5)
+                );
+                assert_abs_diff_eq!(
+                    mode.poll(&target, (), at(150)).unwrap().value(),
+                    abs_con_val(1.0)
+                );
+                assert!(mode.poll(&target, (), at(200)).is_none());
+            }
+
+            #[test]
+            fn transition_button() {
+                // Given
+                let mut mode: Mode<TestTransformation, TestFeedbackScript, Instant> =
+                    Mode::new(ModeSettings {
+                        absolute_mode: AbsoluteMode::ToggleButton,
+                        transition_time: Duration::from_millis(200),
+                        ..Default::default()
+                    });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.0)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                let start = Instant::now();
+                let at = |millis| start + Duration::from_millis(millis);
+                // When
+                let result = mode.control(ControlEvent::new(abs_con_val(1.0), start), &target, ());
+                // Then
+                assert!(mode.wants_to_be_polled());
+                assert_eq!(result, None);
+                assert_abs_diff_eq!(
+                    mode.poll(&target, (), at(50)).unwrap().value(),
+                    abs_con_val(0.25)
+                );
+                assert_abs_diff_eq!(
+                    mode.poll(&target, (), at(100)).unwrap().value(),
+                    abs_con_val(0.5)
+                );
+                assert_abs_diff_eq!(
+                    mode.poll(&target, (), at(250)).unwrap().value(),
+                    abs_con_val(1.0)
+                );
+                assert!(mode.poll(&target, (), at(300)).is_none());
+            }
+
+            #[test]
+            fn velocity_curve() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    velocity_sensitive_source: true,
+                    velocity_curve: VelocityCurve::Hard,
+                    ..Default::default()
+                });
+                let mut fader_mode: TestMode = Mode::new(ModeSettings {
+                    velocity_curve: VelocityCurve::Hard,
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.777)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                // When
+                // Then
+                assert_abs_diff_eq!(
+                    fader_mode.control(abs_con_evt(0.5), &target, ()).unwrap(),
+                    abs_con_val(0.5)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.5), &target, ()).unwrap(),
+                    abs_con_val(0.25)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(1.0), &target, ()).unwrap(),
+                    abs_con_val(1.0)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.0), &target, ()).unwrap(),
+                    abs_con_val(0.0)
+                );
+            }
+
+            #[test]
+            fn response_curve() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    response_curve: ResponseCurve::Logarithmic { strength: 1.0 },
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.777)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                // When
+                // Then
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.0), &target, ()).unwrap(),
+                    abs_con_val(0.0)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(1.0 / 9.0), &target, ()).unwrap(),
+                    abs_con_val(2.0f64.log10()),
+                    epsilon = BASE_EPSILON
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(1.0), &target, ()).unwrap(),
+                    abs_con_val(1.0)
+                );
+                assert_abs_diff_eq!(
+                    mode.feedback(con_val(2.0f64.log10())).unwrap(),
+                    con_val(1.0 / 9.0),
+                    epsilon = BASE_EPSILON
+                );
+            }
+
+            #[test]
+            fn fire_on_multi_press() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    fire_mode: FireMode::OnMultiPress,
+                    multi_press_count: 3,
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.0)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                // When
+                // Then
+                assert!(mode.control(abs_con_evt(1.0), &target, ()).is_none());
+                assert!(mode.control(abs_con_evt(0.0), &target, ()).is_none());
+                assert!(mode.control(abs_con_evt(1.0), &target, ()).is_none());
+                assert!(mode.control(abs_con_evt(0.0), &target, ()).is_none());
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(1.0), &target, ()).unwrap(),
+                    abs_con_val(1.0)
+                );
+                assert!(mode.control(abs_con_evt(0.0), &target, ()).is_none());
+            }
+
+            #[test]
+            fn fire_on_multi_press_window_expired() {
+                // Given
+                let mut mode: Mode<TestTransformation, TestFeedbackScript, Instant> =
+                    Mode::new(ModeSettings {
+                        fire_mode: FireMode::OnMultiPress,
+                        multi_press_count: 2,
+                        ..Default::default()
+                    });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.0)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                let start = Instant::now();
+                let at = |millis| start + Duration::from_millis(millis);
+                let mut control = |value, millis| {
+                    mode.control(
+                        ControlEvent::new(abs_con_val(value), at(millis)),
+                        &target,
+                        (),
+                    )
+                };
+                // When
+                // Then
+                assert!(control(1.0, 0).is_none());
+                assert!(control(0.0, 50).is_none());
+                // Window of 300ms expired, so this press starts counting from scratch.
+                assert!(control(1.0, 400).is_none());
+                assert!(control(0.0, 450).is_none());
+                assert_abs_diff_eq!(control(1.0, 500).unwrap(), abs_con_val(1.0));
+            }
+
+            #[test]
+            fn fire_short_or_long_press() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    fire_mode: FireMode::ShortOrLongPress,
+                    press_duration_interval: Interval::new(
+                        Duration::from_millis(500),
+                        Duration::from_millis(500),
+                    ),
+                    short_long_press_values: [UnitValue::new(0.3), UnitValue::new(0.8)],
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.0)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                // When
+                // Then
+                assert!(mode.wants_to_be_polled());
+                assert!(mode.control(abs_con_evt(1.0), &target, ()).is_none());
+                assert!(mode.poll(&target, (), NoopTimestamp).is_none());
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.0), &target, ()).unwrap(),
+                    abs_con_val(0.3)
+                );
+                assert!(mode.control(abs_con_evt(0.0), &target, ()).is_none());
+            }
+
+            #[test]
+            fn fire_typematic() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    fire_mode: FireMode::Typematic,
+                    press_duration_interval: Interval::new(
+                        Duration::from_millis(500),
+                        Duration::from_millis(500),
+                    ),
+                    turbo_rate: Duration::from_millis(50),
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.5)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                // When
+                // Then
+                assert!(mode.wants_to_be_polled());
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(1.0), &target, ()).unwrap(),
+                    abs_con_val(1.0)
+                );
+                // Initial delay not yet over
+                assert!(mode.poll(&target, (), NoopTimestamp).is_none());
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.0), &target, ()).unwrap(),
+                    abs_con_val(0.0)
+                );
+            }
+
+            #[test]
+            fn fire_press_duration_as_value() {
+                // Given
+                let mut mode: Mode<TestTransformation, TestFeedbackScript, Instant> =
+                    Mode::new(ModeSettings {
+                        fire_mode: FireMode::PressDurationAsValue,
+                        press_duration_interval: Interval::new(
+                            Duration::from_millis(100),
+                            Duration::from_millis(200),
+                        ),
+                        ..Default::default()
+                    });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.25)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                let start = Instant::now();
+                let at = |millis| start + Duration::from_millis(millis);
+                // When
+                // Then
+                assert!(mode.wants_to_be_polled());
+                assert!(mode
+                    .control(ControlEvent::new(abs_con_val(1.0), at(0)), &target, ())
+                    .is_none());
+                // Minimum press duration not yet reached
+                assert_abs_diff_eq!(
+                    mode.poll(&target, (), at(50)).unwrap().value(),
+                    abs_con_val(0.0)
+                );
+                // Value didn't change, so nothing is emitted
+                assert!(mode.poll(&target, (), at(80)).is_none());
+                assert_abs_diff_eq!(
+                    mode.poll(&target, (), at(150)).unwrap().value(),
+                    abs_con_val(0.5)
+                );
+                // Maximum press duration exceeded
+                assert_abs_diff_eq!(
+                    mode.poll(&target, (), at(250)).unwrap().value(),
+                    abs_con_val(1.0)
+                );
+                assert!(mode.poll(&target, (), at(300)).is_none());
+                // Release keeps the reached value
+                assert!(mode
+                    .control(ControlEvent::new(abs_con_val(0.0), at(350)), &target, ())
+                    .is_none());
+                assert!(mode.poll(&target, (), at(400)).is_none());
+            }
+
+            #[test]
+            fn hysteresis() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    hysteresis: UnitValue::new(0.05),
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.0)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                // When
+                // Then
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.5), &target, ()).unwrap(),
+                    abs_con_val(0.5)
+                );
+                assert!(mode.control(abs_con_evt(0.52), &target, ()).is_none());
+                assert!(mode.control(abs_con_evt(0.48), &target, ()).is_none());
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.56), &target, ()).unwrap(),
+                    abs_con_val(0.56)
+                );
+                assert!(mode.control(abs_con_evt(0.6), &target, ()).is_none());
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(1.0), &target, ()).unwrap(),
+                    abs_con_val(1.0)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.0), &target, ()).unwrap(),
+                    abs_con_val(0.0)
+                );
+            }
+
+            #[test]
+            fn center_deadzone() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    center_deadzone: UnitValue::new(0.05),
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.0)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                // When
+                // Then
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.3), &target, ()).unwrap(),
+                    abs_con_val(0.3)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.46), &target, ()).unwrap(),
+                    abs_con_val(0.5)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.54), &target, ()).unwrap(),
+                    abs_con_val(0.5)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.6), &target, ()).unwrap(),
+                    abs_con_val(0.6)
+                );
+            }
+
+            #[test]
+            fn sample_and_hold() {
+                // Given
+                let mut mode: Mode<TestTransformation, TestFeedbackScript, Instant> =
+                    Mode::new(ModeSettings {
+                        absolute_mode: AbsoluteMode::SampleAndHold,
+                        sample_interval: Duration::from_millis(100),
+                        ..Default::default()
+                    });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.0)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                let start = Instant::now();
+                let at = |millis| start + Duration::from_millis(millis);
+                let evt = |v, millis| ControlEvent::new(abs_con_val(v), at(millis));
+                // When
+                // Then
+                assert!(mode.wants_to_be_polled());
+                assert_eq!(mode.control(evt(0.3, 0), &target, ()), None);
+                assert_abs_diff_eq!(
+                    mode.poll(&target, (), at(0)).unwrap().value(),
+                    abs_con_val(0.3)
+                );
+                assert_eq!(mode.control(evt(0.5, 10), &target, ()), None);
+                assert_eq!(mode.control(evt(0.6, 20), &target, ()), None);
+                assert!(mode.poll(&target, (), at(50)).is_none());
+                assert_abs_diff_eq!(
+                    mode.poll(&target, (), at(100)).unwrap().value(),
+                    abs_con_val(0.6)
+                );
+                assert!(mode.poll(&target, (), at(200)).is_none());
+                // On demand
+                assert_eq!(mode.control(evt(0.8, 210), &target, ()), None);
+                assert_abs_diff_eq!(mode.sample().unwrap().value(), abs_con_val(0.8));
+                assert!(mode.sample().is_none());
+            }
+
+            #[test]
+            fn press_velocity_interval() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    velocity_sensitive_source: true,
+                    press_velocity_interval: create_unit_value_interval(0.3, 0.9),
+                    ..Default::default()
+                });
+                let mut fader_mode: TestMode = Mode::new(ModeSettings {
+                    press_velocity_interval: create_unit_value_interval(0.3, 0.9),
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.5)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                // When
+                // Then
+                assert_abs_diff_eq!(
+                    fader_mode.control(abs_con_evt(0.1), &target, ()).unwrap(),
+                    abs_con_val(0.1)
+                );
+                assert!(mode.control(abs_con_evt(0.1), &target, ()).is_none());
+                assert!(mode.control(abs_con_evt(0.0), &target, ()).is_none());
+                assert!(mode.control(abs_con_evt(0.95), &target, ()).is_none());
+                assert!(mode.control(abs_con_evt(0.0), &target, ()).is_none());
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.6), &target, ()).unwrap(),
+                    abs_con_val(0.6)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.0), &target, ()).unwrap(),
+                    abs_con_val(0.0)
+                );
+            }
+
+            #[test]
+            fn schmitt_trigger() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    schmitt_trigger_interval: Some(create_unit_value_interval(0.3, 0.7)),
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.5)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                // When
+                // Then
+                assert!(mode.control(abs_con_evt(0.5), &target, ()).is_none());
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.75), &target, ()).unwrap(),
+                    abs_con_val(1.0)
+                );
+                assert!(mode.control(abs_con_evt(0.8), &target, ()).is_none());
+                assert!(mode.control(abs_con_evt(0.4), &target, ()).is_none());
+                assert!(mode.control(abs_con_evt(0.69), &target, ()).is_none());
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.2), &target, ()).unwrap(),
+                    abs_con_val(0.0)
+                );
+                assert!(mode.control(abs_con_evt(0.1), &target, ()).is_none());
+            }
+
+            #[test]
+            fn quantize_step() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    quantize_step: Some(UnitValue::new(0.25)),
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.777)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                // When
+                // Then
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.3), &target, ()).unwrap(),
+                    abs_con_val(0.25)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.4), &target, ()).unwrap(),
+                    abs_con_val(0.5)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(1.0), &target, ()).unwrap(),
+                    abs_con_val(1.0)
+                );
+            }
+
+            #[test]
+            fn revert_on_release() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    target_value_interval: create_unit_value_interval(0.0, 0.8),
+                    revert_on_release: true,
+                    ..Default::default()
+                });
+                let target_at = |v: f64| TestTarget {
+                    current_value: Some(con_val(v)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                // When
+                // Then
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(1.0), &target_at(0.3), ()).unwrap(),
+                    abs_con_val(0.8)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.0), &target_at(0.8), ()).unwrap(),
+                    abs_con_val(0.3)
+                );
+                // Release without press is ignored
+                assert!(mode
+                    .control(abs_con_evt(0.0), &target_at(0.3), ())
+                    .is_none());
+            }
+
+            #[test]
+            fn transformation_err() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    control_transformation: Some(TestTransformation::new(
+                        ControlValueKind::AbsoluteContinuous,
+                        |_| Err("oh no!"),
+                    )),
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.777)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                // When
+                // Then
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.0), &target, ()).unwrap(),
+                    abs_con_val(0.0)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.5), &target, ()).unwrap(),
+                    abs_con_val(0.5)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(1.0), &target, ()).unwrap(),
+                    abs_con_val(1.0)
+                );
+            }
+
+            // TODO-medium-discrete Add tests for discrete processing
+            #[test]
+            fn target_value_sequence_interpolated() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    target_value_sequence: "~ 0.2, 0.8, 0.4".parse().unwrap(),
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.0)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                mode.update_from_target(&target, ());
+                // When
+                // Then
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.0), &target, ()).unwrap(),
+                    abs_con_val(0.2)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.25), &target, ()).unwrap(),
+                    abs_con_val(0.5),
+                    epsilon = BASE_EPSILON
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.5), &target, ()).unwrap(),
+                    abs_con_val(0.8)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(abs_con_evt(0.75), &target, ()).unwrap(),
+                    abs_con_val(0.6),
+                    epsilon = BASE_EPSILON
                 );
                 assert_abs_diff_eq!(
                     mode.control(abs_con_evt(1.0), &target, ()).unwrap(),
-                    abs_con_val(0.0)
+                    abs_con_val(0.4)
                 );
             }
 
             #[test]
-            fn transformation_err() {
+            fn target_value_sequence_generated() {
                 // Given
                 let mut mode: TestMode = Mode::new(ModeSettings {
-                    control_transformation: Some(TestTransformation::new(
-                        ControlValueKind::AbsoluteContinuous,
-                        |_| Err("oh no!"),
+                    target_value_sequence_generator: Some(ValueSequenceGenerator::new(
+                        5,
+                        TestTransformation::new(
+                            ControlValueKind::AbsoluteContinuous,
+                            |i| Ok(i * i),
+                        ),
                     )),
                     ..Default::default()
                 });
                 let target = TestTarget {
-                    current_value: Some(con_val(0.777)),
+                    current_value: Some(con_val(0.0)),
                     control_type: ControlType::AbsoluteContinuous,
                 };
+                mode.update_from_target(&target, ());
                 // When
                 // Then
                 assert_abs_diff_eq!(
-                    mode.control(abs_con_evt(0.0), &target, ()).unwrap(),
-                    abs_con_val(0.0)
+                    mode.control(abs_con_evt(0.25), &target, ()).unwrap(),
+                    abs_con_val(0.0625)
                 );
                 assert_abs_diff_eq!(
                     mode.control(abs_con_evt(0.5), &target, ()).unwrap(),
-                    abs_con_val(0.5)
+                    abs_con_val(0.25)
                 );
                 assert_abs_diff_eq!(
                     mode.control(abs_con_evt(1.0), &target, ()).unwrap(),
@@ -3619,7 +5783,6 @@ mod tests {
                 );
             }
 
-            // TODO-medium-discrete Add tests for discrete processing
             #[test]
             fn target_value_sequence_continuous_target() {
                 // Given
@@ -4116,6 +6279,71 @@ mod tests {
             use super::*;
             use crate::ControlValueKind;
 
+            #[test]
+            fn jump_interval_max_parallel() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    use_discrete_processing: true,
+                    discrete_jump_interval: Interval::new(0, 2),
+                    takeover_mode: TakeoverMode::Parallel,
+                    ..Default::default()
+                });
+                let mut target = TestTarget {
+                    current_value: Some(dis_val(10, 200)),
+                    control_type: ControlType::AbsoluteDiscrete {
+                        atomic_step_size: UnitValue::new(1.0 / 200.0),
+                        is_retriggerable: false,
+                    },
+                };
+                // When
+                // Then
+                // First one indeterminate
+                assert_eq!(mode.control(abs_dis_evt(50, 100), &target, ()), None);
+                // Raising in parallel
+                assert_eq!(
+                    mode.control(abs_dis_evt(51, 100), &target, ()).unwrap(),
+                    abs_dis_val(11, 200)
+                );
+                target.current_value = Some(dis_val(11, 200));
+                // Raising in parallel without exceeding max jump
+                assert_eq!(
+                    mode.control(abs_dis_evt(56, 100), &target, ()).unwrap(),
+                    abs_dis_val(13, 200)
+                );
+                target.current_value = Some(dis_val(13, 200));
+                // Falling in parallel
+                assert_eq!(
+                    mode.control(abs_dis_evt(55, 100), &target, ()).unwrap(),
+                    abs_dis_val(12, 200)
+                );
+            }
+
+            #[test]
+            fn continuous_value_to_discrete_target() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    use_discrete_processing: true,
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(dis_val(0, 20)),
+                    control_type: ControlType::AbsoluteDiscrete {
+                        atomic_step_size: UnitValue::new(1.0 / 20.0),
+                        is_retriggerable: false,
+                    },
+                };
+                // When
+                // Then
+                assert_eq!(
+                    mode.control(abs_con_evt(0.52), &target, ()).unwrap(),
+                    abs_dis_val(10, 20)
+                );
+                assert_eq!(
+                    mode.control(abs_con_evt(1.0), &target, ()).unwrap(),
+                    abs_dis_val(20, 20)
+                );
+            }
+
             #[test]
             fn case_1_no_interval_restriction() {
                 // Given
@@ -6379,6 +8607,225 @@ mod tests {
             assert_abs_diff_eq!(mode.feedback(con_val(0.7)).unwrap(), con_val(1.0));
             assert_abs_diff_eq!(mode.feedback(con_val(1.0)).unwrap(), con_val(1.0));
         }
+
+        #[test]
+        fn multi_state() {
+            // Given
+            let mut mode: TestMode = Mode::new(ModeSettings {
+                absolute_mode: AbsoluteMode::MultiStateToggleButton,
+                target_value_sequence: "0.2, 0.5, 0.8".parse().unwrap(),
+                ..Default::default()
+            });
+            let target_at = |v: f64| TestTarget {
+                current_value: Some(con_val(v)),
+                control_type: ControlType::AbsoluteContinuous,
+            };
+            mode.update_from_target(&target_at(0.0), ());
+            // When
+            // Then
+            assert!(mode
+                .control(abs_con_evt(0.0), &target_at(0.2), ())
+                .is_none());
+            assert_abs_diff_eq!(
+                mode.control(abs_con_evt(1.0), &target_at(0.0), ()).unwrap(),
+                abs_con_val(0.5)
+            );
+            assert_abs_diff_eq!(
+                mode.control(abs_con_evt(1.0), &target_at(0.2), ()).unwrap(),
+                abs_con_val(0.5)
+            );
+            assert_abs_diff_eq!(
+                mode.control(abs_con_evt(1.0), &target_at(0.5), ()).unwrap(),
+                abs_con_val(0.8)
+            );
+            assert!(mode
+                .control(abs_con_evt(1.0), &target_at(0.8), ())
+                .is_none());
+        }
+
+        #[test]
+        fn multi_state_wrap() {
+            // Given
+            let mut mode: TestMode = Mode::new(ModeSettings {
+                absolute_mode: AbsoluteMode::MultiStateToggleButton,
+                target_value_sequence: "0.2, 0.5, 0.8".parse().unwrap(),
+                rotate: true,
+                ..Default::default()
+            });
+            let target = TestTarget {
+                current_value: Some(con_val(0.8)),
+                control_type: ControlType::AbsoluteContinuous,
+            };
+            mode.update_from_target(&target, ());
+            // When
+            // Then
+            assert_abs_diff_eq!(
+                mode.control(abs_con_evt(1.0), &target, ()).unwrap(),
+                abs_con_val(0.2)
+            );
+        }
+
+        #[test]
+        fn discrete_processing() {
+            // Given
+            let mut mode: TestMode = Mode::new(ModeSettings {
+                absolute_mode: AbsoluteMode::ToggleButton,
+                use_discrete_processing: true,
+                discrete_target_value_interval: Interval::new(2, 5),
+                ..Default::default()
+            });
+            let target_at = |actual: u32| TestTarget {
+                current_value: Some(dis_val(actual, 10)),
+                control_type: ControlType::AbsoluteDiscrete {
+                    atomic_step_size: UnitValue::new(0.1),
+                    is_retriggerable: false,
+                },
+            };
+            // When
+            // Then
+            assert!(mode.control(abs_con_evt(0.0), &target_at(2), ()).is_none());
+            assert_eq!(
+                mode.control(abs_con_evt(1.0), &target_at(2), ()).unwrap(),
+                abs_dis_val(5, 10)
+            );
+            assert_eq!(
+                mode.control(abs_con_evt(1.0), &target_at(5), ()).unwrap(),
+                abs_dis_val(2, 10)
+            );
+            assert_eq!(
+                mode.control(abs_con_evt(1.0), &target_at(3), ()).unwrap(),
+                abs_dis_val(5, 10)
+            );
+        }
+
+        #[test]
+        fn ab_toggle() {
+            // Given
+            let mut mode: TestMode = Mode::new(ModeSettings {
+                absolute_mode: AbsoluteMode::AbToggleButton,
+                ab_toggle_values: [UnitValue::new(0.7), UnitValue::new(0.2)],
+                ..Default::default()
+            });
+            let target_at = |v: f64| TestTarget {
+                current_value: Some(con_val(v)),
+                control_type: ControlType::AbsoluteContinuous,
+            };
+            // When
+            // Then
+            assert!(mode
+                .control(abs_con_evt(0.0), &target_at(0.7), ())
+                .is_none());
+            assert_abs_diff_eq!(
+                mode.control(abs_con_evt(1.0), &target_at(0.7), ()).unwrap(),
+                abs_con_val(0.2)
+            );
+            assert_abs_diff_eq!(
+                mode.control(abs_con_evt(1.0), &target_at(0.2), ()).unwrap(),
+                abs_con_val(0.7)
+            );
+            assert_abs_diff_eq!(
+                mode.control(abs_con_evt(1.0), &target_at(0.9), ()).unwrap(),
+                abs_con_val(0.2)
+            );
+            assert_abs_diff_eq!(mode.feedback(con_val(0.7)).unwrap(), con_val(0.0));
+            assert_abs_diff_eq!(mode.feedback(con_val(0.6)).unwrap(), con_val(0.0));
+            assert_abs_diff_eq!(mode.feedback(con_val(0.2)).unwrap(), con_val(1.0));
+            assert_abs_diff_eq!(mode.feedback(con_val(0.0)).unwrap(), con_val(1.0));
+        }
+
+        #[test]
+        fn internal_toggle_state() {
+            // Given
+            let mut mode: TestMode = Mode::new(ModeSettings {
+                absolute_mode: AbsoluteMode::ToggleButton,
+                use_internal_toggle_state: true,
+                ..Default::default()
+            });
+            let target = TestTarget {
+                current_value: None,
+                control_type: ControlType::AbsoluteContinuous,
+            };
+            // When
+            // Then
+            assert_abs_diff_eq!(mode.feedback(con_val(0.7)).unwrap(), con_val(0.0));
+            assert_abs_diff_eq!(
+                mode.control(abs_con_evt(1.0), &target, ()).unwrap(),
+                abs_con_val(1.0)
+            );
+            assert!(mode.control(abs_con_evt(0.0), &target, ()).is_none());
+            assert_abs_diff_eq!(mode.feedback(con_val(0.0)).unwrap(), con_val(1.0));
+            assert_abs_diff_eq!(
+                mode.control(abs_con_evt(1.0), &target, ()).unwrap(),
+                abs_con_val(0.0)
+            );
+            assert_abs_diff_eq!(mode.feedback(con_val(1.0)).unwrap(), con_val(0.0));
+        }
+
+        #[test]
+        fn exclusive_group() {
+            // Given
+            let settings = || ModeSettings {
+                absolute_mode: AbsoluteMode::ToggleButton,
+                use_internal_toggle_state: true,
+                ..Default::default()
+            };
+            let mut modes: [TestMode; 2] = [Mode::new(settings()), Mode::new(settings())];
+            let mut group = ExclusiveGroup::new([0, 1]);
+            let target = TestTarget {
+                current_value: None,
+                control_type: ControlType::AbsoluteContinuous,
+            };
+            // When
+            let result = modes[0].control(abs_con_evt(1.0), &target, ()).unwrap();
+            let is_on = modes[0].target_value_is_on(result.to_absolute_value().unwrap());
+            let others = group.notify(0, is_on);
+            // Then
+            assert_eq!(others, vec![1]);
+            assert_abs_diff_eq!(
+                modes[1]
+                    .exclusive_off_value(ControlType::AbsoluteContinuous)
+                    .value(),
+                abs_con_val(0.0)
+            );
+        }
+
+        #[test]
+        fn hybrid() {
+            // Given
+            let mut mode: Mode<TestTransformation, TestFeedbackScript, Instant> =
+                Mode::new(ModeSettings {
+                    absolute_mode: AbsoluteMode::HybridToggleButton,
+                    hold_threshold: Duration::from_millis(300),
+                    ..Default::default()
+                });
+            let target_at = |v: f64| TestTarget {
+                current_value: Some(con_val(v)),
+                control_type: ControlType::AbsoluteContinuous,
+            };
+            let start = Instant::now();
+            let evt = |v, millis| {
+                ControlEvent::new(abs_con_val(v), start + Duration::from_millis(millis))
+            };
+            // When
+            // Then
+            // Tap latches
+            assert_abs_diff_eq!(
+                mode.control(evt(1.0, 0), &target_at(0.0), ()).unwrap(),
+                abs_con_val(1.0)
+            );
+            assert!(mode.control(evt(0.0, 100), &target_at(1.0), ()).is_none());
+            // Hold is momentary
+            assert_abs_diff_eq!(
+                mode.control(evt(1.0, 1000), &target_at(1.0), ()).unwrap(),
+                abs_con_val(0.0)
+            );
+            assert_abs_diff_eq!(
+                mode.control(evt(0.0, 1500), &target_at(0.0), ()).unwrap(),
+                abs_con_val(1.0)
+            );
+            // Release without press is ignored
+            assert!(mode.control(evt(0.0, 1600), &target_at(1.0), ()).is_none());
+        }
     }
 
     mod make_relative {
@@ -6407,6 +8854,29 @@ mod tests {
             test(1.0, Some(1.0));
         }
 
+        #[test]
+        fn continuous_to_continuous_sensitivity() {
+            // Given
+            let mut mode: TestMode = Mode::new(ModeSettings {
+                absolute_mode: AbsoluteMode::MakeRelative,
+                make_relative_sensitivity: 0.5,
+                ..Default::default()
+            });
+            let mut target = TestTarget {
+                current_value: Some(con_val(0.0)),
+                control_type: ControlType::AbsoluteContinuous,
+            };
+            // When
+            // Then
+            let mut test = |i, o| {
+                abs_con_test_cumulative(&mut mode, &mut target, i, o);
+            };
+            test(0.0, None);
+            test(0.2, Some(0.1));
+            test(0.6, Some(0.3));
+            test(1.0, Some(0.5));
+        }
+
         #[test]
         fn continuous_to_continuous_shifted() {
             // Given
@@ -6468,6 +8938,34 @@ mod tests {
             test(0.0, Some(0.0));
         }
 
+        #[test]
+        fn continuous_to_discrete_accumulate_fractional_steps() {
+            // Given
+            let mut mode: TestMode = Mode::new(ModeSettings {
+                absolute_mode: AbsoluteMode::MakeRelative,
+                accumulate_fractional_steps: true,
+                ..Default::default()
+            });
+            let mut target = TestTarget {
+                current_value: Some(con_val(0.0)),
+                control_type: ControlType::AbsoluteDiscrete {
+                    atomic_step_size: UnitValue::new(0.25),
+                    is_retriggerable: false,
+                },
+            };
+            // When
+            // Then
+            let mut test = |i, o| {
+                abs_con_test_cumulative(&mut mode, &mut target, i, o);
+            };
+            test(0.0, None);
+            test(0.1, None);
+            test(0.2, None);
+            test(0.3, Some(0.25));
+            test(0.6, Some(0.5));
+            test(0.7, None);
+        }
+
         /// Absolute discrete becomes relative continuous when not using discrete processing.
         #[test]
         fn discrete_to_continuous_shifted() {
@@ -6497,6 +8995,47 @@ mod tests {
             test(Fraction::new(0, 10), Some(0.0));
         }
 
+        #[test]
+        fn discrete_to_discrete_sensitivity() {
+            // Given
+            let mut mode: TestMode = Mode::new(ModeSettings {
+                absolute_mode: AbsoluteMode::MakeRelative,
+                use_discrete_processing: true,
+                make_relative_sensitivity: 0.4,
+                ..Default::default()
+            });
+            let target = TestTarget {
+                current_value: Some(dis_val(10, 100)),
+                control_type: ControlType::AbsoluteDiscrete {
+                    atomic_step_size: UnitValue::new(0.01),
+                    is_retriggerable: false,
+                },
+            };
+            // When
+            let results: Vec<_> = (0..6)
+                .map(|i| mode.control(abs_dis_evt(i, 127), &target, ()))
+                .collect();
+            // Then
+            // Each single step is scaled down to 0.4 steps but the remainders add up
+            assert_eq!(
+                results,
+                vec![
+                    None,
+                    None,
+                    Some(abs_dis_val(11, 100)),
+                    None,
+                    Some(abs_dis_val(11, 100)),
+                    None,
+                ]
+            );
+            // A remainder left over from before deactivation doesn't add up with new steps
+            mode.control(abs_dis_evt(0, 127), &target, ());
+            mode.control(abs_dis_evt(1, 127), &target, ());
+            mode.on_deactivate();
+            assert_eq!(mode.control(abs_dis_evt(1, 127), &target, ()), None);
+            assert_eq!(mode.control(abs_dis_evt(2, 127), &target, ()), None);
+        }
+
         /// Absolute discrete becomes relative continuous when not using discrete processing.
         #[test]
         fn discrete_to_discrete_shifted() {
@@ -6593,6 +9132,83 @@ mod tests {
             test(1.0, Some(1.0));
         }
 
+        #[test]
+        fn performance_control_reanchor_at_neutral() {
+            // Given
+            let mut mode: TestMode = Mode::new(ModeSettings {
+                absolute_mode: AbsoluteMode::PerformanceControl,
+                performance_anchor_strategy: PerformanceAnchorStrategy::ReanchorAtNeutral,
+                ..Default::default()
+            });
+            let target = TestTarget {
+                current_value: Some(con_val(0.0)),
+                control_type: ControlType::AbsoluteContinuous,
+            };
+            // When
+            // Then
+            perf_test(
+                &mut mode,
+                &target,
+                abs_con_val(0.5),
+                Some(0.75),
+                Some(con_val(0.5)),
+            );
+            // Anchor kept although caller passes a different one
+            perf_test(
+                &mut mode,
+                &target,
+                abs_con_val(1.0),
+                Some(1.0),
+                Some(con_val(0.8)),
+            );
+            perf_test(
+                &mut mode,
+                &target,
+                abs_con_val(0.0),
+                Some(0.5),
+                Some(con_val(0.8)),
+            );
+            // Returned to neutral, so new anchor is taken
+            perf_test(
+                &mut mode,
+                &target,
+                abs_con_val(0.5),
+                Some(0.9),
+                Some(con_val(0.8)),
+            );
+        }
+
+        #[test]
+        fn performance_control_reanchor_after_timeout() {
+            // Given
+            let mut mode: Mode<TestTransformation, TestFeedbackScript, Instant> =
+                Mode::new(ModeSettings {
+                    absolute_mode: AbsoluteMode::PerformanceControl,
+                    performance_anchor_strategy: PerformanceAnchorStrategy::ReanchorAfterTimeout,
+                    performance_anchor_timeout: Duration::from_millis(1000),
+                    ..Default::default()
+                });
+            let target = TestTarget {
+                current_value: Some(con_val(0.0)),
+                control_type: ControlType::AbsoluteContinuous,
+            };
+            let start = Instant::now();
+            let mut test = |input, millis, anchor| {
+                mode.control_with_options(
+                    ControlEvent::new(abs_con_val(input), start + Duration::from_millis(millis)),
+                    &target,
+                    (),
+                    ModeControlOptions::default(),
+                    Some(con_val(anchor)),
+                )
+            };
+            // When
+            // Then
+            assert_abs_diff_eq!(test(0.5, 0, 0.5).unwrap().value(), abs_con_val(0.75));
+            assert_abs_diff_eq!(test(0.5, 500, 0.8).unwrap().value(), abs_con_val(0.75));
+            assert_abs_diff_eq!(test(0.5, 2000, 0.8).unwrap().value(), abs_con_val(0.9));
+        }
+
         #[test]
         fn performance_control_max() {
             // Given
@@ -6680,6 +9296,36 @@ mod tests {
             test(0.5, Some(0.65));
             test(1.0, Some(0.4));
         }
+
+        #[test]
+        fn bipolar_performance_control() {
+            // Given
+            let mut mode: TestMode = Mode::new(ModeSettings {
+                absolute_mode: AbsoluteMode::BipolarPerformanceControl,
+                ..Default::default()
+            });
+            let target = TestTarget {
+                current_value: Some(con_val(0.1)),
+                control_type: ControlType::AbsoluteContinuous,
+            };
+            let last_non_performance_target_value = Some(con_val(0.6));
+            // When
+            // Then
+            let mut test = |i, o| {
+                perf_test(
+                    &mut mode,
+                    &target,
+                    abs_con_val(i),
+                    o,
+                    last_non_performance_target_value,
+                );
+            };
+            test(0.0, Some(0.0));
+            test(0.25, Some(0.3));
+            test(0.5, Some(0.6));
+            test(0.75, Some(0.8));
+            test(1.0, Some(1.0));
+        }
     }
 
     mod relative {
@@ -6717,6 +9363,126 @@ mod tests {
                 );
             }
 
+            #[test]
+            fn encoder_acceleration() {
+                // Given
+                let mut mode: Mode<TestTransformation, TestFeedbackScript, Instant> =
+                    Mode::new(ModeSettings {
+                        step_size_interval: create_unit_value_interval(0.01, 0.05),
+                        encoder_acceleration: true,
+                        ..Default::default()
+                    });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.5)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                let start = Instant::now();
+                let evt = |inc, millis| {
+                    ControlEvent::new(rel_dis_val(inc), start + Duration::from_millis(millis))
+                };
+                // When
+                // Then
+                assert_abs_diff_eq!(
+                    mode.control(evt(1, 0), &target, ()).unwrap(),
+                    abs_con_val(0.51)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(evt(1, 5), &target, ()).unwrap(),
+                    abs_con_val(0.55),
+                    epsilon = BASE_EPSILON
+                );
+                assert_abs_diff_eq!(
+                    mode.control(evt(1, 500), &target, ()).unwrap(),
+                    abs_con_val(0.51)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(evt(-1, 505), &target, ()).unwrap(),
+                    abs_con_val(0.49)
+                );
+            }
+
+            #[test]
+            fn increment_rate_limit() {
+                // Given
+                let mut mode: Mode<TestTransformation, TestFeedbackScript, Instant> =
+                    Mode::new(ModeSettings {
+                        step_size_interval: create_unit_value_interval(0.01, 0.05),
+                        min_increment_interval: Duration::from_millis(100),
+                        ..Default::default()
+                    });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.5)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                let start = Instant::now();
+                let evt = |inc, millis| {
+                    ControlEvent::new(rel_dis_val(inc), start + Duration::from_millis(millis))
+                };
+                // When
+                // Then
+                assert_abs_diff_eq!(
+                    mode.control(evt(1, 0), &target, ()).unwrap(),
+                    abs_con_val(0.51)
+                );
+                assert!(mode.control(evt(1, 10), &target, ()).is_none());
+                assert!(mode.control(evt(1, 90), &target, ()).is_none());
+                assert_abs_diff_eq!(
+                    mode.control(evt(1, 100), &target, ()).unwrap(),
+                    abs_con_val(0.51)
+                );
+            }
+
+            #[test]
+            fn step_size_relative_to_target_interval() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    step_size_interval: create_unit_value_interval(0.1, 0.1),
+                    target_value_interval: create_unit_value_interval(0.2, 0.4),
+                    step_size_relative_to_target_interval: true,
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.3)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                // When
+                // Then
+                assert_abs_diff_eq!(
+                    mode.control(rel_dis_evt(1), &target, ()).unwrap(),
+                    abs_con_val(0.32),
+                    epsilon = BASE_EPSILON
+                );
+                assert_abs_diff_eq!(
+                    mode.control(rel_dis_evt(-1), &target, ()).unwrap(),
+                    abs_con_val(0.28),
+                    epsilon = BASE_EPSILON
+                );
+            }
+
+            #[test]
+            fn separate_decrement_step_size() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    step_size_interval: create_unit_value_interval(0.01, 0.01),
+                    decrement_step_size_interval: Some(create_unit_value_interval(0.1, 0.1)),
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.5)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                // When
+                // Then
+                assert_abs_diff_eq!(
+                    mode.control(rel_dis_evt(1), &target, ()).unwrap(),
+                    abs_con_val(0.51)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(rel_dis_evt(-1), &target, ()).unwrap(),
+                    abs_con_val(0.4)
+                );
+            }
+
             #[test]
             fn default_2() {
                 // Given
@@ -7372,6 +10138,37 @@ mod tests {
                 );
             }
 
+            #[test]
+            fn target_value_sequence_snap() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    // Should be translated to set of 0.0, 0.2, 0.4, 0.5, 0.9!
+                    target_value_sequence: "0.2, 0.4, 0.4, 0.5, 0.0, 0.9".parse().unwrap(),
+                    snap_to_target_value_sequence: true,
+                    step_factor_interval: create_discrete_increment_interval(1, 5),
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.6)),
+                    control_type: ControlType::AbsoluteContinuous,
+                };
+                mode.update_from_target(&target, ());
+                // When
+                // Then
+                assert_abs_diff_eq!(
+                    mode.control(rel_dis_evt(1), &target, ()).unwrap(),
+                    abs_con_val(0.9)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(rel_dis_evt(-1), &target, ()).unwrap(),
+                    abs_con_val(0.4)
+                );
+                assert_abs_diff_eq!(
+                    mode.control(rel_dis_evt(-2), &target, ()).unwrap(),
+                    abs_con_val(0.2)
+                );
+            }
+
             // TODO-medium-discrete Add tests for discrete processing
             #[test]
             fn target_value_sequence_rotate() {
@@ -8827,6 +11624,90 @@ mod tests {
                 );
             }
 
+            #[test]
+            fn reverse_decrements_only() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    reverse: true,
+                    reverse_direction: ReverseDirection::DecrementOnly,
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.0)),
+                    control_type: ControlType::Relative,
+                };
+                // When
+                // Then
+                assert_eq!(
+                    mode.control(rel_dis_evt(1), &target, ()),
+                    Some(rel_dis_val(1))
+                );
+                assert_eq!(
+                    mode.control(rel_dis_evt(-1), &target, ()),
+                    Some(rel_dis_val(1))
+                );
+            }
+
+            #[test]
+            fn max_increment_magnitude() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    max_increment_magnitude: Some(3),
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.0)),
+                    control_type: ControlType::Relative,
+                };
+                // When
+                // Then
+                assert_eq!(
+                    mode.control(rel_dis_evt(3), &target, ()),
+                    Some(rel_dis_val(1))
+                );
+                assert_eq!(
+                    mode.control(rel_dis_evt(-2), &target, ()),
+                    Some(rel_dis_val(-1))
+                );
+                assert_eq!(mode.control(rel_dis_evt(60), &target, ()), None);
+                assert_eq!(mode.control(rel_dis_evt(-60), &target, ()), None);
+            }
+
+            #[test]
+            fn increment_mapping_table() {
+                // Given
+                let mut mode: TestMode = Mode::new(ModeSettings {
+                    increment_mapping_table: Some(IncrementMappingTable::new([
+                        (1, 1),
+                        (2, 5),
+                        (3, 20),
+                    ])),
+                    ..Default::default()
+                });
+                let target = TestTarget {
+                    current_value: Some(con_val(0.0)),
+                    control_type: ControlType::Relative,
+                };
+                // When
+                // Then
+                assert_eq!(
+                    mode.control(rel_dis_evt(1), &target, ()),
+                    Some(rel_dis_val(1))
+                );
+                assert_eq!(
+                    mode.control(rel_dis_evt(2), &target, ()),
+                    Some(rel_dis_val(5))
+                );
+                assert_eq!(
+                    mode.control(rel_dis_evt(-3), &target, ()),
+                    Some(rel_dis_val(-20))
+                );
+                assert_eq!(
+                    mode.control(rel_dis_evt(10), &target, ()),
+                    Some(rel_dis_val(20))
+                );
+            }
+
             #[test]
             fn min_step_count() {
                 // Given
@@ -10574,9 +13455,9 @@ mod tests {
     mod text_feedback {
         use crate::mode::mode_struct::tests::TestMode;
         use crate::{
-            AbsoluteValue, FeedbackStyle, FeedbackValue, FeedbackValueTable, Fraction, Mode,
-            ModeFeedbackOptions, ModeSettings, NumericFeedbackValue, RgbColor,
-            TextualFeedbackValue,
+            AbsoluteValue, FeedbackProcessor, FeedbackStyle, FeedbackValue, FeedbackValueTable,
+            Fraction, Mode, ModeContext, ModeFeedbackOptions, ModeSettings, NumericFeedbackValue,
+            PropValue, RgbColor, TextualFeedbackValue, UnitValue, MODE_SEQUENCE_LABEL_PROP_KEY,
         };
         use std::borrow::Cow;
 
@@ -10638,6 +13519,58 @@ mod tests {
             );
             assert_eq!(unmatched_result, None);
         }
+
+        #[test]
+        fn sequence_label_prop() {
+            // Given
+            let mode: TestMode = Mode::new(ModeSettings {
+                target_value_sequence: "0 \"Verse\", 0.3 \"Chorus\", 1".parse().unwrap(),
+                feedback_processor: FeedbackProcessor::Text {
+                    expression: "{{ mode.sequence_label }}".to_owned(),
+                },
+                ..Default::default()
+            });
+            // When
+            let label = |v| {
+                mode.get_prop_value(
+                    MODE_SEQUENCE_LABEL_PROP_KEY,
+                    AbsoluteValue::Continuous(UnitValue::new(v)),
+                )
+            };
+            let build = |target_value| {
+                mode.build_feedback_with_target_value(
+                    &|_: &str| None,
+                    target_value,
+                    ModeContext {
+                        additional_script_input: (),
+                    },
+                )
+            };
+            // Then
+            assert_eq!(label(0.0), Some(PropValue::Text("Verse".into())));
+            assert_eq!(label(0.3), Some(PropValue::Text("Chorus".into())));
+            assert_eq!(label(0.29), None);
+            assert_eq!(label(1.0), None);
+            // Discrete target values match within half a step
+            assert_eq!(
+                mode.get_prop_value(
+                    MODE_SEQUENCE_LABEL_PROP_KEY,
+                    AbsoluteValue::Discrete(Fraction::new(38, 127))
+                ),
+                Some(PropValue::Text("Chorus".into()))
+            );
+            assert_eq!(
+                build(Some(AbsoluteValue::Continuous(UnitValue::new(0.3)))),
+                FeedbackValue::Textual(TextualFeedbackValue::new(
+                    Default::default(),
+                    "Chorus".into()
+                ))
+            );
+            assert_eq!(
+                build(None),
+                FeedbackValue::Textual(TextualFeedbackValue::new(Default::default(), "".into()))
+            );
+        }
     }
 
     /// Absolute continuous control event.
@@ -1,79 +1,108 @@
-use crate::{AbsoluteValue, ButtonUsage, FireMode, Interval};
-use std::time::{Duration, Instant};
+use crate::{AbsoluteValue, AbstractTimestamp, ButtonUsage, FireMode, Interval, UnitValue};
+use std::time::Duration;
 
+/// Durations are measured with the timestamps of control events and polls, so fire modes which
+/// depend on time don't work with [`crate::NoopTimestamp`].
 #[derive(Clone, Debug)]
-pub struct PressDurationProcessor {
+pub struct PressDurationProcessor<S: AbstractTimestamp> {
     // # Configuration data (stays constant)
     fire_mode: FireMode,
     interval: Interval<Duration>,
     /// Double press detection: How long to wait for a second press
     multi_press_span: Duration,
+    /// Multi press detection: How many taps are necessary to fire
+    multi_press_count: u32,
     turbo_rate: Duration,
+    /// Turbo acceleration: Hold duration interval within which the turbo rate ramps from
+    /// `turbo_rate` to `turbo_end_rate`.
+    turbo_ramp_interval: Interval<Duration>,
+    /// Zero means no acceleration.
+    turbo_end_rate: Duration,
+    /// Short-or-long-press mode: Values to fire on short and long press.
+    short_long_press_values: [UnitValue; 2],
     // # Runtime data (changes during usage)
-    last_button_press: Option<ButtonPress>,
+    last_button_press: Option<ButtonPress<S>>,
     button_usage: ButtonUsage,
 }
 
 #[derive(Clone, Debug)]
-struct ButtonPress {
-    time: Instant,
+struct ButtonPress<S: AbstractTimestamp> {
+    time: S,
     value: AbsoluteValue,
     /// Used for after-timeout-keep-firing mode.
-    time_of_last_turbo_fire: Option<Instant>,
+    time_of_last_turbo_fire: Option<S>,
     /// Whether we already fired in response to this press.
     ///
     /// Important for after-timeout mode: We must not clear the press on first fire, otherwise we can't
     /// decide anymore what will happen on release.
     fired_already: bool,
-    /// Number of tap-downs so far. Used for double-press detection.
+    /// Number of tap-downs so far. Used for double- and multi-press detection.
     tap_down_count: u32,
     /// Whether the button has been released already.
     ///
     /// This is relevant for distinction between single and double press. A button press that is
     /// released after a short time can still develop into a double press, so we can't clear the press yet.
     released: bool,
+    /// Used for press-duration-as-value mode in order to not fire the same value repeatedly.
+    last_duration_value: Option<UnitValue>,
 }
 
-impl ButtonPress {
-    pub fn new(value: AbsoluteValue) -> Self {
+impl<S: AbstractTimestamp> ButtonPress<S> {
+    pub fn new(value: AbsoluteValue, time: S) -> Self {
         Self {
-            time: Instant::now(),
+            time,
             value,
             time_of_last_turbo_fire: None,
             fired_already: false,
             tap_down_count: 1,
             released: false,
+            last_duration_value: None,
         }
     }
 }
 
 const ZERO_DURATION: Duration = Duration::from_millis(0);
 
-impl Default for PressDurationProcessor {
+/// Number of presses needed to fire in [`FireMode::OnMultiPress`] unless configured otherwise.
+pub const DEFAULT_MULTI_PRESS_COUNT: u32 = 3;
+
+impl<S: AbstractTimestamp> Default for PressDurationProcessor<S> {
     fn default() -> Self {
         Self {
             fire_mode: FireMode::Normal,
             interval: Interval::new(ZERO_DURATION, ZERO_DURATION),
             multi_press_span: Duration::from_millis(300),
+            multi_press_count: DEFAULT_MULTI_PRESS_COUNT,
             turbo_rate: ZERO_DURATION,
+            turbo_ramp_interval: Interval::new(ZERO_DURATION, ZERO_DURATION),
+            turbo_end_rate: ZERO_DURATION,
+            short_long_press_values: [UnitValue::MIN, UnitValue::MAX],
             last_button_press: None,
             button_usage: ButtonUsage::Both,
         }
     }
 }
 
-impl PressDurationProcessor {
+impl<S: AbstractTimestamp> PressDurationProcessor<S> {
     pub fn new(
         mode: FireMode,
         interval: Interval<Duration>,
         turbo_rate: Duration,
         button_usage: ButtonUsage,
-    ) -> PressDurationProcessor {
-        PressDurationProcessor {
+        multi_press_count: u32,
+        turbo_ramp_interval: Interval<Duration>,
+        turbo_end_rate: Duration,
+        short_long_press_values: [UnitValue; 2],
+    ) -> Self {
+        Self {
             fire_mode: mode,
             interval,
             turbo_rate,
             button_usage,
+            multi_press_count,
+            turbo_ramp_interval,
+            turbo_end_rate,
+            short_long_press_values,
             ..Default::default()
         }
     }
@@ -84,7 +113,13 @@ impl PressDurationProcessor {
         // This must not depend on the button press state!
         use FireMode::*;
         match self.fire_mode {
-            AfterTimeout | AfterTimeoutKeepFiring | OnSinglePress => true,
+            AfterTimeout
+            | AfterTimeoutKeepFiring
+            | OnSinglePress
+            | OnMultiPress
+            | PressDurationAsValue
+            | Typematic
+            | ShortOrLongPress => true,
             Normal | OnDoublePress => false,
         }
     }
@@ -93,6 +128,7 @@ impl PressDurationProcessor {
         &mut self,
         control_value: AbsoluteValue,
         button_usage: ButtonUsage,
+        now: S,
     ) -> Option<AbsoluteValue> {
         let min = self.interval.min_val();
         let max = self.interval.max_val();
@@ -114,7 +150,7 @@ impl PressDurationProcessor {
                 if control_value.is_on() {
                     // This is a button press.
                     // Don't fire now because we don't know yet how long it will be pressed.
-                    self.last_button_press = Some(ButtonPress::new(control_value));
+                    self.last_button_press = Some(ButtonPress::new(control_value, now));
                     None
                 } else {
                     // Looks like a button release.
@@ -124,7 +160,7 @@ impl PressDurationProcessor {
                         None => None,
                         // Button has been pressed before.
                         Some(press) => {
-                            if self.interval.contains(press.time.elapsed()) {
+                            if self.interval.contains(now - press.time) {
                                 // Duration within interval. Fire initial press value.
                                 Some(press.value)
                             } else {
@@ -147,11 +183,11 @@ impl PressDurationProcessor {
                 }
                 if control_value.is_on() {
                     // Button press
-                    self.last_button_press = Some(ButtonPress::new(control_value));
+                    self.last_button_press = Some(ButtonPress::new(control_value, now));
                     None
                 } else {
                     // Button release
-                    self.process_timeout_button_release(control_value)
+                    self.process_timeout_button_release(control_value, now)
                 }
             }
             FireMode::AfterTimeoutKeepFiring => {
@@ -163,10 +199,10 @@ impl PressDurationProcessor {
                 }
                 if control_value.is_on() {
                     // Button press
-                    let mut button_press = ButtonPress::new(control_value);
+                    let mut button_press = ButtonPress::new(control_value, now);
                     let result = if min == ZERO_DURATION {
                         // No initial delay. Fire immediately and count as first turbo fire!
-                        button_press.time_of_last_turbo_fire = Some(Instant::now());
+                        button_press.time_of_last_turbo_fire = Some(now);
                         Some(control_value)
                     } else {
                         // Initial delay (wait for timeout).
@@ -176,7 +212,7 @@ impl PressDurationProcessor {
                     result
                 } else {
                     // Button release
-                    self.process_timeout_button_release(control_value)
+                    self.process_timeout_button_release(control_value, now)
                 }
             }
             FireMode::OnSinglePress => {
@@ -189,10 +225,10 @@ impl PressDurationProcessor {
                     if let Some(press) = self.last_button_press.as_mut() {
                         // Must be more than single press already.
                         press.tap_down_count += 1;
-                        press.time = Instant::now();
+                        press.time = now;
                     } else {
                         // First press
-                        self.last_button_press = Some(ButtonPress::new(control_value));
+                        self.last_button_press = Some(ButtonPress::new(control_value, now));
                     };
                     None
                 } else {
@@ -202,7 +238,7 @@ impl PressDurationProcessor {
                         if press.tap_down_count != 1 {
                             return None;
                         }
-                        let elapsed = press.time.elapsed();
+                        let elapsed = now - press.time;
                         if elapsed < self.multi_press_span {
                             press.released = true;
                             return None;
@@ -227,19 +263,18 @@ impl PressDurationProcessor {
                 if control_value.is_on() {
                     if let Some(press) = &self.last_button_press {
                         // Button was pressed before
-                        let (result, next_press) = if press.time.elapsed() <= self.multi_press_span
-                        {
+                        let (result, next_press) = if now - press.time <= self.multi_press_span {
                             // Double press detected
                             (Some(press.value), None)
                         } else {
                             // Previous press too long in past. Handle just like first press.
-                            (None, Some(ButtonPress::new(control_value)))
+                            (None, Some(ButtonPress::new(control_value, now)))
                         };
                         self.last_button_press = next_press;
                         result
                     } else {
                         // First press
-                        self.last_button_press = Some(ButtonPress::new(control_value));
+                        self.last_button_press = Some(ButtonPress::new(control_value, now));
                         None
                     }
                 } else {
@@ -247,39 +282,135 @@ impl PressDurationProcessor {
                     None
                 }
             }
+            FireMode::OnMultiPress => {
+                // Same as with double press: Button usage doesn't make sense here.
+                if !control_value.is_on() {
+                    // Button release
+                    return None;
+                }
+                let is_subsequent_tap = matches!(
+                    &self.last_button_press,
+                    Some(press) if now - press.time <= self.multi_press_span
+                );
+                if is_subsequent_tap {
+                    let press = self.last_button_press.as_mut()?;
+                    press.tap_down_count += 1;
+                    press.time = now;
+                } else {
+                    // First tap (or previous taps too long in past)
+                    self.last_button_press = Some(ButtonPress::new(control_value, now));
+                }
+                let press = self.last_button_press.as_ref()?;
+                if press.tap_down_count < self.multi_press_count {
+                    // Swallow
+                    return None;
+                }
+                let fire_value = press.value;
+                self.last_button_press = None;
+                Some(fire_value)
+            }
+            FireMode::Typematic => {
+                if button_usage.should_ignore(control_value) {
+                    return None;
+                }
+                if control_value.is_on() {
+                    // Button press. Fire immediately, repetitions are emitted by `poll()`.
+                    self.last_button_press = Some(ButtonPress::new(control_value, now));
+                } else {
+                    // Button release. Stop repeating.
+                    self.last_button_press = None;
+                }
+                Some(control_value)
+            }
+            FireMode::ShortOrLongPress => {
+                // Button usage doesn't make sense here either. We need both press and release.
+                if control_value.is_on() {
+                    // Button press. Wait until we know whether it's a short or long press.
+                    self.last_button_press = Some(ButtonPress::new(control_value, now));
+                    return None;
+                }
+                // Button release
+                let press = self.last_button_press.take()?;
+                if press.fired_already {
+                    // Long press value has been fired by `poll()` already
+                    return None;
+                }
+                Some(AbsoluteValue::Continuous(self.short_long_press_values[0]))
+            }
+            FireMode::PressDurationAsValue => {
+                // Button usage doesn't make sense here either. The values are emitted by `poll()`.
+                if control_value.is_on() {
+                    self.last_button_press = Some(ButtonPress::new(control_value, now));
+                } else {
+                    // Keep the value that has been reached while holding.
+                    self.last_button_press = None;
+                }
+                None
+            }
         }
     }
 
     /// Should be called regularly if `wants_to_be_polled()` returned `true` at initialization
     /// time.
-    pub fn poll(&mut self) -> Option<AbsoluteValue> {
+    pub fn poll(&mut self, now: S) -> Option<AbsoluteValue> {
         match self.fire_mode {
             FireMode::Normal | FireMode::OnDoublePress => None,
+            FireMode::PressDurationAsValue => {
+                let elapsed = now - self.last_button_press.as_ref()?.time;
+                let value = self.press_duration_to_value(elapsed);
+                let press = self.last_button_press.as_mut()?;
+                if press.last_duration_value == Some(value) {
+                    return None;
+                }
+                press.last_duration_value = Some(value);
+                Some(AbsoluteValue::Continuous(value))
+            }
+            FireMode::OnMultiPress => {
+                let press = self.last_button_press.as_ref()?;
+                if now - press.time > self.multi_press_span {
+                    // Not enough taps within window. Reset!
+                    self.last_button_press = None;
+                }
+                None
+            }
+            FireMode::ShortOrLongPress => {
+                let last_button_press = self.last_button_press.as_mut()?;
+                if last_button_press.fired_already
+                    || now - last_button_press.time < self.interval.min_val()
+                {
+                    return None;
+                }
+                last_button_press.fired_already = true;
+                Some(AbsoluteValue::Continuous(self.short_long_press_values[1]))
+            }
             FireMode::AfterTimeout => {
                 let last_button_press = self.last_button_press.as_mut()?;
                 if last_button_press.fired_already
-                    || last_button_press.time.elapsed() < self.interval.min_val()
+                    || now - last_button_press.time < self.interval.min_val()
                 {
                     return None;
                 }
                 last_button_press.fired_already = true;
                 Some(last_button_press.value)
             }
-            FireMode::AfterTimeoutKeepFiring => {
+            // Typematic differs from turbo only in that it has fired immediately on press already
+            FireMode::AfterTimeoutKeepFiring | FireMode::Typematic => {
+                let hold_duration = now - self.last_button_press.as_ref()?.time;
+                let turbo_rate = self.current_turbo_rate(hold_duration);
                 let last_button_press = self.last_button_press.as_mut()?;
                 if let Some(last_turbo) = last_button_press.time_of_last_turbo_fire {
                     // We are in turbo stage already.
-                    if last_turbo.elapsed() >= self.turbo_rate {
+                    if now - last_turbo >= turbo_rate {
                         // Subsequent turbo fire!
-                        last_button_press.time_of_last_turbo_fire = Some(Instant::now());
+                        last_button_press.time_of_last_turbo_fire = Some(now);
                         Some(last_button_press.value)
                     } else {
                         // Not yet time for next turbo fire.
                         None
                     }
-                } else if last_button_press.time.elapsed() >= self.interval.min_val() {
+                } else if now - last_button_press.time >= self.interval.min_val() {
                     // We reached the initial delay. First turbo fire!
-                    last_button_press.time_of_last_turbo_fire = Some(Instant::now());
+                    last_button_press.time_of_last_turbo_fire = Some(now);
                     Some(last_button_press.value)
                 } else {
                     None
@@ -288,7 +419,7 @@ impl PressDurationProcessor {
             FireMode::OnSinglePress => {
                 let fire_value = {
                     let press = self.last_button_press.as_ref()?;
-                    let elapsed = press.time.elapsed();
+                    let elapsed = now - press.time;
                     if elapsed < self.multi_press_span {
                         // Can't decide yet if this is a single press.
                         return None;
@@ -314,17 +445,96 @@ impl PressDurationProcessor {
         }
     }
 
+    fn current_turbo_rate(&self, hold_duration: Duration) -> Duration {
+        let min = self.turbo_ramp_interval.min_val();
+        let max = self.turbo_ramp_interval.max_val();
+        if max == ZERO_DURATION || self.turbo_end_rate == ZERO_DURATION || hold_duration <= min {
+            return self.turbo_rate;
+        }
+        if hold_duration >= max {
+            return self.turbo_end_rate;
+        }
+        let progress = (hold_duration - min).as_secs_f64() / (max - min).as_secs_f64();
+        let start = self.turbo_rate.as_secs_f64();
+        let end = self.turbo_end_rate.as_secs_f64();
+        Duration::from_secs_f64(start + (end - start) * progress)
+    }
+
+    /// Maps the given press duration into the press duration interval.
+    fn press_duration_to_value(&self, elapsed: Duration) -> UnitValue {
+        let min = self.interval.min_val();
+        let max = self.interval.max_val();
+        if elapsed < min {
+            return UnitValue::MIN;
+        }
+        if elapsed >= max {
+            return UnitValue::MAX;
+        }
+        let ratio = (elapsed - min).as_secs_f64() / (max - min).as_secs_f64();
+        UnitValue::new_clamped(ratio)
+    }
+
     fn process_timeout_button_release(
         &mut self,
         control_value: AbsoluteValue,
+        now: S,
     ) -> Option<AbsoluteValue> {
         let last_button_press = self.last_button_press.take()?;
         if self.button_usage == ButtonUsage::PressOnly {
             return None;
         }
-        if last_button_press.time.elapsed() < self.interval.min_val() {
+        if now - last_button_press.time < self.interval.min_val() {
             return None;
         }
         Some(control_value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoopTimestamp;
+    use approx::*;
+
+    #[test]
+    fn turbo_acceleration() {
+        // Given
+        let processor: PressDurationProcessor<NoopTimestamp> = PressDurationProcessor {
+            fire_mode: FireMode::AfterTimeoutKeepFiring,
+            turbo_rate: Duration::from_millis(200),
+            turbo_ramp_interval: Interval::new(
+                Duration::from_millis(1000),
+                Duration::from_millis(2000),
+            ),
+            turbo_end_rate: Duration::from_millis(50),
+            ..Default::default()
+        };
+        // When
+        let rate = |millis| processor.current_turbo_rate(Duration::from_millis(millis));
+        // Then
+        assert_eq!(rate(500), Duration::from_millis(200));
+        assert_eq!(rate(1000), Duration::from_millis(200));
+        assert_abs_diff_eq!(rate(1500).as_secs_f64(), 0.125, epsilon = 0.000_001);
+        assert_eq!(rate(2000), Duration::from_millis(50));
+        assert_eq!(rate(3000), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn turbo_acceleration_without_end_rate() {
+        // Given
+        let processor: PressDurationProcessor<NoopTimestamp> = PressDurationProcessor {
+            fire_mode: FireMode::AfterTimeoutKeepFiring,
+            turbo_rate: Duration::from_millis(200),
+            turbo_ramp_interval: Interval::new(
+                Duration::from_millis(1000),
+                Duration::from_millis(2000),
+            ),
+            ..Default::default()
+        };
+        // When
+        let rate = |millis| processor.current_turbo_rate(Duration::from_millis(millis));
+        // Then
+        assert_eq!(rate(1500), Duration::from_millis(200));
+        assert_eq!(rate(3000), Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,86 @@
+use crate::UnitValue;
+use serde::{Deserialize, Serialize};
+
+/// A built-in curve which shapes absolute continuous control values without the need to write
+/// a control transformation.
+///
+/// The strength determines how pronounced the curve is. A strength of zero (or less) behaves
+/// like linear.
+#[derive(Copy, Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum ResponseCurve {
+    #[default]
+    #[serde(rename = "linear")]
+    Linear,
+    /// Rises quickly at first, then flattens (like a log taper).
+    #[serde(rename = "logarithmic")]
+    Logarithmic { strength: f64 },
+    /// Rises slowly at first, then steepens.
+    #[serde(rename = "exponential")]
+    Exponential { strength: f64 },
+    /// Flat at both ends, steep in the middle.
+    #[serde(rename = "s-curve")]
+    SCurve { strength: f64 },
+}
+
+impl ResponseCurve {
+    pub fn is_linear(&self) -> bool {
+        use ResponseCurve::*;
+        match self {
+            Linear => true,
+            Logarithmic { strength } | Exponential { strength } | SCurve { strength } => {
+                *strength <= 0.0
+            }
+        }
+    }
+
+    /// Applies this curve to the given control value.
+    pub fn apply(&self, x: UnitValue) -> UnitValue {
+        if self.is_linear() {
+            return x;
+        }
+        use ResponseCurve::*;
+        let x = x.get();
+        let y = match *self {
+            Linear => x,
+            Logarithmic { strength } => log_curve(x, strength),
+            Exponential { strength } => exp_curve(x, strength),
+            SCurve { strength } => s_curve(x, 1.0 + strength),
+        };
+        UnitValue::new_clamped(y)
+    }
+
+    /// Applies the inverse of this curve (used for feedback).
+    pub fn apply_inverse(&self, y: UnitValue) -> UnitValue {
+        if self.is_linear() {
+            return y;
+        }
+        use ResponseCurve::*;
+        let y = y.get();
+        let x = match *self {
+            Linear => y,
+            Logarithmic { strength } => exp_curve(y, strength),
+            Exponential { strength } => log_curve(y, strength),
+            SCurve { strength } => s_curve(y, 1.0 / (1.0 + strength)),
+        };
+        UnitValue::new_clamped(x)
+    }
+}
+
+/// A strength of 1.0 yields the classic `log10(1 + 9x)` curve.
+fn log_curve(x: f64, strength: f64) -> f64 {
+    let k = 9.0 * strength;
+    (1.0 + k * x).ln() / (1.0 + k).ln()
+}
+
+/// Inverse of [`log_curve`].
+fn exp_curve(x: f64, strength: f64) -> f64 {
+    let k = 9.0 * strength;
+    ((1.0 + k).powf(x) - 1.0) / k
+}
+
+fn s_curve(x: f64, exponent: f64) -> f64 {
+    let a = x.powf(exponent);
+    let b = (1.0 - x).powf(exponent);
+    a / (a + b)
+}
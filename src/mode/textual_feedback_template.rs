@@ -0,0 +1,419 @@
+use crate::{NumericValue, PropProvider, PropValue};
+use std::borrow::Cow;
+use std::mem;
+
+/// Maximum width, character count and precision accepted by filters. Displays are much
+/// narrower anyway, and larger values would make each rendering allocate huge strings.
+const MAX_FILTER_ARGUMENT: usize = 256;
+
+/// Parsed textual feedback expression, e.g. `Vol: {{ target.volume | precision(1) }}`.
+///
+/// Each placeholder refers to a prop and can be followed by filters, which are applied from
+/// left to right. Conditional sections (`{{if target.is_muted}}MUTE{{else}}...{{end}}`) are
+/// rendered depending on whether the prop value is truthy. Tags which can't be parsed are kept
+/// as they are.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct TextualFeedbackTemplate {
+    nodes: Vec<TemplateNode>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum TemplateNode {
+    Literal(String),
+    Placeholder(Placeholder),
+    Conditional(Conditional),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct Placeholder {
+    prop_key: String,
+    filters: Vec<TextFilter>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct Conditional {
+    prop_key: String,
+    then_nodes: Vec<TemplateNode>,
+    else_nodes: Vec<TemplateNode>,
+}
+
+/// A conditional section whose end tag hasn't been encountered yet during parsing.
+struct OpenConditional<'a> {
+    if_tag: &'a str,
+    prop_key: &'a str,
+    /// Nodes before the if tag.
+    outer_nodes: Vec<TemplateNode>,
+    /// Else tag and nodes between the if and the else tag.
+    else_branch: Option<(&'a str, Vec<TemplateNode>)>,
+}
+
+enum Tag<'a> {
+    If(&'a str),
+    Else,
+    End,
+    Placeholder(Placeholder),
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum TextFilter {
+    /// Right-aligns the text by padding it with spaces on the left to the given width.
+    PadLeft(usize),
+    /// Left-aligns the text by padding it with spaces on the right to the given width.
+    PadRight(usize),
+    /// Cuts the text after the given number of characters.
+    Truncate(usize),
+    /// Formats numbers (and percentages) with the given number of decimal places.
+    Precision(usize),
+    Upper,
+    Lower,
+}
+
+impl TextualFeedbackTemplate {
+    pub fn parse(expression: &str) -> Self {
+        let mut nodes = vec![];
+        let mut open_conditionals: Vec<OpenConditional> = vec![];
+        let mut rest = expression;
+        while let Some(tag_start) = rest.find("{{") {
+            let Some(tag_length) = rest[tag_start..].find("}}") else {
+                break;
+            };
+            let tag_end = tag_start + tag_length + 2;
+            let raw_tag = &rest[tag_start..tag_end];
+            push_literal(&mut nodes, &rest[..tag_start]);
+            rest = &rest[tag_end..];
+            match parse_tag(&raw_tag[2..raw_tag.len() - 2]) {
+                Some(Tag::Placeholder(p)) => nodes.push(TemplateNode::Placeholder(p)),
+                Some(Tag::If(prop_key)) => open_conditionals.push(OpenConditional {
+                    if_tag: raw_tag,
+                    prop_key,
+                    outer_nodes: mem::take(&mut nodes),
+                    else_branch: None,
+                }),
+                Some(Tag::Else) => match open_conditionals.last_mut() {
+                    Some(c) if c.else_branch.is_none() => {
+                        c.else_branch = Some((raw_tag, mem::take(&mut nodes)));
+                    }
+                    _ => push_literal(&mut nodes, raw_tag),
+                },
+                Some(Tag::End) => match open_conditionals.pop() {
+                    Some(c) => {
+                        let (then_nodes, else_nodes) = match c.else_branch {
+                            None => (mem::take(&mut nodes), vec![]),
+                            Some((_, then_nodes)) => (then_nodes, mem::take(&mut nodes)),
+                        };
+                        nodes = c.outer_nodes;
+                        nodes.push(TemplateNode::Conditional(Conditional {
+                            prop_key: c.prop_key.to_string(),
+                            then_nodes,
+                            else_nodes,
+                        }));
+                    }
+                    None => push_literal(&mut nodes, raw_tag),
+                },
+                None => push_literal(&mut nodes, raw_tag),
+            }
+        }
+        push_literal(&mut nodes, rest);
+        // Keep conditional sections without end tag as they are
+        while let Some(c) = open_conditionals.pop() {
+            let section_nodes = mem::replace(&mut nodes, c.outer_nodes);
+            push_literal(&mut nodes, c.if_tag);
+            if let Some((else_tag, then_nodes)) = c.else_branch {
+                append_nodes(&mut nodes, then_nodes);
+                push_literal(&mut nodes, else_tag);
+            }
+            append_nodes(&mut nodes, section_nodes);
+        }
+        Self { nodes }
+    }
+
+    /// Returns the keys of all props referred to in this template.
+    pub fn used_props(&self) -> Vec<&str> {
+        let mut props = vec![];
+        collect_used_props(&self.nodes, &mut props);
+        props
+    }
+
+    /// Produces the text by resolving all placeholders and conditions using the given prop
+    /// provider.
+    pub fn render(&self, prop_provider: &(impl PropProvider + ?Sized)) -> String {
+        let mut text = String::new();
+        render_nodes(&self.nodes, prop_provider, &mut text);
+        text
+    }
+}
+
+fn collect_used_props<'a>(nodes: &'a [TemplateNode], props: &mut Vec<&'a str>) {
+    for node in nodes {
+        match node {
+            TemplateNode::Literal(_) => {}
+            TemplateNode::Placeholder(p) => props.push(&p.prop_key),
+            TemplateNode::Conditional(c) => {
+                props.push(&c.prop_key);
+                collect_used_props(&c.then_nodes, props);
+                collect_used_props(&c.else_nodes, props);
+            }
+        }
+    }
+}
+
+fn render_nodes(
+    nodes: &[TemplateNode],
+    prop_provider: &(impl PropProvider + ?Sized),
+    text: &mut String,
+) {
+    for node in nodes {
+        match node {
+            TemplateNode::Literal(l) => text.push_str(l),
+            TemplateNode::Placeholder(p) => text.push_str(&p.render(prop_provider)),
+            TemplateNode::Conditional(c) => {
+                let is_true = prop_provider
+                    .get_prop_value(&c.prop_key)
+                    .is_some_and(|v| is_truthy(&v));
+                let branch = if is_true {
+                    &c.then_nodes
+                } else {
+                    &c.else_nodes
+                };
+                render_nodes(branch, prop_provider, text);
+            }
+        }
+    }
+}
+
+/// Decides whether a conditional section shows its first branch.
+///
+/// Zero, empty text and off are considered false, just like missing props.
+fn is_truthy(value: &PropValue) -> bool {
+    use PropValue::*;
+    match value {
+        Normalized(v) => v.get() > 0.0,
+        Index(_) | Color(_) => true,
+        Boolean(state) => *state,
+        Numeric(NumericValue::Decimal(v)) => *v != 0.0,
+        Numeric(NumericValue::Discrete(v)) => *v != 0,
+        Text(text) => !text.is_empty(),
+        DurationInMillis(millis) => *millis > 0,
+    }
+}
+
+impl Placeholder {
+    fn render(&self, prop_provider: &(impl PropProvider + ?Sized)) -> Cow<'static, str> {
+        let value = prop_provider
+            .get_prop_value(&self.prop_key)
+            .unwrap_or_default();
+        self.filters
+            .iter()
+            .fold(value, |v, f| f.apply(v))
+            .into_textual()
+    }
+}
+
+impl TextFilter {
+    fn parse(text: &str) -> Option<Self> {
+        let (name, arg) = match text.split_once('(') {
+            None => (text, None),
+            Some((name, arg)) => {
+                let arg: usize = arg.strip_suffix(')')?.trim().parse().ok()?;
+                if arg > MAX_FILTER_ARGUMENT {
+                    return None;
+                }
+                (name.trim_end(), Some(arg))
+            }
+        };
+        use TextFilter::*;
+        let filter = match (name, arg) {
+            ("pad_left", Some(width)) => PadLeft(width),
+            ("pad_right", Some(width)) => PadRight(width),
+            ("truncate", Some(count)) => Truncate(count),
+            ("precision", Some(decimal_places)) => Precision(decimal_places),
+            ("upper", None) => Upper,
+            ("lower", None) => Lower,
+            _ => return None,
+        };
+        Some(filter)
+    }
+
+    fn apply(self, value: PropValue) -> PropValue {
+        use TextFilter::*;
+        let text = match self {
+            Precision(decimal_places) => {
+                let number = match &value {
+                    PropValue::Normalized(v) => v.get() * 100.0,
+                    PropValue::Numeric(NumericValue::Decimal(v)) => *v,
+                    PropValue::Numeric(NumericValue::Discrete(v)) => *v as f64,
+                    PropValue::Text(text) => match text.trim().parse() {
+                        Ok(v) => v,
+                        Err(_) => return value,
+                    },
+                    _ => return value,
+                };
+                format!("{number:.decimal_places$}")
+            }
+            PadLeft(width) => format!("{:>width$}", value.into_textual()),
+            PadRight(width) => format!("{:<width$}", value.into_textual()),
+            Truncate(count) => value.into_textual().chars().take(count).collect(),
+            Upper => value.into_textual().to_uppercase(),
+            Lower => value.into_textual().to_lowercase(),
+        };
+        PropValue::Text(text.into())
+    }
+}
+
+fn parse_tag(tag_content: &str) -> Option<Tag> {
+    let tag_content = tag_content.trim();
+    let tag = match tag_content {
+        "else" => Tag::Else,
+        "end" => Tag::End,
+        _ => match tag_content.strip_prefix("if ") {
+            Some(prop_key) => {
+                let prop_key = prop_key.trim();
+                if !is_valid_prop_key(prop_key) {
+                    return None;
+                }
+                Tag::If(prop_key)
+            }
+            None => Tag::Placeholder(parse_placeholder(tag_content)?),
+        },
+    };
+    Some(tag)
+}
+
+fn parse_placeholder(tag_content: &str) -> Option<Placeholder> {
+    let mut parts = tag_content.split('|').map(str::trim);
+    let prop_key = parts.next()?;
+    if !is_valid_prop_key(prop_key) {
+        return None;
+    }
+    let placeholder = Placeholder {
+        prop_key: prop_key.to_string(),
+        filters: parts.map(TextFilter::parse).collect::<Option<_>>()?,
+    };
+    Some(placeholder)
+}
+
+fn is_valid_prop_key(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_')
+}
+
+fn append_nodes(nodes: &mut Vec<TemplateNode>, other_nodes: Vec<TemplateNode>) {
+    for node in other_nodes {
+        match node {
+            TemplateNode::Literal(l) => push_literal(nodes, &l),
+            _ => nodes.push(node),
+        }
+    }
+}
+
+fn push_literal(nodes: &mut Vec<TemplateNode>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    if let Some(TemplateNode::Literal(l)) = nodes.last_mut() {
+        l.push_str(text);
+    } else {
+        nodes.push(TemplateNode::Literal(text.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UnitValue;
+
+    fn props(key: &str) -> Option<PropValue> {
+        let value = match key {
+            "target.volume" => PropValue::Numeric(NumericValue::Decimal(-6.34)),
+            "target.name" => PropValue::Text("Drums".into()),
+            "target.position" => PropValue::Normalized(UnitValue::new(0.5)),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    #[test]
+    fn filters() {
+        // Given
+        let template = TextualFeedbackTemplate::parse(
+            "{{ target.name | upper }}:{{target.volume|precision(1)|pad_left(6)}} \
+             {{ target.position | precision(0) }}% \
+             [{{ target.name | truncate(3) | pad_right(4) }}]",
+        );
+        // When
+        let text = template.render(&props);
+        // Then
+        assert_eq!(text, "DRUMS:  -6.3 50% [Dru ]");
+        assert_eq!(
+            template.used_props(),
+            vec![
+                "target.name",
+                "target.volume",
+                "target.position",
+                "target.name"
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_tags_are_kept() {
+        // Given
+        let template = TextualFeedbackTemplate::parse(
+            "{{ target.name | foo }} {{ a b }} {{ target.name | pad_left(4000000000) }} \
+             {{ target.name }",
+        );
+        // When
+        let text = template.render(&props);
+        // Then
+        assert_eq!(
+            text,
+            "{{ target.name | foo }} {{ a b }} {{ target.name | pad_left(4000000000) }} \
+             {{ target.name }"
+        );
+        assert!(template.used_props().is_empty());
+    }
+
+    #[test]
+    fn conditional_sections() {
+        // Given
+        let template = TextualFeedbackTemplate::parse(
+            "{{if target.is_muted}}MUTE{{else}}{{ target.name }}\
+             {{ if target.position }} {{target.position | precision(0)}}%{{end}}{{end}}",
+        );
+        let props_with_mute = |is_muted: bool| {
+            move |key: &str| match key {
+                "target.is_muted" => Some(PropValue::Boolean(is_muted)),
+                _ => props(key),
+            }
+        };
+        // When
+        let muted = template.render(&props_with_mute(true));
+        let unmuted = template.render(&props_with_mute(false));
+        // Then
+        assert_eq!(muted, "MUTE");
+        assert_eq!(unmuted, "Drums 50%");
+        assert_eq!(
+            template.used_props(),
+            vec![
+                "target.is_muted",
+                "target.name",
+                "target.position",
+                "target.position"
+            ]
+        );
+    }
+
+    #[test]
+    fn unbalanced_conditional_sections_are_kept() {
+        // Given
+        let template = TextualFeedbackTemplate::parse(
+            "{{end}}{{if target.name}}{{else}}{{ target.name }}{{else}}",
+        );
+        // When
+        let text = template.render(&props);
+        // Then
+        assert_eq!(text, "{{end}}{{if target.name}}{{else}}Drums{{else}}");
+    }
+}
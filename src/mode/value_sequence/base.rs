@@ -10,6 +10,21 @@ use std::fmt::{Debug, Display, Formatter, Write};
 #[derive(Clone, Eq, PartialEq, Debug, Default, SerializeDisplay, DeserializeFromStr)]
 pub struct ValueSequence {
     entries: Vec<ValueSequenceEntry>,
+    /// Optional labels, one for each entry.
+    labels: Vec<Option<String>>,
+    interpolation: ValueSequenceInterpolation,
+}
+
+/// Defines how an absolute control value is mapped to the unpacked sequence values.
+///
+/// In textual form, linear interpolation is expressed by a leading `~`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum ValueSequenceInterpolation {
+    /// Snaps to the nearest sequence value.
+    #[default]
+    Snap,
+    /// Interpolates linearly between the two neighboring sequence values.
+    Linear,
 }
 
 impl ValueSequence {
@@ -17,17 +32,24 @@ impl ValueSequence {
         single_value_parser: &P,
         input: &str,
     ) -> Result<Self, &'static str> {
-        let (_, raw_entries) =
-            super::parser::parse_entries(input).map_err(|_| "couldn't parse sequence")?;
+        let (_, (is_interpolated, raw_entries)) =
+            super::parser::parse_sequence(input).map_err(|_| "couldn't parse sequence")?;
         let sequence = ValueSequence {
+            interpolation: if is_interpolated {
+                ValueSequenceInterpolation::Linear
+            } else {
+                ValueSequenceInterpolation::Snap
+            },
             entries: {
                 raw_entries
                     .iter()
                     .map(|e| match e {
-                        RawEntry::SingleValue(e) => ValueSequenceEntry::SingleValue(
-                            single_value_parser.parse_value(e).unwrap_or_default(),
-                        ),
-                        RawEntry::Range(e) => {
+                        RawEntry::SingleValue(e) | RawEntry::LabeledValue(e, _) => {
+                            ValueSequenceEntry::SingleValue(
+                                single_value_parser.parse_value(e).unwrap_or_default(),
+                            )
+                        }
+                        RawEntry::Range(e) | RawEntry::LabeledRange(e, _) => {
                             let entry = ValueSequenceRangeEntry {
                                 from: single_value_parser
                                     .parse_value(e.simple_range.from)
@@ -44,6 +66,15 @@ impl ValueSequence {
                     })
                     .collect()
             },
+            labels: raw_entries
+                .iter()
+                .map(|e| match e {
+                    RawEntry::LabeledValue(_, label) | RawEntry::LabeledRange(_, label) => {
+                        Some(label.to_string())
+                    }
+                    _ => None,
+                })
+                .collect(),
         };
         Ok(sequence)
     }
@@ -56,6 +87,45 @@ impl ValueSequence {
         &self.entries
     }
 
+    /// Returns the label of the entry at the given index, if any.
+    pub fn label(&self, index: usize) -> Option<&str> {
+        self.labels.get(index)?.as_deref()
+    }
+
+    /// Returns the label of the first labeled entry which matches the given value.
+    ///
+    /// A single-value entry matches if it doesn't deviate from the given value by more than the
+    /// given tolerance. A range entry matches if the given value is within the range.
+    pub fn label_at(&self, value: UnitValue, tolerance: f64) -> Option<&str> {
+        let v = value.get();
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| match e {
+                ValueSequenceEntry::SingleValue(sv) => (sv.get() - v).abs() <= tolerance,
+                ValueSequenceEntry::Range(r) => {
+                    let (min, max) = if r.from <= r.to {
+                        (r.from.get(), r.to.get())
+                    } else {
+                        (r.to.get(), r.from.get())
+                    };
+                    v >= min - tolerance && v <= max + tolerance
+                }
+            })
+            .find_map(|(i, _)| self.label(i))
+    }
+
+    pub fn interpolation(&self) -> ValueSequenceInterpolation {
+        self.interpolation
+    }
+
+    pub fn with_interpolation(self, interpolation: ValueSequenceInterpolation) -> Self {
+        Self {
+            interpolation,
+            ..self
+        }
+    }
+
     pub fn displayable<'a>(&'a self, f: &'a impl ValueFormatter) -> impl Display + 'a {
         DisplayableValueSequence {
             value_sequence: self,
@@ -110,9 +180,19 @@ impl<'a, F: ValueFormatter> Display for DisplayableValueSequence<'a, F> {
             .value_sequence
             .entries
             .iter()
-            .map(|e| WithFormatter::new(e, self.value_formatter).to_string())
+            .enumerate()
+            .map(|(i, e)| {
+                let snippet = WithFormatter::new(e, self.value_formatter).to_string();
+                match self.value_sequence.label(i) {
+                    None => snippet,
+                    Some(label) => format!("{snippet} \"{label}\""),
+                }
+            })
             .collect();
         let csv = snippets.join(", ");
+        if self.value_sequence.interpolation == ValueSequenceInterpolation::Linear {
+            f.write_str("~ ")?;
+        }
         f.write_str(&csv)
     }
 }
@@ -456,6 +536,66 @@ mod tests {
         assert_abs_diff_eq!(at(28), uv(0.10));
     }
 
+    #[test]
+    fn interpolated() {
+        // Given
+        let sequence = ValueSequence::parse(&TestValueContext, "~ 250, 500").unwrap();
+        // When
+        // Then
+        assert_eq!(sequence.interpolation(), ValueSequenceInterpolation::Linear);
+        assert_eq!(
+            sequence.unpack(default_test_step_size()),
+            vec![uv(0.25), uv(0.50)]
+        );
+        assert_eq!(
+            &sequence.displayable(&TestValueContext).to_string(),
+            "~ 250, 500"
+        );
+        assert_eq!(&sequence.to_string(), "~ 0.25, 0.5")
+    }
+
+    #[test]
+    fn labels() {
+        // Given
+        let sequence =
+            ValueSequence::parse(&TestValueContext, "250 \"Verse\", 500, 750 \"Bridge\"").unwrap();
+        let ranged_sequence = ValueSequence::parse(
+            &TestValueContext,
+            "0 - 100 (0.01) \"Intro\", 800 - 700 \"Outro\"",
+        )
+        .unwrap();
+        // When
+        // Then
+        assert_eq!(
+            sequence.unpack(default_test_step_size()),
+            vec![uv(0.25), uv(0.50), uv(0.75)]
+        );
+        assert_eq!(sequence.label(0), Some("Verse"));
+        assert_eq!(sequence.label(1), None);
+        assert_eq!(sequence.label_at(uv(0.25), BASE_EPSILON), Some("Verse"));
+        assert_eq!(sequence.label_at(uv(0.26), BASE_EPSILON), None);
+        assert_eq!(sequence.label_at(uv(0.26), 0.01), Some("Verse"));
+        assert_eq!(sequence.label_at(uv(0.5), BASE_EPSILON), None);
+        assert_eq!(sequence.label_at(uv(0.75), BASE_EPSILON), Some("Bridge"));
+        assert_eq!(
+            &sequence.displayable(&TestValueContext).to_string(),
+            "250 \"Verse\", 500, 750 \"Bridge\""
+        );
+        assert_eq!(
+            ranged_sequence.label_at(uv(0.05), BASE_EPSILON),
+            Some("Intro")
+        );
+        assert_eq!(
+            ranged_sequence.label_at(uv(0.75), BASE_EPSILON),
+            Some("Outro")
+        );
+        assert_eq!(ranged_sequence.label_at(uv(0.5), BASE_EPSILON), None);
+        assert_eq!(
+            &ranged_sequence.displayable(&TestValueContext).to_string(),
+            "0 - 100 (0.01) \"Intro\", 800 - 700 \"Outro\""
+        );
+    }
+
     #[test]
     fn range_corner_cases() {
         // Given
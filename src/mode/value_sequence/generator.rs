@@ -0,0 +1,56 @@
+use crate::{
+    Transformation, TransformationInput, TransformationInputContext, TransformationInputEvent,
+    UnitValue,
+};
+
+/// Generates sequence values by evaluating a transformation instead of listing them by hand.
+///
+/// The transformation is invoked once per index `i` (0 <= i < count) with the normalized index
+/// `i / (count - 1)` as input value and the previously generated value as output value. This
+/// makes it possible to express things like dB ladders in a single formula.
+#[derive(Clone, Debug)]
+pub struct ValueSequenceGenerator<T> {
+    pub count: u32,
+    pub transformation: T,
+}
+
+impl<T: Transformation> ValueSequenceGenerator<T> {
+    pub fn new(count: u32, transformation: T) -> Self {
+        Self {
+            count,
+            transformation,
+        }
+    }
+
+    /// Evaluates the transformation for each index.
+    ///
+    /// Indexes for which the transformation fails or doesn't return a value are skipped.
+    pub fn generate(&self) -> Vec<UnitValue> {
+        let max_index = self.count.saturating_sub(1).max(1) as f64;
+        let mut values = Vec::with_capacity(self.count as usize);
+        let mut prev_value = 0.0;
+        for i in 0..self.count {
+            let input = TransformationInput {
+                event: TransformationInputEvent {
+                    input_value: i as f64 / max_index,
+                    timestamp: Default::default(),
+                },
+                context: TransformationInputContext {
+                    output_value: prev_value,
+                    rel_time: Default::default(),
+                },
+                additional_input: Default::default(),
+            };
+            let Ok(output) = self.transformation.transform(input) else {
+                continue;
+            };
+            let Some(value) = output.value else {
+                continue;
+            };
+            let value = UnitValue::new_clamped(value);
+            prev_value = value.get();
+            values.push(value);
+        }
+        values
+    }
+}
@@ -1,3 +1,5 @@
 mod base;
+mod generator;
 mod parser;
 pub use base::*;
+pub use generator::*;
@@ -2,7 +2,7 @@ use nom::branch::alt;
 use nom::character::complete::{space0, space1};
 use nom::combinator::opt;
 use nom::multi::separated_list0;
-use nom::sequence::separated_pair;
+use nom::sequence::{preceded, separated_pair};
 use nom::{
     bytes::complete::is_not, character::complete::char, sequence::delimited, sequence::tuple,
     IResult,
@@ -34,13 +34,28 @@ fn parse_full_range(input: &str) -> IResult<&str, RawFullRange> {
 }
 
 fn parse_range_entry(input: &str) -> IResult<&str, RawEntry> {
-    let (remainder, range) = parse_full_range(input)?;
-    Ok((remainder, RawEntry::Range(range)))
+    // The full range parser already consumes the space in front of the label
+    let mut parser = tuple((parse_full_range, opt(preceded(space0, parse_label))));
+    let (remainder, (range, label)) = parser(input)?;
+    let entry = match label {
+        None => RawEntry::Range(range),
+        Some(label) => RawEntry::LabeledRange(range, label),
+    };
+    Ok((remainder, entry))
+}
+
+fn parse_label(input: &str) -> IResult<&str, &str> {
+    delimited(char('"'), is_not("\""), char('"'))(input)
 }
 
 fn parse_single_value_entry(input: &str) -> IResult<&str, RawEntry> {
-    let (remainder, single_value) = parse_value(input)?;
-    Ok((remainder, RawEntry::SingleValue(single_value)))
+    let mut parser = tuple((parse_value, opt(preceded(space1, parse_label))));
+    let (remainder, (single_value, label)) = parser(input)?;
+    let entry = match label {
+        None => RawEntry::SingleValue(single_value),
+        Some(label) => RawEntry::LabeledValue(single_value, label),
+    };
+    Ok((remainder, entry))
 }
 
 fn parse_entry(input: &str) -> IResult<&str, RawEntry> {
@@ -53,10 +68,19 @@ pub fn parse_entries(input: &str) -> IResult<&str, Vec<RawEntry>> {
     parser(input)
 }
 
+/// Parses an optional leading `~`, which marks the sequence as interpolated.
+pub fn parse_sequence(input: &str) -> IResult<&str, (bool, Vec<RawEntry>)> {
+    let mut parser = tuple((opt(tuple((char('~'), space0))), parse_entries));
+    let (remainder, (marker, entries)) = parser(input)?;
+    Ok((remainder, (marker.is_some(), entries)))
+}
+
 #[derive(Eq, PartialEq, Debug)]
 pub enum RawEntry<'a> {
     SingleValue(&'a str),
+    LabeledValue(&'a str, &'a str),
     Range(RawFullRange<'a>),
+    LabeledRange(RawFullRange<'a>, &'a str),
 }
 
 #[derive(Eq, PartialEq, Debug)]
@@ -142,6 +166,48 @@ mod tests {
             ))
         );
         assert_eq!(parse_entry("75.5"), Ok(("", RawEntry::SingleValue("75.5"))));
+        assert_eq!(
+            parse_entry("75.5 \"Chorus\""),
+            Ok(("", RawEntry::LabeledValue("75.5", "Chorus")))
+        );
+        assert_eq!(
+            parse_entry("5 - 10 \"Verse\""),
+            Ok((
+                "",
+                RawEntry::LabeledRange(
+                    RawFullRange::new(RawSimpleRange::new("5", "10"), None),
+                    "Verse"
+                )
+            ))
+        );
+        assert_eq!(
+            parse_entry("5 - 10 (0.1) \"Verse\""),
+            Ok((
+                "",
+                RawEntry::LabeledRange(
+                    RawFullRange::new(RawSimpleRange::new("5", "10"), Some("0.1")),
+                    "Verse"
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn sequence() {
+        assert_eq!(
+            parse_sequence("~ 5, 10"),
+            Ok((
+                "",
+                (
+                    true,
+                    vec![RawEntry::SingleValue("5"), RawEntry::SingleValue("10")]
+                )
+            ))
+        );
+        assert_eq!(
+            parse_sequence("5"),
+            Ok(("", (false, vec![RawEntry::SingleValue("5")])))
+        );
     }
 
     #[test]
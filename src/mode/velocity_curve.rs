@@ -0,0 +1,117 @@
+use crate::UnitValue;
+use serde::{Deserialize, Serialize};
+
+/// A built-in curve which shapes incoming button velocities without the need to write a control
+/// transformation.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum VelocityCurve {
+    #[default]
+    #[serde(rename = "linear")]
+    Linear,
+    /// Boosts low velocities, so soft hits already produce large values.
+    #[serde(rename = "soft")]
+    Soft,
+    /// Attenuates low velocities, so it takes hard hits to produce large values.
+    #[serde(rename = "hard")]
+    Hard,
+    /// Connects the given (velocity, output) breakpoints linearly.
+    ///
+    /// Breakpoints must be sorted by velocity. If not given explicitly, the curve starts at
+    /// (0, 0) and ends at (1, 1).
+    #[serde(rename = "custom")]
+    Custom { breakpoints: Vec<(f64, f64)> },
+}
+
+impl VelocityCurve {
+    pub fn is_linear(&self) -> bool {
+        use VelocityCurve::*;
+        match self {
+            Linear => true,
+            Soft | Hard => false,
+            Custom { breakpoints } => breakpoints.is_empty(),
+        }
+    }
+
+    /// Applies this curve to the given velocity.
+    pub fn apply(&self, x: UnitValue) -> UnitValue {
+        use VelocityCurve::*;
+        let x = x.get();
+        let y = match self {
+            Linear => x,
+            Soft => x.sqrt(),
+            Hard => x * x,
+            Custom { breakpoints } => interpolate(breakpoints.iter().copied(), x),
+        };
+        UnitValue::new_clamped(y)
+    }
+
+    /// Reverts this curve, e.g. for turning a feedback value back into a velocity.
+    ///
+    /// Custom curves can only be reverted correctly if their outputs are increasing.
+    pub fn apply_inverse(&self, y: UnitValue) -> UnitValue {
+        use VelocityCurve::*;
+        let y = y.get();
+        let x = match self {
+            Linear => y,
+            Soft => y * y,
+            Hard => y.sqrt(),
+            Custom { breakpoints } => interpolate(breakpoints.iter().map(|&(bx, by)| (by, bx)), y),
+        };
+        UnitValue::new_clamped(x)
+    }
+}
+
+fn interpolate(breakpoints: impl Iterator<Item = (f64, f64)>, x: f64) -> f64 {
+    let mut prev = (0.0, 0.0);
+    for (bx, by) in breakpoints {
+        if x <= bx {
+            let (px, py) = prev;
+            if bx <= px {
+                return by;
+            }
+            return py + (by - py) * (x - px) / (bx - px);
+        }
+        prev = (bx, by);
+    }
+    let (px, py) = prev;
+    if px >= 1.0 {
+        return py;
+    }
+    py + (1.0 - py) * (x - px) / (1.0 - px)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn custom() {
+        // Given
+        let curve = VelocityCurve::Custom {
+            breakpoints: vec![(0.5, 0.8)],
+        };
+        // When
+        // Then
+        assert_abs_diff_eq!(curve.apply(UnitValue::new(0.0)).get(), 0.0);
+        assert_abs_diff_eq!(curve.apply(UnitValue::new(0.25)).get(), 0.4);
+        assert_abs_diff_eq!(curve.apply(UnitValue::new(0.5)).get(), 0.8);
+        assert_abs_diff_eq!(curve.apply(UnitValue::new(0.75)).get(), 0.9);
+        assert_abs_diff_eq!(curve.apply(UnitValue::new(1.0)).get(), 1.0);
+        assert_abs_diff_eq!(curve.apply_inverse(UnitValue::new(0.4)).get(), 0.25);
+        assert_abs_diff_eq!(curve.apply_inverse(UnitValue::new(0.9)).get(), 0.75);
+    }
+
+    #[test]
+    fn presets_inverse() {
+        for curve in [
+            VelocityCurve::Linear,
+            VelocityCurve::Soft,
+            VelocityCurve::Hard,
+        ] {
+            let y = curve.apply(UnitValue::new(0.3));
+            assert_abs_diff_eq!(curve.apply_inverse(y).get(), 0.3, epsilon = 0.000_001);
+        }
+    }
+}
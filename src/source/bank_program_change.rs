@@ -0,0 +1,239 @@
+use crate::{AbstractTimestamp, ControlEvent, MidiSourceValue};
+use helgoboss_midi::{
+    Channel, ControllerNumber, ShortMessage, ShortMessageFactory, StructuredShortMessage, U14, U7,
+};
+
+const CHANNEL_COUNT: usize = 16;
+const BANK_SELECT_MSB_CONTROLLER_NUMBER: u8 = 0;
+const BANK_SELECT_LSB_CONTROLLER_NUMBER: u8 = 32;
+
+/// A program change preceded by a 14-bit bank select (CC0 = MSB, CC32 = LSB).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct BankProgramChangeMessage {
+    channel: Channel,
+    bank: U14,
+    program_number: U7,
+}
+
+impl BankProgramChangeMessage {
+    /// Highest combined value (last program of last bank).
+    pub const MAX_VALUE: u32 = 16384 * 128 - 1;
+
+    pub fn new(channel: Channel, bank: U14, program_number: U7) -> Self {
+        Self {
+            channel,
+            bank,
+            program_number,
+        }
+    }
+
+    /// Creates a message from a value which combines bank and program number (see
+    /// [`Self::value`]). The value is clamped to [`Self::MAX_VALUE`].
+    pub fn from_value(channel: Channel, value: u32) -> Self {
+        let value = value.min(Self::MAX_VALUE);
+        Self {
+            channel,
+            bank: U14::new((value >> 7) as u16),
+            program_number: U7::new((value & 0x7f) as u8),
+        }
+    }
+
+    pub fn channel(&self) -> Channel {
+        self.channel
+    }
+
+    pub fn bank(&self) -> U14 {
+        self.bank
+    }
+
+    pub fn program_number(&self) -> U7 {
+        self.program_number
+    }
+
+    /// Returns bank and program number combined into one value: `bank * 128 + program_number`.
+    pub fn value(&self) -> u32 {
+        ((self.bank.get() as u32) << 7) | self.program_number.get() as u32
+    }
+
+    /// Returns bank select MSB, bank select LSB and program change, in the order in which they
+    /// must be sent.
+    pub fn to_short_messages<M: ShortMessageFactory>(&self) -> [M; 3] {
+        let bank = self.bank.get();
+        [
+            M::control_change(
+                self.channel,
+                ControllerNumber::new(BANK_SELECT_MSB_CONTROLLER_NUMBER),
+                U7::new((bank >> 7) as u8),
+            ),
+            M::control_change(
+                self.channel,
+                ControllerNumber::new(BANK_SELECT_LSB_CONTROLLER_NUMBER),
+                U7::new((bank & 0x7f) as u8),
+            ),
+            M::program_change(self.channel, self.program_number),
+        ]
+    }
+}
+
+/// Observes a stream of short MIDI messages and combines program changes with the preceding bank
+/// select messages.
+///
+/// Program changes on channels which have seen a bank select are turned into
+/// [`MidiSourceValue::BankProgramChange`]. A missing MSB or LSB counts as zero. Bank selects stay
+/// in effect for subsequent program changes, as the MIDI specification defines. Everything else
+/// (including the bank select messages themselves) is passed through as it is.
+///
+/// Combined values carry the timestamp of the bank select which directly preceded the program
+/// change (or of the program change itself if the bank select was sent earlier).
+#[derive(Clone, Debug)]
+pub struct BankProgramChangeCombiner<S: AbstractTimestamp> {
+    banks: [Option<Bank>; CHANNEL_COUNT],
+    /// Timestamps of bank selects which haven't been followed by a program change yet.
+    bank_select_timestamps: [Option<S>; CHANNEL_COUNT],
+}
+
+impl<S: AbstractTimestamp> Default for BankProgramChangeCombiner<S> {
+    fn default() -> Self {
+        Self {
+            banks: Default::default(),
+            bank_select_timestamps: [None; CHANNEL_COUNT],
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+struct Bank {
+    msb: u8,
+    lsb: u8,
+}
+
+impl<S: AbstractTimestamp> BankProgramChangeCombiner<S> {
+    /// Feeds the given message into the combiner and returns the value which should be processed.
+    pub fn feed<M: ShortMessage + Copy>(
+        &mut self,
+        msg: M,
+        timestamp: S,
+    ) -> ControlEvent<MidiSourceValue<'static, M>, S> {
+        use StructuredShortMessage::*;
+        match msg.to_structured() {
+            ControlChange {
+                channel,
+                controller_number,
+                control_value,
+            } => {
+                let value = control_value.get();
+                match controller_number.get() {
+                    BANK_SELECT_MSB_CONTROLLER_NUMBER => {
+                        self.bank_mut(channel).msb = value;
+                        self.memorize_bank_select_timestamp(channel, timestamp);
+                    }
+                    BANK_SELECT_LSB_CONTROLLER_NUMBER => {
+                        self.bank_mut(channel).lsb = value;
+                        self.memorize_bank_select_timestamp(channel, timestamp);
+                    }
+                    _ => {}
+                }
+            }
+            ProgramChange {
+                channel,
+                program_number,
+            } => {
+                let channel_index = channel.get() as usize;
+                if let Some(bank) = self.banks[channel_index] {
+                    let bank = U14::new(((bank.msb as u16) << 7) | bank.lsb as u16);
+                    let msg = BankProgramChangeMessage::new(channel, bank, program_number);
+                    let timestamp = self.bank_select_timestamps[channel_index]
+                        .take()
+                        .unwrap_or(timestamp);
+                    return ControlEvent::new(MidiSourceValue::BankProgramChange(msg), timestamp);
+                }
+            }
+            _ => {}
+        }
+        ControlEvent::new(MidiSourceValue::Plain(msg), timestamp)
+    }
+
+    /// Forgets all bank selects.
+    pub fn reset(&mut self) {
+        self.banks = Default::default();
+        self.bank_select_timestamps = [None; CHANNEL_COUNT];
+    }
+
+    fn memorize_bank_select_timestamp(&mut self, channel: Channel, timestamp: S) {
+        self.bank_select_timestamps[channel.get() as usize].get_or_insert(timestamp);
+    }
+
+    fn bank_mut(&mut self, channel: Channel) -> &mut Bank {
+        self.banks[channel.get() as usize].get_or_insert_with(Default::default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_midi::test_util::*;
+    use helgoboss_midi::RawShortMessage;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn combine() {
+        // Given
+        let mut combiner = BankProgramChangeCombiner::default();
+        let mut feed = |msg: RawShortMessage| combiner.feed(msg, Instant::now()).into_payload();
+        // When
+        // Then
+        assert_eq!(
+            feed(program_change(1, 5)),
+            MidiSourceValue::Plain(program_change(1, 5))
+        );
+        assert_eq!(
+            feed(control_change(1, 0, 2)),
+            MidiSourceValue::Plain(control_change(1, 0, 2))
+        );
+        feed(control_change(1, 32, 3));
+        let expected =
+            BankProgramChangeMessage::new(Channel::new(1), U14::new(2 * 128 + 3), U7::new(5));
+        assert_eq!(
+            feed(program_change(1, 5)),
+            MidiSourceValue::BankProgramChange(expected)
+        );
+        assert_eq!(expected.value(), (2 * 128 + 3) * 128 + 5);
+        assert_eq!(
+            BankProgramChangeMessage::from_value(Channel::new(1), expected.value()),
+            expected
+        );
+        assert_eq!(
+            expected.to_short_messages::<RawShortMessage>(),
+            [
+                control_change(1, 0, 2),
+                control_change(1, 32, 3),
+                program_change(1, 5)
+            ]
+        );
+        // Other channel
+        assert_eq!(
+            feed(program_change(2, 5)),
+            MidiSourceValue::Plain(program_change(2, 5))
+        );
+    }
+
+    #[test]
+    fn keep_timestamp_of_bank_select() {
+        // Given
+        let mut combiner = BankProgramChangeCombiner::default();
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        // When
+        combiner.feed::<RawShortMessage>(control_change(1, 0, 2), at(0));
+        combiner.feed::<RawShortMessage>(control_change(1, 32, 3), at(1));
+        let directly_preceded = combiner.feed::<RawShortMessage>(program_change(1, 5), at(2));
+        let bank_selected_earlier = combiner.feed::<RawShortMessage>(program_change(1, 6), at(50));
+        // Then
+        assert_eq!(directly_preceded.timestamp(), at(0));
+        assert_eq!(bank_selected_earlier.timestamp(), at(50));
+        assert!(matches!(
+            bank_selected_earlier.payload_ref(),
+            MidiSourceValue::BankProgramChange(_)
+        ));
+    }
+}
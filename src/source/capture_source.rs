@@ -0,0 +1,267 @@
+use crate::{ControlValue, MidiSource, MidiSourceScript, MidiSourceValue, OscSource};
+use derivative::Derivative;
+use derive_more::Display;
+use helgoboss_midi::{Channel, ShortMessage, ShortMessageType};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use rosc::OscMessage;
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use strum::EnumIter;
+
+/// Class of short MIDI messages matched by a [`MidiCaptureSource`].
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize_repr,
+    Deserialize_repr,
+)]
+#[repr(usize)]
+pub enum MidiCaptureClass {
+    #[default]
+    #[display(fmt = "Any channel message")]
+    Any = 0,
+    #[display(fmt = "CC value")]
+    ControlChange = 1,
+    #[display(fmt = "Note velocity")]
+    Note = 2,
+    #[display(fmt = "Polyphonic after touch")]
+    PolyphonicKeyPressure = 3,
+    #[display(fmt = "Program change")]
+    ProgramChange = 4,
+    #[display(fmt = "Channel after touch")]
+    ChannelPressure = 5,
+    #[display(fmt = "Pitch wheel")]
+    PitchBend = 6,
+}
+
+impl MidiCaptureClass {
+    fn matches(&self, msg_type: ShortMessageType) -> bool {
+        use ShortMessageType as T;
+        match self {
+            MidiCaptureClass::Any => true,
+            MidiCaptureClass::ControlChange => msg_type == T::ControlChange,
+            MidiCaptureClass::Note => matches!(msg_type, T::NoteOn | T::NoteOff),
+            MidiCaptureClass::PolyphonicKeyPressure => msg_type == T::PolyphonicKeyPressure,
+            MidiCaptureClass::ProgramChange => msg_type == T::ProgramChange,
+            MidiCaptureClass::ChannelPressure => msg_type == T::ChannelPressure,
+            MidiCaptureClass::PitchBend => msg_type == T::PitchBendChange,
+        }
+    }
+}
+
+/// Source which matches any short channel message of a given class (e.g. any CC on a channel)
+/// and tells which concrete element has been hit.
+///
+/// Meant for learn flows and monitors, not for controlling things directly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct MidiCaptureSource {
+    /// `None` means any channel.
+    pub channel: Option<Channel>,
+    pub class: MidiCaptureClass,
+}
+
+/// The concrete element captured by a [`MidiCaptureSource`].
+#[derive(Clone, Debug, Derivative)]
+#[derivative(PartialEq)]
+pub struct MidiCapture<S: for<'a> MidiSourceScript<'a>> {
+    /// Source which exclusively matches the captured element.
+    pub source: MidiSource<S>,
+    /// Value which the captured element would produce with the default source settings.
+    pub value: Option<ControlValue>,
+}
+
+impl MidiCaptureSource {
+    pub fn new(channel: Option<Channel>, class: MidiCaptureClass) -> Self {
+        Self { channel, class }
+    }
+
+    /// Returns the captured element if the given value is a short message of the configured
+    /// class and channel.
+    pub fn capture<S, M>(&self, value: &MidiSourceValue<M>) -> Option<MidiCapture<S>>
+    where
+        S: for<'a> MidiSourceScript<'a>,
+        M: ShortMessage + Copy,
+    {
+        let MidiSourceValue::Plain(msg) = value else {
+            return None;
+        };
+        let channel = msg.channel()?;
+        if self.channel.is_some_and(|ch| ch != channel) || !self.class.matches(msg.r#type()) {
+            return None;
+        }
+        let source = MidiSource::from_source_value(MidiSourceValue::<M>::Plain(*msg), None)?;
+        let value = source.control(value);
+        Some(MidiCapture { source, value })
+    }
+}
+
+/// Source which matches any OSC message whose address is located under a given prefix and tells
+/// which concrete address has been hit.
+///
+/// Meant for learn flows and monitors, not for controlling things directly.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct OscCaptureSource {
+    address_prefix: String,
+}
+
+/// The concrete element captured by an [`OscCaptureSource`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct OscCapture<'a> {
+    msg: &'a OscMessage,
+    relative_address: &'a str,
+}
+
+impl OscCaptureSource {
+    /// The prefix is interpreted part-wise, so `/track` matches `/track/1/volume` but not
+    /// `/tracks`. An empty prefix or `/` matches everything.
+    pub fn new(address_prefix: impl Into<String>) -> Self {
+        let mut address_prefix = address_prefix.into();
+        while address_prefix.ends_with('/') {
+            address_prefix.pop();
+        }
+        Self { address_prefix }
+    }
+
+    pub fn address_prefix(&self) -> &str {
+        &self.address_prefix
+    }
+
+    /// Returns the captured element if the address of the given message is located under the
+    /// configured prefix.
+    pub fn capture<'a>(&self, msg: &'a OscMessage) -> Option<OscCapture<'a>> {
+        let rest = msg.addr.strip_prefix(self.address_prefix.as_str())?;
+        if !rest.is_empty() && !rest.starts_with('/') {
+            return None;
+        }
+        let capture = OscCapture {
+            msg,
+            relative_address: rest.trim_start_matches('/'),
+        };
+        Some(capture)
+    }
+}
+
+impl<'a> OscCapture<'a> {
+    /// The complete address of the captured message.
+    pub fn address(&self) -> &'a str {
+        &self.msg.addr
+    }
+
+    /// The part of the address after the prefix, e.g. `1/volume` for prefix `/track` and address
+    /// `/track/1/volume`.
+    pub fn relative_address(&self) -> &'a str {
+        self.relative_address
+    }
+
+    /// Returns a source which exclusively matches the captured address, using the first
+    /// argument.
+    ///
+    /// Allocates!
+    pub fn to_source(&self) -> OscSource {
+        OscSource::from_source_value(self.msg.clone(), Some(0))
+    }
+
+    /// Returns the value which the captured element would produce with the default source
+    /// settings.
+    ///
+    /// Allocates!
+    pub fn value(&self) -> Option<ControlValue> {
+        self.to_source().control(self.msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::test_util::TestMidiSourceScript;
+    use crate::{full_7_bit_interval, Fraction, UnitValue};
+    use helgoboss_midi::test_util::{channel as ch, controller_number as cn, key_number as kn, *};
+    use helgoboss_midi::RawShortMessage;
+    use rosc::OscType;
+
+    #[test]
+    fn capture_any_cc_on_channel() {
+        // Given
+        let source = MidiCaptureSource::new(Some(ch(0)), MidiCaptureClass::ControlChange);
+        let capture = |msg: RawShortMessage| -> Option<MidiCapture<TestMidiSourceScript>> {
+            source.capture(&MidiSourceValue::Plain(msg))
+        };
+        // When
+        let captured = capture(control_change(0, 7, 127)).unwrap();
+        // Then
+        assert_eq!(
+            captured.source,
+            MidiSource::ControlChangeValue {
+                channel: Some(ch(0)),
+                controller_number: Some(cn(7)),
+                custom_character: Default::default(),
+                value_interval: full_7_bit_interval(),
+            }
+        );
+        assert_eq!(
+            captured.value,
+            Some(ControlValue::AbsoluteDiscrete(Fraction::new_max(127)))
+        );
+        assert!(capture(control_change(0, 64, 0)).is_some());
+        assert!(capture(control_change(1, 7, 127)).is_none());
+        assert!(capture(note_on(0, 64, 100)).is_none());
+    }
+
+    #[test]
+    fn capture_any_note() {
+        // Given
+        let source = MidiCaptureSource::new(None, MidiCaptureClass::Note);
+        // When
+        let captured: MidiCapture<TestMidiSourceScript> = source
+            .capture(&MidiSourceValue::<RawShortMessage>::Plain(note_on(
+                3, 60, 127,
+            )))
+            .unwrap();
+        // Then
+        assert!(matches!(
+            captured.source,
+            MidiSource::NoteVelocity {
+                channel: Some(c),
+                key_number: Some(k),
+                ..
+            } if c == ch(3) && k == kn(60)
+        ));
+        assert!(source
+            .capture::<TestMidiSourceScript, _>(&MidiSourceValue::<RawShortMessage>::Plain(
+                pitch_bend_change(3, 0)
+            ))
+            .is_none());
+    }
+
+    #[test]
+    fn capture_osc_prefix() {
+        // Given
+        let source = OscCaptureSource::new("/track/");
+        let msg = |addr: &str| OscMessage {
+            addr: addr.to_owned(),
+            args: vec![OscType::Float(0.5)],
+        };
+        // When
+        let volume_msg = msg("/track/1/volume");
+        let captured = source.capture(&volume_msg).unwrap();
+        // Then
+        assert_eq!(captured.address(), "/track/1/volume");
+        assert_eq!(captured.relative_address(), "1/volume");
+        assert_eq!(captured.to_source().address_pattern(), "/track/1/volume");
+        assert_eq!(
+            captured.value(),
+            Some(ControlValue::AbsoluteContinuous(UnitValue::new(0.5)))
+        );
+        assert!(source.capture(&msg("/track")).is_some());
+        assert!(source.capture(&msg("/tracks/1")).is_none());
+        assert!(source.capture(&msg("/master/volume")).is_none());
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{AbstractTimestamp, ControlValue, UnitValue};
+use helgoboss_midi::{Channel, KeyNumber, ShortMessage, StructuredShortMessage};
+use std::time::Duration;
+
+/// Source which fires when a set of notes is held simultaneously, e.g. for shift-style chord
+/// triggers on keyboards.
+///
+/// Emits a press as soon as the last member of the chord is pressed, provided that all members
+/// have been pressed within the configured window. Emits a release as soon as any member is
+/// released.
+#[derive(Clone, Debug)]
+pub struct ChordSource<S: AbstractTimestamp> {
+    /// `None` means any channel.
+    channel: Option<Channel>,
+    key_numbers: Vec<KeyNumber>,
+    window: Duration,
+    /// Press timestamps of the currently held members, in the same order as `key_numbers`.
+    press_timestamps: Vec<Option<S>>,
+    is_active: bool,
+}
+
+impl<S: AbstractTimestamp> ChordSource<S> {
+    /// Creates a chord source made of the given notes. Duplicates are ignored.
+    pub fn new(
+        channel: Option<Channel>,
+        key_numbers: impl IntoIterator<Item = KeyNumber>,
+        window: Duration,
+    ) -> Result<Self, &'static str> {
+        let mut key_numbers: Vec<_> = key_numbers.into_iter().collect();
+        key_numbers.sort();
+        key_numbers.dedup();
+        if key_numbers.is_empty() {
+            return Err("chord must contain at least one note");
+        }
+        let source = Self {
+            channel,
+            press_timestamps: vec![None; key_numbers.len()],
+            key_numbers,
+            window,
+            is_active: false,
+        };
+        Ok(source)
+    }
+
+    pub fn channel(&self) -> Option<Channel> {
+        self.channel
+    }
+
+    pub fn key_numbers(&self) -> &[KeyNumber] {
+        &self.key_numbers
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Returns whether the chord is currently held.
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    /// Feeds the given message into the source.
+    ///
+    /// Returns [`UnitValue::MAX`] when the chord has been completed and [`UnitValue::MIN`] when
+    /// the completed chord has been released. Returns `None` for all other messages.
+    pub fn control(&mut self, msg: impl ShortMessage, timestamp: S) -> Option<ControlValue> {
+        let (channel, key_number, is_press) = match msg.to_structured() {
+            StructuredShortMessage::NoteOn {
+                channel,
+                key_number,
+                velocity,
+            } => (channel, key_number, velocity.get() > 0),
+            StructuredShortMessage::NoteOff {
+                channel,
+                key_number,
+                ..
+            } => (channel, key_number, false),
+            _ => return None,
+        };
+        if self.channel.is_some_and(|ch| ch != channel) {
+            return None;
+        }
+        let index = self.key_numbers.binary_search(&key_number).ok()?;
+        if is_press {
+            self.press_timestamps[index] = Some(timestamp);
+            if self.is_active {
+                return None;
+            }
+            let all_pressed_within_window = self
+                .press_timestamps
+                .iter()
+                .all(|t| t.is_some_and(|t| timestamp - t <= self.window));
+            if !all_pressed_within_window {
+                return None;
+            }
+            self.is_active = true;
+            Some(ControlValue::AbsoluteContinuous(UnitValue::MAX))
+        } else {
+            self.press_timestamps[index] = None;
+            if !self.is_active {
+                return None;
+            }
+            self.is_active = false;
+            Some(ControlValue::AbsoluteContinuous(UnitValue::MIN))
+        }
+    }
+
+    /// Forgets all held notes, e.g. after the device has been reconnected.
+    pub fn reset(&mut self) {
+        self.press_timestamps.fill(None);
+        self.is_active = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_midi::test_util::{channel as ch, key_number as kn, *};
+    use helgoboss_midi::RawShortMessage;
+    use std::time::Instant;
+
+    const PRESS: Option<ControlValue> = Some(ControlValue::AbsoluteContinuous(UnitValue::MAX));
+    const RELEASE: Option<ControlValue> = Some(ControlValue::AbsoluteContinuous(UnitValue::MIN));
+
+    #[test]
+    fn press_and_release() {
+        // Given
+        let mut source =
+            ChordSource::new(Some(ch(0)), [kn(60), kn(64)], Duration::from_millis(50)).unwrap();
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let mut feed = |msg: RawShortMessage, millis| source.control(msg, at(millis));
+        // When
+        // Then
+        assert_eq!(feed(note_on(0, 60, 100), 0), None);
+        assert_eq!(feed(note_on(1, 64, 100), 10), None);
+        assert_eq!(feed(note_on(0, 62, 100), 10), None);
+        assert_eq!(feed(note_on(0, 64, 100), 20), PRESS);
+        assert_eq!(feed(note_on(0, 64, 0), 30), RELEASE);
+        assert_eq!(feed(note_off(0, 60, 0), 40), None);
+    }
+
+    #[test]
+    fn window() {
+        // Given
+        let mut source =
+            ChordSource::new(None, [kn(60), kn(64)], Duration::from_millis(50)).unwrap();
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let mut feed = |msg: RawShortMessage, millis| source.control(msg, at(millis));
+        // When
+        let too_late = [feed(note_on(0, 60, 100), 0), feed(note_on(0, 64, 100), 100)];
+        let repressed = feed(note_on(0, 60, 100), 120);
+        // Then
+        assert_eq!(too_late, [None, None]);
+        assert_eq!(repressed, PRESS);
+        assert!(source.is_active());
+        assert!(ChordSource::<Instant>::new(None, [], Duration::ZERO).is_err());
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{AbstractTimestamp, ControlEvent, MidiSourceValue};
+use helgoboss_midi::{
+    Channel, ControlChange14BitMessage, ControllerNumber, ShortMessage, StructuredShortMessage, U14,
+};
+use std::time::Duration;
+
+const CHANNEL_COUNT: usize = 16;
+const MSB_CONTROLLER_COUNT: usize = 32;
+
+/// Observes a stream of short MIDI messages and pairs MSB/LSB control change messages into 14-bit
+/// control change messages automatically.
+///
+/// Controllers are considered 14-bit as soon as an LSB has been observed following their MSB.
+/// Until then, their MSBs are passed through as they are. The MSB of a 14-bit controller is held
+/// back until the corresponding LSB arrives. If the LSB doesn't arrive within the timeout, the
+/// MSB is emitted with an LSB of zero (see [`Self::poll`]).
+///
+/// Emitted 14-bit values carry the timestamp of their MSB, that is, the time when the first part
+/// of the message arrived.
+#[derive(Clone, Debug)]
+pub struct ControlChange14BitPairer<S: AbstractTimestamp> {
+    timeout: Duration,
+    slots: Vec<Slot<S>>,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Slot<S> {
+    /// Whether an LSB has ever been observed for this controller.
+    is_14_bit: bool,
+    last_msb: Option<u8>,
+    /// If set, we are still waiting for the LSB which belongs to the last MSB.
+    msb_timestamp: Option<S>,
+}
+
+impl<S> Default for Slot<S> {
+    fn default() -> Self {
+        Self {
+            is_14_bit: false,
+            last_msb: None,
+            msb_timestamp: None,
+        }
+    }
+}
+
+impl<S: AbstractTimestamp> ControlChange14BitPairer<S> {
+    /// Creates a pairer which waits at most the given time for a missing LSB.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            slots: vec![Default::default(); CHANNEL_COUNT * MSB_CONTROLLER_COUNT],
+        }
+    }
+
+    /// Feeds the given message into the pairer.
+    ///
+    /// Returns a value which is ready for processing, if there is one. Messages which can't be
+    /// part of a 14-bit control change message are returned unchanged.
+    pub fn feed<M: ShortMessage + Copy>(
+        &mut self,
+        msg: M,
+        timestamp: S,
+    ) -> Option<ControlEvent<MidiSourceValue<'static, M>, S>> {
+        let plain = ControlEvent::new(MidiSourceValue::Plain(msg), timestamp);
+        let StructuredShortMessage::ControlChange {
+            channel,
+            controller_number,
+            control_value,
+        } = msg.to_structured()
+        else {
+            return Some(plain);
+        };
+        let number = controller_number.get() as usize;
+        let value = control_value.get();
+        if number < MSB_CONTROLLER_COUNT {
+            // MSB
+            let slot = &mut self.slots[slot_index(channel, number)];
+            let previous = if slot.is_14_bit {
+                slot.msb_timestamp
+                    .zip(slot.last_msb)
+                    .map(|(t, msb)| ControlEvent::new(create_value(channel, number, msb, 0), t))
+            } else {
+                None
+            };
+            slot.last_msb = Some(value);
+            slot.msb_timestamp = Some(timestamp);
+            if slot.is_14_bit {
+                // Wait for LSB. If an MSB was still waiting, it won't get its LSB anymore.
+                previous
+            } else {
+                Some(plain)
+            }
+        } else if number < 2 * MSB_CONTROLLER_COUNT {
+            // LSB
+            let msb_number = number - MSB_CONTROLLER_COUNT;
+            let slot = &mut self.slots[slot_index(channel, msb_number)];
+            let Some(msb) = slot.last_msb else {
+                return Some(plain);
+            };
+            let msb_timestamp = slot
+                .msb_timestamp
+                .filter(|t| timestamp - *t <= self.timeout);
+            if msb_timestamp.is_none() && !slot.is_14_bit {
+                return Some(plain);
+            }
+            // Either the LSB directly follows the MSB or we know it's a 14-bit controller whose
+            // MSB didn't change.
+            slot.is_14_bit = true;
+            slot.msb_timestamp = None;
+            let value = create_value(channel, msb_number, msb, value);
+            Some(ControlEvent::new(value, msb_timestamp.unwrap_or(timestamp)))
+        } else {
+            Some(plain)
+        }
+    }
+
+    /// Returns the next MSB whose LSB didn't arrive in time, combined with an LSB of zero.
+    ///
+    /// Should be called regularly, repeatedly until it returns `None`.
+    pub fn poll<M: ShortMessage>(
+        &mut self,
+        now: S,
+    ) -> Option<ControlEvent<MidiSourceValue<'static, M>, S>> {
+        for (i, slot) in self.slots.iter_mut().enumerate() {
+            let Some(t) = slot.msb_timestamp else {
+                continue;
+            };
+            if now - t <= self.timeout {
+                continue;
+            }
+            slot.msb_timestamp = None;
+            if !slot.is_14_bit {
+                // Has been passed through already
+                continue;
+            }
+            let channel = Channel::new((i / MSB_CONTROLLER_COUNT) as u8);
+            let msb = slot.last_msb?;
+            let value = create_value(channel, i % MSB_CONTROLLER_COUNT, msb, 0);
+            return Some(ControlEvent::new(value, t));
+        }
+        None
+    }
+
+    /// Forgets everything observed so far.
+    pub fn reset(&mut self) {
+        self.slots.fill(Default::default());
+    }
+}
+
+fn slot_index(channel: Channel, msb_number: usize) -> usize {
+    channel.get() as usize * MSB_CONTROLLER_COUNT + msb_number
+}
+
+fn create_value<M: ShortMessage>(
+    channel: Channel,
+    msb_number: usize,
+    msb: u8,
+    lsb: u8,
+) -> MidiSourceValue<'static, M> {
+    let value = U14::new(((msb as u16) << 7) | lsb as u16);
+    let msg =
+        ControlChange14BitMessage::new(channel, ControllerNumber::new(msb_number as u8), value);
+    MidiSourceValue::ControlChange14Bit(msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_midi::test_util::*;
+    use helgoboss_midi::RawShortMessage;
+    use std::time::Instant;
+
+    #[test]
+    fn pair() {
+        // Given
+        let mut pairer = ControlChange14BitPairer::new(Duration::from_millis(10));
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let feed = |pairer: &mut ControlChange14BitPairer<Instant>, msg, millis| {
+            pairer
+                .feed::<RawShortMessage>(msg, at(millis))
+                .map(|e| e.into_payload())
+        };
+        // When
+        // Then
+        // Unknown controller: MSB is passed through, LSB completes it
+        assert_eq!(
+            feed(&mut pairer, control_change(1, 10, 93), 0),
+            Some(MidiSourceValue::Plain(control_change(1, 10, 93)))
+        );
+        assert_eq!(
+            feed(&mut pairer, control_change(1, 42, 96), 1),
+            Some(MidiSourceValue::ControlChange14Bit(control_change_14_bit(
+                1, 10, 12000
+            )))
+        );
+        // Known 14-bit controller: MSB waits for LSB
+        assert_eq!(feed(&mut pairer, control_change(1, 10, 94), 20), None);
+        assert_eq!(
+            feed(&mut pairer, control_change(1, 42, 0), 21),
+            Some(MidiSourceValue::ControlChange14Bit(control_change_14_bit(
+                1, 10, 12032
+            )))
+        );
+        // LSB only
+        assert_eq!(
+            feed(&mut pairer, control_change(1, 42, 1), 40),
+            Some(MidiSourceValue::ControlChange14Bit(control_change_14_bit(
+                1, 10, 12033
+            )))
+        );
+        // Missing LSB
+        assert_eq!(feed(&mut pairer, control_change(1, 10, 95), 60), None);
+        assert_eq!(pairer.poll::<RawShortMessage>(at(65)), None);
+        assert_eq!(
+            pairer.poll::<RawShortMessage>(at(80)),
+            Some(ControlEvent::new(
+                MidiSourceValue::ControlChange14Bit(control_change_14_bit(1, 10, 12160)),
+                at(60)
+            ))
+        );
+        assert_eq!(pairer.poll::<RawShortMessage>(at(90)), None);
+        // Other messages
+        assert_eq!(
+            feed(&mut pairer, control_change(1, 64, 127), 100),
+            Some(MidiSourceValue::Plain(control_change(1, 64, 127)))
+        );
+    }
+
+    #[test]
+    fn keep_msb_timestamp() {
+        // Given
+        let mut pairer = ControlChange14BitPairer::new(Duration::from_millis(10));
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        // When
+        pairer.feed::<RawShortMessage>(control_change(1, 10, 93), at(0));
+        let paired = pairer.feed::<RawShortMessage>(control_change(1, 42, 96), at(3));
+        let lsb_only = pairer.feed::<RawShortMessage>(control_change(1, 42, 97), at(50));
+        // Then
+        assert_eq!(paired.map(|e| e.timestamp()), Some(at(0)));
+        assert_eq!(lsb_only.map(|e| e.timestamp()), Some(at(50)));
+    }
+}
@@ -0,0 +1,261 @@
+use crate::{AbstractTimestamp, ControlEvent, MidiSourceValue};
+use helgoboss_midi::{
+    Channel, ControllerNumber, DataEntryByteOrder, ShortMessage, ShortMessageFactory,
+    StructuredShortMessage, U14, U7,
+};
+
+const CHANNEL_COUNT: usize = 16;
+
+/// Two arbitrary control change messages which together make up a 14-bit value.
+///
+/// In contrast to [`helgoboss_midi::ControlChange14BitMessage`], the LSB controller doesn't need to
+/// be the MSB controller + 32. Some devices use non-standard pairs, swap MSB and LSB or send the
+/// LSB first.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ControlChangePair {
+    pub msb_controller_number: ControllerNumber,
+    pub lsb_controller_number: ControllerNumber,
+    /// Order in which the device sends (and expects) the two messages.
+    pub byte_order: DataEntryByteOrder,
+}
+
+impl ControlChangePair {
+    pub fn new(
+        msb_controller_number: ControllerNumber,
+        lsb_controller_number: ControllerNumber,
+        byte_order: DataEntryByteOrder,
+    ) -> Self {
+        Self {
+            msb_controller_number,
+            lsb_controller_number,
+            byte_order,
+        }
+    }
+
+    /// Returns whether the given controller number is the MSB or LSB controller of this pair.
+    pub fn contains(&self, controller_number: ControllerNumber) -> bool {
+        controller_number == self.msb_controller_number
+            || controller_number == self.lsb_controller_number
+    }
+
+    /// Returns the controller number of the message which completes a value.
+    fn last_controller_number(&self) -> ControllerNumber {
+        match self.byte_order {
+            DataEntryByteOrder::MsbFirst => self.lsb_controller_number,
+            DataEntryByteOrder::LsbFirst => self.msb_controller_number,
+        }
+    }
+}
+
+/// A 14-bit value transmitted via a [`ControlChangePair`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ControlChangePairMessage {
+    channel: Channel,
+    pair: ControlChangePair,
+    value: U14,
+}
+
+impl ControlChangePairMessage {
+    pub fn new(channel: Channel, pair: ControlChangePair, value: U14) -> Self {
+        Self {
+            channel,
+            pair,
+            value,
+        }
+    }
+
+    pub fn channel(&self) -> Channel {
+        self.channel
+    }
+
+    pub fn pair(&self) -> ControlChangePair {
+        self.pair
+    }
+
+    pub fn value(&self) -> U14 {
+        self.value
+    }
+
+    /// Returns the MSB and LSB control change messages in the order defined by the pair.
+    pub fn to_short_messages<M: ShortMessageFactory>(&self) -> [M; 2] {
+        let value = self.value.get();
+        let msb = M::control_change(
+            self.channel,
+            self.pair.msb_controller_number,
+            U7::new((value >> 7) as u8),
+        );
+        let lsb = M::control_change(
+            self.channel,
+            self.pair.lsb_controller_number,
+            U7::new((value & 0x7f) as u8),
+        );
+        match self.pair.byte_order {
+            DataEntryByteOrder::MsbFirst => [msb, lsb],
+            DataEntryByteOrder::LsbFirst => [lsb, msb],
+        }
+    }
+}
+
+/// Observes a stream of short MIDI messages and combines the control change messages of the
+/// given pairs into [`MidiSourceValue::ControlChangePair`] values.
+///
+/// A value is emitted as soon as the second message of a pair arrives (according to the pair's
+/// byte order). If a device omits the first message because its value didn't change, the last
+/// received one is used. The first message itself is consumed. Everything else is passed
+/// through as it is.
+///
+/// Combined values carry the timestamp of the first message of the pair (or of the second one if
+/// the first one was omitted).
+#[derive(Clone, Debug)]
+pub struct ControlChangePairCombiner<S: AbstractTimestamp> {
+    pairs: Vec<ControlChangePair>,
+    /// Last received values, per channel and pair.
+    slots: Vec<Slot<S>>,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Slot<S> {
+    msb: u8,
+    lsb: u8,
+    /// If set, the first message of the pair has been received but the second one not yet.
+    first_timestamp: Option<S>,
+}
+
+impl<S> Default for Slot<S> {
+    fn default() -> Self {
+        Self {
+            msb: 0,
+            lsb: 0,
+            first_timestamp: None,
+        }
+    }
+}
+
+impl<S: AbstractTimestamp> ControlChangePairCombiner<S> {
+    /// Creates a combiner for the given pairs.
+    pub fn new(pairs: Vec<ControlChangePair>) -> Self {
+        Self {
+            slots: vec![Default::default(); CHANNEL_COUNT * pairs.len()],
+            pairs,
+        }
+    }
+
+    /// Feeds the given message into the combiner and returns the value which should be
+    /// processed, if any.
+    pub fn feed<M: ShortMessage + Copy>(
+        &mut self,
+        msg: M,
+        timestamp: S,
+    ) -> Option<ControlEvent<MidiSourceValue<'static, M>, S>> {
+        let plain = ControlEvent::new(MidiSourceValue::Plain(msg), timestamp);
+        let StructuredShortMessage::ControlChange {
+            channel,
+            controller_number,
+            control_value,
+        } = msg.to_structured()
+        else {
+            return Some(plain);
+        };
+        let Some(pair_index) = self
+            .pairs
+            .iter()
+            .position(|p| p.contains(controller_number))
+        else {
+            return Some(plain);
+        };
+        let pair = self.pairs[pair_index];
+        let slot = &mut self.slots[channel.get() as usize * self.pairs.len() + pair_index];
+        if controller_number == pair.msb_controller_number {
+            slot.msb = control_value.get();
+        } else {
+            slot.lsb = control_value.get();
+        }
+        if controller_number != pair.last_controller_number() {
+            slot.first_timestamp = Some(timestamp);
+            return None;
+        }
+        let value = U14::new(((slot.msb as u16) << 7) | slot.lsb as u16);
+        let msg = ControlChangePairMessage::new(channel, pair, value);
+        let timestamp = slot.first_timestamp.take().unwrap_or(timestamp);
+        Some(ControlEvent::new(
+            MidiSourceValue::ControlChangePair(msg),
+            timestamp,
+        ))
+    }
+
+    /// Forgets all received values.
+    pub fn reset(&mut self) {
+        self.slots.fill(Default::default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_midi::test_util::*;
+    use helgoboss_midi::RawShortMessage;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn combine() {
+        // Given
+        let msb_first = ControlChangePair::new(
+            ControllerNumber::new(40),
+            ControllerNumber::new(8),
+            DataEntryByteOrder::MsbFirst,
+        );
+        let lsb_first = ControlChangePair::new(
+            ControllerNumber::new(20),
+            ControllerNumber::new(21),
+            DataEntryByteOrder::LsbFirst,
+        );
+        let mut combiner = ControlChangePairCombiner::new(vec![msb_first, lsb_first]);
+        let mut feed =
+            |msg: RawShortMessage| combiner.feed(msg, Instant::now()).map(|e| e.into_payload());
+        let value = |pair, value| {
+            Some(MidiSourceValue::ControlChangePair(
+                ControlChangePairMessage::new(Channel::new(1), pair, U14::new(value)),
+            ))
+        };
+        // When
+        // Then
+        // Swapped controller numbers
+        assert_eq!(feed(control_change(1, 40, 93)), None);
+        assert_eq!(feed(control_change(1, 8, 96)), value(msb_first, 12000));
+        // MSB omitted
+        assert_eq!(feed(control_change(1, 8, 97)), value(msb_first, 12001));
+        // LSB first
+        assert_eq!(feed(control_change(1, 21, 1)), None);
+        assert_eq!(feed(control_change(1, 20, 2)), value(lsb_first, 257));
+        // Other messages
+        assert_eq!(
+            feed(control_change(1, 9, 5)),
+            Some(MidiSourceValue::Plain(control_change(1, 9, 5)))
+        );
+        assert_eq!(
+            ControlChangePairMessage::new(Channel::new(1), lsb_first, U14::new(257))
+                .to_short_messages::<RawShortMessage>(),
+            [control_change(1, 21, 1), control_change(1, 20, 2)]
+        );
+    }
+
+    #[test]
+    fn keep_timestamp_of_first_message() {
+        // Given
+        let pair = ControlChangePair::new(
+            ControllerNumber::new(40),
+            ControllerNumber::new(8),
+            DataEntryByteOrder::MsbFirst,
+        );
+        let mut combiner = ControlChangePairCombiner::new(vec![pair]);
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        // When
+        combiner.feed::<RawShortMessage>(control_change(1, 40, 93), at(0));
+        let complete = combiner.feed::<RawShortMessage>(control_change(1, 8, 96), at(2));
+        let msb_omitted = combiner.feed::<RawShortMessage>(control_change(1, 8, 97), at(20));
+        // Then
+        assert_eq!(complete.map(|e| e.timestamp()), Some(at(0)));
+        assert_eq!(msb_omitted.map(|e| e.timestamp()), Some(at(20)));
+    }
+}
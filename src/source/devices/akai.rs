@@ -0,0 +1,269 @@
+use crate::source::color_util::find_closest_color_in_palette;
+use crate::RgbColor;
+use derive_more::Display;
+use helgoboss_midi::{Channel, U7};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use strum::EnumIter;
+
+/// Akai controllers with RGB pads.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize_repr,
+    Deserialize_repr,
+)]
+#[repr(usize)]
+pub enum AkaiModel {
+    #[default]
+    #[display(fmt = "APC mini mk2")]
+    ApcMiniMk2 = 0,
+    #[display(fmt = "APC Key 25 mk2")]
+    ApcKey25Mk2 = 1,
+    #[display(fmt = "Fire")]
+    Fire = 2,
+}
+
+/// How an LED should light up.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize_repr,
+    Deserialize_repr,
+)]
+#[repr(usize)]
+pub enum AkaiLedAnimation {
+    #[default]
+    #[display(fmt = "Solid")]
+    Solid = 0,
+    #[display(fmt = "Pulse")]
+    Pulse = 1,
+    #[display(fmt = "Blink")]
+    Blink = 2,
+}
+
+impl AkaiModel {
+    /// Returns the palette which maps pad velocities to colors.
+    ///
+    /// Returns `None` for the Fire because its pads are colored via RGB sys-ex messages (see
+    /// [`fire_pad_rgb_sysex`]).
+    pub fn palette(self) -> Option<&'static [RgbColor]> {
+        use AkaiModel::*;
+        match self {
+            ApcMiniMk2 | ApcKey25Mk2 => Some(&APC_COLOR_PALETTE),
+            Fire => None,
+        }
+    }
+
+    /// Returns the velocity of the palette color which comes closest to the given color.
+    pub fn closest_palette_index(self, color: RgbColor) -> Option<u8> {
+        Some(find_closest_color_in_palette(color, self.palette()?))
+    }
+
+    /// Returns the channel on which pad note-ons must be sent in order to get the given
+    /// animation.
+    ///
+    /// The APC models encode brightness and animation in the channel. Solid means full
+    /// brightness, pulse and blink are synchronized with the host tempo at a rate of 1/4.
+    pub fn pad_channel(self, animation: AkaiLedAnimation) -> Channel {
+        use AkaiLedAnimation::*;
+        use AkaiModel::*;
+        let channel = match self {
+            ApcMiniMk2 | ApcKey25Mk2 => match animation {
+                Solid => 6,
+                Pulse => 9,
+                Blink => 14,
+            },
+            // Animations are not supported
+            Fire => 0,
+        };
+        Channel::new(channel)
+    }
+
+    /// Returns the velocity (or CC value) for single-color button LEDs.
+    ///
+    /// On the APC models, single-color buttons can blink (pulsing is treated as blinking). The
+    /// Fire can't animate LEDs, so "on" means high brightness.
+    pub fn single_color_button_value(self, on: bool, animation: AkaiLedAnimation) -> U7 {
+        use AkaiModel::*;
+        let value = match (self, on) {
+            (_, false) => 0,
+            (ApcMiniMk2 | ApcKey25Mk2, true) => match animation {
+                AkaiLedAnimation::Solid => 1,
+                AkaiLedAnimation::Pulse | AkaiLedAnimation::Blink => 2,
+            },
+            (Fire, true) => 2,
+        };
+        U7::new(value)
+    }
+}
+
+/// Returns the sys-ex bytes for coloring the given pad (0 - 63) of the Akai Fire.
+///
+/// The Fire takes 7-bit color components.
+pub fn fire_pad_rgb_sysex(pad_index: u8, color: RgbColor) -> impl Iterator<Item = u8> {
+    // Manufacturer, "all call" device ID, product ID, "write pad array" command, payload length
+    let start = [0xF0, 0x47, 0x7F, 0x43, 0x65, 0x00, 0x04];
+    let payload = [
+        pad_index & 0x3f,
+        color.r() >> 1,
+        color.g() >> 1,
+        color.b() >> 1,
+    ];
+    start
+        .into_iter()
+        .chain(payload)
+        .chain(std::iter::once(0xF7))
+}
+
+/// Velocity-to-color table of the APC mini mk2 and APC Key 25 mk2 pads.
+pub const APC_COLOR_PALETTE: [RgbColor; 128] = [
+    // 0..64
+    RgbColor::new(0x00, 0x00, 0x00),
+    RgbColor::new(0x1e, 0x1e, 0x1e),
+    RgbColor::new(0x7f, 0x7f, 0x7f),
+    RgbColor::new(0xff, 0xff, 0xff),
+    RgbColor::new(0xff, 0x4c, 0x4c),
+    RgbColor::new(0xff, 0x00, 0x00),
+    RgbColor::new(0x59, 0x00, 0x00),
+    RgbColor::new(0x19, 0x00, 0x00),
+    RgbColor::new(0xff, 0xbd, 0x6c),
+    RgbColor::new(0xff, 0x54, 0x00),
+    RgbColor::new(0x59, 0x1d, 0x00),
+    RgbColor::new(0x27, 0x1b, 0x00),
+    RgbColor::new(0xff, 0xff, 0x4c),
+    RgbColor::new(0xff, 0xff, 0x00),
+    RgbColor::new(0x59, 0x59, 0x00),
+    RgbColor::new(0x19, 0x19, 0x00),
+    RgbColor::new(0x88, 0xff, 0x4c),
+    RgbColor::new(0x54, 0xff, 0x00),
+    RgbColor::new(0x1d, 0x59, 0x00),
+    RgbColor::new(0x14, 0x2b, 0x00),
+    RgbColor::new(0x4c, 0xff, 0x4c),
+    RgbColor::new(0x00, 0xff, 0x00),
+    RgbColor::new(0x00, 0x59, 0x00),
+    RgbColor::new(0x00, 0x19, 0x00),
+    RgbColor::new(0x4c, 0xff, 0x5e),
+    RgbColor::new(0x00, 0xff, 0x19),
+    RgbColor::new(0x00, 0x59, 0x0d),
+    RgbColor::new(0x00, 0x19, 0x02),
+    RgbColor::new(0x4c, 0xff, 0x88),
+    RgbColor::new(0x00, 0xff, 0x55),
+    RgbColor::new(0x00, 0x59, 0x1d),
+    RgbColor::new(0x00, 0x1f, 0x12),
+    RgbColor::new(0x4c, 0xff, 0xb7),
+    RgbColor::new(0x00, 0xff, 0x99),
+    RgbColor::new(0x00, 0x59, 0x35),
+    RgbColor::new(0x00, 0x19, 0x12),
+    RgbColor::new(0x4c, 0xc3, 0xff),
+    RgbColor::new(0x00, 0xa9, 0xff),
+    RgbColor::new(0x00, 0x41, 0x52),
+    RgbColor::new(0x00, 0x10, 0x19),
+    RgbColor::new(0x4c, 0x88, 0xff),
+    RgbColor::new(0x00, 0x55, 0xff),
+    RgbColor::new(0x00, 0x1d, 0x59),
+    RgbColor::new(0x00, 0x08, 0x19),
+    RgbColor::new(0x4c, 0x4c, 0xff),
+    RgbColor::new(0x00, 0x00, 0xff),
+    RgbColor::new(0x00, 0x00, 0x59),
+    RgbColor::new(0x00, 0x00, 0x19),
+    RgbColor::new(0x87, 0x4c, 0xff),
+    RgbColor::new(0x54, 0x00, 0xff),
+    RgbColor::new(0x19, 0x00, 0x64),
+    RgbColor::new(0x0f, 0x00, 0x30),
+    RgbColor::new(0xff, 0x4c, 0xff),
+    RgbColor::new(0xff, 0x00, 0xff),
+    RgbColor::new(0x59, 0x00, 0x59),
+    RgbColor::new(0x19, 0x00, 0x19),
+    RgbColor::new(0xff, 0x4c, 0x87),
+    RgbColor::new(0xff, 0x00, 0x54),
+    RgbColor::new(0x59, 0x00, 0x1d),
+    RgbColor::new(0x22, 0x00, 0x13),
+    RgbColor::new(0xff, 0x15, 0x00),
+    RgbColor::new(0x99, 0x35, 0x00),
+    RgbColor::new(0x79, 0x51, 0x00),
+    RgbColor::new(0x43, 0x64, 0x00),
+    // 64..128
+    RgbColor::new(0x03, 0x39, 0x00),
+    RgbColor::new(0x00, 0x57, 0x35),
+    RgbColor::new(0x00, 0x54, 0x7f),
+    RgbColor::new(0x00, 0x00, 0xff),
+    RgbColor::new(0x00, 0x45, 0x4f),
+    RgbColor::new(0x25, 0x00, 0xcc),
+    RgbColor::new(0x7f, 0x7f, 0x7f),
+    RgbColor::new(0x20, 0x20, 0x20),
+    RgbColor::new(0xff, 0x00, 0x00),
+    RgbColor::new(0xbd, 0xff, 0x2d),
+    RgbColor::new(0xaf, 0xed, 0x06),
+    RgbColor::new(0x64, 0xff, 0x09),
+    RgbColor::new(0x10, 0x8b, 0x00),
+    RgbColor::new(0x00, 0xff, 0x87),
+    RgbColor::new(0x00, 0xa9, 0xff),
+    RgbColor::new(0x00, 0x2a, 0xff),
+    RgbColor::new(0x3f, 0x00, 0xff),
+    RgbColor::new(0x7a, 0x00, 0xff),
+    RgbColor::new(0xb2, 0x1a, 0x7d),
+    RgbColor::new(0x40, 0x21, 0x00),
+    RgbColor::new(0xff, 0x4a, 0x00),
+    RgbColor::new(0x88, 0xe1, 0x06),
+    RgbColor::new(0x72, 0xff, 0x15),
+    RgbColor::new(0x00, 0xff, 0x00),
+    RgbColor::new(0x3b, 0xff, 0x26),
+    RgbColor::new(0x59, 0xff, 0x71),
+    RgbColor::new(0x38, 0xff, 0xcc),
+    RgbColor::new(0x5b, 0x8a, 0xff),
+    RgbColor::new(0x31, 0x51, 0xc6),
+    RgbColor::new(0x87, 0x7f, 0xe9),
+    RgbColor::new(0xd3, 0x1d, 0xff),
+    RgbColor::new(0xff, 0x00, 0x5d),
+    RgbColor::new(0xff, 0x7f, 0x00),
+    RgbColor::new(0xb9, 0xb0, 0x00),
+    RgbColor::new(0x90, 0xff, 0x00),
+    RgbColor::new(0x83, 0x5d, 0x07),
+    RgbColor::new(0x39, 0x2b, 0x00),
+    RgbColor::new(0x14, 0x4c, 0x10),
+    RgbColor::new(0x0d, 0x50, 0x38),
+    RgbColor::new(0x15, 0x15, 0x2a),
+    RgbColor::new(0x16, 0x20, 0x5a),
+    RgbColor::new(0x69, 0x3c, 0x1c),
+    RgbColor::new(0xa8, 0x00, 0x0a),
+    RgbColor::new(0xde, 0x51, 0x3d),
+    RgbColor::new(0xd8, 0x6a, 0x1c),
+    RgbColor::new(0xff, 0xe1, 0x26),
+    RgbColor::new(0x9e, 0xe1, 0x2f),
+    RgbColor::new(0x67, 0xb5, 0x0f),
+    RgbColor::new(0x1e, 0x1e, 0x30),
+    RgbColor::new(0xdc, 0xff, 0x6b),
+    RgbColor::new(0x80, 0xff, 0xbd),
+    RgbColor::new(0x9a, 0x99, 0xff),
+    RgbColor::new(0x8e, 0x66, 0xff),
+    RgbColor::new(0x40, 0x40, 0x40),
+    RgbColor::new(0x75, 0x75, 0x75),
+    RgbColor::new(0xe0, 0xff, 0xff),
+    RgbColor::new(0xa0, 0x00, 0x00),
+    RgbColor::new(0x35, 0x00, 0x00),
+    RgbColor::new(0x1a, 0xd0, 0x00),
+    RgbColor::new(0x07, 0x42, 0x00),
+    RgbColor::new(0xb9, 0xb0, 0x00),
+    RgbColor::new(0x3f, 0x31, 0x00),
+    RgbColor::new(0xb3, 0x5f, 0x00),
+    RgbColor::new(0x4b, 0x15, 0x02),
+];
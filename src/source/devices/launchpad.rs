@@ -1,5 +1,88 @@
 //! Initially taken from https://github.com/jamesmunns/launch-rs/blob/master/lib/src/color.rs
+use crate::source::color_util::find_closest_color_in_palette;
 use crate::RgbColor;
+use derive_more::Display;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use strum::EnumIter;
+
+/// Novation Launchpad models with RGB pads.
+///
+/// All of them use the same color palette ([`COLOR_PALETTE`]) for velocity-based colors.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize_repr,
+    Deserialize_repr,
+)]
+#[repr(usize)]
+pub enum LaunchpadModel {
+    #[display(fmt = "Launchpad MK2")]
+    Mk2 = 0,
+    #[display(fmt = "Launchpad Pro")]
+    Pro = 1,
+    #[default]
+    #[display(fmt = "Launchpad X")]
+    X = 2,
+    #[display(fmt = "Launchpad Mini MK3")]
+    MiniMk3 = 3,
+    #[display(fmt = "Launchpad Pro MK3")]
+    ProMk3 = 4,
+}
+
+impl LaunchpadModel {
+    pub fn palette(self) -> &'static [RgbColor] {
+        &COLOR_PALETTE
+    }
+
+    /// Returns the index of the palette color which comes closest to the given color.
+    ///
+    /// Sending it as velocity of a note-on or as value of a CC message lights the pad.
+    pub fn closest_palette_index(self, color: RgbColor) -> u8 {
+        find_closest_color_in_palette(color, self.palette())
+    }
+
+    /// Returns the sys-ex bytes for lighting the given LED with the exact RGB color.
+    ///
+    /// The LED index is the note or controller number of the pad in programmer mode. The
+    /// resolution of the color components depends on the model (6 or 7 bits).
+    pub fn rgb_sysex(self, led_index: u8, color: RgbColor) -> impl Iterator<Item = u8> {
+        use LaunchpadModel::*;
+        let (r, g, b) = (color.r(), color.g(), color.b());
+        let (body, body_len) = match self {
+            // Old models: 6-bit components
+            Mk2 | Pro => ([0x0B, led_index, r >> 2, g >> 2, b >> 2, 0], 5),
+            // MK3 models: LED lighting message with one RGB color spec
+            X | MiniMk3 | ProMk3 => ([0x03, 0x03, led_index, r >> 1, g >> 1, b >> 1], 6),
+        };
+        self.sysex_header()
+            .into_iter()
+            .chain(body.into_iter().take(body_len))
+            .chain(std::iter::once(0xF7))
+    }
+
+    /// Returns the bytes which introduce a sys-ex message for this model.
+    pub fn sysex_header(self) -> [u8; 6] {
+        use LaunchpadModel::*;
+        let device_id = match self {
+            Mk2 => 0x18,
+            Pro => 0x10,
+            X => 0x0C,
+            MiniMk3 => 0x0D,
+            ProMk3 => 0x0E,
+        };
+        [0xF0, 0x00, 0x20, 0x29, 0x02, device_id]
+    }
+}
 
 /// http://launchpaddr.com/mk2palette/
 pub const COLOR_PALETTE: [RgbColor; 128] = [
@@ -134,3 +217,30 @@ pub const COLOR_PALETTE: [RgbColor; 128] = [
     RgbColor::new(0xb4, 0x5d, 0x00),
     RgbColor::new(0x4c, 0x13, 0x00),
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rgb_sysex() {
+        // Given
+        let color = RgbColor::new(255, 128, 0);
+        // When
+        let mk2_sysex: Vec<_> = LaunchpadModel::Mk2.rgb_sysex(11, color).collect();
+        let x_sysex: Vec<_> = LaunchpadModel::X.rgb_sysex(11, color).collect();
+        // Then
+        assert_eq!(
+            LaunchpadModel::ProMk3.sysex_header(),
+            [0xF0, 0x00, 0x20, 0x29, 0x02, 0x0E]
+        );
+        assert_eq!(
+            mk2_sysex,
+            [0xF0, 0x00, 0x20, 0x29, 0x02, 0x18, 0x0B, 11, 0x3F, 0x20, 0x00, 0xF7]
+        );
+        assert_eq!(
+            x_sysex,
+            [0xF0, 0x00, 0x20, 0x29, 0x02, 0x0C, 0x03, 0x03, 11, 0x7F, 0x40, 0x00, 0xF7]
+        );
+    }
+}
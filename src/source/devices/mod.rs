@@ -1,2 +1,84 @@
+pub mod akai;
 pub mod launchpad;
 pub mod x_touch;
+
+mod palettes;
+pub use palettes::*;
+
+mod quirks;
+pub use quirks::*;
+
+use crate::RgbColor;
+use akai::{AkaiLedAnimation, AkaiModel};
+use helgoboss_midi::{Channel, U7};
+use launchpad::LaunchpadModel;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// A device whose LEDs take palette indexes as velocities (see [`crate::SourceContext`]).
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum DevicePalette {
+    Launchpad(LaunchpadModel),
+    Akai(AkaiModel),
+    /// Registered at runtime (see [`DevicePaletteRegistry`]).
+    Custom(Arc<CustomDevicePalette>),
+}
+
+impl DevicePalette {
+    /// Returns the name under which this palette can be looked up in a
+    /// [`DevicePaletteRegistry`].
+    pub fn name(&self) -> Cow<'static, str> {
+        match self {
+            DevicePalette::Launchpad(model) => model.to_string().into(),
+            DevicePalette::Akai(model) => model.to_string().into(),
+            DevicePalette::Custom(palette) => palette.name().to_string().into(),
+        }
+    }
+
+    /// Returns the velocity which makes the device show the closest color to the given one.
+    ///
+    /// Returns `None` if the device doesn't color LEDs via velocity.
+    pub fn velocity_for_color(&self, color: RgbColor) -> Option<U7> {
+        let index = match self {
+            DevicePalette::Launchpad(model) => model.closest_palette_index(color),
+            DevicePalette::Akai(model) => model.closest_palette_index(color)?,
+            DevicePalette::Custom(palette) => palette.closest_palette_index(color)?,
+        };
+        Some(U7::new(index))
+    }
+
+    /// Returns the channel on which note-ons with palette colors must be sent in order to get the
+    /// given animation.
+    ///
+    /// Returns `None` if the device doesn't encode animations in the channel.
+    pub fn pad_channel(&self, animation: AkaiLedAnimation) -> Option<Channel> {
+        match self {
+            DevicePalette::Akai(model) if model.palette().is_some() => {
+                Some(model.pad_channel(animation))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the velocity (or CC value) which makes LEDs without color light up with the given
+    /// animation.
+    ///
+    /// Returns `None` if the device doesn't have special conventions for such LEDs.
+    pub fn single_color_value(&self, on: bool, animation: AkaiLedAnimation) -> Option<U7> {
+        match self {
+            DevicePalette::Akai(model) => Some(model.single_color_button_value(on, animation)),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes for lighting the LED with the given index with the exact RGB color.
+    ///
+    /// Returns `None` if the device doesn't support that.
+    pub fn rgb_message(&self, led_index: u8, color: RgbColor) -> Option<Vec<u8>> {
+        match self {
+            DevicePalette::Launchpad(model) => Some(model.rgb_sysex(led_index, color).collect()),
+            DevicePalette::Akai(_) => None,
+            DevicePalette::Custom(palette) => palette.rgb_message(led_index, color),
+        }
+    }
+}
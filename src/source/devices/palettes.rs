@@ -0,0 +1,178 @@
+use crate::devices::akai::AkaiModel;
+use crate::devices::launchpad::LaunchpadModel;
+use crate::devices::DevicePalette;
+use crate::source::color_util::find_closest_color_in_palette;
+use crate::{
+    AbsoluteValue, Fraction, RawMidiPattern, RawMidiPatternColor, RawMidiPatternPlaceholderValues,
+    RgbColor,
+};
+use std::sync::Arc;
+use strum::IntoEnumIterator;
+
+/// Maximum number of colors of a velocity-based palette (one per 7-bit value).
+const MAX_PALETTE_COLOR_COUNT: usize = 128;
+
+/// Color palette of a device which is not built into this crate, e.g. loaded from a data file.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct CustomDevicePalette {
+    name: String,
+    colors: Vec<RgbColor>,
+    rgb_message_pattern: Option<RawMidiPattern>,
+}
+
+impl CustomDevicePalette {
+    /// Creates a palette.
+    ///
+    /// The color at index i is shown when sending i as velocity (or CC value). The optional
+    /// pattern builds messages for lighting a LED with an exact RGB color: Its variable bits
+    /// take the LED index, its color placeholders the color (e.g.
+    /// `F0 00 20 29 02 0C 03 03 [0gfe dcba] {color:r} {color:g} {color:b} F7`).
+    pub fn new(
+        name: impl Into<String>,
+        colors: Vec<RgbColor>,
+        rgb_message_pattern: Option<RawMidiPattern>,
+    ) -> Result<Self, &'static str> {
+        if colors.is_empty() && rgb_message_pattern.is_none() {
+            return Err("palette must have colors or an RGB message pattern");
+        }
+        if colors.len() > MAX_PALETTE_COLOR_COUNT {
+            return Err("palette must not have more than 128 colors");
+        }
+        let palette = Self {
+            name: name.into(),
+            colors,
+            rgb_message_pattern,
+        };
+        Ok(palette)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn colors(&self) -> &[RgbColor] {
+        &self.colors
+    }
+
+    pub fn rgb_message_pattern(&self) -> Option<&RawMidiPattern> {
+        self.rgb_message_pattern.as_ref()
+    }
+
+    /// Returns the index of the palette color which comes closest to the given color.
+    ///
+    /// Returns `None` if the palette doesn't have colors.
+    pub fn closest_palette_index(&self, color: RgbColor) -> Option<u8> {
+        if self.colors.is_empty() {
+            return None;
+        }
+        Some(find_closest_color_in_palette(color, &self.colors))
+    }
+
+    /// Returns the bytes for lighting the given LED with the exact RGB color.
+    ///
+    /// Returns `None` if the palette doesn't have an RGB message pattern.
+    pub fn rgb_message(&self, led_index: u8, color: RgbColor) -> Option<Vec<u8>> {
+        let pattern = self.rgb_message_pattern.as_ref()?;
+        let led_index = Fraction::new(led_index as u32, pattern.max_discrete_value());
+        let values = RawMidiPatternPlaceholderValues {
+            color: RawMidiPatternColor {
+                rgb: Some(color),
+                palette_index: self.closest_palette_index(color),
+            },
+            ..Default::default()
+        };
+        let bytes = pattern
+            .byte_iter_with_placeholder_values(AbsoluteValue::Discrete(led_index), values)
+            .collect();
+        Some(bytes)
+    }
+}
+
+/// Looks up device palettes by name, both the ones built into this crate and the ones registered
+/// at runtime.
+#[derive(Clone, Debug, Default)]
+pub struct DevicePaletteRegistry {
+    custom_palettes: Vec<Arc<CustomDevicePalette>>,
+}
+
+impl DevicePaletteRegistry {
+    /// Registers the given palette, replacing any custom palette with the same name (ignoring
+    /// case).
+    ///
+    /// A replaced palette stays alive as long as source contexts still refer to it.
+    pub fn register(&mut self, palette: CustomDevicePalette) -> DevicePalette {
+        self.custom_palettes
+            .retain(|p| !p.name.eq_ignore_ascii_case(&palette.name));
+        let palette = Arc::new(palette);
+        self.custom_palettes.push(palette.clone());
+        DevicePalette::Custom(palette)
+    }
+
+    /// Returns the palette with the given name (ignoring case). Custom palettes take precedence
+    /// over built-in ones.
+    pub fn lookup(&self, name: &str) -> Option<DevicePalette> {
+        self.custom_palettes
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+            .map(|p| DevicePalette::Custom(p.clone()))
+            .or_else(|| builtin_palettes().find(|p| p.name().eq_ignore_ascii_case(name)))
+    }
+
+    /// Returns all palettes, built-in ones first.
+    pub fn palettes(&self) -> impl Iterator<Item = DevicePalette> + '_ {
+        let custom_palettes = self.custom_palettes.iter().cloned();
+        builtin_palettes().chain(custom_palettes.map(DevicePalette::Custom))
+    }
+}
+
+fn builtin_palettes() -> impl Iterator<Item = DevicePalette> {
+    let launchpad_palettes = LaunchpadModel::iter().map(DevicePalette::Launchpad);
+    let akai_palettes = AkaiModel::iter()
+        .filter(|m| m.palette().is_some())
+        .map(DevicePalette::Akai);
+    launchpad_palettes.chain(akai_palettes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_and_lookup() {
+        // Given
+        let mut registry = DevicePaletteRegistry::default();
+        let colors = vec![
+            RgbColor::new(0, 0, 0),
+            RgbColor::new(255, 0, 0),
+            RgbColor::new(0, 255, 0),
+        ];
+        let pattern = "F0 7D [0gfe dcba] {color:r} {color:g} {color:b} F7"
+            .parse()
+            .unwrap();
+        let palette = CustomDevicePalette::new("Acme Pads", colors, Some(pattern)).unwrap();
+        // When
+        registry.register(palette);
+        // Then
+        let palette = registry.lookup("acme pads").unwrap();
+        assert_eq!(palette.name(), "Acme Pads");
+        assert_eq!(
+            palette
+                .velocity_for_color(RgbColor::new(0, 200, 0))
+                .map(|v| v.get()),
+            Some(2)
+        );
+        let DevicePalette::Custom(custom) = palette else {
+            panic!("expected custom palette");
+        };
+        assert_eq!(
+            custom.rgb_message(5, RgbColor::new(255, 0, 128)),
+            Some(vec![0xF0, 0x7D, 0x05, 0x7F, 0x00, 0x40, 0xF7])
+        );
+        assert_eq!(
+            registry.lookup("Launchpad X"),
+            Some(DevicePalette::Launchpad(LaunchpadModel::X))
+        );
+        assert_eq!(registry.lookup("Fire"), None);
+        assert!(CustomDevicePalette::new("Empty", vec![], None).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+/// Known deviations of a device from how MIDI sources expect devices to behave.
+///
+/// Applied by [`crate::MidiSource::control_with_context`] and
+/// [`crate::MidiSource::emulated_release`] when the source context carries them.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct DeviceQuirks {
+    /// Relative encoders send 0 for decrementing by one instead of the value prescribed by the
+    /// configured encoder type.
+    pub encoder_sends_zero_for_decrement: bool,
+    /// Relative encoders decrement when turned clockwise and increment when turned
+    /// counter-clockwise.
+    pub inverted_encoder_direction: bool,
+    /// Momentary buttons send a message when pressed but none when released.
+    pub button_never_sends_release: bool,
+}
+
+impl DeviceQuirks {
+    /// Returns whether there's nothing to adapt.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Looks up the quirks of a device by its name (e.g. the name of its MIDI port).
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct DeviceQuirkRegistry {
+    entries: Vec<(String, DeviceQuirks)>,
+}
+
+impl DeviceQuirkRegistry {
+    /// Registers quirks for all devices whose name starts with the given prefix (ignoring case).
+    ///
+    /// If multiple prefixes match a device, the longest one wins.
+    pub fn register(&mut self, device_name_prefix: impl Into<String>, quirks: DeviceQuirks) {
+        let prefix = device_name_prefix.into().to_lowercase();
+        self.entries.retain(|(p, _)| *p != prefix);
+        self.entries.push((prefix, quirks));
+    }
+
+    /// Returns the quirks of the device with the given name. If the device is unknown, there are
+    /// no quirks.
+    pub fn lookup(&self, device_name: &str) -> DeviceQuirks {
+        let device_name = device_name.to_lowercase();
+        self.entries
+            .iter()
+            .filter(|(prefix, _)| device_name.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, quirks)| *quirks)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup() {
+        // Given
+        let mut registry = DeviceQuirkRegistry::default();
+        let zero_decrement = DeviceQuirks {
+            encoder_sends_zero_for_decrement: true,
+            ..Default::default()
+        };
+        let inverted = DeviceQuirks {
+            inverted_encoder_direction: true,
+            ..Default::default()
+        };
+        registry.register("Acme Controller", zero_decrement);
+        registry.register("Acme Controller XL", inverted);
+        // When
+        // Then
+        assert_eq!(registry.lookup("acme controller mini"), zero_decrement);
+        assert_eq!(registry.lookup("Acme Controller XL Port 2"), inverted);
+        assert!(registry.lookup("Other Device").is_empty());
+    }
+}
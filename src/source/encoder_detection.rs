@@ -0,0 +1,109 @@
+use crate::{DiscreteIncrement, SourceCharacter};
+use helgoboss_midi::U7;
+
+/// Increments with a higher absolute value are considered implausible for a relative encoder,
+/// even with acceleration.
+const MAX_PLAUSIBLE_INCREMENT: u32 = 16;
+/// Consecutive values of an absolute control which differ by more than this are considered
+/// implausible (a knob or fader doesn't jump).
+const MAX_PLAUSIBLE_ABSOLUTE_JUMP: u8 = 16;
+const MIN_VALUE_COUNT: usize = 4;
+
+/// Guesses the source character of a control-change control element from a series of captured
+/// 7-bit values, e.g. recorded during a few seconds of learning.
+///
+/// Returns one of the encoder characters if the values look like relative increments,
+/// [`SourceCharacter::RangeElement`] if they look like absolute values and `None` if the values
+/// are not conclusive. For a conclusive result, the control element should be turned in both
+/// directions, because values representing only increments (e.g. 1, 2, 1) could belong to more
+/// than one relative encoding.
+pub fn guess_source_character(values: &[U7]) -> Option<SourceCharacter> {
+    if values.len() < MIN_VALUE_COUNT {
+        return None;
+    }
+    let candidates: Vec<_> = [
+        SourceCharacter::Encoder1,
+        SourceCharacter::Encoder2,
+        SourceCharacter::Encoder3,
+    ]
+    .into_iter()
+    .filter(|character| is_plausible_relative_encoding(*character, values))
+    .collect();
+    // Absolute control elements usually only send values when they change. Relative encoders
+    // send the same value again and again when turned in the same direction.
+    let looks_absolute = values.windows(2).all(|w| {
+        let (prev, next) = (w[0].get(), w[1].get());
+        prev != next && prev.abs_diff(next) <= MAX_PLAUSIBLE_ABSOLUTE_JUMP
+    });
+    match (candidates.as_slice(), looks_absolute) {
+        ([character], false) => Some(*character),
+        ([], true) => Some(SourceCharacter::RangeElement),
+        _ => None,
+    }
+}
+
+/// Returns whether all values can be interpreted as small increments in the given encoding and
+/// both directions occur.
+fn is_plausible_relative_encoding(character: SourceCharacter, values: &[U7]) -> bool {
+    let mut has_increments = false;
+    let mut has_decrements = false;
+    for value in values {
+        let increment = match character {
+            SourceCharacter::Encoder1 => DiscreteIncrement::from_encoder_1_value(*value),
+            SourceCharacter::Encoder2 => DiscreteIncrement::from_encoder_2_value(*value),
+            SourceCharacter::Encoder3 => DiscreteIncrement::from_encoder_3_value(*value),
+            _ => return false,
+        };
+        let Ok(increment) = increment else {
+            return false;
+        };
+        if increment.get().unsigned_abs() > MAX_PLAUSIBLE_INCREMENT {
+            return false;
+        }
+        if increment.is_positive() {
+            has_increments = true;
+        } else {
+            has_decrements = true;
+        }
+    }
+    has_increments && has_decrements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guess(values: &[u8]) -> Option<SourceCharacter> {
+        let values: Vec<_> = values.iter().map(|v| U7::new(*v)).collect();
+        guess_source_character(&values)
+    }
+
+    #[test]
+    fn guess_character() {
+        // Given
+        // When
+        // Then
+        assert_eq!(
+            guess(&[1, 1, 2, 1, 127, 127, 126]),
+            Some(SourceCharacter::Encoder1)
+        );
+        assert_eq!(
+            guess(&[65, 65, 66, 65, 63, 63, 62]),
+            Some(SourceCharacter::Encoder2)
+        );
+        assert_eq!(
+            guess(&[1, 1, 2, 1, 65, 65, 66]),
+            Some(SourceCharacter::Encoder3)
+        );
+        assert_eq!(
+            guess(&[10, 11, 13, 14, 12, 9, 8]),
+            Some(SourceCharacter::RangeElement)
+        );
+        // Only turned in one direction
+        assert_eq!(guess(&[1, 1, 2, 1, 1]), None);
+        // Not enough values
+        assert_eq!(guess(&[1, 127]), None);
+        // Neither relative nor absolute
+        assert_eq!(guess(&[0, 127, 0, 127]), None);
+    }
+}
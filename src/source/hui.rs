@@ -0,0 +1,387 @@
+use crate::{
+    ControlValue, DiscreteIncrement, FeedbackValue, Fraction, MackieVPotRingMode, UnitValue,
+};
+use helgoboss_midi::{
+    Channel, ControllerNumber, KeyNumber, ShortMessage, ShortMessageFactory,
+    StructuredShortMessage, U14, U7,
+};
+
+/// Number of channel strips of a HUI surface.
+pub const HUI_CHANNEL_COUNT: u8 = 8;
+/// Index of the jog wheel among the V-Pots.
+pub const HUI_JOG_WHEEL_INDEX: u8 = 0x0D;
+/// Maximum level of a VU meter.
+const HUI_VU_METER_MAX_LEVEL: u32 = 0x0C;
+/// Port of the fader touch switch within the zone of a channel strip.
+const HUI_FADER_TOUCH_PORT: u8 = 0;
+/// Highest port within a zone.
+const HUI_MAX_SWITCH_PORT: u8 = 0x07;
+
+// Controller numbers
+const SWITCH_ZONE_IN: u8 = 0x0F;
+const SWITCH_PORT_IN: u8 = 0x2F;
+const SWITCH_ZONE_OUT: u8 = 0x0C;
+const SWITCH_PORT_OUT: u8 = 0x2C;
+const FADER_MSB: u8 = 0x00;
+const FADER_LSB: u8 = 0x20;
+const V_POT_IN: u8 = 0x40;
+const V_POT_RING_OUT: u8 = 0x10;
+
+/// Bit which marks a switch as pressed (or a LED as lit).
+const SWITCH_ON_BIT: u8 = 0x40;
+
+/// A HUI button, addressed by zone and port.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct HuiSwitch {
+    pub zone: u8,
+    pub port: u8,
+}
+
+impl HuiSwitch {
+    /// The touch sensor of the fader of the given channel strip.
+    pub fn fader_touch(channel_index: u8) -> Self {
+        Self {
+            zone: channel_index,
+            port: HUI_FADER_TOUCH_PORT,
+        }
+    }
+}
+
+/// Something which happened on a HUI surface, decoded by [`HuiDecoder`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum HuiEvent {
+    Switch {
+        switch: HuiSwitch,
+        is_pressed: bool,
+    },
+    Fader {
+        index: u8,
+        value: U14,
+    },
+    VPot {
+        index: u8,
+        increment: DiscreteIncrement,
+    },
+    /// Answer of the surface to [`hui_ping`].
+    PingReply,
+}
+
+/// Decodes HUI events from the short messages sent by a HUI surface.
+///
+/// Switch and fader messages consist of two consecutive control change messages, so the
+/// decoder needs to remember the first one.
+#[derive(Clone, Debug, Default)]
+pub struct HuiDecoder {
+    zone: Option<u8>,
+    fader_msbs: [Option<u8>; HUI_CHANNEL_COUNT as usize],
+}
+
+impl HuiDecoder {
+    /// Feeds the given message into the decoder.
+    ///
+    /// Returns an event as soon as it's complete. Messages which are not part of the HUI
+    /// protocol are ignored.
+    pub fn feed(&mut self, msg: impl ShortMessage) -> Option<HuiEvent> {
+        match msg.to_structured() {
+            StructuredShortMessage::NoteOn {
+                key_number,
+                velocity,
+                ..
+            } if key_number == KeyNumber::MIN && velocity == U7::MAX => Some(HuiEvent::PingReply),
+            StructuredShortMessage::ControlChange {
+                controller_number,
+                control_value,
+                ..
+            } => self.feed_control_change(controller_number.get(), control_value.get()),
+            _ => None,
+        }
+    }
+
+    fn feed_control_change(&mut self, number: u8, value: u8) -> Option<HuiEvent> {
+        match number {
+            SWITCH_ZONE_IN => {
+                self.zone = Some(value);
+                None
+            }
+            SWITCH_PORT_IN => {
+                let port = value & 0x0F;
+                if port > HUI_MAX_SWITCH_PORT {
+                    return None;
+                }
+                let switch = HuiSwitch {
+                    zone: self.zone?,
+                    port,
+                };
+                let is_pressed = value & SWITCH_ON_BIT != 0;
+                Some(HuiEvent::Switch { switch, is_pressed })
+            }
+            n if (FADER_MSB..FADER_MSB + HUI_CHANNEL_COUNT).contains(&n) => {
+                self.fader_msbs[(n - FADER_MSB) as usize] = Some(value);
+                None
+            }
+            n if (FADER_LSB..FADER_LSB + HUI_CHANNEL_COUNT).contains(&n) => {
+                let index = n - FADER_LSB;
+                let msb = self.fader_msbs[index as usize]?;
+                let value = U14::new(((msb as u16) << 7) | value as u16);
+                Some(HuiEvent::Fader { index, value })
+            }
+            n if (V_POT_IN..V_POT_IN + HUI_CHANNEL_COUNT).contains(&n)
+                || n == V_POT_IN + HUI_JOG_WHEEL_INDEX =>
+            {
+                // Bit 6 set means clockwise, the lower bits are the number of steps.
+                let steps = (value & 0x3F) as i32;
+                let increment = if value & 0x40 != 0 { steps } else { -steps };
+                let increment = DiscreteIncrement::new_checked(increment)?;
+                Some(HuiEvent::VPot {
+                    index: n - V_POT_IN,
+                    increment,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Source which reacts to one element of a HUI surface.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum HuiSource {
+    /// Button. Feedback lights its LED.
+    Switch(HuiSwitch),
+    /// Motorized fader of a channel strip.
+    Fader { index: u8 },
+    /// V-Pot of a channel strip (or the jog wheel, see [`HUI_JOG_WHEEL_INDEX`]). Feedback sets
+    /// the position of its LED ring.
+    VPot { index: u8 },
+}
+
+impl HuiSource {
+    pub fn control(&self, event: &HuiEvent) -> Option<ControlValue> {
+        let value = match (self, event) {
+            (HuiSource::Switch(s), HuiEvent::Switch { switch, is_pressed }) if s == switch => {
+                let value = if *is_pressed {
+                    UnitValue::MAX
+                } else {
+                    UnitValue::MIN
+                };
+                ControlValue::AbsoluteContinuous(value)
+            }
+            (HuiSource::Fader { index: i }, HuiEvent::Fader { index, value }) if i == index => {
+                ControlValue::AbsoluteDiscrete(Fraction::new(
+                    value.get() as u32,
+                    U14::MAX.get() as u32,
+                ))
+            }
+            (HuiSource::VPot { index: i }, HuiEvent::VPot { index, increment }) if i == index => {
+                ControlValue::RelativeDiscrete(*increment)
+            }
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Returns the messages to be sent to the surface. Sends nothing for the jog wheel and for
+    /// elements which don't exist on a HUI surface (e.g. a fader index out of range).
+    pub fn feedback<M: ShortMessageFactory>(&self, value: FeedbackValue) -> Option<[Option<M>; 2]> {
+        let value = value.to_numeric()?.value;
+        let channel = Channel::new(0);
+        let cc = |number: u8, value: u8| {
+            M::control_change(channel, ControllerNumber::new(number), U7::new(value))
+        };
+        let messages = match self {
+            HuiSource::Switch(switch)
+                if switch.zone <= U7::MAX.get() && switch.port <= HUI_MAX_SWITCH_PORT =>
+            {
+                let on_bit = if value.is_on() { SWITCH_ON_BIT } else { 0 };
+                [
+                    Some(cc(SWITCH_ZONE_OUT, switch.zone)),
+                    Some(cc(SWITCH_PORT_OUT, on_bit | switch.port)),
+                ]
+            }
+            HuiSource::Fader { index } if *index < HUI_CHANNEL_COUNT => {
+                let v = (value.to_unit_value().get() * U14::MAX.get() as f64).round() as u16;
+                [
+                    Some(cc(FADER_MSB + index, (v >> 7) as u8)),
+                    Some(cc(FADER_LSB + index, (v & 0x7F) as u8)),
+                ]
+            }
+            HuiSource::VPot { index } if *index < HUI_CHANNEL_COUNT => {
+                let position = if value.is_on() {
+                    // HUI rings work like Mackie rings in single-dot mode.
+                    MackieVPotRingMode::SingleDot.position(value.to_unit_value().get())
+                } else {
+                    0
+                };
+                [Some(cc(V_POT_RING_OUT + index, position)), None]
+            }
+            _ => return None,
+        };
+        Some(messages)
+    }
+}
+
+/// Message which the host must send regularly (about once per second). Otherwise the surface
+/// considers itself as disconnected. The surface answers with [`HuiEvent::PingReply`].
+pub fn hui_ping<M: ShortMessageFactory>() -> M {
+    M::note_on(Channel::new(0), KeyNumber::MIN, U7::MIN)
+}
+
+/// Sets the level of one side (0 = left, 1 = right) of the VU meter of the given channel strip.
+///
+/// Returns `None` if the channel strip doesn't exist.
+pub fn hui_vu_meter<M: ShortMessageFactory>(
+    channel_index: u8,
+    side: u8,
+    level: UnitValue,
+) -> Option<M> {
+    if channel_index >= HUI_CHANNEL_COUNT {
+        return None;
+    }
+    let level = (level.get() * HUI_VU_METER_MAX_LEVEL as f64).round() as u8;
+    let msg = M::polyphonic_key_pressure(
+        Channel::new(0),
+        KeyNumber::new(channel_index),
+        U7::new(((side & 0x01) << 4) | level),
+    );
+    Some(msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AbsoluteValue, NumericFeedbackValue};
+    use helgoboss_midi::test_util::*;
+    use helgoboss_midi::RawShortMessage;
+
+    #[test]
+    fn decode() {
+        // Given
+        let mut decoder = HuiDecoder::default();
+        // When
+        let events: Vec<_> = [
+            control_change(0, 0x0F, 0x02),
+            control_change(0, 0x2F, 0x43),
+            control_change(0, 0x2F, 0x03),
+            control_change(0, 0x01, 0x40),
+            control_change(0, 0x21, 0x00),
+            control_change(0, 0x42, 0x41),
+            control_change(0, 0x4D, 0x03),
+            note_on(0, 0, 127),
+        ]
+        .into_iter()
+        .filter_map(|msg: RawShortMessage| decoder.feed(msg))
+        .collect();
+        // Then
+        let switch = HuiSwitch { zone: 2, port: 3 };
+        assert_eq!(
+            events,
+            vec![
+                HuiEvent::Switch {
+                    switch,
+                    is_pressed: true
+                },
+                HuiEvent::Switch {
+                    switch,
+                    is_pressed: false
+                },
+                HuiEvent::Fader {
+                    index: 1,
+                    value: U14::new(8192)
+                },
+                HuiEvent::VPot {
+                    index: 2,
+                    increment: DiscreteIncrement::new(1)
+                },
+                HuiEvent::VPot {
+                    index: HUI_JOG_WHEEL_INDEX,
+                    increment: DiscreteIncrement::new(-3)
+                },
+                HuiEvent::PingReply,
+            ]
+        );
+        assert_eq!(
+            HuiSource::Switch(switch).control(&events[0]),
+            Some(ControlValue::AbsoluteContinuous(UnitValue::MAX))
+        );
+        assert_eq!(HuiSource::Fader { index: 0 }.control(&events[2]), None);
+        assert_eq!(
+            HuiSource::VPot { index: 2 }.control(&events[3]),
+            Some(ControlValue::RelativeDiscrete(DiscreteIncrement::new(1)))
+        );
+    }
+
+    #[test]
+    fn feedback() {
+        // Given
+        let fv = |v: f64| {
+            FeedbackValue::Numeric(NumericFeedbackValue::new(
+                Default::default(),
+                AbsoluteValue::Continuous(UnitValue::new(v)),
+            ))
+        };
+        // When
+        // Then
+        assert_eq!(
+            HuiSource::Switch(HuiSwitch::fader_touch(3)).feedback::<RawShortMessage>(fv(1.0)),
+            Some([
+                Some(control_change(0, 0x0C, 0x03)),
+                Some(control_change(0, 0x2C, 0x40))
+            ])
+        );
+        assert_eq!(
+            HuiSource::Fader { index: 1 }.feedback::<RawShortMessage>(fv(1.0)),
+            Some([
+                Some(control_change(0, 0x01, 0x7F)),
+                Some(control_change(0, 0x21, 0x7F))
+            ])
+        );
+        assert_eq!(
+            HuiSource::VPot { index: 0 }.feedback::<RawShortMessage>(fv(0.5)),
+            Some([Some(control_change(0, 0x10, 6)), None])
+        );
+        assert_eq!(
+            hui_vu_meter::<RawShortMessage>(2, 1, UnitValue::MAX),
+            Some(polyphonic_key_pressure(0, 2, 0x1C))
+        );
+        assert_eq!(
+            hui_vu_meter::<RawShortMessage>(HUI_CHANNEL_COUNT, 0, UnitValue::MAX),
+            None
+        );
+        assert_eq!(
+            HuiSource::Fader { index: 200 }.feedback::<RawShortMessage>(fv(1.0)),
+            None
+        );
+        let invalid_switch = |zone, port| HuiSource::Switch(HuiSwitch { zone, port });
+        assert_eq!(
+            invalid_switch(0x80, 0).feedback::<RawShortMessage>(fv(1.0)),
+            None
+        );
+        assert_eq!(
+            invalid_switch(0, 8).feedback::<RawShortMessage>(fv(1.0)),
+            None
+        );
+        assert_eq!(hui_ping::<RawShortMessage>(), note_on(0, 0, 0));
+    }
+
+    #[test]
+    fn reject_invalid_switch_port() {
+        // Given
+        let mut decoder = HuiDecoder::default();
+        // When
+        let events: Vec<_> = [
+            control_change(0, 0x0F, 0x02),
+            control_change(0, 0x2F, 0x48),
+            control_change(0, 0x2F, 0x47),
+        ]
+        .into_iter()
+        .filter_map(|msg: RawShortMessage| decoder.feed(msg))
+        .collect();
+        // Then
+        assert_eq!(
+            events,
+            vec![HuiEvent::Switch {
+                switch: HuiSwitch { zone: 2, port: 7 },
+                is_pressed: true
+            }]
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use crate::{
+    FeedbackValue, MidiSourceAddress, MidiSourceScript, MidiSourceScriptOutcome, NumericValue,
+    PropProvider, PropValue, RawMidiEvent, RgbColor, ScriptDiagnostic,
+};
+use mlua::{Function, Lua, LuaOptions, LuaSerdeExt, RegistryKey, StdLib, Table, Value};
+use std::borrow::Cow;
+use std::error::Error;
+
+/// MIDI source script written in Lua.
+///
+/// The script runs with a restricted standard library (no `io`, `os` or file loading) and gets
+/// the following locals:
+///
+/// - `y`: The feedback value. A number between 0 and 1 for numeric feedback, a string for textual
+///   feedback, a table for complex feedback and `nil` if the target is off.
+/// - `context.feedback_event.color` and `context.feedback_event.background_color`: Tables with
+///   fields `r`, `g` and `b` or `nil`.
+/// - `context.prop(key)`: Returns the value of the given prop.
+///
+/// It must return a table with the following fields:
+///
+/// - `messages`: An array of messages, each one an array of bytes (integers from 0 to 255).
+/// - `address` (optional): An integer which uniquely identifies the feedback destination.
+///
+/// # Example
+///
+/// ```lua
+/// return {
+///     address = 0x4bb0,
+///     messages = {
+///         { 0xb0, 0x4b, math.floor(y * 127) }
+///     }
+/// }
+/// ```
+pub struct LuaMidiSourceScript {
+    lua: Lua,
+    function_key: RegistryKey,
+}
+
+const CHUNK_NAME: &str = "MIDI source script";
+
+/// Prepended to the code in order to make the inputs available as locals instead of globals, so
+/// that nothing leaks from one execution into the next. Doesn't contain a line break, so line
+/// numbers in error messages stay correct.
+const CHUNK_PROLOGUE: &str = "local y, context = ...; ";
+
+/// Additional input available to Lua MIDI source scripts.
+#[derive(Copy, Clone, Default)]
+pub struct LuaMidiSourceScriptInput<'a> {
+    pub prop_provider: Option<&'a dyn PropProvider>,
+}
+
+impl LuaMidiSourceScript {
+    /// Compiles the given Lua code.
+    pub fn compile(code: &str) -> Result<Self, Box<dyn Error>> {
+        let lua = create_sandboxed_lua()?;
+        let function = load_function(&lua, code)?;
+        let function_key = lua.create_registry_value(function)?;
+        let script = Self { lua, function_key };
+        Ok(script)
+    }
+
+    fn execute_internal(
+        &self,
+        input_value: FeedbackValue,
+        additional_input: LuaMidiSourceScriptInput,
+    ) -> mlua::Result<MidiSourceScriptOutcome> {
+        let lua = &self.lua;
+        let y = match &input_value {
+            FeedbackValue::Off => Value::Nil,
+            FeedbackValue::Numeric(v) => Value::Number(v.value.to_unit_value().get()),
+            FeedbackValue::Textual(v) => Value::String(lua.create_string(v.text.as_ref())?),
+            FeedbackValue::Complex(v) => lua.to_value(&v.value)?,
+        };
+        let feedback_event = lua.create_table()?;
+        feedback_event.set("color", color_to_lua(lua, input_value.color())?)?;
+        feedback_event.set(
+            "background_color",
+            color_to_lua(lua, input_value.background_color())?,
+        )?;
+        let context = lua.create_table()?;
+        context.set("feedback_event", feedback_event)?;
+        lua.scope(|scope| {
+            let prop = scope.create_function(|lua, key: String| {
+                let value = additional_input
+                    .prop_provider
+                    .and_then(|p| p.get_prop_value(&key));
+                prop_value_to_lua(lua, value)
+            })?;
+            context.set("prop", prop)?;
+            let function: Function = lua.registry_value(&self.function_key)?;
+            let output: Table = function.call((y, context))?;
+            let address = output
+                .get::<_, Option<u64>>("address")?
+                .map(|bytes| MidiSourceAddress::Script { bytes });
+            let messages: Vec<Vec<f64>> = output.get("messages")?;
+            let events: Result<Vec<_>, _> = messages
+                .into_iter()
+                .map(|bytes| {
+                    let bytes: Vec<u8> = bytes
+                        .into_iter()
+                        .map(lua_number_to_byte)
+                        .collect::<mlua::Result<_>>()?;
+                    RawMidiEvent::try_from_slice(0, &bytes)
+                        .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+                })
+                .collect();
+            let outcome = MidiSourceScriptOutcome {
+                address,
+                events: events?,
+            };
+            Ok(outcome)
+        })
+    }
+}
+
+impl<'a> MidiSourceScript<'a> for LuaMidiSourceScript {
+    type AdditionalInput = LuaMidiSourceScriptInput<'a>;
+
+    fn execute(
+        &self,
+        input_value: FeedbackValue,
+        additional_input: LuaMidiSourceScriptInput<'a>,
+    ) -> Result<MidiSourceScriptOutcome, Cow<'static, str>> {
+        self.execute_internal(input_value, additional_input)
+            .map_err(|e| e.to_string().into())
+    }
+
+    fn validate(code: &str) -> Result<(), Vec<ScriptDiagnostic>> {
+        create_sandboxed_lua()
+            .and_then(|lua| load_function(&lua, code).map(|_| ()))
+            .map_err(|e| vec![lua_error_to_diagnostic(&e)])
+    }
+}
+
+/// Creates a Lua state which can't access the file system or the operating system.
+fn create_sandboxed_lua() -> mlua::Result<Lua> {
+    let lua = Lua::new_with(
+        StdLib::MATH | StdLib::STRING | StdLib::TABLE,
+        LuaOptions::default(),
+    )?;
+    let globals = lua.globals();
+    for unsafe_function in ["dofile", "loadfile", "load", "require"] {
+        globals.set(unsafe_function, Value::Nil)?;
+    }
+    Ok(lua)
+}
+
+fn load_function(lua: &Lua, code: &str) -> mlua::Result<Function> {
+    lua.load(format!("{CHUNK_PROLOGUE}{code}"))
+        .set_name(CHUNK_NAME)
+        .into_function()
+}
+
+/// Extracts the line from Lua error messages such as
+/// `[string "MIDI source script"]:3: unexpected symbol near 'x'`.
+fn lua_error_to_diagnostic(error: &mlua::Error) -> ScriptDiagnostic {
+    let message = match error {
+        mlua::Error::SyntaxError { message, .. } => message.clone(),
+        e => e.to_string(),
+    };
+    let located = message.split_once("]:").and_then(|(_, rest)| {
+        let (line, msg) = rest.split_once(':')?;
+        Some((line.parse().ok()?, msg.trim()))
+    });
+    match located {
+        Some((line, msg)) => ScriptDiagnostic {
+            message: msg.to_owned(),
+            line: Some(line),
+            column: None,
+        },
+        None => ScriptDiagnostic {
+            message,
+            line: None,
+            column: None,
+        },
+    }
+}
+
+fn lua_number_to_byte(number: f64) -> mlua::Result<u8> {
+    if number.fract() != 0.0 || !(0.0..=255.0).contains(&number) {
+        let msg = format!("message contains {number}, which is not a byte");
+        return Err(mlua::Error::RuntimeError(msg));
+    }
+    Ok(number as u8)
+}
+
+fn color_to_lua(lua: &Lua, color: Option<RgbColor>) -> mlua::Result<Value> {
+    let Some(color) = color else {
+        return Ok(Value::Nil);
+    };
+    let table = lua.create_table()?;
+    table.set("r", color.r())?;
+    table.set("g", color.g())?;
+    table.set("b", color.b())?;
+    Ok(Value::Table(table))
+}
+
+fn prop_value_to_lua(lua: &Lua, value: Option<PropValue>) -> mlua::Result<Value> {
+    let Some(value) = value else {
+        return Ok(Value::Nil);
+    };
+    let lua_value = match value {
+        PropValue::Normalized(v) => Value::Number(v.get()),
+        PropValue::Index(i) => Value::Integer(i as _),
+        PropValue::Boolean(b) => Value::Boolean(b),
+        PropValue::Numeric(NumericValue::Decimal(v)) => Value::Number(v),
+        PropValue::Numeric(NumericValue::Discrete(v)) => Value::Integer(v as _),
+        PropValue::Text(text) => Value::String(lua.create_string(text.as_ref())?),
+        PropValue::Color(color) => color_to_lua(lua, Some(color))?,
+        PropValue::DurationInMillis(millis) => Value::Integer(millis as _),
+    };
+    Ok(lua_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AbsoluteValue, FeedbackStyle, NumericFeedbackValue, UnitValue};
+
+    #[test]
+    fn generate_messages() {
+        // Given
+        let script = LuaMidiSourceScript::compile(
+            r#"
+                local color = context.feedback_event.color
+                return {
+                    address = 0x4bb0,
+                    messages = {
+                        { 0xb0, 0x4b, y * 127 },
+                        { 0xf0, color.r // 2, color.g // 2, color.b // 2, context.prop("index"), 0xf7 },
+                    }
+                }
+            "#,
+        )
+        .unwrap();
+        let value = FeedbackValue::Numeric(NumericFeedbackValue::new(
+            FeedbackStyle {
+                color: Some(RgbColor::new(0xff, 0x00, 0x80)),
+                background_color: None,
+            },
+            AbsoluteValue::Continuous(UnitValue::MAX),
+        ));
+        let prop_provider = |key: &str| (key == "index").then_some(PropValue::Index(5));
+        let input = LuaMidiSourceScriptInput {
+            prop_provider: Some(&prop_provider),
+        };
+        // When
+        let outcome = script.execute(value, input).unwrap();
+        // Then
+        assert_eq!(
+            outcome.address,
+            Some(MidiSourceAddress::Script { bytes: 0x4bb0 })
+        );
+        assert_eq!(outcome.events.len(), 2);
+        assert_eq!(outcome.events[0].bytes(), &[0xb0, 0x4b, 0x7f]);
+        assert_eq!(
+            outcome.events[1].bytes(),
+            &[0xf0, 0x7f, 0x00, 0x40, 0x05, 0xf7]
+        );
+    }
+
+    #[test]
+    fn runtime_error() {
+        // Given
+        let script = LuaMidiSourceScript::compile("error('oops')").unwrap();
+        // When
+        let result = script.execute(FeedbackValue::Off, Default::default());
+        // Then
+        assert!(result.is_err());
+        assert!(LuaMidiSourceScript::compile("return {").is_err());
+        for invalid_byte in ["300", "-1", "1.5"] {
+            let code = format!("return {{ messages = {{ {{ 0xb0, 0x4b, {invalid_byte} }} }} }}");
+            let script = LuaMidiSourceScript::compile(&code).unwrap();
+            assert!(script
+                .execute(FeedbackValue::Off, Default::default())
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn sandbox() {
+        // Given
+        let script = LuaMidiSourceScript::compile(
+            r#"
+                local function flag(v) return v and 1 or 0 end
+                return {
+                    messages = {
+                        {
+                            flag(io == nil),
+                            flag(os == nil),
+                            flag(dofile == nil),
+                            flag(rawget(_G, "y") == nil),
+                            flag(rawget(_G, "context") == nil),
+                        },
+                    }
+                }
+            "#,
+        )
+        .unwrap();
+        // When
+        let first_outcome = script
+            .execute(FeedbackValue::Off, Default::default())
+            .unwrap();
+        let second_outcome = script
+            .execute(FeedbackValue::Off, Default::default())
+            .unwrap();
+        // Then
+        assert_eq!(first_outcome.events[0].bytes(), &[1, 1, 1, 1, 1]);
+        assert_eq!(second_outcome.events[0].bytes(), &[1, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn validate() {
+        // Given
+        let valid = "return { messages = {} }";
+        let invalid = "local a = 1\nreturn { messages = }";
+        // When
+        let valid_result = LuaMidiSourceScript::validate(valid);
+        let invalid_result = LuaMidiSourceScript::validate(invalid);
+        // Then
+        assert_eq!(valid_result, Ok(()));
+        let diagnostics = invalid_result.unwrap_err();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, Some(2));
+        assert_eq!(diagnostics[0].column, None);
+        assert!(!diagnostics[0].message.is_empty());
+    }
+}
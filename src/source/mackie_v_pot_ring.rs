@@ -0,0 +1,179 @@
+use crate::FeedbackValue;
+use derive_more::Display;
+use helgoboss_midi::{Channel, ControllerNumber, ShortMessageFactory, U7};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use strum::EnumIter;
+
+/// How a Mackie V-Pot LED ring displays the feedback value.
+///
+/// The numeric representation corresponds to the mode bits of the ring message.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize_repr,
+    Deserialize_repr,
+)]
+#[repr(usize)]
+pub enum MackieVPotRingMode {
+    /// Lights exactly one LED.
+    #[default]
+    #[display(fmt = "Single dot")]
+    SingleDot = 0,
+    /// Lights all LEDs between the center and the value.
+    #[display(fmt = "Boost/cut")]
+    BoostCut = 1,
+    /// Lights all LEDs from the left up to the value.
+    #[display(fmt = "Wrap")]
+    Wrap = 2,
+    /// Lights LEDs symmetrically around the center, the higher the value the wider.
+    #[display(fmt = "Spread")]
+    Spread = 3,
+}
+
+impl MackieVPotRingMode {
+    /// Returns the ring position (1 to 11, 0 = all LEDs off) for the given unit value.
+    pub(crate) fn position(self, value: f64) -> u8 {
+        use MackieVPotRingMode::*;
+        match self {
+            // One LED is always lit in these modes, otherwise the value 0 wouldn't be visible.
+            SingleDot | BoostCut => {
+                1 + (value * (V_POT_RING_POSITION_COUNT - 1) as f64).round() as u8
+            }
+            Wrap => (value * V_POT_RING_POSITION_COUNT as f64).round() as u8,
+            // Only the first half of the positions makes a difference.
+            Spread => (value * V_POT_RING_SPREAD_WIDTH_COUNT as f64).round() as u8,
+        }
+    }
+}
+
+/// Number of LEDs of a Mackie V-Pot ring (not counting the center LED below the ring).
+const V_POT_RING_POSITION_COUNT: u8 = 11;
+const V_POT_RING_SPREAD_WIDTH_COUNT: u8 = 6;
+const V_POT_RING_CONTROLLER_NUMBER_OFFSET: u8 = 0x30;
+const V_POT_RING_CENTER_LED_BIT: u8 = 0x40;
+
+/// Encodes feedback for the LED ring around a V-Pot of a Mackie Control compatible device.
+///
+/// Display sources expose it via [`crate::DisplaySpec::MackieVPotRing`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct MackieVPotRingFeedback {
+    /// Channel strip, 0 to 7.
+    pub channel_index: u8,
+    pub mode: MackieVPotRingMode,
+    /// Whether to light the separate LED below the ring.
+    pub center_led: bool,
+}
+
+impl MackieVPotRingFeedback {
+    pub fn new(channel_index: u8, mode: MackieVPotRingMode) -> Self {
+        Self {
+            channel_index,
+            mode,
+            center_led: false,
+        }
+    }
+
+    /// Returns the ring message (0xB0) for the given feedback value. Switches all LEDs off if the
+    /// value is off.
+    ///
+    /// Returns `None` if the value is not numeric or the channel index is out of range.
+    pub fn feedback<M: ShortMessageFactory>(&self, value: FeedbackValue) -> Option<M> {
+        if self.channel_index >= 8 {
+            return None;
+        }
+        let position = match value {
+            FeedbackValue::Off => 0,
+            v => self
+                .mode
+                .position(v.to_numeric()?.value.to_unit_value().get()),
+        };
+        let center_bit = if self.center_led {
+            V_POT_RING_CENTER_LED_BIT
+        } else {
+            0
+        };
+        let mode_bits = (usize::from(self.mode) as u8) << 4;
+        let msg = M::control_change(
+            Channel::new(0),
+            ControllerNumber::new(V_POT_RING_CONTROLLER_NUMBER_OFFSET + self.channel_index),
+            U7::new(center_bit | mode_bits | position),
+        );
+        Some(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AbsoluteValue, NumericFeedbackValue, UnitValue};
+    use helgoboss_midi::test_util::*;
+    use helgoboss_midi::RawShortMessage;
+
+    fn fv(value: f64) -> FeedbackValue<'static> {
+        FeedbackValue::Numeric(NumericFeedbackValue::new(
+            Default::default(),
+            AbsoluteValue::Continuous(UnitValue::new(value)),
+        ))
+    }
+
+    #[test]
+    fn ring_modes() {
+        // Given
+        let feedback = |mode, value| -> Option<RawShortMessage> {
+            MackieVPotRingFeedback::new(2, mode).feedback(value)
+        };
+        // When
+        // Then
+        use MackieVPotRingMode::*;
+        assert_eq!(
+            feedback(SingleDot, fv(0.0)),
+            Some(control_change(0, 0x32, 0x01))
+        );
+        assert_eq!(
+            feedback(SingleDot, fv(1.0)),
+            Some(control_change(0, 0x32, 0x0B))
+        );
+        assert_eq!(
+            feedback(BoostCut, fv(0.5)),
+            Some(control_change(0, 0x32, 0x16))
+        );
+        assert_eq!(feedback(Wrap, fv(0.0)), Some(control_change(0, 0x32, 0x20)));
+        assert_eq!(feedback(Wrap, fv(1.0)), Some(control_change(0, 0x32, 0x2B)));
+        assert_eq!(
+            feedback(Spread, fv(1.0)),
+            Some(control_change(0, 0x32, 0x36))
+        );
+        assert_eq!(
+            feedback(Spread, FeedbackValue::Off),
+            Some(control_change(0, 0x32, 0x30))
+        );
+        assert_eq!(
+            MackieVPotRingFeedback::new(8, SingleDot).feedback::<RawShortMessage>(fv(1.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn center_led() {
+        // Given
+        let feedback = MackieVPotRingFeedback {
+            channel_index: 0,
+            mode: MackieVPotRingMode::BoostCut,
+            center_led: true,
+        };
+        // When
+        let msg: Option<RawShortMessage> = feedback.feedback(fv(0.0));
+        // Then
+        assert_eq!(msg, Some(control_change(0, 0x30, 0x51)));
+    }
+}
@@ -1,10 +1,15 @@
 use crate::{
     create_raw_midi_events_singleton, format_percentage_without_unit,
-    parse_percentage_without_unit, AbsoluteValue, ControlValue, DetailedSourceCharacter,
-    DiscreteIncrement, FeedbackValue, Fraction, MidiSourceScript, MidiSourceValue,
-    PreliminaryMidiSourceFeedbackValue, RawFeedbackAddressInfo, RawMidiEvent, RawMidiEvents,
-    RawMidiPattern, RgbColor, SourceContext, TextualFeedbackValue, UnitValue,
-    XTouchMackieLcdColorRequest,
+    parse_percentage_without_unit, AbsoluteValue, AbstractTimestamp, BankProgramChangeMessage,
+    ControlChangePair, ControlChangePairMessage, ControlEvent, ControlValue,
+    DetailedSourceCharacter, DiscreteIncrement, FeedbackValue, Fraction, Interval,
+    MackieVPotRingFeedback, MidiSourceScript, MidiSourceValue, MidiTimeCode, MmcCommand,
+    MmcMessage, MpeDimension, MpeZone, NumericFeedbackValue, PreliminaryMidiSourceFeedbackValue,
+    RawFeedbackAddressInfo, RawMidiEvent, RawMidiEvents, RawMidiPattern, RawMidiPatternColor,
+    RawMidiPatternPlaceholderValues, RgbColor, SourceContext, SysExPollRequest,
+    TextualFeedbackValue, UmpChannelVoiceMessage, UmpMessageKind, UnitValue, VelocityCurve,
+    XTouchMackieLcdColorRequest, MIDI_TIME_CODE_MAX_SECONDS, MMC_ALL_DEVICES,
+    MPE_SLIDE_CONTROLLER_NUMBER,
 };
 use core::iter;
 use derivative::Derivative;
@@ -13,10 +18,12 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 use std::cell::Cell;
 use strum::EnumIter;
 
+use crate::devices::akai::AkaiLedAnimation;
+use crate::devices::launchpad::LaunchpadModel;
 use crate::devices::x_touch::get_x_touch_color_index_for_color;
-use crate::source::color_util::find_closest_color_in_palette;
+use crate::devices::{DevicePalette, DeviceQuirks};
 use helgoboss_midi::{
-    Channel, ControlChange14BitMessage, ControllerNumber, DataType, KeyNumber,
+    Channel, ControlChange14BitMessage, ControllerNumber, DataEntryByteOrder, DataType, KeyNumber,
     ParameterNumberMessage, RawShortMessage, ShortMessage, ShortMessageFactory, ShortMessageType,
     StructuredShortMessage, U14, U7,
 };
@@ -59,6 +66,11 @@ pub enum SourceCharacter {
     /// hardware toggle is switching to off.   
     #[display(fmt = "Toggle-only button (avoid!)")]
     ToggleButton = 5,
+    /// Alternative workaround for toggle-only buttons. A source with this character treats the
+    /// alternating values as on/off state of a switch: It emits 1 if the hardware toggle is
+    /// switching to on (any value greater than zero) and 0 if it's switching to off.
+    #[display(fmt = "Toggle-only button (as on/off switch)")]
+    ToggleSwitch = 6,
 }
 
 impl SourceCharacter {
@@ -79,10 +91,43 @@ impl SourceCharacter {
             ],
             Encoder1 | Encoder2 | Encoder3 => vec![DetailedSourceCharacter::Relative],
             ToggleButton => vec![DetailedSourceCharacter::Trigger],
+            ToggleSwitch => vec![DetailedSourceCharacter::RangeControl],
         }
     }
 }
 
+/// What a source is expected to emit, for deriving sensible defaults (e.g. in host UIs).
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct SourceCharacterInfo {
+    /// The most likely character, that is, the first one of the possible detailed characters.
+    pub character: DetailedSourceCharacter,
+    /// Number of distinct values minus one. `None` if the values are continuous, relative or
+    /// unknown.
+    pub max_discrete_value: Option<u32>,
+}
+
+impl SourceCharacterInfo {
+    /// Takes the first one of the given possible characters as expected character.
+    pub fn new(
+        possible_characters: &[DetailedSourceCharacter],
+        max_discrete_value: Option<u32>,
+    ) -> Self {
+        Self {
+            character: possible_characters
+                .first()
+                .copied()
+                .unwrap_or(DetailedSourceCharacter::RangeControl),
+            max_discrete_value,
+        }
+    }
+
+    /// Returns the resolution in bits, e.g. 7 for 128 distinct values.
+    pub fn resolution_in_bits(&self) -> Option<u32> {
+        let max = self.max_discrete_value?;
+        Some(u32::BITS - max.leading_zeros())
+    }
+}
+
 #[derive(
     Clone,
     Copy,
@@ -106,6 +151,150 @@ pub enum MidiClockTransportMessage {
     Stop = 2,
 }
 
+impl MidiClockTransportMessage {
+    pub fn to_short_message<M: ShortMessageFactory>(self) -> M {
+        use MidiClockTransportMessage::*;
+        match self {
+            Start => M::start(),
+            Continue => M::r#continue(),
+            Stop => M::stop(),
+        }
+    }
+}
+
+/// Type of the message whose channel is used as value by [`MidiSource::ChannelNumber`].
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize_repr,
+    Deserialize_repr,
+)]
+#[repr(usize)]
+pub enum ChannelNumberMessageType {
+    #[default]
+    #[display(fmt = "Note")]
+    Note = 0,
+    #[display(fmt = "CC")]
+    ControlChange = 1,
+}
+
+/// What a note velocity source does with Note On messages with velocity zero.
+///
+/// The MIDI specification defines them as equivalent to Note Off but not all devices agree.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize_repr,
+    Deserialize_repr,
+)]
+#[repr(usize)]
+pub enum ZeroVelocityNoteOnBehavior {
+    /// Emits zero, just like a Note Off message.
+    #[default]
+    #[display(fmt = "Treat as release")]
+    Release = 0,
+    /// Handled like any other Note On message, so the velocity curve applies.
+    #[display(fmt = "Treat as value 0")]
+    Zero = 1,
+    #[display(fmt = "Ignore")]
+    Ignore = 2,
+}
+
+/// How a note velocity source handles the end of a note.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct NoteOffPolicy {
+    pub zero_velocity_note_on: ZeroVelocityNoteOnBehavior,
+    /// If `true`, Note Off messages emit their release velocity instead of zero.
+    pub forward_note_off_velocity: bool,
+}
+
+impl NoteOffPolicy {
+    /// Returns the value to be emitted for a Note Off message with the given velocity.
+    fn release_value(&self, velocity_curve: &VelocityCurve, velocity: U7) -> Fraction {
+        if self.forward_note_off_velocity {
+            apply_velocity_curve(velocity_curve, velocity)
+        } else {
+            MIN_U7_FRACTION
+        }
+    }
+}
+
+/// Notes whose note-on has been ignored because of the velocity interval of a note velocity
+/// source, so that their releases can be ignored as well.
+#[derive(Clone, Debug, Default)]
+pub struct VelocityFilteredNotes(Cell<[u128; 16]>);
+
+impl VelocityFilteredNotes {
+    fn set(&self, channel: Channel, key_number: KeyNumber, filtered: bool) {
+        let mut notes = self.0.get();
+        let bit = 1u128 << key_number.get();
+        let channel_notes = &mut notes[channel.get() as usize];
+        if filtered {
+            *channel_notes |= bit;
+        } else {
+            *channel_notes &= !bit;
+        }
+        self.0.set(notes);
+    }
+
+    /// Returns whether the given note was filtered and forgets about it.
+    fn take(&self, channel: Channel, key_number: KeyNumber) -> bool {
+        let was_filtered = self.0.get()[channel.get() as usize] & (1u128 << key_number.get()) != 0;
+        if was_filtered {
+            self.set(channel, key_number, false);
+        }
+        was_filtered
+    }
+}
+
+/// How a 14-bit control change source sends feedback.
+///
+/// Some devices handle the LSB twice, which makes their motorized faders jitter.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize_repr,
+    Deserialize_repr,
+)]
+#[repr(usize)]
+pub enum ControlChange14BitFeedbackMode {
+    #[default]
+    #[display(fmt = "MSB, then LSB")]
+    MsbThenLsb = 0,
+    #[display(fmt = "LSB, then MSB")]
+    LsbThenMsb = 1,
+    /// Sends the MSB only, so the feedback resolution is 7-bit.
+    #[display(fmt = "MSB only")]
+    MsbOnly = 2,
+}
+
 impl From<MidiClockTransportMessage> for ShortMessageType {
     fn from(msg: MidiClockTransportMessage) -> Self {
         use MidiClockTransportMessage::*;
@@ -123,10 +312,32 @@ pub enum MidiSource<S: for<'a> MidiSourceScript<'a>> {
     NoteVelocity {
         channel: Option<Channel>,
         key_number: Option<KeyNumber>,
+        /// Shapes incoming velocities. Feedback values are shaped inversely.
+        velocity_curve: VelocityCurve,
+        note_off_policy: NoteOffPolicy,
+        /// Note-ons with a velocity outside of this range are ignored, and so are their releases.
+        ///
+        /// Allows splitting one pad into multiple logical sources, e.g. soft and hard hits.
+        velocity_interval: Interval<U7>,
+        #[derivative(PartialEq = "ignore")]
+        velocity_filtered_notes: VelocityFilteredNotes,
     },
     NoteKeyNumber {
         channel: Option<Channel>,
     },
+    // ShortMessageType::NoteOn within a key range
+    NoteKeyZone {
+        channel: Option<Channel>,
+        /// Note-ons in this range produce a value proportional to the key's position within the
+        /// range (e.g. one octave as 12-step selector). Velocity is ignored.
+        key_number_interval: Interval<KeyNumber>,
+    },
+    // ShortMessageType::{NoteOn, ControlChange} on any channel
+    ChannelNumber {
+        message_type: ChannelNumberMessageType,
+        /// Key number or controller number.
+        number: Option<U7>,
+    },
     // ShortMessageType::PolyphonicKeyPressure
     PolyphonicKeyPressureAmount {
         channel: Option<Channel>,
@@ -137,6 +348,12 @@ pub enum MidiSource<S: for<'a> MidiSourceScript<'a>> {
         channel: Option<Channel>,
         controller_number: Option<ControllerNumber>,
         custom_character: SourceCharacter,
+        /// Values outside of this range are ignored. Absolute values within the range are
+        /// normalized to the range (encoder values are passed through as they are).
+        ///
+        /// Allows splitting one controller into multiple logical sources, e.g. a jog wheel or
+        /// multi-function button which sends different value ranges for different purposes.
+        value_interval: Interval<U7>,
     },
     // ShortMessageType::ProgramChange
     ProgramChangeNumber {
@@ -147,6 +364,10 @@ pub enum MidiSource<S: for<'a> MidiSourceScript<'a>> {
         channel: Option<Channel>,
         program_number: Option<U7>,
     },
+    // BankProgramChangeMessage
+    BankProgramChangeNumber {
+        channel: Option<Channel>,
+    },
     // ShortMessageType::ChannelPressure
     ChannelPressureAmount {
         channel: Option<Channel>,
@@ -154,12 +375,26 @@ pub enum MidiSource<S: for<'a> MidiSourceScript<'a>> {
     // ShortMessageType::PitchBendChange
     PitchBendChangeValue {
         channel: Option<Channel>,
+        /// If enabled, the lower and upper half of the range are mapped separately, so the center
+        /// (8192) corresponds to exactly 0.5 and both extremes correspond to exactly 0.0 and 1.0.
+        ///
+        /// Otherwise the range is spread evenly, which makes the step between 16382 and 16383
+        /// larger than the others.
+        is_bipolar: bool,
     },
     // ControlChange14BitMessage
     ControlChange14BitValue {
         channel: Option<Channel>,
         msb_controller_number: Option<ControllerNumber>,
         custom_character: SourceCharacter,
+        /// Ignored if the device doesn't support 14-bit feedback (then it's always MSB only).
+        feedback_mode: ControlChange14BitFeedbackMode,
+    },
+    // ControlChangePairMessage
+    ControlChangePairValue {
+        channel: Option<Channel>,
+        pair: ControlChangePair,
+        custom_character: SourceCharacter,
     },
     // ParameterNumberMessage
     ParameterNumberValue {
@@ -167,18 +402,51 @@ pub enum MidiSource<S: for<'a> MidiSourceScript<'a>> {
         number: Option<U14>,
         is_14_bit: Option<bool>,
         is_registered: Option<bool>,
+        /// If enabled and `is_14_bit` is set, incoming data entry values are interpreted in that
+        /// resolution no matter what has been detected. Otherwise `is_14_bit` acts as a filter.
+        ///
+        /// Useful for devices whose resolution is mis-detected.
+        enforce_resolution: bool,
+        custom_character: SourceCharacter,
+    },
+    // UmpChannelVoiceMessage (MIDI 2.0)
+    UmpValue {
+        group: Option<u8>,
+        channel: Option<Channel>,
+        kind: UmpMessageKind,
+        /// Note number, controller number or bank/index (see [`UmpChannelVoiceMessage::new`]).
+        index: Option<u16>,
         custom_character: SourceCharacter,
     },
+    // MidiSourceValue::Mpe
+    MpeValue {
+        zone: MpeZone,
+        dimension: MpeDimension,
+        key_number_interval: Interval<KeyNumber>,
+    },
     // ShortMessageType::TimingClock
     ClockTempo,
     // ShortMessageType::{Start, Continue, Stop}
     ClockTransport {
         message: MidiClockTransportMessage,
     },
+    // ShortMessageType::SongPositionPointer
+    SongPosition,
+    // MidiSourceValue::TimeCode or MTC full frame sys-ex
+    TimeCode,
+    // MIDI Machine Control sys-ex
+    MachineControl {
+        /// `None` means any device (control) or all devices (feedback).
+        device_id: Option<u8>,
+        command: MmcCommand,
+    },
     // E.g. SysEx
     Raw {
         pattern: RawMidiPattern,
         custom_character: SourceCharacter,
+        /// For devices which don't push parameter changes by themselves. Their response to this
+        /// request is matched against the pattern.
+        poll_request: Option<SysExPollRequest>,
     },
     // For advanced programmable feedback (e.g. to drive hardware displays).
     Script {
@@ -206,9 +474,17 @@ pub enum MidiSourceAddress {
         controller_number: ControllerNumber,
         is_14_bit: bool,
     },
+    ControlChangePair {
+        channel: Channel,
+        msb_controller_number: ControllerNumber,
+        lsb_controller_number: ControllerNumber,
+    },
     ProgramChange {
         channel: Channel,
     },
+    BankProgramChange {
+        channel: Channel,
+    },
     ChannelPressure {
         channel: Channel,
     },
@@ -220,9 +496,23 @@ pub enum MidiSourceAddress {
         number: U14,
         is_registered: bool,
     },
+    Ump {
+        group: u8,
+        channel: Channel,
+        kind: UmpMessageKind,
+        index: u16,
+    },
     Display {
         spec: DisplaySpecAddress,
     },
+    ClockTransport {
+        message: MidiClockTransportMessage,
+    },
+    SongPosition,
+    MachineControl {
+        device_id: u8,
+        command: MmcCommand,
+    },
     Raw {
         pattern: Vec<PatternByte>,
     },
@@ -256,6 +546,7 @@ where
             NoteVelocity {
                 channel: Some(ch),
                 key_number: Some(kn),
+                ..
             } => MidiSourceAddress::Note {
                 channel: *ch,
                 key_number: *kn,
@@ -279,12 +570,15 @@ where
             ProgramChangeNumber { channel: Some(ch) } => {
                 MidiSourceAddress::ProgramChange { channel: *ch }
             }
+            BankProgramChangeNumber { channel: Some(ch) } => {
+                MidiSourceAddress::BankProgramChange { channel: *ch }
+            }
             ChannelPressureAmount { channel: Some(ch) } => {
                 MidiSourceAddress::ChannelPressure { channel: *ch }
             }
-            PitchBendChangeValue { channel: Some(ch) } => {
-                MidiSourceAddress::PitchBendChange { channel: *ch }
-            }
+            PitchBendChangeValue {
+                channel: Some(ch), ..
+            } => MidiSourceAddress::PitchBendChange { channel: *ch },
             ControlChange14BitValue {
                 channel: Some(ch),
                 msb_controller_number: Some(cn),
@@ -294,6 +588,15 @@ where
                 controller_number: *cn,
                 is_14_bit: true,
             },
+            ControlChangePairValue {
+                channel: Some(ch),
+                pair,
+                ..
+            } => MidiSourceAddress::ControlChangePair {
+                channel: *ch,
+                msb_controller_number: pair.msb_controller_number,
+                lsb_controller_number: pair.lsb_controller_number,
+            },
             ParameterNumberValue {
                 channel: Some(ch),
                 number: Some(n),
@@ -304,9 +607,27 @@ where
                 number: *n,
                 is_registered: *is_registered,
             },
+            UmpValue {
+                group: Some(group),
+                channel: Some(ch),
+                kind,
+                index,
+                ..
+            } if index.is_some() || !kind.has_index() => MidiSourceAddress::Ump {
+                group: *group,
+                channel: *ch,
+                kind: *kind,
+                index: index.unwrap_or_default(),
+            },
             Display { spec } => MidiSourceAddress::Display {
                 spec: spec.clone().into(),
             },
+            ClockTransport { message } => MidiSourceAddress::ClockTransport { message: *message },
+            SongPosition => MidiSourceAddress::SongPosition,
+            MachineControl { device_id, command } => MidiSourceAddress::MachineControl {
+                device_id: device_id.unwrap_or(MMC_ALL_DEVICES),
+                command: *command,
+            },
             Raw { pattern, .. } => MidiSourceAddress::Raw {
                 pattern: pattern.to_pattern_bytes(),
             },
@@ -320,7 +641,12 @@ where
                 };
             }
             // No feedback
-            ClockTempo | ClockTransport { .. } | NoteKeyNumber { .. } => return None,
+            ClockTempo
+            | NoteKeyNumber { .. }
+            | NoteKeyZone { .. }
+            | ChannelNumber { .. }
+            | MpeValue { .. }
+            | TimeCode => return None,
             // Non-feedback-compatible configurations (e.g. channel == <Any>)
             _ => return None,
         };
@@ -367,9 +693,9 @@ where
         context: SourceContext<<S as MidiSourceScript<'_>>::AdditionalInput>,
     ) -> bool
     where
-        for<'a> <S as MidiSourceScript<'a>>::AdditionalInput: Copy,
+        for<'a> <S as MidiSourceScript<'a>>::AdditionalInput: Clone,
     {
-        self.extract_feedback_address(context) == other.extract_feedback_address(context)
+        self.extract_feedback_address(context.clone()) == other.extract_feedback_address(context)
     }
 
     /// Used for creating sources when learning.
@@ -386,27 +712,67 @@ where
                 number: Some(msg.number()),
                 is_14_bit: Some(msg.is_14_bit()),
                 is_registered: Some(msg.is_registered()),
+                enforce_resolution: false,
                 custom_character: custom_character_hint.unwrap_or_default(),
             },
             ControlChange14Bit(msg) => MidiSource::ControlChange14BitValue {
                 channel: Some(msg.channel()),
                 msb_controller_number: Some(msg.msb_controller_number()),
                 custom_character: custom_character_hint.unwrap_or_default(),
+                feedback_mode: Default::default(),
+            },
+            ControlChangePair(msg) => MidiSource::ControlChangePairValue {
+                channel: Some(msg.channel()),
+                pair: msg.pair(),
+                custom_character: custom_character_hint.unwrap_or_default(),
+            },
+            Ump(msg) => MidiSource::UmpValue {
+                group: Some(msg.group()),
+                channel: Some(msg.channel()),
+                kind: msg.kind(),
+                index: Some(msg.index()),
+                custom_character: custom_character_hint.unwrap_or_default(),
+            },
+            BankProgramChange(msg) => MidiSource::BankProgramChangeNumber {
+                channel: Some(msg.channel()),
             },
             Tempo(_) => MidiSource::ClockTempo,
+            TimeCode(_) => MidiSource::TimeCode,
+            Mpe { msg, .. } => MidiSource::MpeValue {
+                zone: Default::default(),
+                dimension: MpeDimension::of(&msg)?,
+                key_number_interval: full_key_number_interval(),
+            },
             Plain(msg) => MidiSource::from_short_message(msg, custom_character_hint)?,
-            BorrowedSysEx(msg) => MidiSource::from_raw(msg),
+            BorrowedSysEx(msg) => MidiSource::from_sys_ex(msg),
             // Important (and working) for learning.
-            Raw { events, .. } => MidiSource::from_raw(events.first()?.bytes()),
+            Raw { events, .. } => MidiSource::from_sys_ex(events.first()?.bytes()),
         };
         Some(source)
     }
 
+    /// Like [`Self::from_raw`] but recognizes sys-ex messages which have dedicated source types.
+    ///
+    /// Allocates!
+    fn from_sys_ex(msg: &[u8]) -> Self {
+        if MidiTimeCode::from_full_frame_sysex(msg).is_some() {
+            return MidiSource::TimeCode;
+        }
+        if let Some(mmc) = MmcMessage::from_sysex(msg) {
+            return MidiSource::MachineControl {
+                device_id: Some(mmc.device_id),
+                command: mmc.command,
+            };
+        }
+        MidiSource::from_raw(msg)
+    }
+
     /// Allocates!
     pub fn from_raw(msg: &[u8]) -> Self {
         MidiSource::Raw {
             pattern: RawMidiPattern::fixed_from_slice(msg),
             custom_character: Default::default(),
+            poll_request: None,
         }
     }
 
@@ -428,6 +794,10 @@ where
             } => MidiSource::NoteVelocity {
                 channel: Some(channel),
                 key_number: Some(key_number),
+                velocity_curve: VelocityCurve::Linear,
+                note_off_policy: Default::default(),
+                velocity_interval: full_7_bit_interval(),
+                velocity_filtered_notes: Default::default(),
             },
             PolyphonicKeyPressure {
                 channel,
@@ -445,6 +815,7 @@ where
                 channel: Some(channel),
                 controller_number: Some(controller_number),
                 custom_character: custom_character_hint.unwrap_or_default(),
+                value_interval: full_7_bit_interval(),
             },
             ProgramChange {
                 channel,
@@ -458,6 +829,7 @@ where
             },
             PitchBendChange { channel, .. } => MidiSource::PitchBendChangeValue {
                 channel: Some(channel),
+                is_bipolar: false,
             },
             TimingClock => MidiSource::ClockTempo,
             Start => MidiSource::ClockTransport {
@@ -469,6 +841,7 @@ where
             Stop => MidiSource::ClockTransport {
                 message: MidiClockTransportMessage::Stop,
             },
+            SongPositionPointer { .. } => MidiSource::SongPosition,
             _ => {
                 return None;
             }
@@ -481,17 +854,37 @@ where
         match self {
             NoteVelocity { channel, .. }
             | NoteKeyNumber { channel }
+            | NoteKeyZone { channel, .. }
             | PolyphonicKeyPressureAmount { channel, .. }
             | ControlChangeValue { channel, .. }
             | ProgramChangeNumber { channel }
+            | BankProgramChangeNumber { channel }
             | SpecificProgramChange { channel, .. }
             | ChannelPressureAmount { channel }
-            | PitchBendChangeValue { channel }
+            | PitchBendChangeValue { channel, .. }
             | ControlChange14BitValue { channel, .. }
-            | ParameterNumberValue { channel, .. } => *channel,
-            ClockTempo | ClockTransport { .. } | Raw { .. } | Script { .. } | Display { .. } => {
-                None
-            }
+            | ControlChangePairValue { channel, .. }
+            | ParameterNumberValue { channel, .. }
+            | UmpValue { channel, .. } => *channel,
+            ClockTempo
+            | ChannelNumber { .. }
+            | ClockTransport { .. }
+            | SongPosition
+            | TimeCode
+            | MachineControl { .. }
+            | Raw { .. }
+            | Script { .. }
+            | Display { .. }
+            | MpeValue { .. } => None,
+        }
+    }
+
+    /// Returns the request which should be sent periodically to the device (see
+    /// [`crate::SysExPoller`]), if any.
+    pub fn sys_ex_poll_request(&self) -> Option<&SysExPollRequest> {
+        match self {
+            MidiSource::Raw { poll_request, .. } => poll_request.as_ref(),
+            _ => None,
         }
     }
 
@@ -503,6 +896,9 @@ where
             ClockTransport { .. } | SpecificProgramChange { .. } => {
                 SourceCharacter::MomentaryButton
             }
+            MachineControl { command, .. } if *command != MmcCommand::Locate => {
+                SourceCharacter::MomentaryButton
+            }
             Raw {
                 custom_character, ..
             }
@@ -512,16 +908,29 @@ where
             | ControlChange14BitValue {
                 custom_character, ..
             }
+            | ControlChangePairValue {
+                custom_character, ..
+            }
             | ParameterNumberValue {
                 custom_character, ..
+            }
+            | UmpValue {
+                custom_character, ..
             } => *custom_character,
             NoteKeyNumber { .. }
+            | NoteKeyZone { .. }
+            | ChannelNumber { .. }
             | PolyphonicKeyPressureAmount { .. }
             | ProgramChangeNumber { .. }
+            | BankProgramChangeNumber { .. }
             | ChannelPressureAmount { .. }
             | PitchBendChangeValue { .. }
             | Script { .. }
             | Display { .. }
+            | MpeValue { .. }
+            | SongPosition
+            | TimeCode
+            | MachineControl { .. }
             | ClockTempo => SourceCharacter::RangeElement,
         }
     }
@@ -536,6 +945,9 @@ where
             ClockTransport { .. } | SpecificProgramChange { .. } => {
                 vec![DetailedSourceCharacter::Trigger]
             }
+            MachineControl { command, .. } if *command != MmcCommand::Locate => {
+                vec![DetailedSourceCharacter::Trigger]
+            }
             // User can choose.
             Raw {
                 custom_character, ..
@@ -545,6 +957,12 @@ where
             }
             | ControlChange14BitValue {
                 custom_character, ..
+            }
+            | ControlChangePairValue {
+                custom_character, ..
+            }
+            | UmpValue {
+                custom_character, ..
             } => custom_character.possible_detailed_characters(),
             ParameterNumberValue {
                 custom_character,
@@ -560,7 +978,9 @@ where
                 }
             }
             // Usually a range control but sometimes more like a button (e.g. see #316).
-            ProgramChangeNumber { .. } | ChannelPressureAmount { .. } => vec![
+            ProgramChangeNumber { .. }
+            | BankProgramChangeNumber { .. }
+            | ChannelPressureAmount { .. } => vec![
                 DetailedSourceCharacter::RangeControl,
                 DetailedSourceCharacter::MomentaryOnOffButton,
                 DetailedSourceCharacter::Trigger,
@@ -576,14 +996,16 @@ where
             }
             // We exposed this as range-only ("key range") before but this actually also works as
             // buttons that are never released.
-            NoteKeyNumber { .. } => {
+            NoteKeyNumber { .. } | NoteKeyZone { .. } | ChannelNumber { .. } => {
                 vec![
                     DetailedSourceCharacter::RangeControl,
                     DetailedSourceCharacter::Trigger,
                 ]
             }
             // Special targets for which we can safely say it's a range.
-            ClockTempo => vec![DetailedSourceCharacter::RangeControl],
+            ClockTempo | MpeValue { .. } | SongPosition | TimeCode | MachineControl { .. } => {
+                vec![DetailedSourceCharacter::RangeControl]
+            }
             // Feedback-only but characters also matter for feedback.
             Script { .. } => {
                 vec![
@@ -598,6 +1020,16 @@ where
         }
     }
 
+    /// Returns the expected character and value resolution of this source.
+    ///
+    /// Allocates!
+    pub fn character_info(&self) -> SourceCharacterInfo {
+        SourceCharacterInfo::new(
+            &self.possible_detailed_characters(),
+            self.max_discrete_value(),
+        )
+    }
+
     /// Determines the appropriate control value from the given MIDI source value. If this source
     /// doesn't process values of that type or just consumes the value without leading to a
     /// control value (e.g. relative-zero), it returns None.
@@ -608,9 +1040,66 @@ where
         }
     }
 
+    /// Like [`Self::control`] but takes and returns events.
+    ///
+    /// The resulting event keeps the timestamp of the given one. For values assembled from
+    /// multiple messages (e.g. by [`crate::ControlChange14BitPairer`]), that's the arrival time of
+    /// the first message.
+    pub fn control_event<S: AbstractTimestamp>(
+        &self,
+        event: &ControlEvent<MidiSourceValue<impl ShortMessage>, S>,
+    ) -> Option<ControlEvent<ControlValue, S>> {
+        let control_value = self.control(event.payload_ref())?;
+        Some(event.with_payload(control_value))
+    }
+
+    /// Like [`Self::control`] but compensates the quirks of the connected device as given in the
+    /// context (see [`crate::devices::DeviceQuirks`]).
+    ///
+    /// If the device never sends button releases, the caller should process the value returned by
+    /// [`Self::emulated_release`] right after the returned press.
+    pub fn control_with_context<A>(
+        &self,
+        value: &MidiSourceValue<impl ShortMessage>,
+        context: &SourceContext<A>,
+    ) -> Option<ControlValue> {
+        match self.control_flexible_with_quirks(value, context.device_quirks)? {
+            ControlResult::Consumed => None,
+            ControlResult::Processed(v) => Some(v),
+        }
+    }
+
+    /// Returns the release which should follow the given press if the connected device never
+    /// sends button releases by itself (see [`crate::devices::DeviceQuirks`]).
+    pub fn emulated_release<A>(
+        &self,
+        press: ControlValue,
+        context: &SourceContext<A>,
+    ) -> Option<ControlValue> {
+        if !context.device_quirks.button_never_sends_release
+            || self.character() != SourceCharacter::MomentaryButton
+            || !press.is_on()
+        {
+            return None;
+        }
+        let release = match press {
+            ControlValue::AbsoluteDiscrete(f) => ControlValue::AbsoluteDiscrete(f.with_actual(0)),
+            _ => ControlValue::AbsoluteContinuous(UnitValue::MIN),
+        };
+        Some(release)
+    }
+
     pub fn control_flexible(
         &self,
         value: &MidiSourceValue<impl ShortMessage>,
+    ) -> Option<ControlResult> {
+        self.control_flexible_with_quirks(value, DeviceQuirks::default())
+    }
+
+    fn control_flexible_with_quirks(
+        &self,
+        value: &MidiSourceValue<impl ShortMessage>,
+        quirks: DeviceQuirks,
     ) -> Option<ControlResult> {
         use MidiSource as S;
         use MidiSourceValue::*;
@@ -619,6 +1108,10 @@ where
             S::NoteVelocity {
                 channel,
                 key_number,
+                velocity_curve,
+                note_off_policy,
+                velocity_interval,
+                velocity_filtered_notes,
             } => match value {
                 Plain(msg) => match msg.to_structured() {
                     NoteOn {
@@ -626,14 +1119,37 @@ where
                         key_number: kn,
                         velocity,
                     } if matches(ch, *channel) && matches(kn, *key_number) => {
-                        Some(abs(normalize_7_bit(velocity)))
+                        use ZeroVelocityNoteOnBehavior::*;
+                        if velocity > U7::MIN {
+                            let filtered = !velocity_interval.contains(velocity);
+                            velocity_filtered_notes.set(ch, kn, filtered);
+                            if filtered {
+                                return None;
+                            }
+                            Some(abs(apply_velocity_curve(velocity_curve, velocity)))
+                        } else if velocity_filtered_notes.take(ch, kn) {
+                            None
+                        } else {
+                            match note_off_policy.zero_velocity_note_on {
+                                Release => Some(abs(MIN_U7_FRACTION)),
+                                Zero => {
+                                    let v = velocity_curve.apply(UnitValue::MIN);
+                                    let max = U7::MAX.get() as u32;
+                                    Some(abs(Fraction::new(v.to_discrete(max), max)))
+                                }
+                                Ignore => None,
+                            }
+                        }
                     }
                     NoteOff {
                         channel: ch,
                         key_number: kn,
-                        ..
+                        velocity,
                     } if matches(ch, *channel) && matches(kn, *key_number) => {
-                        Some(abs(MIN_U7_FRACTION))
+                        if velocity_filtered_notes.take(ch, kn) {
+                            return None;
+                        }
+                        Some(abs(note_off_policy.release_value(velocity_curve, velocity)))
                     }
                     _ => None,
                 },
@@ -652,13 +1168,79 @@ where
                 },
                 _ => None,
             },
-            S::PitchBendChangeValue { channel } => match value {
+            S::NoteKeyZone {
+                channel,
+                key_number_interval,
+            } => match value {
+                Plain(msg) => match msg.to_structured() {
+                    NoteOn {
+                        channel: ch,
+                        key_number,
+                        velocity,
+                    } if velocity > U7::MIN
+                        && matches(ch, *channel)
+                        && key_number_interval.contains(key_number) =>
+                    {
+                        let (offset, span) =
+                            key_zone_offset_and_span(*key_number_interval, key_number);
+                        if span == 0 {
+                            // Just one key. Act like a trigger.
+                            Some(ControlValue::AbsoluteContinuous(UnitValue::MAX))
+                        } else {
+                            Some(abs(Fraction::new(offset, span)))
+                        }
+                    }
+                    _ => None,
+                },
+                _ => None,
+            },
+            S::ChannelNumber {
+                message_type,
+                number,
+            } => match value {
+                Plain(msg) => {
+                    // "Off" messages (velocity or value zero) don't count
+                    let (channel, n) = match (message_type, msg.to_structured()) {
+                        (
+                            ChannelNumberMessageType::Note,
+                            NoteOn {
+                                channel,
+                                key_number,
+                                velocity,
+                            },
+                        ) if velocity > U7::MIN => (channel, key_number.get()),
+                        (
+                            ChannelNumberMessageType::ControlChange,
+                            ControlChange {
+                                channel,
+                                controller_number,
+                                control_value,
+                            },
+                        ) if control_value > U7::MIN => (channel, controller_number.get()),
+                        _ => return None,
+                    };
+                    if !matches(U7::new(n), *number) {
+                        return None;
+                    }
+                    Some(abs(Fraction::new(channel.get() as u32, MAX_CHANNEL)))
+                }
+                _ => None,
+            },
+            S::PitchBendChangeValue {
+                channel,
+                is_bipolar,
+            } => match value {
                 Plain(msg) => match msg.to_structured() {
                     PitchBendChange {
                         channel: ch,
                         pitch_bend_value,
                     } if matches(ch, *channel) => {
-                        Some(abs(normalize_14_bit_centered(pitch_bend_value)))
+                        if *is_bipolar {
+                            let v = normalize_14_bit_bipolar(pitch_bend_value);
+                            Some(ControlValue::AbsoluteContinuous(v))
+                        } else {
+                            Some(abs(normalize_14_bit_centered(pitch_bend_value)))
+                        }
                     }
                     _ => None,
                 },
@@ -682,8 +1264,32 @@ where
                     } if matches(ch, *channel) => Some(abs(normalize_7_bit(program_number))),
                     _ => None,
                 },
+                BankProgramChange(msg) if matches(msg.channel(), *channel) => {
+                    Some(abs(normalize_7_bit(msg.program_number())))
+                }
                 _ => None,
             },
+            S::BankProgramChangeNumber { channel } => {
+                let msg = match value {
+                    BankProgramChange(msg) => *msg,
+                    // Program change without preceding bank select
+                    Plain(msg) => match msg.to_structured() {
+                        ProgramChange {
+                            channel: ch,
+                            program_number,
+                        } => BankProgramChangeMessage::new(ch, U14::MIN, program_number),
+                        _ => return None,
+                    },
+                    _ => return None,
+                };
+                if !matches(msg.channel(), *channel) {
+                    return None;
+                }
+                Some(abs(Fraction::new(
+                    msg.value(),
+                    BankProgramChangeMessage::MAX_VALUE,
+                )))
+            }
             S::SpecificProgramChange {
                 channel,
                 program_number,
@@ -697,6 +1303,12 @@ where
                     }
                     _ => None,
                 },
+                BankProgramChange(msg)
+                    if matches(msg.channel(), *channel)
+                        && matches(msg.program_number(), *program_number) =>
+                {
+                    Some(abs(Fraction::new_max(1)))
+                }
                 _ => None,
             },
             S::PolyphonicKeyPressureAmount {
@@ -719,16 +1331,24 @@ where
                 channel,
                 controller_number,
                 custom_character,
+                value_interval,
             } => match value {
                 Plain(msg) => match msg.to_structured() {
                     ControlChange {
                         channel: ch,
                         controller_number: cn,
                         control_value,
-                    } if matches(ch, *channel) && matches(cn, *controller_number) => {
-                        let control_outcome =
-                            calc_control_value_from_n_bit_cc(*custom_character, control_value, 7)
-                                .map(ControlResult::Processed);
+                    } if matches(ch, *channel)
+                        && matches(cn, *controller_number)
+                        && value_interval.contains(control_value) =>
+                    {
+                        let control_outcome = calc_control_value_from_7_bit_cc_within(
+                            *custom_character,
+                            control_value,
+                            *value_interval,
+                            quirks,
+                        )
+                        .map(ControlResult::Processed);
                         return Some(control_outcome.unwrap_or(ControlResult::Consumed));
                     }
                     _ => None,
@@ -739,12 +1359,27 @@ where
                 channel,
                 msb_controller_number,
                 custom_character,
+                ..
             } => match value {
                 ControlChange14Bit(msg)
                     if matches(msg.channel(), *channel)
                         && matches(msg.msb_controller_number(), *msb_controller_number) =>
                 {
-                    calc_control_value_from_n_bit_cc(*custom_character, msg.value(), 14).ok()
+                    calc_control_value_from_n_bit_cc(*custom_character, msg.value(), 14, quirks)
+                        .ok()
+                }
+                _ => None,
+            },
+            S::ControlChangePairValue {
+                channel,
+                pair,
+                custom_character,
+            } => match value {
+                ControlChangePair(msg)
+                    if matches(msg.channel(), *channel) && msg.pair() == *pair =>
+                {
+                    calc_control_value_from_n_bit_cc(*custom_character, msg.value(), 14, quirks)
+                        .ok()
                 }
                 _ => None,
             },
@@ -753,23 +1388,41 @@ where
                 number,
                 is_14_bit,
                 is_registered,
+                enforce_resolution,
                 custom_character,
             } => match value {
                 ParameterNumber(msg)
                     if matches(msg.channel(), *channel)
                         && matches(msg.number(), *number)
-                        && matches(msg.is_14_bit(), *is_14_bit)
+                        && (*enforce_resolution || matches(msg.is_14_bit(), *is_14_bit))
                         && matches(msg.is_registered(), *is_registered) =>
                 {
                     match msg.data_type() {
                         DataType::DataEntry => {
-                            if msg.is_14_bit() {
-                                calc_control_value_from_n_bit_cc(*custom_character, msg.value(), 14)
-                                    .ok()
+                            let enforced_is_14_bit = is_14_bit.filter(|_| *enforce_resolution);
+                            let interpret_as_14_bit = enforced_is_14_bit.unwrap_or(msg.is_14_bit());
+                            let value = convert_data_entry_resolution(
+                                msg.value(),
+                                msg.is_14_bit(),
+                                interpret_as_14_bit,
+                            );
+                            if interpret_as_14_bit {
+                                calc_control_value_from_n_bit_cc(
+                                    *custom_character,
+                                    value,
+                                    14,
+                                    quirks,
+                                )
+                                .ok()
                             } else {
-                                let u7_value = U7::try_from(msg.value()).unwrap();
-                                calc_control_value_from_n_bit_cc(*custom_character, u7_value, 7)
-                                    .ok()
+                                let u7_value = U7::try_from(value).unwrap();
+                                calc_control_value_from_n_bit_cc(
+                                    *custom_character,
+                                    u7_value,
+                                    7,
+                                    quirks,
+                                )
+                                .ok()
                             }
                         }
                         DataType::DataIncrement => {
@@ -782,6 +1435,37 @@ where
                 }
                 _ => None,
             },
+            S::UmpValue {
+                group,
+                channel,
+                kind,
+                index,
+                custom_character,
+            } => match value {
+                Ump(msg)
+                    if matches(msg.group(), *group)
+                        && matches(msg.channel(), *channel)
+                        && msg.kind() == *kind
+                        && (!kind.has_index() || matches(msg.index(), *index)) =>
+                {
+                    let max = kind.max_value();
+                    match custom_character {
+                        _ if *kind == UmpMessageKind::NoteOff => Some(abs(Fraction::new_min(max))),
+                        SourceCharacter::ToggleButton => Some(abs(Fraction::new_max(max))),
+                        SourceCharacter::ToggleSwitch => {
+                            Some(abs(to_switch_state(msg.value_fraction())))
+                        }
+                        // Relative encoders are not a thing in MIDI 2.0 high-resolution values
+                        SourceCharacter::Encoder1
+                        | SourceCharacter::Encoder2
+                        | SourceCharacter::Encoder3 => None,
+                        SourceCharacter::RangeElement | SourceCharacter::MomentaryButton => {
+                            Some(abs(msg.value_fraction()))
+                        }
+                    }
+                }
+                _ => None,
+            },
             S::ClockTransport { message } => match value {
                 Plain(msg) if msg.r#type() == (*message).into() => Some(abs(Fraction::new_max(1))),
                 _ => None,
@@ -790,9 +1474,83 @@ where
                 Tempo(bpm) => Some(ControlValue::AbsoluteContinuous((*bpm).into())),
                 _ => None,
             },
+            S::SongPosition => match value {
+                Plain(msg) => match msg.to_structured() {
+                    SongPositionPointer { position } => Some(abs(normalize_14_bit(position))),
+                    _ => None,
+                },
+                _ => None,
+            },
+            S::TimeCode => {
+                let time_code = match value {
+                    TimeCode(time_code) => *time_code,
+                    BorrowedSysEx(bytes) => MidiTimeCode::from_full_frame_sysex(bytes)?,
+                    Raw { events, .. } => {
+                        MidiTimeCode::from_full_frame_sysex(events.first()?.bytes())?
+                    }
+                    _ => return None,
+                };
+                Some(time_code_control_value(time_code))
+            }
+            S::MachineControl { device_id, command } => {
+                let msg = match value {
+                    BorrowedSysEx(bytes) => MmcMessage::from_sysex(bytes)?,
+                    Raw { events, .. } => MmcMessage::from_sysex(events.first()?.bytes())?,
+                    _ => return None,
+                };
+                let device_matches =
+                    msg.device_id == MMC_ALL_DEVICES || matches(msg.device_id, *device_id);
+                if !device_matches || msg.command != *command {
+                    return None;
+                }
+                match msg.locate_target {
+                    Some(target) => Some(time_code_control_value(target)),
+                    None => Some(abs(Fraction::new_max(1))),
+                }
+            }
+            S::MpeValue {
+                zone,
+                dimension,
+                key_number_interval,
+            } => match value {
+                Mpe { key_number, msg }
+                    if msg
+                        .channel()
+                        .is_some_and(|ch| zone.contains_member_channel(ch))
+                        && key_number_interval.contains(*key_number) =>
+                {
+                    match (dimension, msg.to_structured()) {
+                        (
+                            MpeDimension::PitchBend,
+                            PitchBendChange {
+                                pitch_bend_value, ..
+                            },
+                        ) => Some(abs(normalize_14_bit_centered(pitch_bend_value))),
+                        (
+                            MpeDimension::Pressure,
+                            ChannelPressure {
+                                pressure_amount, ..
+                            },
+                        ) => Some(abs(normalize_7_bit(pressure_amount))),
+                        (
+                            MpeDimension::Slide,
+                            ControlChange {
+                                controller_number,
+                                control_value,
+                                ..
+                            },
+                        ) if controller_number.get() == MPE_SLIDE_CONTROLLER_NUMBER => {
+                            Some(abs(normalize_7_bit(control_value)))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            },
             S::Raw {
                 pattern,
                 custom_character,
+                ..
             } => {
                 let fraction = match value {
                     Raw { events, .. } => pattern.match_and_capture(events.first()?.bytes())?,
@@ -812,6 +1570,7 @@ where
                         *custom_character,
                         fraction.actual(),
                         pattern.resolution() as _,
+                        quirks,
                     )
                     .ok()
                 }
@@ -850,6 +1609,14 @@ where
                 }
                 _ => false,
             },
+            ControlChangePairValue { channel, pair, .. } => match msg.to_structured() {
+                ControlChange {
+                    channel: ch,
+                    controller_number,
+                    ..
+                } => matches(ch, *channel) && pair.contains(controller_number),
+                _ => false,
+            },
             ParameterNumberValue { channel, .. } => match msg.to_structured() {
                 ControlChange {
                     channel: ch,
@@ -880,16 +1647,78 @@ where
             NoteVelocity {
                 channel: Some(ch),
                 key_number: Some(kn),
-            } => Some(V::Plain(M::note_on(
-                *ch,
-                *kn,
-                denormalize_7_bit(feedback_value.to_numeric()?.value),
-            ))),
+                velocity_curve,
+                ..
+            } => {
+                let mut numeric_value = feedback_value.to_numeric()?;
+                let address = MidiSourceAddress::Note {
+                    channel: *ch,
+                    key_number: *kn,
+                };
+                if let Some(v) = feedback_exact_rgb(&numeric_value, kn.get(), address, &context) {
+                    return Some(PreliminaryMidiSourceFeedbackValue {
+                        final_value: v,
+                        x_touch_mackie_lcd_color_request: None,
+                    });
+                }
+                if !velocity_curve.is_linear() {
+                    let velocity =
+                        velocity_curve.apply_inverse(numeric_value.value.to_unit_value());
+                    numeric_value.value = AbsoluteValue::Continuous(velocity);
+                }
+                // Some devices encode the LED animation of colored pads in the channel
+                let pad_channel = context
+                    .device_palette
+                    .as_ref()
+                    .filter(|_| numeric_value.style.color.is_some() && numeric_value.value.is_on())
+                    .and_then(|p| p.pad_channel(context.led_animation));
+                let velocity = denormalize_7_bit_with_palette(
+                    numeric_value,
+                    context.device_palette.as_ref(),
+                    context.led_animation,
+                );
+                match pad_channel {
+                    Some(pad_ch) if pad_ch != *ch => {
+                        let (b1, b2, b3) = M::note_on(pad_ch, *kn, velocity).to_bytes();
+                        let event =
+                            RawMidiEvent::try_from_slice(0, &[b1, b2.get(), b3.get()]).ok()?;
+                        let address_info = RawFeedbackAddressInfo::Custom(address);
+                        Some(V::single_raw(Some(address_info), event))
+                    }
+                    _ => Some(V::Plain(M::note_on(*ch, *kn, velocity))),
+                }
+            }
             NoteKeyNumber { channel: Some(ch) } => Some(V::Plain(M::note_on(
                 *ch,
                 denormalize_7_bit(feedback_value.to_numeric()?.value),
                 U7::MAX,
             ))),
+            NoteKeyZone {
+                channel: Some(ch),
+                key_number_interval,
+            } => {
+                let min = key_number_interval.min_val().get() as u32;
+                let span = key_number_interval.max_val().get() as u32 - min;
+                let offset = denormalize_discrete(feedback_value.to_numeric()?.value, span);
+                let key_number = KeyNumber::new((min + offset) as u8);
+                Some(V::Plain(M::note_on(*ch, key_number, U7::MAX)))
+            }
+            ChannelNumber {
+                message_type,
+                number: Some(n),
+            } => {
+                let channel = denormalize_discrete(feedback_value.to_numeric()?.value, MAX_CHANNEL);
+                let channel = Channel::new(channel as u8);
+                let msg = match message_type {
+                    ChannelNumberMessageType::Note => {
+                        M::note_on(channel, KeyNumber::new(n.get()), U7::MAX)
+                    }
+                    ChannelNumberMessageType::ControlChange => {
+                        M::control_change(channel, ControllerNumber::new(n.get()), U7::MAX)
+                    }
+                };
+                Some(V::Plain(msg))
+            }
             PolyphonicKeyPressureAmount {
                 channel: Some(ch),
                 key_number: Some(kn),
@@ -901,16 +1730,46 @@ where
             ControlChangeValue {
                 channel: Some(ch),
                 controller_number: Some(cn),
+                value_interval,
                 ..
-            } => Some(V::Plain(M::control_change(
-                *ch,
-                *cn,
-                denormalize_7_bit(feedback_value.to_numeric()?.value),
-            ))),
+            } => {
+                let numeric_value = feedback_value.to_numeric()?;
+                let value = if *value_interval == full_7_bit_interval() {
+                    let address = MidiSourceAddress::ControlChange {
+                        channel: *ch,
+                        controller_number: *cn,
+                        is_14_bit: false,
+                    };
+                    if let Some(v) = feedback_exact_rgb(&numeric_value, cn.get(), address, &context)
+                    {
+                        return Some(PreliminaryMidiSourceFeedbackValue {
+                            final_value: v,
+                            x_touch_mackie_lcd_color_request: None,
+                        });
+                    }
+                    denormalize_7_bit_with_palette(
+                        numeric_value,
+                        context.device_palette.as_ref(),
+                        context.led_animation,
+                    )
+                } else {
+                    let (min, span) = interval_min_and_span(*value_interval);
+                    U7::new((min + denormalize_discrete(numeric_value.value, span)) as u8)
+                };
+                Some(V::Plain(M::control_change(*ch, *cn, value)))
+            }
             ProgramChangeNumber { channel: Some(ch) } => Some(V::Plain(M::program_change(
                 *ch,
                 denormalize_7_bit(feedback_value.to_numeric()?.value),
             ))),
+            BankProgramChangeNumber { channel: Some(ch) } => {
+                let value = denormalize_discrete(
+                    feedback_value.to_numeric()?.value,
+                    BankProgramChangeMessage::MAX_VALUE,
+                );
+                let msg = BankProgramChangeMessage::from_value(*ch, value);
+                Some(V::BankProgramChange(msg))
+            }
             SpecificProgramChange {
                 channel: Some(ch),
                 program_number,
@@ -926,17 +1785,58 @@ where
                 *ch,
                 denormalize_7_bit(feedback_value.to_numeric()?.value),
             ))),
-            PitchBendChangeValue { channel: Some(ch) } => Some(V::Plain(M::pitch_bend_change(
-                *ch,
-                denormalize_14_bit_centered(feedback_value.to_numeric()?.value),
-            ))),
+            PitchBendChangeValue {
+                channel: Some(ch),
+                is_bipolar,
+            } => {
+                let value = feedback_value.to_numeric()?.value;
+                let value = if *is_bipolar {
+                    denormalize_14_bit_bipolar(value.to_unit_value())
+                } else {
+                    denormalize_14_bit_centered(value)
+                };
+                Some(V::Plain(M::pitch_bend_change(*ch, value)))
+            }
             ControlChange14BitValue {
                 channel: Some(ch),
                 msb_controller_number: Some(mcn),
+                feedback_mode,
+                ..
+            } => {
+                let value: U14 = denormalize_14_bit(feedback_value.to_numeric()?.value);
+                let feedback_mode = if context.supports_14_bit_feedback() {
+                    *feedback_mode
+                } else {
+                    ControlChange14BitFeedbackMode::MsbOnly
+                };
+                match feedback_mode {
+                    ControlChange14BitFeedbackMode::MsbThenLsb => Some(V::ControlChange14Bit(
+                        ControlChange14BitMessage::new(*ch, *mcn, value),
+                    )),
+                    ControlChange14BitFeedbackMode::LsbThenMsb => {
+                        let lsb_controller_number = ControllerNumber::new(mcn.get() + 32);
+                        let pair = ControlChangePair::new(
+                            *mcn,
+                            lsb_controller_number,
+                            DataEntryByteOrder::LsbFirst,
+                        );
+                        Some(V::ControlChangePair(ControlChangePairMessage::new(
+                            *ch, pair, value,
+                        )))
+                    }
+                    ControlChange14BitFeedbackMode::MsbOnly => {
+                        let msb = U7::new((value.get() >> 7) as u8);
+                        Some(V::Plain(M::control_change(*ch, *mcn, msb)))
+                    }
+                }
+            }
+            ControlChangePairValue {
+                channel: Some(ch),
+                pair,
                 ..
-            } => Some(V::ControlChange14Bit(ControlChange14BitMessage::new(
+            } => Some(V::ControlChangePair(ControlChangePairMessage::new(
                 *ch,
-                *mcn,
+                *pair,
                 denormalize_14_bit(feedback_value.to_numeric()?.value),
             ))),
             ParameterNumberValue {
@@ -975,9 +1875,86 @@ where
                 };
                 Some(V::ParameterNumber(n))
             }
+            UmpValue {
+                group: Some(group),
+                channel: Some(ch),
+                kind,
+                index,
+                ..
+            } if index.is_some() || !kind.has_index() => {
+                let value =
+                    denormalize_discrete(feedback_value.to_numeric()?.value, kind.max_value());
+                let msg = UmpChannelVoiceMessage::new(
+                    *group,
+                    *ch,
+                    *kind,
+                    index.unwrap_or_default(),
+                    value,
+                );
+                Some(V::Ump(msg))
+            }
+            ClockTransport { message } => {
+                // Transport messages don't carry a value. We send them only when the mapped
+                // transport state becomes active (e.g. "playing" for start).
+                if feedback_value.to_numeric()?.value.is_on() {
+                    Some(V::Plain(message.to_short_message()))
+                } else {
+                    None
+                }
+            }
+            SongPosition => {
+                let position = denormalize_14_bit(feedback_value.to_numeric()?.value);
+                Some(V::Plain(M::song_position_pointer(position)))
+            }
+            MachineControl { device_id, command } => {
+                let value = feedback_value.to_numeric()?.value;
+                let locate_target = if *command == MmcCommand::Locate {
+                    let seconds = value.to_unit_value().get() * MIDI_TIME_CODE_MAX_SECONDS;
+                    Some(MidiTimeCode::from_seconds(seconds))
+                } else if value.is_on() {
+                    // Like clock transport messages, MMC commands don't carry a value.
+                    None
+                } else {
+                    return None;
+                };
+                let msg = MmcMessage {
+                    device_id: device_id.unwrap_or(MMC_ALL_DEVICES),
+                    command: *command,
+                    locate_target,
+                };
+                let event = RawMidiEvent::try_from_iter(0, msg.to_sysex()).ok()?;
+                let address = MidiSourceAddress::MachineControl {
+                    device_id: msg.device_id,
+                    command: msg.command,
+                };
+                let address_info = RawFeedbackAddressInfo::Custom(address);
+                Some(V::single_raw(Some(address_info), event))
+            }
             Raw { pattern, .. } => {
+                let textual_value = if pattern.contains_text_placeholders() {
+                    feedback_value.to_textual()
+                } else {
+                    Default::default()
+                };
+                let (value, style) = match feedback_value.to_numeric() {
+                    Some(v) => (v.value, v.style),
+                    // Textual feedback only makes sense if the pattern can display it
+                    None if pattern.contains_text_placeholders() => {
+                        (AbsoluteValue::default(), textual_value.style)
+                    }
+                    None => return None,
+                };
+                let mut color =
+                    RawMidiPatternColor::new(style.color, context.device_palette.as_ref());
+                if !context.supports_rgb_color() {
+                    color.rgb = None;
+                }
+                let values = RawMidiPatternPlaceholderValues {
+                    color,
+                    text: context.fit_text(&textual_value.text),
+                };
                 let raw_midi_event =
-                    pattern.to_concrete_midi_event(0, feedback_value.to_numeric()?.value);
+                    pattern.to_concrete_midi_event_with_placeholder_values(0, value, values);
                 let address_info = RawFeedbackAddressInfo::Raw {
                     variable_range: pattern.variable_range(),
                 };
@@ -1113,6 +2090,16 @@ where
                             .collect();
                         (events, None)
                     }
+                    DisplaySpec::GenericLcd { layout, scope } => {
+                        (feedback_generic_lcd(&value, layout, scope), None)
+                    }
+                    DisplaySpec::MackieVPotRing { ring } => {
+                        let msg: RawShortMessage = ring.feedback(feedback_value.clone())?;
+                        let (b1, b2, b3) = msg.to_bytes();
+                        let event =
+                            RawMidiEvent::try_from_slice(0, &[b1, b2.get(), b3.get()]).ok()?;
+                        (create_raw_midi_events_singleton(event), None)
+                    }
                     DisplaySpec::LaunchpadProScrollingText => {
                         let body = filter_ascii_chars(&value.text);
                         let color = style.color.unwrap_or(RgbColor::WHITE);
@@ -1168,9 +2155,20 @@ where
             ClockTransport { .. } => {
                 return Err("clock transport sources have just one possible control value");
             }
+            TimeCode
+            | MachineControl {
+                command: MmcCommand::Locate,
+                ..
+            } => format_time_code_position(value.to_unit_value()?),
+            MachineControl { .. } => {
+                return Err("MMC command sources have just one possible control value");
+            }
             Script { .. } | Display { .. } => {
                 format_percentage_without_unit(value.to_unit_value()?.get())
             }
+            UmpValue { kind, .. } => {
+                denormalize_discrete(value.to_absolute_value()?, kind.max_value()).to_string()
+            }
             _ => self
                 .convert_control_value_to_midi_value(value.to_unit_value()?)?
                 .to_string(),
@@ -1190,7 +2188,17 @@ where
             ClockTransport { .. } => {
                 return Err("parsing doesn't make sense for clock transport MIDI source");
             }
+            TimeCode | MachineControl { .. } => {
+                return Err("parsing not supported for time code and MMC MIDI sources")
+            }
             Script { .. } | Display { .. } => parse_percentage_without_unit(text)?.try_into()?,
+            UmpValue { kind, .. } => {
+                let midi_value: u32 = text.parse().map_err(|_| "not a valid integer")?;
+                if midi_value > kind.max_value() {
+                    return Err("value too high");
+                }
+                Fraction::new(midi_value, kind.max_value()).to_unit_value()
+            }
             _ => {
                 let midi_value: i32 = text.parse().map_err(|_| "not a valid integer")?;
                 self.convert_midi_value_to_control_value(midi_value)?
@@ -1207,6 +2215,8 @@ where
                 custom_character, ..
             } | MidiSource::ControlChange14BitValue {
               custom_character, ..
+            } | MidiSource::ControlChangePairValue {
+              custom_character, ..
             } | MidiSource::ParameterNumberValue {
               custom_character, ..
             } if custom_character.emits_increments()
@@ -1235,8 +2245,27 @@ where
             | ProgramChangeNumber { .. }
             | ChannelPressureAmount { .. }
             | ControlChangeValue { .. } => denormalize_7_bit(value),
-            PitchBendChangeValue { .. } => denormalize_14_bit_centered::<i32>(value) - 8192,
-            ControlChange14BitValue { .. } => denormalize_14_bit(value),
+            PitchBendChangeValue { .. }
+            | MpeValue {
+                dimension: MpeDimension::PitchBend,
+                ..
+            } => denormalize_14_bit_centered::<i32>(value) - 8192,
+            MpeValue { .. } => denormalize_7_bit(value),
+            ControlChange14BitValue { .. } | ControlChangePairValue { .. } | SongPosition => {
+                denormalize_14_bit(value)
+            }
+            NoteKeyZone {
+                key_number_interval,
+                ..
+            } => {
+                let min = key_number_interval.min_val().get() as u32;
+                let span = key_number_interval.max_val().get() as u32 - min;
+                (min + denormalize_discrete(value, span)) as i32
+            }
+            ChannelNumber { .. } => denormalize_discrete(value, MAX_CHANNEL) as i32,
+            BankProgramChangeNumber { .. } => {
+                denormalize_discrete(value, BankProgramChangeMessage::MAX_VALUE) as i32
+            }
             ParameterNumberValue { is_14_bit, .. } => match *is_14_bit {
                 None => return Err("not clear if 7- or 14-bit"),
                 Some(is_14_bit) => {
@@ -1250,11 +2279,15 @@ where
             Raw { pattern, .. } => v.to_discrete(pattern.max_discrete_value()) as _,
             ClockTempo
             | ClockTransport { .. }
+            | TimeCode
+            | MachineControl { .. }
             | SpecificProgramChange { .. }
             | Script { .. }
             | Display { .. } => {
                 return Err("not supported");
             }
+            // Doesn't fit into i32
+            UmpValue { .. } => return Err("not supported"),
         };
         Ok(midi_value)
     }
@@ -1273,12 +2306,42 @@ where
             ControlChangeValue { .. } => {
                 normalize_7_bit(U7::try_from(value).map_err(|_| "value not 7-bit")?)
             }
-            PitchBendChangeValue { .. } => normalize_14_bit_centered(
+            PitchBendChangeValue { .. }
+            | MpeValue {
+                dimension: MpeDimension::PitchBend,
+                ..
+            } => normalize_14_bit_centered(
                 U14::try_from(value + 8192).map_err(|_| "value not 14-bit")?,
             ),
-            ControlChange14BitValue { .. } => {
+            MpeValue { .. } => normalize_7_bit(U7::try_from(value).map_err(|_| "value not 7-bit")?),
+            ControlChange14BitValue { .. } | ControlChangePairValue { .. } | SongPosition => {
                 normalize_14_bit(U14::try_from(value).map_err(|_| "value not 14-bit")?)
             }
+            NoteKeyZone {
+                key_number_interval,
+                ..
+            } => {
+                let key_number = KeyNumber::try_from(value).map_err(|_| "value not 7-bit")?;
+                if !key_number_interval.contains(key_number) {
+                    return Err("key not in zone");
+                }
+                let (offset, span) = key_zone_offset_and_span(*key_number_interval, key_number);
+                Fraction::new(offset, span)
+            }
+            ChannelNumber { .. } => {
+                let channel = u8::try_from(value)
+                    .ok()
+                    .and_then(|v| Channel::try_from(v).ok())
+                    .ok_or("value not a channel")?;
+                Fraction::new(channel.get() as u32, MAX_CHANNEL)
+            }
+            BankProgramChangeNumber { .. } => {
+                let max = BankProgramChangeMessage::MAX_VALUE;
+                match u32::try_from(value) {
+                    Ok(v) if v <= max => Fraction::new(v, max),
+                    _ => return Err("value not a valid bank/program combination"),
+                }
+            }
             ParameterNumberValue { is_14_bit, .. } => match *is_14_bit {
                 None => return Err("not clear if 7- or 14-bit"),
                 Some(is_14_bit) => {
@@ -1297,11 +2360,15 @@ where
             }
             ClockTempo
             | ClockTransport { .. }
+            | TimeCode
+            | MachineControl { .. }
             | SpecificProgramChange { .. }
             | Script { .. }
             | Display { .. } => {
                 return Err("not supported");
             }
+            // Doesn't fit into i32
+            UmpValue { .. } => return Err("not supported"),
         };
         Ok(unit_value.to_unit_value())
     }
@@ -1314,14 +2381,42 @@ where
             | ProgramChangeNumber { .. }
             | ChannelPressureAmount { .. }
             | NoteKeyNumber { .. } => Some(127),
-            ControlChange14BitValue { .. } | PitchBendChangeValue { .. } => Some(16383),
+            ControlChange14BitValue { .. }
+            | ControlChangePairValue { .. }
+            | PitchBendChangeValue { .. }
+            | SongPosition => Some(16383),
+            BankProgramChangeNumber { .. } => Some(BankProgramChangeMessage::MAX_VALUE),
+            ChannelNumber { .. } => Some(MAX_CHANNEL),
+            NoteKeyZone {
+                key_number_interval,
+                ..
+            } => Some(
+                (key_number_interval.max_val().get() - key_number_interval.min_val().get()) as u32,
+            ),
+            MpeValue { dimension, .. } => match dimension {
+                MpeDimension::PitchBend => Some(16383),
+                MpeDimension::Pressure | MpeDimension::Slide => Some(127),
+            },
+            UmpValue {
+                custom_character,
+                kind,
+                ..
+            } => {
+                if custom_character.emits_increments() {
+                    None
+                } else {
+                    Some(kind.max_value())
+                }
+            }
             ControlChangeValue {
-                custom_character, ..
+                custom_character,
+                value_interval,
+                ..
             } => {
                 if custom_character.emits_increments() {
                     None
                 } else {
-                    Some(127)
+                    Some(interval_min_and_span(*value_interval).1)
                 }
             }
             ParameterNumberValue {
@@ -1339,12 +2434,15 @@ where
             }
             ClockTempo
             | ClockTransport { .. }
+            | TimeCode
+            | MachineControl { .. }
             | SpecificProgramChange { .. }
             | Script { .. }
             | Display { .. } => None,
             Raw {
                 custom_character,
                 pattern,
+                ..
             } => {
                 if custom_character.emits_increments() {
                     None
@@ -1356,6 +2454,60 @@ where
     }
 }
 
+/// Shapes the given note-on velocity. Releases (zero) are left alone.
+fn apply_velocity_curve(curve: &VelocityCurve, velocity: U7) -> Fraction {
+    let f = normalize_7_bit(velocity);
+    if curve.is_linear() || velocity == U7::MIN {
+        return f;
+    }
+    let uv = curve.apply(f.to_unit_value());
+    let actual = (uv.get() * f.max_val() as f64).round() as u32;
+    // A press must stay a press
+    f.with_actual(actual.max(1))
+}
+
+/// Like [`denormalize_7_bit`] but if a device palette is given, "on" values with a color are
+/// translated into the velocity of the closest palette color. Values without color are translated
+/// according to the device's conventions for single-color LEDs, if it has any.
+fn denormalize_7_bit_with_palette(
+    value: NumericFeedbackValue,
+    palette: Option<&DevicePalette>,
+    animation: AkaiLedAnimation,
+) -> U7 {
+    if let Some(palette) = palette {
+        let special_velocity = match value.style.color {
+            Some(color) if value.value.is_on() => palette.velocity_for_color(color),
+            Some(_) => None,
+            None => palette.single_color_value(value.value.is_on(), animation),
+        };
+        if let Some(velocity) = special_velocity {
+            return velocity;
+        }
+    }
+    denormalize_7_bit(value.value)
+}
+
+/// If the device palette supports exact RGB colors, returns the message which lights the LED with
+/// the given index in the color of the given "on" value.
+fn feedback_exact_rgb<M: ShortMessage, A>(
+    value: &NumericFeedbackValue,
+    led_index: u8,
+    address: MidiSourceAddress,
+    context: &SourceContext<A>,
+) -> Option<MidiSourceValue<'static, M>> {
+    let color = value.style.color?;
+    if !value.value.is_on() || !context.supports_rgb_color() {
+        return None;
+    }
+    let bytes = context
+        .device_palette
+        .as_ref()?
+        .rgb_message(led_index, color)?;
+    let event = RawMidiEvent::try_from_slice(0, &bytes).ok()?;
+    let address_info = RawFeedbackAddressInfo::Custom(address);
+    Some(MidiSourceValue::single_raw(Some(address_info), event))
+}
+
 fn feedback_mackie_lcd<'a, 'b>(
     value: &'a TextualFeedbackValue,
     scope: &'b MackieLcdScope,
@@ -1374,6 +2526,41 @@ where
     })
 }
 
+fn feedback_generic_lcd(
+    value: &TextualFeedbackValue,
+    layout: &GenericLcdLayout,
+    scope: &GenericLcdScope,
+) -> RawMidiEvents {
+    let mut ascii_chars = filter_ascii_chars(&value.text);
+    let max_chunk_len = layout.max_chunk_len.unwrap_or(u8::MAX).max(1);
+    scope
+        .lcd_portions(layout)
+        .into_iter()
+        .flat_map(|range| {
+            let end = range.end;
+            range
+                .step_by(max_chunk_len as usize)
+                .map(move |start| start..end.min(start.saturating_add(max_chunk_len)))
+        })
+        .filter_map(|chunk| {
+            // Characters must be consumed even if the chunk can't be addressed
+            let body: Vec<_> = chunk
+                .clone()
+                .map(|_| ascii_chars.next().unwrap_or(ASCII_SPACE))
+                .collect();
+            if chunk.start > 0x7f {
+                return None;
+            }
+            let sysex = iter::once(0xF0)
+                .chain(layout.sysex_header.iter().copied())
+                .chain(iter::once(chunk.start))
+                .chain(body)
+                .chain(end());
+            RawMidiEvent::try_from_iter(0, sysex).ok()
+        })
+        .collect()
+}
+
 pub enum ControlResult {
     /// The value is consumed but doesn't emit a control value.
     ///
@@ -1394,13 +2581,27 @@ fn matches<T: PartialEq + Eq>(actual_value: T, configured_value: Option<T>) -> b
     }
 }
 
+/// Converts the given data entry value from the detected resolution to the desired one.
+fn convert_data_entry_resolution(value: U14, is_14_bit: bool, to_14_bit: bool) -> U14 {
+    match (is_14_bit, to_14_bit) {
+        // Data entry MSB only, so it becomes the MSB of the 14-bit value
+        (false, true) => U14::new(value.get() << 7),
+        // Ignore data entry LSB
+        (true, false) => U14::new(value.get() >> 7),
+        _ => value,
+    }
+}
+
 /// Returns an error if the source character is relative (one of the encoders types) but the
 /// value is neutral (neither an increment nor a decrement), in which case you can discard the
 /// value.
+///
+/// Compensates the encoder quirks of the given device quirks.
 fn calc_control_value_from_n_bit_cc<T: Into<u32>>(
     character: SourceCharacter,
     cc_control_value: T,
     resolution: u32,
+    quirks: DeviceQuirks,
 ) -> Result<ControlValue, &'static str> {
     use SourceCharacter::*;
     let cc_control_value = cc_control_value.into();
@@ -1409,19 +2610,69 @@ fn calc_control_value_from_n_bit_cc<T: Into<u32>>(
         Encoder1 | Encoder2 | Encoder3 => {
             let value_7_bit = extract_low_7_bit(cc_control_value);
             let increment = match character {
+                _ if quirks.encoder_sends_zero_for_decrement && value_7_bit == U7::MIN => {
+                    DiscreteIncrement::NEGATIVE_MIN
+                }
                 Encoder1 => DiscreteIncrement::from_encoder_1_value(value_7_bit)?,
                 Encoder2 => DiscreteIncrement::from_encoder_2_value(value_7_bit)?,
                 Encoder3 => DiscreteIncrement::from_encoder_3_value(value_7_bit)?,
                 _ => unreachable!("impossible"),
             };
-            rel(increment)
+            if quirks.inverted_encoder_direction {
+                rel(increment.inverse())
+            } else {
+                rel(increment)
+            }
         }
         ToggleButton => abs(max_n_bit_fraction(resolution)),
+        ToggleSwitch => abs(to_switch_state(normalize_n_bit(
+            cc_control_value,
+            resolution,
+        ))),
+    };
+    Ok(result)
+}
+
+/// Like [`calc_control_value_from_n_bit_cc`] but normalizes absolute values to the given range.
+///
+/// The value must be within the range.
+fn calc_control_value_from_7_bit_cc_within(
+    character: SourceCharacter,
+    cc_control_value: U7,
+    interval: Interval<U7>,
+    quirks: DeviceQuirks,
+) -> Result<ControlValue, &'static str> {
+    if interval == full_7_bit_interval() || character.emits_increments() {
+        return calc_control_value_from_n_bit_cc(character, cc_control_value, 7, quirks);
+    }
+    let (min, span) = interval_min_and_span(interval);
+    if span == 0 {
+        // Just one value. Act like a trigger.
+        return Ok(ControlValue::AbsoluteContinuous(UnitValue::MAX));
+    }
+    let fraction = Fraction::new(cc_control_value.get() as u32 - min, span);
+    use SourceCharacter::*;
+    let result = match character {
+        RangeElement | MomentaryButton => abs(fraction),
+        ToggleButton => abs(fraction.with_actual(span)),
+        ToggleSwitch => abs(to_switch_state(fraction)),
+        Encoder1 | Encoder2 | Encoder3 => unreachable!("handled above"),
     };
     Ok(result)
 }
 
+/// Turns any value greater than zero into the maximum value.
+fn to_switch_state(value: Fraction) -> Fraction {
+    if value.actual() > 0 {
+        value.with_actual(value.max_val())
+    } else {
+        value
+    }
+}
+
 const MIN_U7_FRACTION: Fraction = Fraction::new_min(U7::MAX.get() as _);
+const MAX_CHANNEL: u32 = 15;
+const PITCH_BEND_CENTER: u16 = 8192;
 
 fn normalize_7_bit<T: Into<u32>>(value: T) -> Fraction {
     normalize_n_bit(value, 7)
@@ -1447,6 +2698,29 @@ fn normalize_14_bit_centered(value: U14) -> Fraction {
     Fraction::new(value.into(), U14::MAX.get() as u32 + 1)
 }
 
+/// Maps the lower and upper half separately, so that the center is exactly 0.5.
+fn normalize_14_bit_bipolar(value: U14) -> UnitValue {
+    let value = value.get() as f64;
+    let center = PITCH_BEND_CENTER as f64;
+    let normalized = if value < center {
+        0.5 * value / center
+    } else {
+        0.5 + 0.5 * (value - center) / (U14::MAX.get() as f64 - center)
+    };
+    UnitValue::new_clamped(normalized)
+}
+
+fn denormalize_14_bit_bipolar(value: UnitValue) -> U14 {
+    let value = value.get();
+    let center = PITCH_BEND_CENTER as f64;
+    let denormalized = if value < 0.5 {
+        value * 2.0 * center
+    } else {
+        center + (value - 0.5) * 2.0 * (U14::MAX.get() as f64 - center)
+    };
+    U14::new((denormalized.round() as u16).min(U14::MAX.get()))
+}
+
 fn denormalize_7_bit<T: From<U7>>(value: AbsoluteValue) -> T {
     match value {
         AbsoluteValue::Continuous(v) => {
@@ -1490,6 +2764,51 @@ fn denormalize_14_bit_centered<T: From<U14>>(value: AbsoluteValue) -> T {
     }
 }
 
+fn time_code_control_value(time_code: MidiTimeCode) -> ControlValue {
+    let position = time_code.to_seconds() / MIDI_TIME_CODE_MAX_SECONDS;
+    ControlValue::AbsoluteContinuous(UnitValue::new_clamped(position))
+}
+
+fn format_time_code_position(value: UnitValue) -> String {
+    let millis = (value.get() * MIDI_TIME_CODE_MAX_SECONDS * 1000.0).round() as u64;
+    format!(
+        "{}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+/// Returns the position of the given key within the given zone and the span of the zone.
+fn key_zone_offset_and_span(zone: Interval<KeyNumber>, key_number: KeyNumber) -> (u32, u32) {
+    let min = zone.min_val().get() as u32;
+    let offset = key_number.get() as u32 - min;
+    let span = zone.max_val().get() as u32 - min;
+    (offset, span)
+}
+
+fn full_key_number_interval() -> Interval<KeyNumber> {
+    Interval::new(KeyNumber::MIN, KeyNumber::MAX)
+}
+
+/// Value interval which doesn't restrict anything.
+pub fn full_7_bit_interval() -> Interval<U7> {
+    Interval::new(U7::MIN, U7::MAX)
+}
+
+fn interval_min_and_span(interval: Interval<U7>) -> (u32, u32) {
+    let min = interval.min_val().get() as u32;
+    (min, interval.max_val().get() as u32 - min)
+}
+
+fn denormalize_discrete(value: AbsoluteValue, max: u32) -> u32 {
+    match value {
+        AbsoluteValue::Continuous(v) => (v.get() * max as f64).round() as u32,
+        AbsoluteValue::Discrete(f) => std::cmp::min(f.actual(), max),
+    }
+}
+
 const fn abs(value: Fraction) -> ControlValue {
     ControlValue::AbsoluteDiscrete(value)
 }
@@ -1595,20 +2914,15 @@ fn launchpad_pro_scrolling_text_sysex(
     looped: bool,
     body: impl Iterator<Item = u8>,
 ) -> impl Iterator<Item = u8> {
-    let color_code =
-        find_closest_color_in_palette(color, &super::devices::launchpad::COLOR_PALETTE);
-    let start = [
-        0xF0,
-        0x00,
-        0x20,
-        0x29,
-        0x02,
-        0x10,
-        0x14,
-        color_code,
-        u8::from(looped),
-    ];
-    start.into_iter().chain(body).chain(end())
+    let model = LaunchpadModel::Pro;
+    let color_code = model.closest_palette_index(color);
+    let start = [0x14, color_code, u8::from(looped)];
+    model
+        .sysex_header()
+        .into_iter()
+        .chain(start)
+        .chain(body)
+        .chain(end())
 }
 
 fn filter_ascii_chars(text: &str) -> impl Iterator<Item = u8> + '_ {
@@ -1659,13 +2973,16 @@ pub enum DisplayType {
     #[serde(rename = "sl-keyboard")]
     #[display(fmt = "Studiologic SL Keyboard display")]
     SlKeyboardDisplay,
+    #[serde(rename = "mackie-v-pot-ring")]
+    #[display(fmt = "Mackie V-Pot ring")]
+    MackieVPotRing,
 }
 
 impl DisplayType {
     pub fn display_count(self) -> u8 {
         use DisplayType::*;
         match self {
-            MackieLcd | MackieXtLcd | XTouchMackieLcd | XTouchMackieXtLcd => {
+            MackieLcd | MackieXtLcd | XTouchMackieLcd | XTouchMackieXtLcd | MackieVPotRing => {
                 MackieLcdScope::CHANNEL_COUNT
             }
             SiniConE24 => SiniConE24Scope::CELL_COUNT,
@@ -1684,7 +3001,7 @@ impl DisplayType {
             SiniConE24 => SiniConE24Scope::ITEM_COUNT,
             SlKeyboardDisplay => 2,
             // Not applicable
-            MackieSevenSegmentDisplay | LaunchpadProScrollingText => 1,
+            MackieSevenSegmentDisplay | LaunchpadProScrollingText | MackieVPotRing => 1,
         }
     }
 }
@@ -1703,6 +3020,10 @@ pub enum DisplaySpec {
     MackieSevenSegmentDisplay {
         scope: MackieSevenSegmentDisplayScope,
     },
+    /// LED ring around a V-Pot, takes numeric feedback.
+    MackieVPotRing {
+        ring: MackieVPotRingFeedback,
+    },
     SlKeyboard {
         scope: SlKeyboardDisplayScope,
     },
@@ -1712,6 +3033,10 @@ pub enum DisplaySpec {
         last_sent_background_color: Cell<Option<RgbColor>>,
     },
     LaunchpadProScrollingText,
+    GenericLcd {
+        layout: GenericLcdLayout,
+        scope: GenericLcdScope,
+    },
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
@@ -1723,6 +3048,9 @@ pub enum DisplaySpecAddress {
     MackieSevenSegmentDisplay {
         scope: MackieSevenSegmentDisplayScope,
     },
+    MackieVPotRing {
+        channel_index: u8,
+    },
     SlKeyboardDisplay {
         scope: SlKeyboardDisplayScope,
     },
@@ -1733,6 +3061,10 @@ pub enum DisplaySpecAddress {
     XTouchMackieLcdColors {
         extender_index: u8,
     },
+    GenericLcd {
+        sysex_header: Vec<u8>,
+        scope: GenericLcdScope,
+    },
 }
 
 impl From<DisplaySpec> for DisplaySpecAddress {
@@ -1751,9 +3083,16 @@ impl From<DisplaySpec> for DisplaySpecAddress {
                 extender_index,
             },
             MackieSevenSegmentDisplay { scope } => Self::MackieSevenSegmentDisplay { scope },
+            MackieVPotRing { ring } => Self::MackieVPotRing {
+                channel_index: ring.channel_index,
+            },
             SiniConE24 { scope, .. } => Self::SiniConE24 { scope },
             LaunchpadProScrollingText => Self::LaunchpadProScrollingText,
             SlKeyboard { scope } => Self::SlKeyboardDisplay { scope },
+            GenericLcd { layout, scope } => Self::GenericLcd {
+                sysex_header: layout.sysex_header,
+                scope,
+            },
         }
     }
 }
@@ -1828,27 +3167,88 @@ impl MackieLcdScope {
     }
 
     pub fn lcd_portions(&self) -> LcdPortions {
-        fn range(start: u8, len: u8) -> Range<u8> {
-            start..(start + len)
+        calc_lcd_portions(
+            self.channel,
+            self.line,
+            Self::CHANNEL_LEN,
+            Self::LINE_LEN,
+            Self::LINE_COUNT,
+        )
+    }
+}
+
+/// Describes a text display which is addressed like the Mackie Control LCD (a sys-ex message per
+/// contiguous portion of characters, starting with the offset of the first character) but has
+/// a different sys-ex header and dimensions.
+///
+/// The display can't have more than 128 characters because the offset is sent as one data byte.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct GenericLcdLayout {
+    /// Bytes between `F0` and the offset, e.g. `00 00 66 14 12` for the Mackie Control LCD.
+    pub sysex_header: Vec<u8>,
+    pub channel_count: u8,
+    pub channel_len: u8,
+    pub line_count: u8,
+    /// Maximum number of characters per sys-ex message. Longer portions are split into several
+    /// messages.
+    pub max_chunk_len: Option<u8>,
+}
+
+impl GenericLcdLayout {
+    pub fn line_len(&self) -> u8 {
+        self.channel_count.saturating_mul(self.channel_len)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct GenericLcdScope {
+    pub channel: Option<u8>,
+    pub line: Option<u8>,
+}
+
+impl GenericLcdScope {
+    pub fn new(layout: &GenericLcdLayout, channel: Option<u8>, line: Option<u8>) -> Self {
+        Self {
+            channel: channel.map(|ch| ch.min(layout.channel_count.saturating_sub(1))),
+            line: line.map(|l| l.min(layout.line_count.saturating_sub(1))),
         }
-        let ranges = match (self.channel, self.line) {
-            (None, None) => vec![range(0, Self::LINE_COUNT * Self::LINE_LEN)],
-            (None, Some(l)) => vec![range(l * Self::LINE_LEN, Self::LINE_LEN)],
-            (Some(ch), None) => (0..Self::LINE_COUNT)
-                .map(|l| {
-                    range(
-                        l * Self::LINE_LEN + ch * Self::CHANNEL_LEN,
-                        Self::CHANNEL_LEN,
-                    )
-                })
-                .collect(),
-            (Some(ch), Some(l)) => vec![range(
-                l * Self::LINE_LEN + ch * Self::CHANNEL_LEN,
-                Self::CHANNEL_LEN,
-            )],
-        };
-        LcdPortions::new(ranges)
     }
+
+    pub fn lcd_portions(&self, layout: &GenericLcdLayout) -> LcdPortions {
+        calc_lcd_portions(
+            self.channel,
+            self.line,
+            layout.channel_len,
+            layout.line_len(),
+            layout.line_count,
+        )
+    }
+}
+
+fn calc_lcd_portions(
+    channel: Option<u8>,
+    line: Option<u8>,
+    channel_len: u8,
+    line_len: u8,
+    line_count: u8,
+) -> LcdPortions {
+    // Saturating because generic layouts could be misconfigured
+    fn range(start: u8, len: u8) -> Range<u8> {
+        start..start.saturating_add(len)
+    }
+    let offset = |ch: u8, l: u8| {
+        l.saturating_mul(line_len)
+            .saturating_add(ch.saturating_mul(channel_len))
+    };
+    let ranges = match (channel, line) {
+        (None, None) => vec![range(0, line_count.saturating_mul(line_len))],
+        (None, Some(l)) => vec![range(offset(0, l), line_len)],
+        (Some(ch), None) => (0..line_count)
+            .map(|l| range(offset(ch, l), channel_len))
+            .collect(),
+        (Some(ch), Some(l)) => vec![range(offset(ch, l), channel_len)],
+    };
+    LcdPortions::new(ranges)
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
@@ -2065,11 +3465,17 @@ const ASCII_SPACE: u8 = b' ';
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::devices::akai::AkaiModel;
+    use crate::devices::DeviceQuirks;
     use crate::source::test_util::TestMidiSourceScript;
-    use crate::NumericFeedbackValue;
+    use crate::{
+        ControlChange14BitPairer, DeviceCapabilities, FeedbackStyle, MackieVPotRingMode,
+        MpeZoneKind, MtcFrameRate,
+    };
     use approx::*;
     use helgoboss_midi::test_util::{channel as ch, controller_number as cn, key_number as kn, *};
-    use helgoboss_midi::RawShortMessage;
+    use helgoboss_midi::{DataEntryByteOrder, RawShortMessage};
+    use std::time::{Duration, Instant};
 
     type TestMidiSource = MidiSource<TestMidiSourceScript>;
 
@@ -2079,6 +3485,10 @@ mod tests {
         let source = TestMidiSource::NoteVelocity {
             channel: Some(ch(0)),
             key_number: None,
+            velocity_curve: VelocityCurve::Linear,
+            note_off_policy: Default::default(),
+            velocity_interval: full_7_bit_interval(),
+            velocity_filtered_notes: Default::default(),
         };
         // When
         // Then
@@ -2155,6 +3565,10 @@ mod tests {
         let source = TestMidiSource::NoteVelocity {
             channel: Some(ch(4)),
             key_number: Some(kn(20)),
+            velocity_curve: VelocityCurve::Linear,
+            note_off_policy: Default::default(),
+            velocity_interval: full_7_bit_interval(),
+            velocity_filtered_notes: Default::default(),
         };
         // When
         // Then
@@ -2179,11 +3593,210 @@ mod tests {
     }
 
     #[test]
-    fn note_key_number_1() {
+    fn note_velocity_with_curve() {
         // Given
-        let source = TestMidiSource::NoteKeyNumber { channel: None };
-        // When
-        // Then
+        let source = TestMidiSource::NoteVelocity {
+            channel: Some(ch(4)),
+            key_number: Some(kn(20)),
+            velocity_curve: VelocityCurve::Hard,
+            note_off_policy: Default::default(),
+            velocity_interval: full_7_bit_interval(),
+            velocity_filtered_notes: Default::default(),
+        };
+        // When
+        // Then
+        assert_eq!(
+            source.control(&plain(note_on(4, 20, 64))).unwrap(),
+            frac(32, 127)
+        );
+        assert_eq!(
+            source.control(&plain(note_on(4, 20, 1))).unwrap(),
+            frac(1, 127)
+        );
+        assert_eq!(
+            source.control(&plain(note_on(4, 20, 0))).unwrap(),
+            frac(0, 127)
+        );
+        assert_eq!(
+            source.test_feedback::<RawShortMessage>(fv(0.25)),
+            Some(plain(note_on(4, 20, 64)))
+        );
+    }
+
+    #[test]
+    fn note_velocity_with_velocity_interval() {
+        // Given
+        let source = TestMidiSource::NoteVelocity {
+            channel: Some(ch(0)),
+            key_number: Some(kn(36)),
+            velocity_curve: VelocityCurve::Linear,
+            note_off_policy: Default::default(),
+            velocity_interval: Interval::new(U7::new(100), U7::MAX),
+            velocity_filtered_notes: Default::default(),
+        };
+        // When
+        // Then
+        assert_eq!(source.control(&plain(note_on(0, 36, 50))), None);
+        assert_eq!(
+            source.control(&plain(note_on(0, 36, 110))),
+            Some(frac(110, 127))
+        );
+        assert_eq!(
+            source.control(&plain(note_off(0, 36, 0))),
+            Some(frac(0, 127))
+        );
+    }
+
+    #[test]
+    fn note_velocity_with_velocity_interval_ignores_releases_of_ignored_presses() {
+        // Given
+        let source = TestMidiSource::NoteVelocity {
+            channel: None,
+            key_number: None,
+            velocity_curve: VelocityCurve::Linear,
+            note_off_policy: Default::default(),
+            velocity_interval: Interval::new(U7::new(100), U7::MAX),
+            velocity_filtered_notes: Default::default(),
+        };
+        // When
+        // Then
+        // Soft hit on the hard layer, released via note-off
+        assert_eq!(source.control(&plain(note_on(0, 36, 50))), None);
+        assert_eq!(source.control(&plain(note_off(0, 36, 0))), None);
+        // Soft hit on the hard layer, released via zero-velocity note-on
+        assert_eq!(source.control(&plain(note_on(0, 36, 50))), None);
+        assert_eq!(source.control(&plain(note_on(0, 36, 0))), None);
+        // Releases of other notes are not affected
+        assert_eq!(source.control(&plain(note_on(1, 36, 50))), None);
+        assert_eq!(
+            source.control(&plain(note_off(0, 36, 0))),
+            Some(frac(0, 127))
+        );
+        assert_eq!(
+            source.control(&plain(note_off(1, 37, 0))),
+            Some(frac(0, 127))
+        );
+        assert_eq!(source.control(&plain(note_off(1, 36, 0))), None);
+        // Hard hit
+        assert_eq!(
+            source.control(&plain(note_on(0, 36, 110))),
+            Some(frac(110, 127))
+        );
+        assert_eq!(
+            source.control(&plain(note_on(0, 36, 0))),
+            Some(frac(0, 127))
+        );
+    }
+
+    #[test]
+    fn control_change_value_with_value_interval() {
+        // Given
+        let lower = TestMidiSource::ControlChangeValue {
+            channel: Some(ch(0)),
+            controller_number: Some(cn(60)),
+            custom_character: SourceCharacter::RangeElement,
+            value_interval: Interval::new(U7::MIN, U7::new(63)),
+        };
+        let upper = TestMidiSource::ControlChangeValue {
+            channel: Some(ch(0)),
+            controller_number: Some(cn(60)),
+            custom_character: SourceCharacter::RangeElement,
+            value_interval: Interval::new(U7::new(64), U7::MAX),
+        };
+        let single = TestMidiSource::ControlChangeValue {
+            channel: Some(ch(0)),
+            controller_number: Some(cn(60)),
+            custom_character: SourceCharacter::MomentaryButton,
+            value_interval: Interval::new(U7::new(5), U7::new(5)),
+        };
+        let encoder = TestMidiSource::ControlChangeValue {
+            channel: Some(ch(0)),
+            controller_number: Some(cn(60)),
+            custom_character: SourceCharacter::Encoder1,
+            value_interval: Interval::new(U7::new(1), U7::new(63)),
+        };
+        // When
+        // Then
+        assert_eq!(
+            lower.control(&plain(control_change(0, 60, 21))),
+            Some(frac(21, 63))
+        );
+        assert_eq!(lower.control(&plain(control_change(0, 60, 100))), None);
+        assert_eq!(upper.control(&plain(control_change(0, 60, 21))), None);
+        assert_eq!(
+            upper.control(&plain(control_change(0, 60, 100))),
+            Some(frac(36, 63))
+        );
+        assert_eq!(
+            single.control(&plain(control_change(0, 60, 5))),
+            Some(ControlValue::AbsoluteContinuous(UnitValue::MAX))
+        );
+        assert_eq!(single.control(&plain(control_change(0, 60, 6))), None);
+        assert_eq!(
+            encoder.control(&plain(control_change(0, 60, 3))),
+            Some(rel(3))
+        );
+        assert_eq!(encoder.control(&plain(control_change(0, 60, 125))), None);
+        assert_eq!(
+            upper.test_feedback::<RawShortMessage>(fv(1.0)),
+            Some(plain(control_change(0, 60, 127)))
+        );
+        assert_eq!(
+            upper.test_feedback::<RawShortMessage>(fv(0.0)),
+            Some(plain(control_change(0, 60, 64)))
+        );
+    }
+
+    #[test]
+    fn note_velocity_with_note_off_policy() {
+        // Given
+        let source =
+            |zero_velocity_note_on, forward_note_off_velocity| TestMidiSource::NoteVelocity {
+                channel: Some(ch(4)),
+                key_number: Some(kn(20)),
+                velocity_curve: VelocityCurve::Linear,
+                note_off_policy: NoteOffPolicy {
+                    zero_velocity_note_on,
+                    forward_note_off_velocity,
+                },
+                velocity_interval: full_7_bit_interval(),
+                velocity_filtered_notes: Default::default(),
+            };
+        let release = source(ZeroVelocityNoteOnBehavior::Release, true);
+        let zero = source(ZeroVelocityNoteOnBehavior::Zero, true);
+        let ignore = source(ZeroVelocityNoteOnBehavior::Ignore, false);
+        // When
+        // Then
+        assert_eq!(
+            release.control(&plain(note_off(4, 20, 30))),
+            Some(frac(30, 127))
+        );
+        assert_eq!(
+            release.control(&plain(note_on(4, 20, 0))),
+            Some(frac(0, 127))
+        );
+        assert_eq!(
+            zero.control(&plain(note_off(4, 20, 30))),
+            Some(frac(30, 127))
+        );
+        assert_eq!(zero.control(&plain(note_on(4, 20, 0))), Some(frac(0, 127)));
+        assert_eq!(ignore.control(&plain(note_on(4, 20, 0))), None);
+        assert_eq!(
+            ignore.control(&plain(note_off(4, 20, 30))),
+            Some(frac(0, 127))
+        );
+        assert_eq!(
+            ignore.control(&plain(note_on(4, 20, 100))),
+            Some(frac(100, 127))
+        );
+    }
+
+    #[test]
+    fn note_key_number_1() {
+        // Given
+        let source = TestMidiSource::NoteKeyNumber { channel: None };
+        // When
+        // Then
         assert_abs_diff_eq!(
             source
                 .control(&plain(note_on(0, 127, 55,)))
@@ -2381,6 +3994,7 @@ mod tests {
             channel: Some(ch(1)),
             controller_number: None,
             custom_character: SourceCharacter::RangeElement,
+            value_interval: full_7_bit_interval(),
         };
         // When
         // Then
@@ -2434,6 +4048,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn control_change_value_toggle_switch() {
+        // Given
+        let source = TestMidiSource::ControlChangeValue {
+            channel: Some(ch(1)),
+            controller_number: Some(cn(64)),
+            custom_character: SourceCharacter::ToggleSwitch,
+            value_interval: full_7_bit_interval(),
+        };
+        // When
+        // Then
+        assert_eq!(
+            source.control(&plain(control_change(1, 64, 100))),
+            Some(frac(127, 127))
+        );
+        assert_eq!(
+            source.control(&plain(control_change(1, 64, 0))),
+            Some(frac(0, 127))
+        );
+        assert_eq!(
+            source.possible_detailed_characters(),
+            vec![DetailedSourceCharacter::RangeControl]
+        );
+    }
+
     #[test]
     fn control_change_value_2() {
         // Given
@@ -2441,6 +4080,7 @@ mod tests {
             channel: Some(ch(1)),
             controller_number: Some(cn(64)),
             custom_character: SourceCharacter::Encoder2,
+            value_interval: full_7_bit_interval(),
         };
         // When
         // Then
@@ -2677,7 +4317,10 @@ mod tests {
     #[test]
     fn pitch_bend_change_value_1() {
         // Given
-        let source = TestMidiSource::PitchBendChangeValue { channel: None };
+        let source = TestMidiSource::PitchBendChangeValue {
+            channel: None,
+            is_bipolar: false,
+        };
         // When
         // Then
         assert_eq!(source.control(&plain(note_on(0, 127, 55,))), None);
@@ -2787,6 +4430,7 @@ mod tests {
         // Given
         let source = TestMidiSource::PitchBendChangeValue {
             channel: Some(ch(3)),
+            is_bipolar: false,
         };
         // When
         // Then
@@ -2825,6 +4469,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pitch_bend_change_value_bipolar() {
+        // Given
+        let source = TestMidiSource::PitchBendChangeValue {
+            channel: Some(ch(0)),
+            is_bipolar: true,
+        };
+        let control = |value| {
+            source
+                .control(&plain(pitch_bend_change(0, value)))
+                .unwrap()
+                .to_absolute_continuous()
+                .unwrap()
+        };
+        // When
+        // Then
+        assert_eq!(control(0), UnitValue::MIN);
+        assert_eq!(control(4096), UnitValue::new(0.25));
+        assert_eq!(control(8192), UnitValue::new(0.5));
+        assert_eq!(control(16383), UnitValue::MAX);
+        assert!(control(8191) < UnitValue::new(0.5));
+        assert!(control(8193) > UnitValue::new(0.5));
+        assert_eq!(
+            source.test_feedback::<RawShortMessage>(fv(0.0)),
+            Some(plain(pitch_bend_change(0, 0)))
+        );
+        assert_eq!(
+            source.test_feedback::<RawShortMessage>(fv(0.5)),
+            Some(plain(pitch_bend_change(0, 8192)))
+        );
+        assert_eq!(
+            source.test_feedback::<RawShortMessage>(fv(1.0)),
+            Some(plain(pitch_bend_change(0, 16383)))
+        );
+    }
+
     #[test]
     fn control_change_14_bit_value_1() {
         // Given
@@ -2832,6 +4512,7 @@ mod tests {
             channel: Some(ch(1)),
             msb_controller_number: None,
             custom_character: Default::default(),
+            feedback_mode: Default::default(),
         };
         // When
         // Then
@@ -2898,6 +4579,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn control_change_14_bit_value_keeps_msb_timestamp() {
+        // Given
+        let source = TestMidiSource::ControlChange14BitValue {
+            channel: Some(ch(1)),
+            msb_controller_number: Some(cn(7)),
+            custom_character: Default::default(),
+            feedback_mode: Default::default(),
+        };
+        let mut pairer = ControlChange14BitPairer::new(Duration::from_millis(10));
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        // When
+        pairer.feed::<RawShortMessage>(control_change(1, 7, 127), at(0));
+        let event = pairer
+            .feed::<RawShortMessage>(control_change(1, 39, 127), at(5))
+            .unwrap();
+        let control_event = source.control_event(&event).unwrap();
+        // Then
+        assert_eq!(control_event.payload(), frac(16383, 16383));
+        assert_eq!(control_event.timestamp(), at(0));
+    }
+
     #[test]
     fn control_change_14_bit_value_2() {
         // Given
@@ -2905,6 +4609,7 @@ mod tests {
             channel: Some(ch(1)),
             msb_controller_number: Some(cn(7)),
             custom_character: Default::default(),
+            feedback_mode: Default::default(),
         };
         // When
         // Then
@@ -2951,6 +4656,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn control_change_pair_value() {
+        // Given
+        let pair = ControlChangePair::new(cn(40), cn(8), DataEntryByteOrder::LsbFirst);
+        let source = TestMidiSource::ControlChangePairValue {
+            channel: Some(ch(1)),
+            pair,
+            custom_character: Default::default(),
+        };
+        let pair_value =
+            |channel, pair, value| {
+                MidiSourceValue::<RawShortMessage>::ControlChangePair(
+                    ControlChangePairMessage::new(ch(channel), pair, U14::new(value)),
+                )
+            };
+        // When
+        // Then
+        assert_eq!(source.control(&pair_value(2, pair, 4096)), None);
+        let other_pair = ControlChangePair::new(cn(40), cn(8), DataEntryByteOrder::MsbFirst);
+        assert_eq!(source.control(&pair_value(1, other_pair, 4096)), None);
+        assert_eq!(source.control(&cc(control_change_14_bit(1, 8, 4096))), None);
+        assert_eq!(
+            source.control(&pair_value(1, pair, 4096)).unwrap(),
+            frac(4096, 16383)
+        );
+        assert!(source.consumes(&control_change(1, 40, 0)));
+        assert!(source.consumes(&control_change(1, 8, 0)));
+        assert!(!source.consumes(&control_change(1, 72, 0)));
+        assert_eq!(
+            source.test_feedback::<RawShortMessage>(fv(0.25)),
+            Some(pair_value(1, pair, 4096))
+        );
+        assert_eq!(
+            pair_value(1, pair, 4096).to_short_messages(DataEntryByteOrder::MsbFirst),
+            [
+                Some(control_change(1, 8, 0)),
+                Some(control_change(1, 40, 32)),
+                None,
+                None
+            ]
+        );
+        assert_eq!(
+            source.format_control_value(frac(4096, 16383)).unwrap(),
+            "4096"
+        );
+    }
+
     #[test]
     fn parameter_number_value_1() {
         // Given
@@ -2959,6 +4711,7 @@ mod tests {
             number: None,
             is_14_bit: None,
             is_registered: None,
+            enforce_resolution: false,
             custom_character: SourceCharacter::RangeElement,
         };
         // When
@@ -3052,6 +4805,7 @@ mod tests {
             number: Some(u14(3000)),
             is_14_bit: Some(false),
             is_registered: Some(true),
+            enforce_resolution: false,
             custom_character: SourceCharacter::RangeElement,
         };
         // When
@@ -3113,6 +4867,7 @@ mod tests {
             number: Some(u14(3000)),
             is_14_bit: Some(false),
             is_registered: Some(true),
+            enforce_resolution: false,
             custom_character: SourceCharacter::ToggleButton,
         };
         // When
@@ -3163,6 +4918,7 @@ mod tests {
             number: Some(u14(3000)),
             is_14_bit: Some(true),
             is_registered: Some(true),
+            enforce_resolution: false,
             custom_character: SourceCharacter::RangeElement,
         };
         // When
@@ -3194,6 +4950,161 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parameter_number_value_enforce_resolution() {
+        // Given
+        let source_14_bit = TestMidiSource::ParameterNumberValue {
+            channel: Some(ch(7)),
+            number: Some(u14(3000)),
+            is_14_bit: Some(true),
+            is_registered: Some(true),
+            enforce_resolution: true,
+            custom_character: SourceCharacter::RangeElement,
+        };
+        let source_7_bit = TestMidiSource::ParameterNumberValue {
+            is_14_bit: Some(false),
+            ..source_14_bit.clone()
+        };
+        // When
+        // Then
+        assert_eq!(
+            source_14_bit.control(&pn(rpn(7, 3000, 64))).unwrap(),
+            frac(8192, 16383)
+        );
+        assert_eq!(
+            source_7_bit
+                .control(&pn(rpn_14_bit(7, 3000, 8192)))
+                .unwrap(),
+            frac(64, 127)
+        );
+        assert_eq!(
+            source_7_bit.test_feedback::<RawShortMessage>(fv(1.0)),
+            Some(pn(rpn(7, 3000, 127)))
+        );
+    }
+
+    #[test]
+    fn ump_value() {
+        // Given
+        let source = TestMidiSource::UmpValue {
+            group: Some(0),
+            channel: Some(ch(5)),
+            kind: UmpMessageKind::ControlChange,
+            index: Some(7),
+            custom_character: SourceCharacter::RangeElement,
+        };
+        let msg = |index, value| {
+            ump(UmpChannelVoiceMessage::new(
+                0,
+                ch(5),
+                UmpMessageKind::ControlChange,
+                index,
+                value,
+            ))
+        };
+        // When
+        // Then
+        assert_eq!(
+            source.control(&msg(7, 0x8000_0001)).unwrap(),
+            frac(0x8000_0001, u32::MAX)
+        );
+        assert_eq!(source.control(&msg(8, 0x8000_0001)), None);
+        assert_eq!(source.control(&plain(control_change(5, 7, 64))), None);
+        assert_eq!(
+            source.test_feedback::<RawShortMessage>(fv(1.0)),
+            Some(msg(7, u32::MAX))
+        );
+        assert_eq!(
+            source.test_feedback::<RawShortMessage>(FeedbackValue::Numeric(
+                NumericFeedbackValue::new(
+                    Default::default(),
+                    AbsoluteValue::Discrete(Fraction::new(0x8000_0001, u32::MAX))
+                )
+            )),
+            Some(msg(7, 0x8000_0001))
+        );
+        assert_eq!(source.max_discrete_value(), Some(u32::MAX));
+        assert_eq!(
+            TestMidiSource::from_source_value(msg(7, 5), None),
+            Some(source)
+        );
+    }
+
+    #[test]
+    fn mpe_value() {
+        // Given
+        let source = TestMidiSource::MpeValue {
+            zone: MpeZone::new(MpeZoneKind::Lower, 7),
+            dimension: MpeDimension::Slide,
+            key_number_interval: Interval::new(kn(48), kn(59)),
+        };
+        let mpe = |key_number, msg| MidiSourceValue::Mpe {
+            key_number: kn(key_number),
+            msg,
+        };
+        // When
+        // Then
+        assert_eq!(
+            source.control(&mpe(50, control_change(3, 74, 127))),
+            Some(frac(127, 127))
+        );
+        assert_eq!(source.control(&mpe(60, control_change(3, 74, 127))), None);
+        assert_eq!(source.control(&mpe(50, control_change(9, 74, 127))), None);
+        assert_eq!(source.control(&mpe(50, control_change(3, 75, 127))), None);
+        assert_eq!(source.control(&mpe(50, channel_pressure(3, 127))), None);
+        assert_eq!(source.control(&plain(control_change(3, 74, 127))), None);
+        assert_eq!(source.test_feedback::<RawShortMessage>(fv(1.0)), None);
+        assert_eq!(source.format_control_value(abs(1.0)).unwrap(), "127");
+    }
+
+    #[test]
+    fn script_with_multiple_messages() {
+        use crate::MidiSourceScriptOutcome;
+        use std::borrow::Cow;
+        // Given
+        struct LedAndDisplayScript;
+        impl MidiSourceScript<'_> for LedAndDisplayScript {
+            type AdditionalInput = ();
+
+            fn execute(
+                &self,
+                input_value: FeedbackValue,
+                _: (),
+            ) -> Result<MidiSourceScriptOutcome, Cow<'static, str>> {
+                let value = denormalize_7_bit::<u8>(input_value.to_numeric().unwrap().value);
+                let led = [0xb0, 0x10, value];
+                let display = [0xf0, 0x00, 0x20, value, 0xf7];
+                let outcome = MidiSourceScriptOutcome::from_messages(
+                    Some(MidiSourceAddress::Script { bytes: 0x10b0 }),
+                    [led.as_slice(), display.as_slice()],
+                )?;
+                Ok(outcome)
+            }
+        }
+        let source = MidiSource::Script {
+            script: LedAndDisplayScript,
+        };
+        // When
+        let value = source
+            .feedback_flexible::<RawShortMessage>(fv(1.0), SourceContext::default())
+            .unwrap()
+            .final_value;
+        // Then
+        let events: Vec<_> = value
+            .to_raw()
+            .unwrap()
+            .map(|e| e.bytes().to_vec())
+            .collect();
+        assert_eq!(
+            events,
+            vec![vec![0xb0, 0x10, 0x7f], vec![0xf0, 0x00, 0x20, 0x7f, 0xf7]]
+        );
+        assert_eq!(
+            value.extract_feedback_address(),
+            Some(MidiSourceAddress::Script { bytes: 0x10b0 })
+        );
+    }
+
     #[test]
     fn clock_tempo() {
         // Given
@@ -3298,26 +5209,792 @@ mod tests {
         assert_eq!(source.control(&pn(nrpn(1, 520, 24))), None);
         assert_eq!(source.control(&plain(pitch_bend_change(6, 8192,))), None);
         assert_eq!(source.control(&tempo(120.0)), None);
-        assert_eq!(source.test_feedback::<RawShortMessage>(fv(0.5)), None);
+        assert_eq!(
+            source.test_feedback::<RawShortMessage>(fv(1.0)),
+            Some(plain(r#continue()))
+        );
+        assert_eq!(source.test_feedback::<RawShortMessage>(fv(0.0)), None);
+        assert_eq!(
+            source.extract_feedback_address(SourceContext::default()),
+            plain(r#continue()).extract_feedback_address()
+        );
         assert!(source.format_control_value(abs(0.5)).is_err());
     }
 
-    fn abs(value: f64) -> ControlValue {
-        ControlValue::absolute_continuous(value)
-    }
-
-    fn frac(actual: u32, max: u32) -> ControlValue {
-        ControlValue::absolute_discrete(actual, max)
-    }
-
-    fn rel(increment: i32) -> ControlValue {
-        ControlValue::relative(increment)
-    }
+    #[test]
+    fn note_key_zone() {
+        // Given
+        let source = TestMidiSource::NoteKeyZone {
+            channel: None,
+            key_number_interval: Interval::new(kn(60), kn(71)),
+        };
+        // When
+        // Then
+        assert_eq!(source.control(&plain(note_on(0, 60, 1))), Some(frac(0, 11)));
+        assert_eq!(
+            source.control(&plain(note_on(3, 67, 127))),
+            Some(frac(7, 11))
+        );
+        assert_eq!(source.control(&plain(note_on(0, 59, 100))), None);
+        assert_eq!(source.control(&plain(note_on(0, 72, 100))), None);
+        assert_eq!(source.control(&plain(note_on(0, 65, 0))), None);
+        assert_eq!(source.control(&plain(note_off(0, 65, 100))), None);
+        assert_eq!(source.max_discrete_value(), Some(11));
+        assert_eq!(source.format_control_value(abs(1.0)).unwrap(), "71");
+        assert_eq!(
+            source.parse_control_value("67").unwrap(),
+            UnitValue::new(7.0 / 11.0)
+        );
+        assert!(source.parse_control_value("72").is_err());
+        let source_with_channel = TestMidiSource::NoteKeyZone {
+            channel: Some(ch(1)),
+            key_number_interval: Interval::new(kn(60), kn(71)),
+        };
+        assert_eq!(
+            source_with_channel.test_feedback::<RawShortMessage>(fv(1.0)),
+            Some(plain(note_on(1, 71, 127)))
+        );
+    }
+
+    #[test]
+    fn channel_number() {
+        // Given
+        let source = TestMidiSource::ChannelNumber {
+            message_type: ChannelNumberMessageType::ControlChange,
+            number: Some(U7::new(20)),
+        };
+        // When
+        // Then
+        assert_eq!(
+            source.control(&plain(control_change(0, 20, 127))),
+            Some(frac(0, 15))
+        );
+        assert_eq!(
+            source.control(&plain(control_change(5, 20, 1))),
+            Some(frac(5, 15))
+        );
+        assert_eq!(source.control(&plain(control_change(5, 20, 0))), None);
+        assert_eq!(source.control(&plain(control_change(5, 21, 127))), None);
+        assert_eq!(source.control(&plain(note_on(5, 20, 127))), None);
+        assert_eq!(source.max_discrete_value(), Some(15));
+        assert_eq!(source.format_control_value(frac(5, 15)).unwrap(), "5");
+        assert_eq!(source.parse_control_value("15").unwrap(), UnitValue::MAX);
+        assert!(source.parse_control_value("16").is_err());
+        assert_eq!(
+            source.test_feedback::<RawShortMessage>(fv(1.0)),
+            Some(plain(control_change(15, 20, 127)))
+        );
+        let note_source = TestMidiSource::ChannelNumber {
+            message_type: ChannelNumberMessageType::Note,
+            number: None,
+        };
+        assert_eq!(
+            note_source.control(&plain(note_on(3, 60, 100))),
+            Some(frac(3, 15))
+        );
+        assert_eq!(note_source.control(&plain(note_off(3, 60, 100))), None);
+        assert_eq!(note_source.test_feedback::<RawShortMessage>(fv(1.0)), None);
+    }
+
+    #[test]
+    fn mackie_v_pot_ring() {
+        // Given
+        let source = TestMidiSource::Display {
+            spec: DisplaySpec::MackieVPotRing {
+                ring: MackieVPotRingFeedback::new(2, MackieVPotRingMode::Wrap),
+            },
+        };
+        // When
+        let value = source.test_feedback::<RawShortMessage>(fv(1.0)).unwrap();
+        // Then
+        let address = value.extract_feedback_address();
+        let MidiSourceValue::Raw { events, .. } = value else {
+            panic!("expected raw value");
+        };
+        assert_eq!(events[0].bytes(), &[0xB0, 0x32, 0x2B]);
+        assert_eq!(
+            address,
+            Some(MidiSourceAddress::Display {
+                spec: DisplaySpecAddress::MackieVPotRing { channel_index: 2 }
+            })
+        );
+        assert_eq!(address, source.extract_feedback_address(Default::default()));
+    }
+
+    #[test]
+    fn generic_lcd() {
+        // Given
+        let layout = GenericLcdLayout {
+            sysex_header: vec![0x00, 0x20, 0x29, 0x12],
+            channel_count: 4,
+            channel_len: 6,
+            line_count: 2,
+            max_chunk_len: Some(10),
+        };
+        let scope = GenericLcdScope::new(&layout, None, Some(5));
+        let source = TestMidiSource::Display {
+            spec: DisplaySpec::GenericLcd { layout, scope },
+        };
+        let text = FeedbackValue::Textual(TextualFeedbackValue::new(
+            Default::default(),
+            "Hello world!".into(),
+        ));
+        // When
+        let value = source.test_feedback::<RawShortMessage>(text).unwrap();
+        // Then
+        let MidiSourceValue::Raw { events, .. } = value else {
+            panic!("expected raw value");
+        };
+        let messages: Vec<_> = events.iter().map(|e| e.bytes().to_vec()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                [
+                    &[0xf0, 0x00, 0x20, 0x29, 0x12, 24][..],
+                    b"Hello worl",
+                    &[0xf7]
+                ]
+                .concat(),
+                [
+                    &[0xf0, 0x00, 0x20, 0x29, 0x12, 34][..],
+                    b"d!        ",
+                    &[0xf7]
+                ]
+                .concat(),
+                [&[0xf0, 0x00, 0x20, 0x29, 0x12, 44][..], b"    ", &[0xf7]].concat(),
+            ]
+        );
+    }
+
+    #[test]
+    fn raw_with_poll_request() {
+        // Given
+        let poll_request = SysExPollRequest::new(
+            vec![0xF0, 0x43, 0x20, 0x01, 0xF7],
+            Duration::from_millis(100),
+        )
+        .unwrap();
+        let source = TestMidiSource::Raw {
+            pattern: "F0 43 00 01 [0gfe dcba] F7".parse().unwrap(),
+            custom_character: SourceCharacter::RangeElement,
+            poll_request: Some(poll_request.clone()),
+        };
+        let response: MidiSourceValue<RawShortMessage> =
+            MidiSourceValue::BorrowedSysEx(&[0xF0, 0x43, 0x00, 0x01, 0x40, 0xF7]);
+        let unrelated: MidiSourceValue<RawShortMessage> =
+            MidiSourceValue::BorrowedSysEx(&[0xF0, 0x43, 0x00, 0x02, 0x40, 0xF7]);
+        // When
+        // Then
+        assert_eq!(source.sys_ex_poll_request(), Some(&poll_request));
+        assert_eq!(
+            source.control(&response),
+            Some(ControlValue::AbsoluteDiscrete(Fraction::new(64, 127)))
+        );
+        assert_eq!(source.control(&unrelated), None);
+    }
+
+    #[test]
+    fn raw_text_feedback() {
+        // Given
+        let source = TestMidiSource::Raw {
+            pattern: "F0 00 20 29 02 {text:4} F7".parse().unwrap(),
+            custom_character: SourceCharacter::RangeElement,
+            poll_request: None,
+        };
+        let text =
+            FeedbackValue::Textual(TextualFeedbackValue::new(Default::default(), "Mix".into()));
+        // When
+        let value = source.test_feedback::<RawShortMessage>(text).unwrap();
+        // Then
+        let MidiSourceValue::Raw { events, .. } = value else {
+            panic!("expected raw value");
+        };
+        assert_eq!(
+            events[0].bytes(),
+            &[0xf0, 0x00, 0x20, 0x29, 0x02, b'M', b'i', b'x', b' ', 0xf7]
+        );
+    }
+
+    #[test]
+    fn feedback_with_device_capabilities() {
+        // Given
+        let cc_14_bit_source = TestMidiSource::ControlChange14BitValue {
+            channel: Some(ch(0)),
+            msb_controller_number: Some(cn(7)),
+            custom_character: SourceCharacter::RangeElement,
+            feedback_mode: Default::default(),
+        };
+        let raw_source = TestMidiSource::Raw {
+            pattern: "F0 {text:4} {color:r} F7".parse().unwrap(),
+            custom_character: SourceCharacter::RangeElement,
+            poll_request: None,
+        };
+        let context = SourceContext {
+            additional_script_input: (),
+            device_palette: None,
+            led_animation: Default::default(),
+            device_capabilities: Some(DeviceCapabilities {
+                supports_rgb_color: false,
+                text_display_length: Some(2),
+                supports_14_bit_feedback: false,
+            }),
+            device_quirks: Default::default(),
+        };
+        let text = FeedbackValue::Textual(TextualFeedbackValue::new(
+            FeedbackStyle {
+                color: Some(RgbColor::new(255, 0, 0)),
+                background_color: None,
+            },
+            "Mix".into(),
+        ));
+        // When
+        let cc_feedback = cc_14_bit_source
+            .feedback_flexible::<RawShortMessage>(fv(1.0), context.clone())
+            .map(|v| v.final_value);
+        let raw_feedback = raw_source
+            .feedback_flexible::<RawShortMessage>(text, context)
+            .unwrap()
+            .final_value;
+        // Then
+        assert_eq!(cc_feedback, Some(plain(control_change(0, 7, 127))));
+        let MidiSourceValue::Raw { events, .. } = raw_feedback else {
+            panic!("expected raw value");
+        };
+        assert_eq!(
+            events[0].bytes(),
+            &[0xf0, b'M', b'i', b' ', b' ', 0x00, 0xf7]
+        );
+    }
+
+    #[test]
+    fn character_info() {
+        // Given
+        let cc_source = TestMidiSource::ControlChangeValue {
+            channel: Some(ch(0)),
+            controller_number: Some(cn(7)),
+            custom_character: SourceCharacter::RangeElement,
+            value_interval: full_7_bit_interval(),
+        };
+        let encoder_source = TestMidiSource::ControlChangeValue {
+            channel: Some(ch(0)),
+            controller_number: Some(cn(7)),
+            custom_character: SourceCharacter::Encoder1,
+            value_interval: full_7_bit_interval(),
+        };
+        let pitch_bend_source = TestMidiSource::PitchBendChangeValue {
+            channel: Some(ch(0)),
+            is_bipolar: false,
+        };
+        // When
+        // Then
+        let info = cc_source.character_info();
+        assert_eq!(info.character, DetailedSourceCharacter::RangeControl);
+        assert_eq!(info.max_discrete_value, Some(127));
+        assert_eq!(info.resolution_in_bits(), Some(7));
+        let info = encoder_source.character_info();
+        assert_eq!(info.character, DetailedSourceCharacter::Relative);
+        assert_eq!(info.resolution_in_bits(), None);
+        assert_eq!(
+            pitch_bend_source.character_info().resolution_in_bits(),
+            Some(14)
+        );
+    }
+
+    #[test]
+    fn control_change_14_bit_feedback_modes() {
+        // Given
+        let source = |feedback_mode| TestMidiSource::ControlChange14BitValue {
+            channel: Some(ch(0)),
+            msb_controller_number: Some(cn(7)),
+            custom_character: SourceCharacter::RangeElement,
+            feedback_mode,
+        };
+        let feedback = |feedback_mode| {
+            source(feedback_mode)
+                .feedback_flexible::<RawShortMessage>(fv(0.5), SourceContext::default())
+                .map(|v| v.final_value)
+        };
+        // When
+        let msb_then_lsb = feedback(ControlChange14BitFeedbackMode::MsbThenLsb);
+        let lsb_then_msb = feedback(ControlChange14BitFeedbackMode::LsbThenMsb);
+        let msb_only = feedback(ControlChange14BitFeedbackMode::MsbOnly);
+        // Then
+        assert_eq!(
+            msb_then_lsb,
+            Some(MidiSourceValue::ControlChange14Bit(
+                ControlChange14BitMessage::new(ch(0), cn(7), u14(8192))
+            ))
+        );
+        let Some(MidiSourceValue::ControlChangePair(msg)) = lsb_then_msb else {
+            panic!("expected control change pair");
+        };
+        assert_eq!(
+            msg.to_short_messages::<RawShortMessage>(),
+            [control_change(0, 39, 0), control_change(0, 7, 64)]
+        );
+        assert_eq!(msb_only, Some(plain(control_change(0, 7, 64))));
+    }
+
+    #[test]
+    fn encoder_with_device_quirks() {
+        // Given
+        let source = TestMidiSource::ControlChangeValue {
+            channel: Some(ch(0)),
+            controller_number: Some(cn(16)),
+            custom_character: SourceCharacter::Encoder2,
+            value_interval: full_7_bit_interval(),
+        };
+        let context = |quirks| SourceContext {
+            additional_script_input: (),
+            device_palette: None,
+            led_animation: Default::default(),
+            device_capabilities: None,
+            device_quirks: quirks,
+        };
+        let zero_decrement = context(DeviceQuirks {
+            encoder_sends_zero_for_decrement: true,
+            ..Default::default()
+        });
+        let inverted = context(DeviceQuirks {
+            inverted_encoder_direction: true,
+            ..Default::default()
+        });
+        let control = |value, context: &SourceContext<()>| {
+            source.control_with_context(&plain(control_change(0, 16, value)), context)
+        };
+        // When
+        // Then
+        assert_eq!(control(0, &context(Default::default())), Some(rel(-64)));
+        assert_eq!(control(0, &zero_decrement), Some(rel(-1)));
+        assert_eq!(control(65, &zero_decrement), Some(rel(1)));
+        assert_eq!(control(65, &inverted), Some(rel(-1)));
+    }
+
+    #[test]
+    fn relative_sources_with_device_quirks() {
+        // Given
+        let cc_14_bit_source = TestMidiSource::ControlChange14BitValue {
+            channel: Some(ch(0)),
+            msb_controller_number: Some(cn(16)),
+            custom_character: SourceCharacter::Encoder1,
+            feedback_mode: Default::default(),
+        };
+        let raw_source = TestMidiSource::Raw {
+            pattern: "F0 10 [0gfe dcba] F7".parse().unwrap(),
+            custom_character: SourceCharacter::Encoder1,
+            poll_request: None,
+        };
+        let context = SourceContext {
+            additional_script_input: (),
+            device_palette: None,
+            led_animation: Default::default(),
+            device_capabilities: None,
+            device_quirks: DeviceQuirks {
+                encoder_sends_zero_for_decrement: true,
+                inverted_encoder_direction: true,
+                button_never_sends_release: false,
+            },
+        };
+        let cc_14_bit_value =
+            |value| {
+                MidiSourceValue::<RawShortMessage>::ControlChange14Bit(
+                    ControlChange14BitMessage::new(ch(0), cn(16), u14(value)),
+                )
+            };
+        let raw_value = |value| MidiSourceValue::<RawShortMessage>::BorrowedSysEx(value);
+        // When
+        // Then
+        assert_eq!(cc_14_bit_source.control(&cc_14_bit_value(0)), None);
+        assert_eq!(
+            cc_14_bit_source.control_with_context(&cc_14_bit_value(0), &context),
+            Some(rel(1))
+        );
+        assert_eq!(
+            cc_14_bit_source.control_with_context(&cc_14_bit_value(1), &context),
+            Some(rel(-1))
+        );
+        assert_eq!(
+            raw_source.control(&raw_value(&[0xF0, 0x10, 0x00, 0xF7])),
+            None
+        );
+        assert_eq!(
+            raw_source.control_with_context(&raw_value(&[0xF0, 0x10, 0x00, 0xF7]), &context),
+            Some(rel(1))
+        );
+        assert_eq!(
+            raw_source.control_with_context(&raw_value(&[0xF0, 0x10, 0x01, 0xF7]), &context),
+            Some(rel(-1))
+        );
+    }
+
+    #[test]
+    fn button_with_release_quirk() {
+        // Given
+        let button_source = TestMidiSource::ControlChangeValue {
+            channel: Some(ch(0)),
+            controller_number: Some(cn(20)),
+            custom_character: SourceCharacter::MomentaryButton,
+            value_interval: full_7_bit_interval(),
+        };
+        let fader_source = TestMidiSource::ControlChangeValue {
+            channel: Some(ch(0)),
+            controller_number: Some(cn(21)),
+            custom_character: SourceCharacter::RangeElement,
+            value_interval: full_7_bit_interval(),
+        };
+        let context = |button_never_sends_release| SourceContext {
+            additional_script_input: (),
+            device_palette: None,
+            led_animation: Default::default(),
+            device_capabilities: None,
+            device_quirks: DeviceQuirks {
+                button_never_sends_release,
+                ..Default::default()
+            },
+        };
+        let press = button_source
+            .control_with_context(&plain(control_change(0, 20, 127)), &context(true))
+            .unwrap();
+        // When
+        // Then
+        assert_eq!(
+            button_source.emulated_release(press, &context(true)),
+            Some(frac(0, 127))
+        );
+        assert_eq!(button_source.emulated_release(press, &context(false)), None);
+        assert_eq!(
+            button_source.emulated_release(frac(0, 127), &context(true)),
+            None
+        );
+        assert_eq!(fader_source.emulated_release(press, &context(true)), None);
+    }
+
+    #[test]
+    fn note_velocity_with_device_palette() {
+        // Given
+        let source = TestMidiSource::NoteVelocity {
+            channel: Some(ch(6)),
+            key_number: Some(kn(0)),
+            velocity_curve: VelocityCurve::Linear,
+            note_off_policy: Default::default(),
+            velocity_interval: full_7_bit_interval(),
+            velocity_filtered_notes: Default::default(),
+        };
+        let context = SourceContext {
+            additional_script_input: (),
+            device_palette: Some(DevicePalette::Akai(AkaiModel::ApcMiniMk2)),
+            led_animation: Default::default(),
+            device_capabilities: None,
+            device_quirks: Default::default(),
+        };
+        let colored_fv = |value: f64| {
+            let style = FeedbackStyle {
+                color: Some(RgbColor::new(255, 0, 0)),
+                background_color: None,
+            };
+            FeedbackValue::Numeric(NumericFeedbackValue::new(
+                style,
+                AbsoluteValue::Continuous(UnitValue::new(value)),
+            ))
+        };
+        let feedback = |value| {
+            source
+                .feedback_flexible::<RawShortMessage>(value, context.clone())
+                .map(|v| v.final_value)
+        };
+        // When
+        // Then
+        assert_eq!(feedback(colored_fv(1.0)), Some(plain(note_on(6, 0, 5))));
+        assert_eq!(feedback(colored_fv(0.0)), Some(plain(note_on(6, 0, 0))));
+        // No color (single-color LEDs)
+        assert_eq!(feedback(fv(1.0)), Some(plain(note_on(6, 0, 1))));
+        assert_eq!(feedback(fv(0.0)), Some(plain(note_on(6, 0, 0))));
+        // Animations are encoded in the channel (pads) or velocity (single-color LEDs)
+        let blink_context = SourceContext {
+            led_animation: AkaiLedAnimation::Blink,
+            ..context.clone()
+        };
+        let blink_feedback = |value| {
+            source
+                .feedback_flexible::<RawShortMessage>(value, blink_context.clone())
+                .map(|v| v.final_value)
+        };
+        let colored_blink_feedback = blink_feedback(colored_fv(1.0)).unwrap();
+        let MidiSourceValue::Raw { events, .. } = &colored_blink_feedback else {
+            panic!("expected raw value");
+        };
+        assert_eq!(events[0].bytes(), &[0x9E, 0x00, 5]);
+        assert_eq!(
+            colored_blink_feedback.extract_feedback_address(),
+            source.extract_feedback_address(blink_context.clone())
+        );
+        assert_eq!(blink_feedback(fv(1.0)), Some(plain(note_on(6, 0, 2))));
+        // Fire pads are colored via sys-ex
+        let fire_context = SourceContext {
+            device_palette: Some(DevicePalette::Akai(AkaiModel::Fire)),
+            ..context
+        };
+        assert_eq!(
+            source
+                .feedback_flexible::<RawShortMessage>(colored_fv(1.0), fire_context)
+                .map(|v| v.final_value),
+            Some(plain(note_on(6, 0, 127)))
+        );
+    }
+
+    #[test]
+    fn feedback_with_exact_rgb_palette() {
+        // Given
+        let note_source = TestMidiSource::NoteVelocity {
+            channel: Some(ch(0)),
+            key_number: Some(kn(11)),
+            velocity_curve: VelocityCurve::Linear,
+            note_off_policy: Default::default(),
+            velocity_interval: full_7_bit_interval(),
+            velocity_filtered_notes: Default::default(),
+        };
+        let cc_source = TestMidiSource::ControlChangeValue {
+            channel: Some(ch(0)),
+            controller_number: Some(cn(19)),
+            custom_character: SourceCharacter::MomentaryButton,
+            value_interval: full_7_bit_interval(),
+        };
+        let context = SourceContext {
+            additional_script_input: (),
+            device_palette: Some(DevicePalette::Launchpad(LaunchpadModel::X)),
+            led_animation: Default::default(),
+            device_capabilities: None,
+            device_quirks: Default::default(),
+        };
+        let no_rgb_context = SourceContext {
+            device_capabilities: Some(DeviceCapabilities {
+                supports_rgb_color: false,
+                ..Default::default()
+            }),
+            ..context.clone()
+        };
+        let colored_fv = |value: f64| {
+            let style = FeedbackStyle {
+                color: Some(RgbColor::new(255, 0, 0)),
+                background_color: None,
+            };
+            FeedbackValue::Numeric(NumericFeedbackValue::new(
+                style,
+                AbsoluteValue::Continuous(UnitValue::new(value)),
+            ))
+        };
+        let feedback = |source: &TestMidiSource, value, context: &SourceContext<()>| {
+            source
+                .feedback_flexible::<RawShortMessage>(value, context.clone())
+                .map(|v| v.final_value)
+        };
+        let rgb_sysex = |led_index| {
+            vec![
+                0xF0, 0x00, 0x20, 0x29, 0x02, 0x0C, 0x03, 0x03, led_index, 0x7F, 0x00, 0x00, 0xF7,
+            ]
+        };
+        // When
+        let note_feedback = feedback(&note_source, colored_fv(1.0), &context).unwrap();
+        let cc_feedback = feedback(&cc_source, colored_fv(1.0), &context).unwrap();
+        // Then
+        let MidiSourceValue::Raw { events, .. } = &note_feedback else {
+            panic!("expected raw value");
+        };
+        assert_eq!(events[0].bytes(), rgb_sysex(11));
+        assert_eq!(
+            note_feedback.extract_feedback_address(),
+            note_source.extract_feedback_address(context.clone())
+        );
+        let MidiSourceValue::Raw { events, .. } = &cc_feedback else {
+            panic!("expected raw value");
+        };
+        assert_eq!(events[0].bytes(), rgb_sysex(19));
+        // Off values and devices without RGB support fall back to the palette index
+        assert_eq!(
+            feedback(&note_source, colored_fv(0.0), &context),
+            Some(plain(note_on(0, 11, 0)))
+        );
+        assert_eq!(
+            feedback(&note_source, colored_fv(1.0), &no_rgb_context),
+            Some(plain(note_on(0, 11, 72)))
+        );
+    }
+
+    #[test]
+    fn bank_program_change_number() {
+        // Given
+        let source = TestMidiSource::BankProgramChangeNumber {
+            channel: Some(ch(1)),
+        };
+        let max = BankProgramChangeMessage::MAX_VALUE;
+        let bpc =
+            |channel, bank, program| {
+                MidiSourceValue::<RawShortMessage>::BankProgramChange(
+                    BankProgramChangeMessage::new(ch(channel), U14::new(bank), U7::new(program)),
+                )
+            };
+        // When
+        // Then
+        assert_eq!(source.control(&bpc(1, 2, 5)), Some(frac(2 * 128 + 5, max)));
+        assert_eq!(source.control(&bpc(2, 2, 5)), None);
+        assert_eq!(
+            source.control(&plain(program_change(1, 5))),
+            Some(frac(5, max))
+        );
+        assert_eq!(source.control(&plain(control_change(1, 0, 2))), None);
+        let discrete_fv = FeedbackValue::Numeric(NumericFeedbackValue::new(
+            Default::default(),
+            AbsoluteValue::Discrete(Fraction::new(2 * 128 + 5, max)),
+        ));
+        assert_eq!(
+            source.test_feedback::<RawShortMessage>(discrete_fv),
+            Some(bpc(1, 2, 5))
+        );
+        assert_eq!(
+            source.extract_feedback_address(SourceContext::default()),
+            bpc(1, 0, 0).extract_feedback_address()
+        );
+        assert_eq!(
+            TestMidiSource::from_source_value(bpc(1, 3, 4), None),
+            Some(source)
+        );
+        // Plain program change sources still work
+        assert_eq!(
+            TestMidiSource::ProgramChangeNumber { channel: None }.control(&bpc(1, 2, 5)),
+            Some(frac(5, 127))
+        );
+    }
+
+    #[test]
+    fn song_position() {
+        // Given
+        let source = TestMidiSource::SongPosition;
+        let spp = |position| RawShortMessage::song_position_pointer(U14::new(position));
+        // When
+        // Then
+        assert_eq!(source.control(&plain(spp(4096))), Some(frac(4096, 16383)));
+        assert_eq!(source.control(&plain(start())), None);
+        assert_eq!(
+            source.test_feedback::<RawShortMessage>(fv(1.0)),
+            Some(plain(spp(16383)))
+        );
+        assert_eq!(
+            source.extract_feedback_address(SourceContext::default()),
+            plain(spp(0)).extract_feedback_address()
+        );
+        assert_eq!(
+            TestMidiSource::from_source_value(plain(spp(5)), None),
+            Some(source)
+        );
+    }
+
+    #[test]
+    fn time_code() {
+        // Given
+        let source = TestMidiSource::TimeCode;
+        let time_code = MidiTimeCode {
+            hours: 12,
+            minutes: 0,
+            seconds: 0,
+            frames: 0,
+            frame_rate: MtcFrameRate::Fps25,
+        };
+        let full_frame = [0xf0, 0x7f, 0x7f, 0x01, 0x01, 0x20, 0x00, 0x00, 0x00, 0xf7];
+        // When
+        // Then
+        assert_eq!(
+            source.control(&MidiSourceValue::TimeCode(time_code)),
+            Some(abs(0.5))
+        );
+        assert_eq!(
+            source.control(&MidiSourceValue::BorrowedSysEx(&full_frame)),
+            Some(abs(0.0))
+        );
+        assert_eq!(source.control(&plain(start())), None);
+        assert_eq!(source.test_feedback::<RawShortMessage>(fv(1.0)), None);
+        assert_eq!(
+            source.format_control_value(abs(0.5)).unwrap(),
+            "12:00:00.000"
+        );
+    }
+
+    #[test]
+    fn machine_control() {
+        // Given
+        let play = TestMidiSource::MachineControl {
+            device_id: Some(1),
+            command: MmcCommand::Play,
+        };
+        let locate = TestMidiSource::MachineControl {
+            device_id: None,
+            command: MmcCommand::Locate,
+        };
+        let sysex = |bytes: &'static [u8]| MidiSourceValue::<RawShortMessage>::BorrowedSysEx(bytes);
+        // When
+        // Then
+        assert_eq!(
+            play.control(&sysex(&[0xf0, 0x7f, 0x01, 0x06, 0x02, 0xf7])),
+            Some(frac(1, 1))
+        );
+        assert_eq!(
+            play.control(&sysex(&[0xf0, 0x7f, 0x7f, 0x06, 0x02, 0xf7])),
+            Some(frac(1, 1))
+        );
+        assert_eq!(
+            play.control(&sysex(&[0xf0, 0x7f, 0x02, 0x06, 0x02, 0xf7])),
+            None
+        );
+        assert_eq!(
+            play.control(&sysex(&[0xf0, 0x7f, 0x01, 0x06, 0x01, 0xf7])),
+            None
+        );
+        assert_eq!(
+            locate.control(&sysex(&[
+                0xf0, 0x7f, 0x7f, 0x06, 0x44, 0x06, 0x01, 0x6c, 0x00, 0x00, 0x00, 0x00, 0xf7
+            ])),
+            Some(abs(0.5))
+        );
+        let play_feedback = play.test_feedback::<RawShortMessage>(fv(1.0)).unwrap();
+        assert_eq!(
+            play_feedback.to_raw().unwrap().next().unwrap().bytes(),
+            &[0xf0, 0x7f, 0x01, 0x06, 0x02, 0xf7]
+        );
+        assert_eq!(
+            play_feedback.extract_feedback_address(),
+            play.extract_feedback_address(SourceContext::default())
+        );
+        assert_eq!(play.test_feedback::<RawShortMessage>(fv(0.0)), None);
+        let locate_feedback = locate.test_feedback::<RawShortMessage>(fv(0.5)).unwrap();
+        assert_eq!(
+            locate_feedback.to_raw().unwrap().next().unwrap().bytes(),
+            &[0xf0, 0x7f, 0x7f, 0x06, 0x44, 0x06, 0x01, 0x6c, 0x00, 0x00, 0x00, 0x00, 0xf7]
+        );
+        assert_eq!(
+            TestMidiSource::from_source_value(sysex(&[0xf0, 0x7f, 0x01, 0x06, 0x02, 0xf7]), None),
+            Some(play)
+        );
+    }
+
+    fn abs(value: f64) -> ControlValue {
+        ControlValue::absolute_continuous(value)
+    }
+
+    fn frac(actual: u32, max: u32) -> ControlValue {
+        ControlValue::absolute_discrete(actual, max)
+    }
+
+    fn rel(increment: i32) -> ControlValue {
+        ControlValue::relative(increment)
+    }
 
     fn plain(msg: RawShortMessage) -> MidiSourceValue<'static, RawShortMessage> {
         MidiSourceValue::Plain(msg)
     }
 
+    fn ump(msg: UmpChannelVoiceMessage) -> MidiSourceValue<'static, RawShortMessage> {
+        MidiSourceValue::Ump(msg)
+    }
+
     fn pn(msg: ParameterNumberMessage) -> MidiSourceValue<'static, RawShortMessage> {
         MidiSourceValue::ParameterNumber(msg)
     }
@@ -1,5 +1,6 @@
-use crate::{FeedbackValue, MidiSourceAddress, RawMidiEvents};
+use crate::{FeedbackValue, MidiSourceAddress, RawMidiEvent, RawMidiEvents};
 use std::borrow::Cow;
+use std::fmt;
 
 // The lifetime 'a is necessary in case we want to parameterize the lifetime
 // of the additional input dynamically. An alternative would have been to
@@ -8,15 +9,70 @@ use std::borrow::Cow;
 pub trait MidiSourceScript<'a> {
     type AdditionalInput: Default;
 
-    /// Returns raw MIDI bytes.
+    /// Returns raw MIDI messages.
+    ///
+    /// One invocation can emit any number of messages, e.g. in order to update both an LED and a
+    /// display segment. They are sent in the given order.
     fn execute(
         &self,
         input_value: FeedbackValue,
         additional_input: Self::AdditionalInput,
     ) -> Result<MidiSourceScriptOutcome, Cow<'static, str>>;
+
+    /// Checks whether the given code compiles, without executing it.
+    ///
+    /// Meant for showing inline diagnostics while the user edits a script. Script types which
+    /// are not created from code accept anything.
+    fn validate(_code: &str) -> Result<(), Vec<ScriptDiagnostic>>
+    where
+        Self: Sized,
+    {
+        Ok(())
+    }
+}
+
+/// Problem found when compiling a script.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ScriptDiagnostic {
+    pub message: String,
+    /// 1-based line, if known.
+    pub line: Option<u32>,
+    /// 1-based column, if known.
+    pub column: Option<u32>,
+}
+
+impl fmt::Display for ScriptDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "{line}:{column}: {}", self.message),
+            (Some(line), None) => write!(f, "{line}: {}", self.message),
+            _ => f.write_str(&self.message),
+        }
+    }
 }
 
 pub struct MidiSourceScriptOutcome {
+    /// Address shared by all emitted messages.
     pub address: Option<MidiSourceAddress>,
+    /// Zero, one or multiple messages.
     pub events: RawMidiEvents,
 }
+
+impl MidiSourceScriptOutcome {
+    pub fn new(address: Option<MidiSourceAddress>, events: RawMidiEvents) -> Self {
+        Self { address, events }
+    }
+
+    /// Creates an outcome which emits the given messages, skipping empty ones.
+    pub fn from_messages<'b>(
+        address: Option<MidiSourceAddress>,
+        messages: impl IntoIterator<Item = &'b [u8]>,
+    ) -> Result<Self, &'static str> {
+        let events: Result<RawMidiEvents, _> = messages
+            .into_iter()
+            .filter(|m| !m.is_empty())
+            .map(|m| RawMidiEvent::try_from_slice(0, m))
+            .collect();
+        Ok(Self::new(address, events?))
+    }
+}
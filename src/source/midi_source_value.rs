@@ -1,7 +1,11 @@
-use crate::{DisplaySpecAddress, MidiSourceAddress, PatternByte, UnitValue};
+use crate::{
+    BankProgramChangeMessage, ControlChangePairMessage, DisplaySpecAddress,
+    MidiClockTransportMessage, MidiSourceAddress, MidiTimeCode, PatternByte,
+    UmpChannelVoiceMessage, UnitValue,
+};
 use helgoboss_midi::{
-    Channel, ControlChange14BitMessage, DataEntryByteOrder, ParameterNumberMessage, ShortMessage,
-    ShortMessageFactory, StructuredShortMessage,
+    Channel, ControlChange14BitMessage, DataEntryByteOrder, KeyNumber, ParameterNumberMessage,
+    ShortMessage, ShortMessageFactory, ShortMessageType, StructuredShortMessage, U7,
 };
 use reaper_common_types::Bpm;
 use std::ops::RangeInclusive;
@@ -39,6 +43,14 @@ pub enum MidiSourceValue<'a, M: ShortMessage> {
     Plain(M),
     ParameterNumber(ParameterNumberMessage),
     ControlChange14Bit(ControlChange14BitMessage),
+    /// 14-bit value transmitted via an arbitrary pair of controllers (see
+    /// [`crate::ControlChangePairCombiner`]).
+    ControlChangePair(ControlChangePairMessage),
+    /// MIDI 2.0 message with high-resolution value.
+    Ump(UmpChannelVoiceMessage),
+    /// Program change combined with the preceding bank select (see
+    /// [`crate::BankProgramChangeCombiner`]).
+    BankProgramChange(BankProgramChangeMessage),
     /// We must take care not to allocate this in real-time thread!
     Raw {
         feedback_address_info: Option<RawFeedbackAddressInfo>,
@@ -46,6 +58,15 @@ pub enum MidiSourceValue<'a, M: ShortMessage> {
     },
     // Control-only
     Tempo(Bpm),
+    /// Control-only: Expression message of an MPE member channel, attributed to the note which is
+    /// currently playing on that channel (see [`crate::MpeNoteTracker`]).
+    Mpe {
+        key_number: KeyNumber,
+        msg: M,
+    },
+    /// Control-only: Position assembled from MIDI Time Code quarter frames (see
+    /// [`crate::MtcQuarterFrameAssembler`]).
+    TimeCode(MidiTimeCode),
     // Control-only
     BorrowedSysEx(&'a [u8]),
 }
@@ -125,6 +146,16 @@ impl<'a, M: ShortMessage + ShortMessageFactory + Copy> MidiSourceValue<'a, M> {
                     PitchBendChange { channel, .. } => {
                         MidiSourceAddress::PitchBendChange { channel }
                     }
+                    Start => MidiSourceAddress::ClockTransport {
+                        message: MidiClockTransportMessage::Start,
+                    },
+                    Continue => MidiSourceAddress::ClockTransport {
+                        message: MidiClockTransportMessage::Continue,
+                    },
+                    Stop => MidiSourceAddress::ClockTransport {
+                        message: MidiClockTransportMessage::Stop,
+                    },
+                    SongPositionPointer { .. } => MidiSourceAddress::SongPosition,
                     // No feedback supported for other types of MIDI messages
                     _ => return None,
                 }
@@ -139,6 +170,20 @@ impl<'a, M: ShortMessage + ShortMessageFactory + Copy> MidiSourceValue<'a, M> {
                 controller_number: msg.msb_controller_number(),
                 is_14_bit: true,
             },
+            ControlChangePair(msg) => MidiSourceAddress::ControlChangePair {
+                channel: msg.channel(),
+                msb_controller_number: msg.pair().msb_controller_number,
+                lsb_controller_number: msg.pair().lsb_controller_number,
+            },
+            Ump(msg) => MidiSourceAddress::Ump {
+                group: msg.group(),
+                channel: msg.channel(),
+                kind: msg.kind(),
+                index: msg.index(),
+            },
+            BankProgramChange(msg) => MidiSourceAddress::BankProgramChange {
+                channel: msg.channel(),
+            },
             Raw {
                 feedback_address_info,
                 events,
@@ -168,7 +213,7 @@ impl<'a, M: ShortMessage + ShortMessageFactory + Copy> MidiSourceValue<'a, M> {
                 RawFeedbackAddressInfo::Custom(addr) => addr.clone(),
             },
             // No feedback
-            Tempo(_) | BorrowedSysEx(_) => return None,
+            Tempo(_) | BorrowedSysEx(_) | Mpe { .. } | TimeCode(_) => return None,
         };
         Some(res)
     }
@@ -179,6 +224,10 @@ impl<'a, M: ShortMessage + ShortMessageFactory + Copy> MidiSourceValue<'a, M> {
             Plain(m) => m.channel(),
             ParameterNumber(m) => Some(m.channel()),
             ControlChange14Bit(m) => Some(m.channel()),
+            ControlChangePair(m) => Some(m.channel()),
+            Ump(m) => Some(m.channel()),
+            BankProgramChange(m) => Some(m.channel()),
+            Mpe { msg, .. } => msg.channel(),
             _ => None,
         }
     }
@@ -193,7 +242,12 @@ impl<'a, M: ShortMessage + ShortMessageFactory + Copy> MidiSourceValue<'a, M> {
             Plain(v) => Plain(v),
             ParameterNumber(v) => ParameterNumber(v),
             ControlChange14Bit(v) => ControlChange14Bit(v),
+            ControlChangePair(v) => ControlChangePair(v),
+            Ump(v) => Ump(v),
+            BankProgramChange(v) => BankProgramChange(v),
             Tempo(v) => Tempo(v),
+            TimeCode(v) => TimeCode(v),
+            Mpe { key_number, msg } => Mpe { key_number, msg },
             Raw {
                 feedback_address_info,
                 events,
@@ -237,14 +291,223 @@ impl<'a, M: ShortMessage + ShortMessageFactory + Copy> MidiSourceValue<'a, M> {
     ) -> [Option<M>; 4] {
         use MidiSourceValue::*;
         match self {
-            Plain(msg) => [Some(*msg), None, None, None],
+            Plain(msg) | Mpe { msg, .. } => [Some(*msg), None, None, None],
             ParameterNumber(msg) => msg.to_short_messages(nrpn_data_entry_byte_order),
             ControlChange14Bit(msg) => {
                 let inner_shorts = msg.to_short_messages();
                 [Some(inner_shorts[0]), Some(inner_shorts[1]), None, None]
             }
-            Tempo(_) | Raw { .. } | BorrowedSysEx(_) => [None; 4],
+            ControlChangePair(msg) => {
+                let [first, second] = msg.to_short_messages();
+                [Some(first), Some(second), None, None]
+            }
+            BankProgramChange(msg) => {
+                let [bank_msb, bank_lsb, program_change] = msg.to_short_messages();
+                [Some(bank_msb), Some(bank_lsb), Some(program_change), None]
+            }
+            // Not representable as MIDI 1.0 short messages, see `to_ump_words()`
+            Ump(_) | Tempo(_) | TimeCode(_) | Raw { .. } | BorrowedSysEx(_) => [None; 4],
+        }
+    }
+
+    /// For values that must be sent as Universal MIDI Packets.
+    pub fn to_ump_words(&self) -> Option<[u32; 2]> {
+        use MidiSourceValue::*;
+        match self {
+            Ump(msg) => Some(msg.to_words()),
+            _ => None,
+        }
+    }
+
+    /// Formats the MIDI 1.0 bytes of this value as hex string, e.g. `B0 07 40`.
+    ///
+    /// Returns `None` if this value doesn't have a MIDI 1.0 byte representation (e.g. tempo).
+    /// Parameter number messages are formatted with the MSB first.
+    ///
+    /// Allocates!
+    pub fn to_hex_string(&self) -> Option<String> {
+        use MidiSourceValue::*;
+        let bytes: Vec<u8> = match self {
+            Raw { events, .. } => events
+                .iter()
+                .flat_map(|e| e.bytes().iter().copied())
+                .collect(),
+            BorrowedSysEx(bytes) => bytes.to_vec(),
+            Ump(_) | Tempo(_) | TimeCode(_) => return None,
+            _ => self
+                .to_short_messages(DataEntryByteOrder::MsbFirst)
+                .into_iter()
+                .flatten()
+                .flat_map(short_message_bytes)
+                .collect(),
+        };
+        Some(format_hex_bytes(&bytes))
+    }
+
+    /// Returns a human-readable description of this value, naming the message type and its
+    /// components, followed by its hex representation (if any), e.g.
+    /// `Note on, channel 1, key 60, velocity 100 [90 3C 64]`.
+    ///
+    /// Channels are displayed 1-based.
+    ///
+    /// Allocates!
+    pub fn describe(&self) -> String {
+        use MidiSourceValue::*;
+        let name = match self {
+            Plain(msg) => describe_short_message(msg),
+            Mpe { key_number, msg } => {
+                format!(
+                    "MPE {} (key {})",
+                    describe_short_message(msg),
+                    key_number.get()
+                )
+            }
+            ParameterNumber(msg) => format!(
+                "{} {}-bit, channel {}, number {}, value {}",
+                if msg.is_registered() { "RPN" } else { "NRPN" },
+                if msg.is_14_bit() { 14 } else { 7 },
+                msg.channel().get() + 1,
+                msg.number().get(),
+                msg.value().get()
+            ),
+            ControlChange14Bit(msg) => format!(
+                "14-bit CC, channel {}, controller {}, value {}",
+                msg.channel().get() + 1,
+                msg.msb_controller_number().get(),
+                msg.value().get()
+            ),
+            ControlChangePair(msg) => format!(
+                "CC pair, channel {}, controllers {}/{}, value {}",
+                msg.channel().get() + 1,
+                msg.pair().msb_controller_number.get(),
+                msg.pair().lsb_controller_number.get(),
+                msg.value().get()
+            ),
+            Ump(msg) => format!(
+                "{} (MIDI 2.0), group {}, channel {}, index {}, value {}",
+                msg.kind(),
+                msg.group(),
+                msg.channel().get() + 1,
+                msg.index(),
+                msg.value()
+            ),
+            BankProgramChange(msg) => format!(
+                "Bank/program change, channel {}, value {}",
+                msg.channel().get() + 1,
+                msg.value()
+            ),
+            Tempo(bpm) => format!("Tempo {:.2} bpm", bpm.get()),
+            TimeCode(tc) => format!(
+                "Time code {:02}:{:02}:{:02}:{:02}",
+                tc.hours, tc.minutes, tc.seconds, tc.frames
+            ),
+            Raw { events, .. } => describe_raw(events.first().map(|e| e.bytes())),
+            BorrowedSysEx(bytes) => describe_raw(Some(bytes)),
+        };
+        match self.to_hex_string() {
+            Some(hex) if !hex.is_empty() => format!("{name} [{hex}]"),
+            _ => name,
+        }
+    }
+}
+
+impl<M: ShortMessage + ShortMessageFactory> MidiSourceValue<'static, M> {
+    /// Parses a hex string such as `B0 07 40` or `F0 7E 7F 06 01 F7`, e.g. one produced by
+    /// [`Self::to_hex_string`].
+    ///
+    /// Bytes which form exactly one short message result in a plain value, everything else
+    /// (e.g. sys-ex) in a raw value without feedback address. That's why this is not an exact
+    /// inverse of [`Self::to_hex_string`]: Composite values such as 14-bit CC or bank/program
+    /// change messages come back as raw values.
+    ///
+    /// Allocates!
+    pub fn from_hex_string(s: &str) -> Result<Self, &'static str> {
+        let bytes = parse_hex_bytes(s)?;
+        if let Some(msg) = short_message_from_bytes(&bytes) {
+            return Ok(MidiSourceValue::Plain(msg));
         }
+        let event = RawMidiEvent::try_from_slice(0, &bytes)?;
+        Ok(MidiSourceValue::single_raw(None, event))
+    }
+}
+
+/// Returns the number of bytes of the short message starting with the given status byte.
+fn short_message_length(status_byte: u8) -> Option<usize> {
+    match status_byte {
+        0x80..=0xBF | 0xE0..=0xEF | 0xF2 => Some(3),
+        0xC0..=0xDF | 0xF1 | 0xF3 => Some(2),
+        0xF6 | 0xF8..=0xFF => Some(1),
+        _ => None,
+    }
+}
+
+fn short_message_bytes(msg: impl ShortMessage) -> impl Iterator<Item = u8> {
+    let (b1, b2, b3) = msg.to_bytes();
+    let len = short_message_length(b1).unwrap_or(3);
+    [b1, b2.get(), b3.get()].into_iter().take(len)
+}
+
+fn short_message_from_bytes<M: ShortMessageFactory>(bytes: &[u8]) -> Option<M> {
+    let (&status_byte, data_bytes) = bytes.split_first()?;
+    if short_message_length(status_byte)? != bytes.len() {
+        return None;
+    }
+    let data_byte = |i: usize| U7::try_from(data_bytes.get(i).copied().unwrap_or(0)).ok();
+    M::from_bytes((status_byte, data_byte(0)?, data_byte(1)?)).ok()
+}
+
+fn format_hex_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Accepts bytes separated by whitespace as well as contiguous bytes (e.g. `B00740`).
+fn parse_hex_bytes(s: &str) -> Result<Vec<u8>, &'static str> {
+    let mut bytes = vec![];
+    for token in s.split_whitespace() {
+        if token.len() % 2 != 0 || !token.is_ascii() {
+            return Err("hex bytes must consist of two digits");
+        }
+        for i in (0..token.len()).step_by(2) {
+            let byte = u8::from_str_radix(&token[i..i + 2], 16).map_err(|_| "invalid hex byte")?;
+            bytes.push(byte);
+        }
+    }
+    if bytes.is_empty() {
+        return Err("no bytes given");
+    }
+    Ok(bytes)
+}
+
+fn describe_short_message(msg: &impl ShortMessage) -> String {
+    use ShortMessageType as T;
+    let data_1 = msg.data_byte_1().get();
+    let data_2 = msg.data_byte_2().get();
+    let channel = msg.channel().map(|ch| ch.get() + 1).unwrap_or_default();
+    match msg.r#type() {
+        T::NoteOn => format!("Note on, channel {channel}, key {data_1}, velocity {data_2}"),
+        T::NoteOff => format!("Note off, channel {channel}, key {data_1}, velocity {data_2}"),
+        T::PolyphonicKeyPressure => {
+            format!("Polyphonic after touch, channel {channel}, key {data_1}, amount {data_2}")
+        }
+        T::ControlChange => format!("CC, channel {channel}, controller {data_1}, value {data_2}"),
+        T::ProgramChange => format!("Program change, channel {channel}, program {data_1}"),
+        T::ChannelPressure => format!("Channel after touch, channel {channel}, amount {data_1}"),
+        T::PitchBendChange => {
+            let value = ((data_2 as u16) << 7) | data_1 as u16;
+            format!("Pitch bend, channel {channel}, value {value}")
+        }
+        t => format!("{t:?}"),
+    }
+}
+
+fn describe_raw(bytes: Option<&[u8]>) -> String {
+    match bytes {
+        Some([0xF0, ..]) => "Sys-ex".to_owned(),
+        _ => "Raw MIDI".to_owned(),
     }
 }
 
@@ -345,3 +608,59 @@ impl AsRef<reaper_low::raw::MIDI_event_t> for RawMidiEvent {
         unsafe { &*(self as *const RawMidiEvent as *const reaper_low::raw::MIDI_event_t) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_midi::test_util::{channel as ch, controller_number as cn, *};
+    use helgoboss_midi::{RawShortMessage, U14};
+
+    type TestValue = MidiSourceValue<'static, RawShortMessage>;
+
+    #[test]
+    fn hex_string_round_trip() {
+        // Given
+        let note = TestValue::Plain(note_on(0, 60, 100));
+        let program = TestValue::Plain(program_change(2, 5));
+        let sys_ex = TestValue::from_hex_string("F0 7E 7F 06 01 F7").unwrap();
+        // When
+        // Then
+        assert_eq!(note.to_hex_string().unwrap(), "90 3C 64");
+        assert_eq!(program.to_hex_string().unwrap(), "C2 05");
+        assert_eq!(TestValue::from_hex_string("90 3C 64"), Ok(note));
+        assert_eq!(TestValue::from_hex_string("c205"), Ok(program));
+        assert_eq!(sys_ex.to_hex_string().unwrap(), "F0 7E 7F 06 01 F7");
+        assert!(matches!(sys_ex, MidiSourceValue::Raw { .. }));
+        assert!(TestValue::from_hex_string("").is_err());
+        assert!(TestValue::from_hex_string("B0 7 40").is_err());
+        assert!(TestValue::from_hex_string("B0 07 XY").is_err());
+    }
+
+    #[test]
+    fn describe() {
+        // Given
+        let cc = TestValue::Plain(control_change(0, 7, 64));
+        let cc_14_bit = TestValue::ControlChange14Bit(ControlChange14BitMessage::new(
+            ch(1),
+            cn(7),
+            U14::new(8192),
+        ));
+        let sys_ex: MidiSourceValue<RawShortMessage> =
+            MidiSourceValue::BorrowedSysEx(&[0xF0, 0x7E, 0xF7]);
+        // When
+        // Then
+        assert_eq!(
+            cc.describe(),
+            "CC, channel 1, controller 7, value 64 [B0 07 40]"
+        );
+        assert_eq!(
+            cc_14_bit.describe(),
+            "14-bit CC, channel 2, controller 7, value 8192 [B1 07 40 B1 27 00]"
+        );
+        assert_eq!(sys_ex.describe(), "Sys-ex [F0 7E F7]");
+        assert_eq!(
+            TestValue::Tempo(Bpm::new_panic(120.0)).describe(),
+            "Tempo 120.00 bpm"
+        );
+    }
+}
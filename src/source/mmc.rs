@@ -0,0 +1,182 @@
+use crate::MidiTimeCode;
+use derive_more::Display;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use strum::EnumIter;
+
+/// Device ID which addresses all devices.
+pub const MMC_ALL_DEVICES: u8 = 0x7f;
+const MMC_LOCATE_COMMAND_BYTE: u8 = 0x44;
+
+/// MIDI Machine Control command.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize_repr,
+    Deserialize_repr,
+)]
+#[repr(usize)]
+pub enum MmcCommand {
+    #[default]
+    #[display(fmt = "Play")]
+    Play = 0,
+    #[display(fmt = "Stop")]
+    Stop = 1,
+    #[display(fmt = "Pause")]
+    Pause = 2,
+    #[display(fmt = "Record (punch in)")]
+    RecordStrobe = 3,
+    #[display(fmt = "Record exit (punch out)")]
+    RecordExit = 4,
+    #[display(fmt = "Fast forward")]
+    FastForward = 5,
+    #[display(fmt = "Rewind")]
+    Rewind = 6,
+    #[display(fmt = "Locate")]
+    Locate = 7,
+}
+
+impl MmcCommand {
+    fn from_command_byte(byte: u8) -> Option<Self> {
+        use MmcCommand::*;
+        let command = match byte {
+            0x01 => Stop,
+            // Deferred play is sent by some devices instead of play
+            0x02 | 0x03 => Play,
+            0x04 => FastForward,
+            0x05 => Rewind,
+            0x06 => RecordStrobe,
+            0x07 => RecordExit,
+            0x09 => Pause,
+            MMC_LOCATE_COMMAND_BYTE => Locate,
+            _ => return None,
+        };
+        Some(command)
+    }
+
+    fn command_byte(&self) -> u8 {
+        use MmcCommand::*;
+        match self {
+            Stop => 0x01,
+            Play => 0x02,
+            FastForward => 0x04,
+            Rewind => 0x05,
+            RecordStrobe => 0x06,
+            RecordExit => 0x07,
+            Pause => 0x09,
+            Locate => MMC_LOCATE_COMMAND_BYTE,
+        }
+    }
+}
+
+/// A parsed MIDI Machine Control sys-ex message.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct MmcMessage {
+    pub device_id: u8,
+    pub command: MmcCommand,
+    /// Target position, only set for [`MmcCommand::Locate`].
+    pub locate_target: Option<MidiTimeCode>,
+}
+
+impl MmcMessage {
+    /// Parses an MMC command message (`F0 7F <device> 06 <command> ... F7`).
+    pub fn from_sysex(bytes: &[u8]) -> Option<Self> {
+        let [0xf0, 0x7f, device_id, 0x06, command_byte, ref rest @ ..] = *bytes else {
+            return None;
+        };
+        let command = MmcCommand::from_command_byte(command_byte)?;
+        let locate_target = if command == MmcCommand::Locate {
+            // Only the "target" sub command is supported
+            match *rest {
+                [0x06, 0x01, hh, mm, ss, ff, _, 0xf7] => {
+                    Some(MidiTimeCode::from_hour_byte(hh, mm, ss, ff))
+                }
+                _ => return None,
+            }
+        } else {
+            if rest != [0xf7] {
+                return None;
+            }
+            None
+        };
+        let msg = Self {
+            device_id,
+            command,
+            locate_target,
+        };
+        Some(msg)
+    }
+
+    /// Returns the sys-ex bytes of this message.
+    pub fn to_sysex(&self) -> Vec<u8> {
+        let mut bytes = vec![
+            0xf0,
+            0x7f,
+            self.device_id,
+            0x06,
+            self.command.command_byte(),
+        ];
+        if self.command == MmcCommand::Locate {
+            let tc = self.locate_target.unwrap_or_default();
+            let hour_byte = (tc.frame_rate.to_bits() << 5) | tc.hours;
+            bytes.extend([
+                0x06, 0x01, hour_byte, tc.minutes, tc.seconds, tc.frames, 0x00,
+            ]);
+        }
+        bytes.push(0xf7);
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MtcFrameRate;
+
+    #[test]
+    fn parse_and_generate() {
+        // Given
+        let play = [0xf0, 0x7f, 0x7f, 0x06, 0x02, 0xf7];
+        let locate = [
+            0xf0, 0x7f, 0x01, 0x06, 0x44, 0x06, 0x01, 0x61, 0x02, 0x03, 0x04, 0x00, 0xf7,
+        ];
+        // When
+        let play_msg = MmcMessage::from_sysex(&play).unwrap();
+        let locate_msg = MmcMessage::from_sysex(&locate).unwrap();
+        // Then
+        assert_eq!(play_msg.device_id, MMC_ALL_DEVICES);
+        assert_eq!(play_msg.command, MmcCommand::Play);
+        assert_eq!(play_msg.locate_target, None);
+        assert_eq!(play_msg.to_sysex(), play);
+        assert_eq!(locate_msg.device_id, 1);
+        assert_eq!(locate_msg.command, MmcCommand::Locate);
+        assert_eq!(
+            locate_msg.locate_target,
+            Some(MidiTimeCode {
+                hours: 1,
+                minutes: 2,
+                seconds: 3,
+                frames: 4,
+                frame_rate: MtcFrameRate::Fps30,
+            })
+        );
+        assert_eq!(locate_msg.to_sysex(), locate);
+        assert_eq!(
+            MmcMessage::from_sysex(&[0xf0, 0x7f, 0x7f, 0x06, 0x02, 0x00, 0xf7]),
+            None
+        );
+        assert_eq!(
+            MmcMessage::from_sysex(&[0xf0, 0x7e, 0x7f, 0x06, 0x02, 0xf7]),
+            None
+        );
+    }
+}
@@ -7,12 +7,74 @@ pub use midi_source::*;
 mod osc_source;
 pub use osc_source::*;
 
+mod osc_address_pattern;
+pub use osc_address_pattern::*;
+
+mod osc_slip;
+pub use osc_slip::*;
+
+mod osc_query;
+pub use osc_query::*;
+
 mod raw_midi;
 pub use raw_midi::*;
 
+mod ump;
+pub use ump::*;
+
+mod mpe;
+pub use mpe::*;
+
+mod mtc;
+pub use mtc::*;
+
+mod mmc;
+pub use mmc::*;
+
+mod control_change_14_bit_pairer;
+pub use control_change_14_bit_pairer::*;
+
+mod control_change_pair;
+pub use control_change_pair::*;
+
+mod bank_program_change;
+pub use bank_program_change::*;
+
+mod parameter_number_combiner;
+pub use parameter_number_combiner::*;
+
+mod encoder_detection;
+pub use encoder_detection::*;
+
+mod chord_source;
+pub use chord_source::*;
+
+mod pressure_increment_source;
+pub use pressure_increment_source::*;
+
+mod sys_ex_polling;
+pub use sys_ex_polling::*;
+
+mod hui;
+pub use hui::*;
+
+mod mackie_v_pot_ring;
+pub use mackie_v_pot_ring::*;
+
+mod capture_source;
+pub use capture_source::*;
+
+mod source_fingerprint;
+pub use source_fingerprint::*;
+
 mod midi_source_script;
 pub use midi_source_script::*;
 
+#[cfg(feature = "mlua")]
+mod lua_midi_source_script;
+#[cfg(feature = "mlua")]
+pub use lua_midi_source_script::*;
+
 mod feedback_script;
 pub use feedback_script::*;
 
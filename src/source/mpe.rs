@@ -0,0 +1,266 @@
+use crate::MidiSourceValue;
+use derive_more::Display;
+use helgoboss_midi::{Channel, KeyNumber, ShortMessage, StructuredShortMessage};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use strum::EnumIter;
+
+/// CC74, used by MPE for the "slide" dimension (vertical finger movement).
+pub const MPE_SLIDE_CONTROLLER_NUMBER: u8 = 74;
+const CHANNEL_COUNT: usize = 16;
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize_repr,
+    Deserialize_repr,
+)]
+#[repr(usize)]
+pub enum MpeZoneKind {
+    /// Master channel 1, member channels ascending from channel 2.
+    #[default]
+    #[display(fmt = "Lower zone")]
+    Lower = 0,
+    /// Master channel 16, member channels descending from channel 15.
+    #[display(fmt = "Upper zone")]
+    Upper = 1,
+}
+
+/// An MPE zone, consisting of a master channel and a number of member channels.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct MpeZone {
+    kind: MpeZoneKind,
+    member_channel_count: u8,
+}
+
+impl Default for MpeZone {
+    fn default() -> Self {
+        Self::new(MpeZoneKind::Lower, 15)
+    }
+}
+
+impl MpeZone {
+    /// Creates a zone. The member channel count is clamped to 1..=15.
+    pub fn new(kind: MpeZoneKind, member_channel_count: u8) -> Self {
+        Self {
+            kind,
+            member_channel_count: member_channel_count.clamp(1, 15),
+        }
+    }
+
+    pub fn kind(&self) -> MpeZoneKind {
+        self.kind
+    }
+
+    pub fn member_channel_count(&self) -> u8 {
+        self.member_channel_count
+    }
+
+    pub fn master_channel(&self) -> Channel {
+        match self.kind {
+            MpeZoneKind::Lower => Channel::new(0),
+            MpeZoneKind::Upper => Channel::new(15),
+        }
+    }
+
+    pub fn contains_member_channel(&self, channel: Channel) -> bool {
+        let ch = channel.get();
+        match self.kind {
+            MpeZoneKind::Lower => (1..=self.member_channel_count).contains(&ch),
+            MpeZoneKind::Upper => (15 - self.member_channel_count..15).contains(&ch),
+        }
+    }
+}
+
+/// Per-note expression dimension of MPE.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize_repr,
+    Deserialize_repr,
+)]
+#[repr(usize)]
+pub enum MpeDimension {
+    #[default]
+    #[display(fmt = "Pitch bend")]
+    PitchBend = 0,
+    #[display(fmt = "Pressure")]
+    Pressure = 1,
+    #[display(fmt = "Slide (CC74)")]
+    Slide = 2,
+}
+
+impl MpeDimension {
+    /// Returns the dimension of the given message if it's a per-note expression message.
+    pub fn of(msg: &impl ShortMessage) -> Option<MpeDimension> {
+        use StructuredShortMessage::*;
+        let dimension = match msg.to_structured() {
+            PitchBendChange { .. } => MpeDimension::PitchBend,
+            ChannelPressure { .. } => MpeDimension::Pressure,
+            ControlChange {
+                controller_number, ..
+            } if controller_number.get() == MPE_SLIDE_CONTROLLER_NUMBER => MpeDimension::Slide,
+            _ => return None,
+        };
+        Some(dimension)
+    }
+}
+
+/// Observes a stream of short MIDI messages and remembers which note is currently playing on which
+/// channel, so that per-channel expression messages of MPE controllers can be attributed to notes.
+///
+/// Pitch bend, channel pressure and slide (CC74) messages on channels with a playing note are
+/// turned into [`MidiSourceValue::Mpe`]. Everything else is passed through as it is, including
+/// expression messages which are sent before the note-on (as MPE recommends for initializing
+/// the note).
+#[derive(Clone, Debug)]
+pub struct MpeNoteTracker {
+    playing_notes: [Option<KeyNumber>; CHANNEL_COUNT],
+}
+
+impl Default for MpeNoteTracker {
+    fn default() -> Self {
+        Self {
+            playing_notes: [None; CHANNEL_COUNT],
+        }
+    }
+}
+
+impl MpeNoteTracker {
+    /// Feeds the given message into the tracker and returns the value which should be processed.
+    pub fn feed<M: ShortMessage + Copy>(&mut self, msg: M) -> MidiSourceValue<'static, M> {
+        use StructuredShortMessage::*;
+        match msg.to_structured() {
+            NoteOn {
+                channel,
+                key_number,
+                velocity,
+            } if velocity.get() > 0 => {
+                self.playing_notes[channel.get() as usize] = Some(key_number);
+            }
+            NoteOn {
+                channel,
+                key_number,
+                ..
+            }
+            | NoteOff {
+                channel,
+                key_number,
+                ..
+            } => {
+                let slot = &mut self.playing_notes[channel.get() as usize];
+                if *slot == Some(key_number) {
+                    *slot = None;
+                }
+            }
+            _ => {
+                if MpeDimension::of(&msg).is_some() {
+                    if let Some(channel) = msg.channel() {
+                        if let Some(key_number) = self.playing_notes[channel.get() as usize] {
+                            return MidiSourceValue::Mpe { key_number, msg };
+                        }
+                    }
+                }
+            }
+        }
+        MidiSourceValue::Plain(msg)
+    }
+
+    /// Returns the note which is currently playing on the given channel.
+    pub fn playing_note(&self, channel: Channel) -> Option<KeyNumber> {
+        self.playing_notes[channel.get() as usize]
+    }
+
+    /// Forgets all playing notes.
+    pub fn reset(&mut self) {
+        self.playing_notes = [None; CHANNEL_COUNT];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_midi::test_util::*;
+    use helgoboss_midi::RawShortMessage;
+
+    #[test]
+    fn track_notes() {
+        // Given
+        let mut tracker = MpeNoteTracker::default();
+        let mut feed = |msg: RawShortMessage| tracker.feed(msg);
+        // When
+        // Then
+        assert_eq!(
+            feed(pitch_bend_change(2, 9000)),
+            MidiSourceValue::Plain(pitch_bend_change(2, 9000))
+        );
+        assert_eq!(
+            feed(note_on(2, 60, 100)),
+            MidiSourceValue::Plain(note_on(2, 60, 100))
+        );
+        assert_eq!(
+            feed(pitch_bend_change(2, 9000)),
+            MidiSourceValue::Mpe {
+                key_number: key_number(60),
+                msg: pitch_bend_change(2, 9000)
+            }
+        );
+        assert_eq!(
+            feed(control_change(2, 74, 20)),
+            MidiSourceValue::Mpe {
+                key_number: key_number(60),
+                msg: control_change(2, 74, 20)
+            }
+        );
+        assert_eq!(
+            feed(control_change(2, 7, 20)),
+            MidiSourceValue::Plain(control_change(2, 7, 20))
+        );
+        assert_eq!(
+            feed(channel_pressure(3, 20)),
+            MidiSourceValue::Plain(channel_pressure(3, 20))
+        );
+        feed(note_on(2, 60, 0));
+        assert_eq!(
+            feed(channel_pressure(2, 20)),
+            MidiSourceValue::Plain(channel_pressure(2, 20))
+        );
+    }
+
+    #[test]
+    fn zones() {
+        // Given
+        let lower = MpeZone::new(MpeZoneKind::Lower, 7);
+        let upper = MpeZone::new(MpeZoneKind::Upper, 7);
+        // When
+        // Then
+        assert_eq!(lower.master_channel(), Channel::new(0));
+        assert!(!lower.contains_member_channel(Channel::new(0)));
+        assert!(lower.contains_member_channel(Channel::new(1)));
+        assert!(lower.contains_member_channel(Channel::new(7)));
+        assert!(!lower.contains_member_channel(Channel::new(8)));
+        assert_eq!(upper.master_channel(), Channel::new(15));
+        assert!(!upper.contains_member_channel(Channel::new(15)));
+        assert!(upper.contains_member_channel(Channel::new(14)));
+        assert!(upper.contains_member_channel(Channel::new(8)));
+        assert!(!upper.contains_member_channel(Channel::new(7)));
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{AbstractTimestamp, ControlEvent, MidiSourceValue};
+use derive_more::Display;
+use helgoboss_midi::{ShortMessage, ShortMessageType};
+
+/// Maximum position representable with MIDI Time Code (24 hours).
+pub const MIDI_TIME_CODE_MAX_SECONDS: f64 = 24.0 * 60.0 * 60.0;
+
+/// Frame rate of MIDI Time Code.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default, Display)]
+pub enum MtcFrameRate {
+    #[display(fmt = "24 fps")]
+    Fps24,
+    #[display(fmt = "25 fps")]
+    Fps25,
+    #[display(fmt = "29.97 fps (drop frame)")]
+    Fps2997DropFrame,
+    #[default]
+    #[display(fmt = "30 fps")]
+    Fps30,
+}
+
+impl MtcFrameRate {
+    fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            0 => Self::Fps24,
+            1 => Self::Fps25,
+            2 => Self::Fps2997DropFrame,
+            _ => Self::Fps30,
+        }
+    }
+
+    pub(crate) fn to_bits(self) -> u8 {
+        match self {
+            Self::Fps24 => 0,
+            Self::Fps25 => 1,
+            Self::Fps2997DropFrame => 2,
+            Self::Fps30 => 3,
+        }
+    }
+
+    pub fn frames_per_second(&self) -> f64 {
+        match self {
+            Self::Fps24 => 24.0,
+            Self::Fps25 => 25.0,
+            Self::Fps2997DropFrame => 30.0 * 1000.0 / 1001.0,
+            Self::Fps30 => 30.0,
+        }
+    }
+}
+
+/// A complete MIDI Time Code position.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct MidiTimeCode {
+    pub hours: u8,
+    pub minutes: u8,
+    pub seconds: u8,
+    pub frames: u8,
+    pub frame_rate: MtcFrameRate,
+}
+
+impl MidiTimeCode {
+    /// Parses an MTC full frame message (`F0 7F <device> 01 01 hh mm ss ff F7`).
+    ///
+    /// Full frame messages are sent when the position jumps (e.g. when locating while stopped).
+    pub fn from_full_frame_sysex(bytes: &[u8]) -> Option<Self> {
+        match *bytes {
+            [0xf0, 0x7f, _, 0x01, 0x01, hh, mm, ss, ff, 0xf7] => {
+                Some(Self::from_hour_byte(hh, mm, ss, ff))
+            }
+            _ => None,
+        }
+    }
+
+    /// Creates a time code at 30 fps from the given position, clamped to the 24-hour MTC range.
+    pub fn from_seconds(seconds: f64) -> Self {
+        let frame_rate = MtcFrameRate::Fps30;
+        let fps = frame_rate.frames_per_second() as u32;
+        let max_frames = MIDI_TIME_CODE_MAX_SECONDS as u32 * fps - 1;
+        let total_frames = ((seconds.max(0.0) * fps as f64).round() as u32).min(max_frames);
+        let total_seconds = total_frames / fps;
+        Self {
+            hours: (total_seconds / 3600) as u8,
+            minutes: (total_seconds / 60 % 60) as u8,
+            seconds: (total_seconds % 60) as u8,
+            frames: (total_frames % fps) as u8,
+            frame_rate,
+        }
+    }
+
+    /// The hour byte also carries the frame rate in bits 5 and 6.
+    pub(crate) fn from_hour_byte(hour_byte: u8, minutes: u8, seconds: u8, frames: u8) -> Self {
+        Self {
+            hours: hour_byte & 0x1f,
+            minutes: minutes & 0x3f,
+            seconds: seconds & 0x3f,
+            frames: frames & 0x1f,
+            frame_rate: MtcFrameRate::from_bits(hour_byte >> 5),
+        }
+    }
+
+    /// Returns the position in seconds.
+    pub fn to_seconds(&self) -> f64 {
+        let whole_seconds =
+            self.hours as u32 * 3600 + self.minutes as u32 * 60 + self.seconds as u32;
+        whole_seconds as f64 + self.frames as f64 / self.frame_rate.frames_per_second()
+    }
+}
+
+/// Observes a stream of short MIDI messages and assembles MIDI Time Code quarter frame messages
+/// into complete positions.
+///
+/// A position is spread across 8 quarter frame messages. It's emitted as
+/// [`MidiSourceValue::TimeCode`] whenever the last piece arrives and all pieces have been
+/// received. As the MTC specification defines, the position refers to the moment when the
+/// first piece was received, so that's also the timestamp of the emitted value. Everything else is
+/// passed through as it is.
+#[derive(Clone, Debug)]
+pub struct MtcQuarterFrameAssembler<S: AbstractTimestamp> {
+    nibbles: [u8; 8],
+    /// Bit mask of the pieces received so far.
+    received_pieces: u8,
+    first_piece_timestamp: Option<S>,
+}
+
+impl<S: AbstractTimestamp> Default for MtcQuarterFrameAssembler<S> {
+    fn default() -> Self {
+        Self {
+            nibbles: Default::default(),
+            received_pieces: 0,
+            first_piece_timestamp: None,
+        }
+    }
+}
+
+impl<S: AbstractTimestamp> MtcQuarterFrameAssembler<S> {
+    /// Feeds the given message into the assembler and returns the value which should be
+    /// processed, if any. Quarter frames which don't complete a position are consumed.
+    pub fn feed<M: ShortMessage + Copy>(
+        &mut self,
+        msg: M,
+        timestamp: S,
+    ) -> Option<ControlEvent<MidiSourceValue<'static, M>, S>> {
+        if msg.r#type() != ShortMessageType::TimeCodeQuarterFrame {
+            return Some(ControlEvent::new(MidiSourceValue::Plain(msg), timestamp));
+        }
+        let data = msg.data_byte_1().get();
+        let piece = (data >> 4) & 0x07;
+        if piece == 0 {
+            // New position starts
+            self.received_pieces = 0;
+            self.first_piece_timestamp = Some(timestamp);
+        }
+        self.nibbles[piece as usize] = data & 0x0f;
+        self.received_pieces |= 1 << piece;
+        if piece != 7 || self.received_pieces != 0xff {
+            return None;
+        }
+        let byte = |i: usize| self.nibbles[i] | (self.nibbles[i + 1] << 4);
+        let time_code = MidiTimeCode::from_hour_byte(byte(6), byte(4), byte(2), byte(0));
+        let value = MidiSourceValue::TimeCode(time_code);
+        Some(ControlEvent::new(
+            value,
+            self.first_piece_timestamp.unwrap_or(timestamp),
+        ))
+    }
+
+    /// Forgets all pieces received so far.
+    pub fn reset(&mut self) {
+        self.received_pieces = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::*;
+    use helgoboss_midi::test_util::*;
+    use helgoboss_midi::{RawShortMessage, ShortMessageFactory, U7};
+    use std::time::{Duration, Instant};
+
+    fn quarter_frame(piece: u8, nibble: u8) -> RawShortMessage {
+        RawShortMessage::from_bytes((0xf1, U7::new((piece << 4) | nibble), U7::new(0))).unwrap()
+    }
+
+    #[test]
+    fn assemble_quarter_frames() {
+        // Given
+        let mut assembler = MtcQuarterFrameAssembler::default();
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        // 01:02:03:04 at 25 fps
+        let nibbles = [0x4, 0x0, 0x3, 0x0, 0x2, 0x0, 0x1, 0x2];
+        // When
+        let events: Vec<_> = nibbles
+            .iter()
+            .enumerate()
+            .map(|(piece, nibble)| {
+                assembler.feed(quarter_frame(piece as u8, *nibble), at(10 * piece as u64))
+            })
+            .collect();
+        // Then
+        assert!(events[..7].iter().all(|v| v.is_none()));
+        assert_eq!(events[7].as_ref().map(|e| e.timestamp()), Some(at(0)));
+        let values: Vec<_> = events
+            .into_iter()
+            .map(|e| e.map(|e| e.into_payload()))
+            .collect();
+        let expected = MidiTimeCode {
+            hours: 1,
+            minutes: 2,
+            seconds: 3,
+            frames: 4,
+            frame_rate: MtcFrameRate::Fps25,
+        };
+        assert_eq!(values[7], Some(MidiSourceValue::TimeCode(expected)));
+        assert_abs_diff_eq!(expected.to_seconds(), 3723.16);
+        assert_eq!(
+            assembler
+                .feed(control_change(0, 7, 100), at(100))
+                .map(|e| e.into_payload()),
+            Some(MidiSourceValue::Plain(control_change(0, 7, 100)))
+        );
+        // Incomplete sequence
+        assembler.feed(quarter_frame(0, 0), at(110));
+        assert_eq!(assembler.feed(quarter_frame(7, 0), at(120)), None);
+    }
+
+    #[test]
+    fn full_frame() {
+        // Given
+        let bytes = [0xf0, 0x7f, 0x7f, 0x01, 0x01, 0x61, 0x02, 0x03, 0x04, 0xf7];
+        // When
+        let time_code = MidiTimeCode::from_full_frame_sysex(&bytes);
+        // Then
+        assert_eq!(
+            time_code,
+            Some(MidiTimeCode {
+                hours: 1,
+                minutes: 2,
+                seconds: 3,
+                frames: 4,
+                frame_rate: MtcFrameRate::Fps30,
+            })
+        );
+        assert_eq!(MidiTimeCode::from_full_frame_sysex(&[0xf0, 0xf7]), None);
+        assert_eq!(
+            MidiTimeCode::from_seconds(3723.5),
+            MidiTimeCode {
+                hours: 1,
+                minutes: 2,
+                seconds: 3,
+                frames: 15,
+                frame_rate: MtcFrameRate::Fps30,
+            }
+        );
+    }
+}
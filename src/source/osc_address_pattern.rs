@@ -0,0 +1,151 @@
+use std::ops::Range;
+
+const WILDCARD_CHARS: &[char] = &['?', '*', '[', ']', '{', '}'];
+
+/// Returns whether the given string contains OSC pattern matching characters, so it can't be used
+/// as a concrete address (e.g. for sending feedback).
+pub fn is_osc_address_pattern(s: &str) -> bool {
+    s.contains(WILDCARD_CHARS)
+}
+
+/// Checks if the given address matches the given OSC address pattern.
+///
+/// Supports the wildcards of the OSC 1.0 specification:
+///
+/// - `?` matches any single character
+/// - `*` matches any sequence of zero or more characters
+/// - `[abc]`, `[a-z]` and `[!abc]` match any character in (or not in) the given set
+/// - `{foo,bar}` matches any of the given strings
+///
+/// Wildcards never match the part separator `/`.
+pub fn osc_address_matches(pattern: &str, address: &str) -> bool {
+    pattern == address || match_pattern(pattern.as_bytes(), address.as_bytes(), 0).is_some()
+}
+
+/// Returns the portion of the address which has been matched by the first wildcard of the
+/// pattern, e.g. `"3"` for pattern `/fader/*` and address `/fader/3`.
+///
+/// Returns `None` if the address doesn't match or the pattern doesn't contain wildcards.
+pub fn capture_osc_address<'a>(pattern: &str, address: &'a str) -> Option<&'a str> {
+    let range = match_pattern(pattern.as_bytes(), address.as_bytes(), 0)??;
+    address.get(range)
+}
+
+/// Returns `None` if there's no match, otherwise the range of the address which has been matched
+/// by the first wildcard (if any).
+///
+/// `pos` is the position of the given address remainder within the complete address.
+fn match_pattern(pattern: &[u8], address: &[u8], pos: usize) -> Option<Option<Range<usize>>> {
+    let Some((&first, pattern_rest)) = pattern.split_first() else {
+        return address.is_empty().then_some(None);
+    };
+    match first {
+        b'*' => {
+            let max_len = address
+                .iter()
+                .position(|c| *c == b'/')
+                .unwrap_or(address.len());
+            (0..=max_len).find_map(|len| {
+                match_pattern(pattern_rest, &address[len..], pos + len)?;
+                Some(Some(pos..pos + len))
+            })
+        }
+        b'?' => {
+            let (&c, address_rest) = address.split_first()?;
+            if c == b'/' {
+                return None;
+            }
+            match_pattern(pattern_rest, address_rest, pos + 1)?;
+            Some(Some(pos..pos + 1))
+        }
+        b'[' => {
+            let end = pattern_rest.iter().position(|c| *c == b']')?;
+            let (&c, address_rest) = address.split_first()?;
+            if c == b'/' || !char_class_contains(&pattern_rest[..end], c) {
+                return None;
+            }
+            match_pattern(&pattern_rest[end + 1..], address_rest, pos + 1)?;
+            Some(Some(pos..pos + 1))
+        }
+        b'{' => {
+            let end = pattern_rest.iter().position(|c| *c == b'}')?;
+            let remaining_pattern = &pattern_rest[end + 1..];
+            pattern_rest[..end].split(|c| *c == b',').find_map(|alt| {
+                let address_rest = address.strip_prefix(alt)?;
+                match_pattern(remaining_pattern, address_rest, pos + alt.len())?;
+                Some(Some(pos..pos + alt.len()))
+            })
+        }
+        _ => {
+            let (&c, address_rest) = address.split_first()?;
+            if c != first {
+                return None;
+            }
+            match_pattern(pattern_rest, address_rest, pos + 1)
+        }
+    }
+}
+
+fn char_class_contains(class: &[u8], c: u8) -> bool {
+    let (negate, class) = match class.split_first() {
+        Some((b'!', rest)) => (true, rest),
+        _ => (false, class),
+    };
+    let mut contains = false;
+    let mut i = 0;
+    while i < class.len() {
+        if i + 2 < class.len() && class[i + 1] == b'-' {
+            contains |= (class[i]..=class[i + 2]).contains(&c);
+            i += 3;
+        } else {
+            contains |= class[i] == c;
+            i += 1;
+        }
+    }
+    contains != negate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards() {
+        // Given
+        // When
+        // Then
+        assert!(osc_address_matches("/fader/1", "/fader/1"));
+        assert!(!osc_address_matches("/fader/1", "/fader/2"));
+        assert!(osc_address_matches("/fader/?", "/fader/2"));
+        assert!(!osc_address_matches("/fader/?", "/fader/12"));
+        assert!(osc_address_matches("/fader/*", "/fader/12"));
+        assert!(osc_address_matches("/fader/*", "/fader/"));
+        assert!(!osc_address_matches("/fader/*", "/fader/1/touch"));
+        assert!(osc_address_matches("/*/1", "/fader/1"));
+        assert!(osc_address_matches("/fader/[1-4]", "/fader/3"));
+        assert!(!osc_address_matches("/fader/[1-4]", "/fader/5"));
+        assert!(osc_address_matches("/fader/[!1-4]", "/fader/5"));
+        assert!(osc_address_matches("/fader/[13]", "/fader/3"));
+        assert!(osc_address_matches("/{fader,knob}/1", "/knob/1"));
+        assert!(!osc_address_matches("/{fader,knob}/1", "/button/1"));
+        assert!(!osc_address_matches("/fader/[1-4", "/fader/3"));
+    }
+
+    #[test]
+    fn capture() {
+        // Given
+        // When
+        // Then
+        assert_eq!(capture_osc_address("/fader/*", "/fader/12"), Some("12"));
+        assert_eq!(
+            capture_osc_address("/track/*/volume", "/track/7/volume"),
+            Some("7")
+        );
+        assert_eq!(
+            capture_osc_address("/{fader,knob}/*", "/knob/1"),
+            Some("knob")
+        );
+        assert_eq!(capture_osc_address("/fader/*", "/knob/12"), None);
+        assert_eq!(capture_osc_address("/fader/1", "/fader/1"), None);
+    }
+}
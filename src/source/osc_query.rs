@@ -0,0 +1,212 @@
+use crate::{
+    Interval, OscArgCombination, OscArgDescriptor, OscSource, OscTypeTag,
+    DEFAULT_OSC_ARG_VALUE_RANGE,
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// A node of an OSCQuery namespace, as returned by OSCQuery servers in JSON format.
+///
+/// Nodes with a type are OSC methods (addresses which accept or send values), nodes without type
+/// are just containers of other nodes.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct OscQueryNode {
+    pub full_path: String,
+    /// OSC type tag string, e.g. `"f"` or `"ff"`.
+    #[serde(default)]
+    pub r#type: Option<String>,
+    /// One entry per argument.
+    #[serde(default)]
+    pub range: Vec<OscQueryRange>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub access: Option<OscQueryAccess>,
+    #[serde(default)]
+    pub contents: BTreeMap<String, OscQueryNode>,
+}
+
+/// Value range of one argument of an OSCQuery node.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct OscQueryRange {
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// Enumeration of possible values.
+    #[serde(default)]
+    pub vals: Vec<serde_json::Value>,
+}
+
+/// Whether the value of an OSCQuery node can be read and/or written.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Deserialize)]
+#[serde(from = "u8")]
+pub enum OscQueryAccess {
+    None,
+    ReadOnly,
+    WriteOnly,
+    ReadWrite,
+}
+
+impl From<u8> for OscQueryAccess {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::ReadOnly,
+            2 => Self::WriteOnly,
+            3 => Self::ReadWrite,
+            _ => Self::None,
+        }
+    }
+}
+
+impl OscQueryAccess {
+    pub fn is_readable(self) -> bool {
+        matches!(self, Self::ReadOnly | Self::ReadWrite)
+    }
+
+    pub fn is_writable(self) -> bool {
+        matches!(self, Self::WriteOnly | Self::ReadWrite)
+    }
+}
+
+impl OscQueryNode {
+    /// Parses a node (usually the root node) from OSCQuery JSON.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Returns this node and all its descendants which are OSC methods, ordered by path.
+    pub fn methods(&self) -> Vec<&OscQueryNode> {
+        let mut methods = vec![];
+        self.collect_methods(&mut methods);
+        methods
+    }
+
+    fn collect_methods<'a>(&'a self, methods: &mut Vec<&'a OscQueryNode>) {
+        if self.type_tags().next().is_some() {
+            methods.push(self);
+        }
+        for child in self.contents.values() {
+            child.collect_methods(methods);
+        }
+    }
+
+    /// Returns the type tags of the arguments of this node.
+    ///
+    /// Unsupported type tag characters are skipped.
+    pub fn type_tags(&self) -> impl Iterator<Item = OscTypeTag> + '_ {
+        self.r#type
+            .iter()
+            .flat_map(|t| t.chars())
+            .filter_map(OscTypeTag::from_type_tag_char)
+    }
+
+    /// Builds an OSC source which is configured according to the metadata of this node.
+    ///
+    /// The first argument determines type and value range. If all arguments have the same type,
+    /// the source covers all of them (e.g. XY pads). A string argument with an enumeration of
+    /// possible values is turned into a string enumeration source.
+    ///
+    /// Returns `None` if this node is not an OSC method.
+    pub fn to_osc_source(&self) -> Option<OscSource> {
+        let type_tags: Vec<_> = self.type_tags().collect();
+        let first_type_tag = *type_tags.first()?;
+        let first_range = self.range.first();
+        let value_range = match first_range {
+            Some(OscQueryRange {
+                min: Some(min),
+                max: Some(max),
+                ..
+            }) if first_type_tag.supports_value_range() && min < max => Interval::new(*min, *max),
+            _ => DEFAULT_OSC_ARG_VALUE_RANGE,
+        };
+        let arg_count = type_tags
+            .iter()
+            .take_while(|t| **t == first_type_tag)
+            .count();
+        let desc = OscArgDescriptor::new(0, first_type_tag, false, value_range)
+            .with_arg_count(arg_count as u32, OscArgCombination::Average);
+        let string_values = match first_range {
+            Some(r) if first_type_tag == OscTypeTag::String => r
+                .vals
+                .iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_owned()))
+                .collect(),
+            _ => vec![],
+        };
+        let source = OscSource::new(self.full_path.clone(), Some(desc), vec![])
+            .with_string_values(string_values);
+        Some(source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_sources() {
+        // Given
+        let root = OscQueryNode::from_json(
+            r#"
+            {
+                "FULL_PATH": "/",
+                "CONTENTS": {
+                    "mixer": {
+                        "FULL_PATH": "/mixer",
+                        "CONTENTS": {
+                            "volume": {
+                                "FULL_PATH": "/mixer/volume",
+                                "TYPE": "f",
+                                "RANGE": [{ "MIN": -60.0, "MAX": 12.0 }],
+                                "DESCRIPTION": "Master volume",
+                                "ACCESS": 3
+                            },
+                            "pan": {
+                                "FULL_PATH": "/mixer/pan",
+                                "TYPE": "ff"
+                            }
+                        }
+                    },
+                    "transport": {
+                        "FULL_PATH": "/transport",
+                        "TYPE": "s",
+                        "RANGE": [{ "VALS": ["stopped", "playing"] }],
+                        "ACCESS": 1
+                    }
+                }
+            }
+            "#,
+        )
+        .unwrap();
+        // When
+        let methods = root.methods();
+        // Then
+        let paths: Vec<_> = methods.iter().map(|m| m.full_path.as_str()).collect();
+        assert_eq!(paths, vec!["/mixer/pan", "/mixer/volume", "/transport"]);
+        let volume = methods[1];
+        assert_eq!(volume.description.as_deref(), Some("Master volume"));
+        assert!(volume.access.unwrap().is_writable());
+        let volume_source = volume.to_osc_source().unwrap();
+        assert_eq!(volume_source.address_pattern(), "/mixer/volume");
+        let volume_desc = volume_source.arg_descriptor().unwrap();
+        assert_eq!(volume_desc.type_tag(), OscTypeTag::Float);
+        assert_eq!(volume_desc.value_range(), Interval::new(-60.0, 12.0));
+        let pan_desc = methods[0]
+            .to_osc_source()
+            .unwrap()
+            .arg_descriptor()
+            .unwrap();
+        assert_eq!(pan_desc.arg_count(), 2);
+        assert_eq!(pan_desc.value_range(), DEFAULT_OSC_ARG_VALUE_RANGE);
+        let transport = methods[2];
+        assert!(!transport.access.unwrap().is_writable());
+        assert_eq!(
+            transport.to_osc_source().unwrap().string_values(),
+            &["stopped".to_owned(), "playing".to_owned()]
+        );
+        assert_eq!(root.to_osc_source(), None);
+    }
+}
@@ -0,0 +1,192 @@
+use rosc::OscPacket;
+
+/// Marks the end (and optionally the start) of a SLIP frame.
+const SLIP_END: u8 = 0xC0;
+/// Escapes END and ESC bytes within a SLIP frame.
+const SLIP_ESC: u8 = 0xDB;
+const SLIP_ESC_END: u8 = 0xDC;
+const SLIP_ESC_ESC: u8 = 0xDD;
+
+/// Default maximum size of one packet, generous enough for large OSC bundles.
+pub const DEFAULT_SLIP_MAX_PACKET_SIZE: usize = 64 * 1024;
+
+/// Splits a SLIP-framed byte stream (as used for OSC 1.1 over TCP and serial connections) into
+/// packets.
+///
+/// Bytes are fed one by one because stream reads can end anywhere within a packet. Doesn't
+/// allocate after the internal buffer has grown to the size of the largest packet.
+#[derive(Clone, Debug)]
+pub struct SlipDecoder {
+    buffer: Vec<u8>,
+    state: SlipDecoderState,
+    max_packet_size: usize,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum SlipDecoderState {
+    Normal,
+    Escaping,
+    /// The current packet is invalid or too large, skip until the next END.
+    Discarding,
+    /// The buffer contains a packet which has been returned already.
+    Complete,
+}
+
+impl Default for SlipDecoder {
+    fn default() -> Self {
+        Self::new(DEFAULT_SLIP_MAX_PACKET_SIZE)
+    }
+}
+
+impl SlipDecoder {
+    /// Creates a decoder which discards packets larger than the given size.
+    pub fn new(max_packet_size: usize) -> Self {
+        Self {
+            buffer: vec![],
+            state: SlipDecoderState::Normal,
+            max_packet_size,
+        }
+    }
+
+    /// Feeds the given byte into the decoder.
+    ///
+    /// Returns the packet which has been completed by this byte, if any. Empty packets, invalid
+    /// escape sequences and oversized packets are skipped.
+    pub fn feed(&mut self, byte: u8) -> Option<&[u8]> {
+        use SlipDecoderState::*;
+        if self.state == Complete {
+            self.buffer.clear();
+            self.state = Normal;
+        }
+        let decoded_byte = match (self.state, byte) {
+            (_, SLIP_END) => {
+                let was_valid = self.state == Normal;
+                if was_valid && !self.buffer.is_empty() {
+                    self.state = Complete;
+                    return Some(&self.buffer);
+                }
+                self.buffer.clear();
+                self.state = Normal;
+                return None;
+            }
+            (Discarding, _) => return None,
+            (Normal, SLIP_ESC) => {
+                self.state = Escaping;
+                return None;
+            }
+            (Normal, b) => b,
+            (Escaping, SLIP_ESC_END) => SLIP_END,
+            (Escaping, SLIP_ESC_ESC) => SLIP_ESC,
+            (Escaping, _) => {
+                self.state = Discarding;
+                return None;
+            }
+            (Complete, _) => unreachable!("complete state is reset above"),
+        };
+        if self.buffer.len() >= self.max_packet_size {
+            self.state = Discarding;
+            return None;
+        }
+        self.buffer.push(decoded_byte);
+        self.state = Normal;
+        None
+    }
+
+    /// Like [`Self::feed`] but also parses the completed packet as OSC packet.
+    pub fn feed_osc(&mut self, byte: u8) -> Option<Result<OscPacket, &'static str>> {
+        let packet = self.feed(byte)?;
+        Some(parse_osc_packet(packet))
+    }
+
+    /// Forgets the partially received packet, e.g. after the connection has been reestablished.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.state = SlipDecoderState::Normal;
+    }
+}
+
+/// Parses one OSC packet (message or bundle).
+pub fn parse_osc_packet(bytes: &[u8]) -> Result<OscPacket, &'static str> {
+    let (_, packet) = rosc::decoder::decode_udp(bytes).map_err(|_| "invalid OSC packet")?;
+    Ok(packet)
+}
+
+/// Wraps the given packet into a SLIP frame.
+///
+/// The frame starts with an END byte as well, which makes the receiver drop any garbage received
+/// before.
+pub fn slip_encode(packet: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(packet.len() + 2);
+    frame.push(SLIP_END);
+    for &b in packet {
+        match b {
+            SLIP_END => frame.extend_from_slice(&[SLIP_ESC, SLIP_ESC_END]),
+            SLIP_ESC => frame.extend_from_slice(&[SLIP_ESC, SLIP_ESC_ESC]),
+            _ => frame.push(b),
+        }
+    }
+    frame.push(SLIP_END);
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rosc::{OscMessage, OscType};
+
+    #[test]
+    fn split_stream() {
+        // Given
+        let mut decoder = SlipDecoder::default();
+        let mut stream = slip_encode(&[0x01, SLIP_END, 0x02]);
+        stream.extend(slip_encode(&[SLIP_ESC]));
+        // Invalid escape sequence
+        stream.extend([0x03, SLIP_ESC, 0x04, SLIP_END]);
+        stream.extend(slip_encode(&[0x05]));
+        // When
+        let packets: Vec<Vec<u8>> = stream
+            .into_iter()
+            .filter_map(|b| decoder.feed(b).map(|p| p.to_vec()))
+            .collect();
+        // Then
+        assert_eq!(
+            packets,
+            vec![vec![0x01, SLIP_END, 0x02], vec![SLIP_ESC], vec![0x05]]
+        );
+    }
+
+    #[test]
+    fn oversized_packet() {
+        // Given
+        let mut decoder = SlipDecoder::new(2);
+        let mut stream = slip_encode(&[0x01, 0x02, 0x03]);
+        stream.extend(slip_encode(&[0x04, 0x05]));
+        // When
+        let packets: Vec<Vec<u8>> = stream
+            .into_iter()
+            .filter_map(|b| decoder.feed(b).map(|p| p.to_vec()))
+            .collect();
+        // Then
+        assert_eq!(packets, vec![vec![0x04, 0x05]]);
+    }
+
+    #[test]
+    fn osc_over_slip() {
+        // Given
+        let msg = OscMessage {
+            addr: "/track/1/volume".to_owned(),
+            args: vec![OscType::Float(0.5)],
+        };
+        let packet = rosc::encoder::encode(&OscPacket::Message(msg.clone())).unwrap();
+        let stream = slip_encode(&packet);
+        let mut decoder = SlipDecoder::default();
+        // When
+        let decoded: Vec<_> = stream
+            .into_iter()
+            .filter_map(|b| decoder.feed_osc(b))
+            .collect();
+        // Then
+        assert_eq!(decoded, vec![Ok(OscPacket::Message(msg))]);
+        assert!(parse_osc_packet(&[0x01, 0x02]).is_err());
+    }
+}
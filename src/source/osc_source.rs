@@ -2,16 +2,21 @@ use crate::DetailedSourceCharacter::Trigger;
 use std::cmp;
 
 use crate::{
-    format_percentage_without_unit, parse_percentage_without_unit, AbsoluteValue, ControlValue,
-    DetailedSourceCharacter, DiscreteIncrement, FeedbackValue, Fraction, Interval, RgbColor,
-    SourceCharacter, UnitValue, UNIT_INTERVAL,
+    capture_osc_address, format_percentage_without_unit, is_osc_address_pattern,
+    osc_address_matches, parse_percentage_without_unit, AbsoluteValue, ControlValue,
+    DetailedSourceCharacter, DiscreteIncrement, FeedbackValue, Fraction, Interval, PropProvider,
+    RgbColor, SourceCharacter, SourceCharacterInfo, TextualFeedbackTemplate, UnitIncrement,
+    UnitValue, UNIT_INTERVAL,
 };
 use derive_more::Display;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
-use rosc::{OscColor, OscMessage, OscType};
+use rosc::{
+    OscArray, OscBundle, OscColor, OscMessage, OscMidiMessage, OscPacket, OscTime, OscType,
+};
 use serde::{Deserialize, Serialize};
 use serde_with::{DeserializeFromStr, SerializeDisplay};
 use std::convert::TryInto;
+use std::iter;
 use strum::EnumIter;
 
 /// With OSC it's easy: The source address is the address!
@@ -20,11 +25,60 @@ pub type OscSourceAddress = String;
 #[derive(Clone, PartialEq, Debug)]
 pub struct OscSource {
     /// To filter out the correct messages.
+    ///
+    /// Only interpreted as OSC address pattern (see [`osc_address_matches`]) if
+    /// `uses_address_pattern_matching` or `captures_address_value` is set. Otherwise wildcard
+    /// characters are matched literally.
     address_pattern: String,
+    /// If set, the address is interpreted as OSC address pattern. If it actually contains
+    /// wildcards, no feedback is sent.
+    uses_address_pattern_matching: bool,
+    /// If set, the portion of the address matched by the first wildcard (e.g. `3` in `/fader/3`
+    /// for pattern `/fader/*`) is interpreted as number and used as control value instead of an
+    /// argument. Implies address pattern matching.
+    captures_address_value: bool,
     /// To process a value (not just trigger).
     arg_descriptor: Option<OscArgDescriptor>,
     /// If non-empty, these are used for mapping feedback data to arguments.
     feedback_args: Vec<OscFeedbackProp>,
+    /// Ordered list of possible values of a string argument (e.g. "stopped", "playing").
+    ///
+    /// If non-empty, string arguments are matched against this list and the index of the match
+    /// is used as discrete value. Feedback works the other way around.
+    string_values: Vec<String>,
+    /// If non-empty, this is used for building feedback messages. Takes precedence over
+    /// `feedback_args`.
+    feedback_template: Vec<OscFeedbackArgTemplate>,
+    /// Messages which are sent together with the main feedback message as one bundle (e.g.
+    /// color and label of the same control).
+    additional_feedback_messages: Vec<OscFeedbackMessageTemplate>,
+}
+
+/// Template of an additional OSC feedback message (see [`OscSource::feedback_bundle`]).
+#[derive(Clone, PartialEq, Debug)]
+pub struct OscFeedbackMessageTemplate {
+    /// Must be a concrete address if the source uses address pattern matching, otherwise the
+    /// message is not sent.
+    pub address: String,
+    pub args: Vec<OscFeedbackArgTemplate>,
+}
+
+/// One argument of an OSC feedback message template.
+#[derive(Clone, PartialEq, Debug)]
+pub enum OscFeedbackArgTemplate {
+    /// Argument derived from the feedback value or its style.
+    Prop(OscFeedbackProp),
+    /// Fixed argument.
+    Constant(OscType),
+    /// String argument which is rendered as [`crate::TextualFeedbackTemplate`], so placeholders
+    /// such as `{{ target.name }}` are replaced with the corresponding prop values.
+    Text(String),
+}
+
+impl From<OscFeedbackProp> for OscFeedbackArgTemplate {
+    fn from(value: OscFeedbackProp) -> Self {
+        Self::Prop(value)
+    }
 }
 
 #[derive(
@@ -88,8 +142,71 @@ pub struct OscArgDescriptor {
     type_tag: OscTypeTag,
     /// Interpret 1 values as increments and 0 values as decrements.
     is_relative: bool,
+    /// If relative, interpret values as deltas instead (e.g. -3 for 3 decrements).
+    is_delta: bool,
     /// Value range for all range types (double, float, int, long).
     value_range: Interval<f64>,
+    /// Number of consecutive arguments, starting at `index` (e.g. 2 for XY pads).
+    arg_count: u32,
+    /// How to combine the values of multiple arguments (or array elements) into one control
+    /// value.
+    combination: OscArgCombination,
+    /// Port, status byte and first data byte of MIDI arguments.
+    ///
+    /// Used for filtering incoming MIDI arguments and for building outgoing ones. The value is
+    /// always carried by the second data byte.
+    midi_address: Option<[u8; 3]>,
+}
+
+/// Describes how multiple OSC arguments are combined into one control value.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+#[repr(usize)]
+pub enum OscArgCombination {
+    #[default]
+    #[display(fmt = "Average")]
+    Average,
+    #[display(fmt = "Minimum")]
+    Minimum,
+    #[display(fmt = "Maximum")]
+    Maximum,
+}
+
+impl OscArgCombination {
+    /// Returns `None` if there are no values.
+    pub fn combine(self, values: impl Iterator<Item = AbsoluteValue>) -> Option<AbsoluteValue> {
+        let mut count = 0;
+        let mut result: Option<f64> = None;
+        for v in values {
+            let v = v.to_unit_value().get();
+            count += 1;
+            result = Some(match (self, result) {
+                (_, None) => v,
+                (OscArgCombination::Average, Some(sum)) => sum + v,
+                (OscArgCombination::Minimum, Some(min)) => min.min(v),
+                (OscArgCombination::Maximum, Some(max)) => max.max(v),
+            });
+        }
+        let mut result = result?;
+        if self == OscArgCombination::Average {
+            result /= count as f64;
+        }
+        Some(AbsoluteValue::Continuous(UnitValue::new_clamped(result)))
+    }
 }
 
 impl OscArgDescriptor {
@@ -103,14 +220,48 @@ impl OscArgDescriptor {
             index,
             type_tag,
             is_relative,
+            is_delta: false,
             value_range,
+            arg_count: 1,
+            combination: Default::default(),
+            midi_address: None,
         }
     }
 
+    /// Sets port, status byte and first data byte of MIDI arguments.
+    pub fn with_midi_address(mut self, midi_address: Option<[u8; 3]>) -> Self {
+        self.midi_address = midi_address;
+        self
+    }
+
+    pub fn midi_address(self) -> Option<[u8; 3]> {
+        self.midi_address
+    }
+
+    /// Makes the descriptor cover the given number of consecutive arguments.
+    pub fn with_arg_count(mut self, arg_count: u32, combination: OscArgCombination) -> Self {
+        self.arg_count = arg_count.max(1);
+        self.combination = combination;
+        self
+    }
+
     pub fn index(self) -> u32 {
         self.index
     }
 
+    pub fn arg_count(self) -> u32 {
+        self.arg_count
+    }
+
+    pub fn combination(self) -> OscArgCombination {
+        self.combination
+    }
+
+    fn arg_indexes(self) -> impl Iterator<Item = usize> {
+        let start = self.index as usize;
+        start..start + self.arg_count as usize
+    }
+
     pub fn type_tag(self) -> OscTypeTag {
         self.type_tag
     }
@@ -119,6 +270,51 @@ impl OscArgDescriptor {
         self.is_relative
     }
 
+    /// Makes a relative descriptor interpret numeric values as deltas.
+    ///
+    /// Int and long values are emitted as discrete increments. Float and double values are
+    /// emitted as continuous increments, relative to the span of the value range.
+    pub fn with_delta_values(mut self, is_delta: bool) -> Self {
+        self.is_delta = is_delta;
+        self
+    }
+
+    pub fn is_delta(self) -> bool {
+        self.is_delta
+    }
+
+    fn interprets_deltas(self) -> bool {
+        self.is_relative && self.is_delta
+    }
+
+    /// Returns `None` if the delta is zero or not finite, if the value range is empty or if the
+    /// argument is not numeric.
+    fn convert_arg_to_increment(self, arg: &OscType) -> Option<ControlValue> {
+        use OscType::*;
+        let continuous = |delta: f64| {
+            let span = self.value_range.span();
+            if span == 0.0 || !delta.is_finite() {
+                return None;
+            }
+            let inc = UnitIncrement::new_clamped_checked(delta / span)?;
+            Some(ControlValue::RelativeContinuous(inc))
+        };
+        match arg {
+            Int(i) => Some(ControlValue::RelativeDiscrete(
+                DiscreteIncrement::new_checked(*i)?,
+            )),
+            Long(l) => {
+                let delta = (*l).clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+                Some(ControlValue::RelativeDiscrete(
+                    DiscreteIncrement::new_checked(delta)?,
+                ))
+            }
+            Float(f) => continuous(*f as f64),
+            Double(d) => continuous(*d),
+            _ => None,
+        }
+    }
+
     pub fn value_range(&self) -> Interval<f64> {
         self.value_range
     }
@@ -134,8 +330,54 @@ impl OscArgDescriptor {
     }
 
     pub fn to_concrete_args(self, value: FeedbackValue) -> Option<Vec<OscType>> {
-        self.type_tag
-            .to_concrete_args(self.index, value, self.value_range)
+        let mut args = match self.type_tag {
+            OscTypeTag::Midi => {
+                let [port, status, data1] = self.midi_address.unwrap_or_default();
+                let value_7_bit = convert_to_7_bit_value(value.to_numeric()?.value);
+                let arg = OscType::Midi(OscMidiMessage {
+                    port,
+                    status,
+                    data1,
+                    data2: value_7_bit,
+                });
+                let mut vec = vec![OscType::Nil; (self.index + 1) as usize];
+                vec[self.index as usize] = arg;
+                vec
+            }
+            _ => self
+                .type_tag
+                .to_concrete_args(self.index, value, self.value_range)?,
+        };
+        // All arguments in the range get the same value
+        let arg = args[self.index as usize].clone();
+        args.resize(self.index as usize + self.arg_count as usize, arg);
+        Some(args)
+    }
+
+    fn convert_arg(self, arg: &OscType) -> Option<AbsoluteValue> {
+        use OscType::*;
+        let v = match arg {
+            // Brightness
+            Color(c) => {
+                let c = convert_osc_color_to_rgb_color(c);
+                let max = c.r().max(c.g()).max(c.b());
+                AbsoluteValue::Continuous(UnitValue::new(max as f64 / 255.0))
+            }
+            Midi(m) => {
+                if self
+                    .midi_address
+                    .is_some_and(|a| a != [m.port, m.status, m.data1])
+                {
+                    return None;
+                }
+                AbsoluteValue::Discrete(Fraction::new((m.data2 & 0x7f) as u32, 127))
+            }
+            Array(a) => self
+                .combination
+                .combine(a.content.iter().filter_map(|e| self.convert_arg(e)))?,
+            _ => convert_arg_to_absolute_value(arg, self.value_range)?,
+        };
+        Some(v)
     }
 
     fn from_arg(index: u32, arg: &OscType) -> Self {
@@ -144,14 +386,28 @@ impl OscArgDescriptor {
             type_tag: OscTypeTag::from_arg(arg),
             // Relative is the exception, so we reset it when learning.
             is_relative: false,
+            is_delta: false,
             value_range: match get_range_value(arg) {
                 None => DEFAULT_OSC_ARG_VALUE_RANGE,
                 Some(v) => Interval::new_auto(0.0, v),
             },
+            arg_count: 1,
+            combination: Default::default(),
+            midi_address: match arg {
+                OscType::Midi(m) => Some([m.port, m.status, m.data1]),
+                _ => None,
+            },
         }
     }
 }
 
+fn convert_to_7_bit_value(value: AbsoluteValue) -> u8 {
+    match value {
+        AbsoluteValue::Continuous(v) => (v.get() * 127.0).round() as u8,
+        AbsoluteValue::Discrete(f) => f.actual().min(127) as u8,
+    }
+}
+
 pub const DEFAULT_OSC_ARG_VALUE_RANGE: Interval<f64> = UNIT_INTERVAL;
 
 fn get_range_value(arg: &OscType) -> Option<f64> {
@@ -195,7 +451,7 @@ pub enum OscTypeTag {
     Inf,
     #[display(fmt = "Int")]
     Int,
-    #[display(fmt = "String (feedback only)")]
+    #[display(fmt = "String")]
     String,
     #[display(fmt = "Blob (ignored)")]
     Blob,
@@ -205,11 +461,11 @@ pub enum OscTypeTag {
     Long,
     #[display(fmt = "Char (ignored)")]
     Char,
-    #[display(fmt = "Color (feedback only)")]
+    #[display(fmt = "Color")]
     Color,
-    #[display(fmt = "MIDI (ignored)")]
+    #[display(fmt = "MIDI")]
     Midi,
-    #[display(fmt = "Array (ignored)")]
+    #[display(fmt = "Array")]
     Array,
 }
 
@@ -240,6 +496,30 @@ impl OscTypeTag {
         }
     }
 
+    /// Returns the type tag which corresponds to the given character of an OSC type tag string.
+    ///
+    /// `T` and `F` both map to [`Self::Bool`], `[` maps to [`Self::Array`].
+    pub fn from_type_tag_char(c: char) -> Option<Self> {
+        let tag = match c {
+            'i' => Self::Int,
+            'f' => Self::Float,
+            's' | 'S' => Self::String,
+            'b' => Self::Blob,
+            't' => Self::Time,
+            'h' => Self::Long,
+            'd' => Self::Double,
+            'c' => Self::Char,
+            'r' => Self::Color,
+            'm' => Self::Midi,
+            'T' | 'F' => Self::Bool,
+            '[' => Self::Array,
+            'N' => Self::Nil,
+            'I' => Self::Inf,
+            _ => return None,
+        };
+        Some(tag)
+    }
+
     pub fn to_concrete_args(
         self,
         index: u32,
@@ -261,6 +541,13 @@ impl OscTypeTag {
             }
             Long => convert_feedback_prop_to_arg(OscFeedbackProp::ValueAsLong, &v, value_range)?,
             Color => convert_feedback_prop_to_arg(OscFeedbackProp::Color, &v, value_range)?,
+            Array => OscType::Array(OscArray {
+                content: vec![convert_feedback_prop_to_arg(
+                    OscFeedbackProp::ValueAsFloat,
+                    &v,
+                    value_range,
+                )?],
+            }),
             _ => return None,
         };
         // Send nil for all other elements
@@ -269,16 +556,21 @@ impl OscTypeTag {
         Some(vec)
     }
 
+    /// Strings support control only if the source defines string values (see
+    /// [`OscSource::supports_control`]).
     pub fn supports_control(self) -> bool {
         use OscTypeTag::*;
-        matches!(self, Float | Double | Bool | Nil | Inf | Int | Long)
+        matches!(
+            self,
+            Float | Double | Bool | Nil | Inf | Int | Long | Color | Midi | Array
+        )
     }
 
     pub fn supports_feedback(self) -> bool {
         use OscTypeTag::*;
         matches!(
             self,
-            Float | Double | Bool | Nil | Inf | Int | String | Long | Color
+            Float | Double | Bool | Nil | Inf | Int | String | Long | Color | Midi | Array
         )
     }
 
@@ -323,11 +615,84 @@ impl OscSource {
     ) -> Self {
         Self {
             address_pattern,
+            uses_address_pattern_matching: false,
+            captures_address_value: false,
             arg_descriptor,
             feedback_args,
+            string_values: vec![],
+            feedback_template: vec![],
+            additional_feedback_messages: vec![],
         }
     }
 
+    /// Makes the source build feedback messages from the given argument template.
+    pub fn with_feedback_template(
+        mut self,
+        feedback_template: Vec<OscFeedbackArgTemplate>,
+    ) -> Self {
+        self.feedback_template = feedback_template;
+        self
+    }
+
+    pub fn feedback_template(&self) -> &[OscFeedbackArgTemplate] {
+        &self.feedback_template
+    }
+
+    /// Makes the source send the given messages together with the main feedback message when
+    /// building feedback bundles (see [`Self::feedback_bundle`]).
+    pub fn with_additional_feedback_messages(
+        mut self,
+        messages: Vec<OscFeedbackMessageTemplate>,
+    ) -> Self {
+        self.additional_feedback_messages = messages;
+        self
+    }
+
+    pub fn additional_feedback_messages(&self) -> &[OscFeedbackMessageTemplate] {
+        &self.additional_feedback_messages
+    }
+
+    /// Makes the source match string arguments against the given ordered list of strings.
+    pub fn with_string_values(mut self, string_values: Vec<String>) -> Self {
+        self.string_values = string_values;
+        self
+    }
+
+    pub fn string_values(&self) -> &[String] {
+        &self.string_values
+    }
+
+    /// Returns whether incoming messages can be converted into control values.
+    pub fn supports_control(&self) -> bool {
+        match self.arg_descriptor {
+            // Acts like a trigger
+            None => true,
+            Some(desc) => desc.type_tag.supports_control() || self.uses_string_values(desc),
+        }
+    }
+
+    /// Makes the source interpret its address as OSC address pattern.
+    pub fn with_address_pattern_matching(mut self, uses_address_pattern_matching: bool) -> Self {
+        self.uses_address_pattern_matching = uses_address_pattern_matching;
+        self
+    }
+
+    pub fn uses_address_pattern_matching(&self) -> bool {
+        self.uses_address_pattern_matching
+    }
+
+    /// Makes the source use the portion of the address matched by the first wildcard as value.
+    ///
+    /// Implies address pattern matching.
+    pub fn with_address_value_capture(mut self, captures_address_value: bool) -> Self {
+        self.captures_address_value = captures_address_value;
+        self
+    }
+
+    pub fn captures_address_value(&self) -> bool {
+        self.captures_address_value
+    }
+
     pub fn from_source_value(msg: OscMessage, arg_index_hint: Option<u32>) -> OscSource {
         let arg_descriptor = OscArgDescriptor::from_msg(&msg, arg_index_hint.unwrap_or(0));
         OscSource::new(msg.addr, arg_descriptor, vec![])
@@ -343,69 +708,154 @@ impl OscSource {
 
     pub fn control(&self, msg: &OscMessage) -> Option<ControlValue> {
         let (absolute_value, is_relative) = {
-            if msg.addr != self.address_pattern {
+            if !self.address_matches(&msg.addr) {
                 return None;
             }
-            if let Some(desc) = self.arg_descriptor {
-                if let Some(arg) = msg.args.get(desc.index as usize) {
-                    use OscType::*;
-                    let v =
-                        match arg {
-                            Float(f) => AbsoluteValue::Continuous(
-                                map_continuous_from_range_to_unit(*f as f64, desc.value_range),
-                            ),
-                            Double(d) => AbsoluteValue::Continuous(
-                                map_continuous_from_range_to_unit(*d, desc.value_range),
-                            ),
-                            Bool(on) => AbsoluteValue::Continuous(if *on {
-                                UnitValue::MAX
-                            } else {
-                                UnitValue::MIN
-                            }),
-                            // Infinity/impulse or nil/null - act like a trigger.
-                            Inf | Nil => AbsoluteValue::Continuous(UnitValue::MAX),
-                            Int(i) => AbsoluteValue::Discrete(map_discrete_from_range_to_positive(
-                                *i,
-                                desc.value_range,
-                            )),
-                            Long(l) => {
-                                // TODO-low-discrete Maybe increase fraction integers to 64-bit? Right now
-                                //  we don't really take advantage of fractions, so we emit continuous control
-                                //  values as long as this doesn't change.
-                                AbsoluteValue::Continuous(map_continuous_from_range_to_unit(
-                                    *l as f64,
-                                    desc.value_range,
-                                ))
-                            }
-                            String(_) | Blob(_) | Time(_) | Char(_) | Color(_) | Midi(_)
-                            | Array(_) => return None,
-                        };
-                    (v, desc.is_relative)
-                } else {
-                    // Argument not found. Don't do anything.
-                    return None;
+            if self.captures_address_value {
+                (self.control_address_value(&msg.addr)?, false)
+            } else if let Some(desc) = self.arg_descriptor {
+                if desc.interprets_deltas() {
+                    // Deltas of multiple arguments are not combined
+                    return desc.convert_arg_to_increment(msg.args.get(desc.index as usize)?);
                 }
+                let v = if desc.arg_count > 1 {
+                    desc.combination.combine(
+                        desc.arg_indexes()
+                            .filter_map(|i| self.convert_arg(desc, msg.args.get(i)?)),
+                    )?
+                } else {
+                    // If argument not found or not supported, don't do anything.
+                    self.convert_arg(desc, msg.args.get(desc.index as usize)?)?
+                };
+                (v, desc.is_relative)
             } else {
                 // Source shall not look at any argument. Act like a trigger.
                 (AbsoluteValue::Continuous(UnitValue::MAX), false)
             }
         };
-        let control_value = if is_relative {
-            let inc = if absolute_value.is_on() { 1 } else { -1 };
-            ControlValue::RelativeDiscrete(DiscreteIncrement::new(inc))
+        Some(convert_to_control_value(absolute_value, is_relative))
+    }
+
+    /// Like [`Self::control`] but returns one control value for each argument in the argument
+    /// range instead of combining them (e.g. one for the x and one for the y axis of an XY pad).
+    /// Array arguments contribute one control value for each array element.
+    ///
+    /// The element is `None` if the corresponding argument is missing or not supported.
+    pub fn control_multiple(&self, msg: &OscMessage) -> Vec<Option<ControlValue>> {
+        match self.arg_descriptor {
+            Some(desc) if !self.captures_address_value => {
+                if !self.address_matches(&msg.addr) {
+                    return vec![];
+                }
+                let convert = |arg: &OscType| {
+                    if desc.interprets_deltas() {
+                        return desc.convert_arg_to_increment(arg);
+                    }
+                    let v = self.convert_arg(desc, arg)?;
+                    Some(convert_to_control_value(v, desc.is_relative))
+                };
+                desc.arg_indexes()
+                    .flat_map(|i| match msg.args.get(i) {
+                        None => vec![None],
+                        Some(OscType::Array(a)) => a.content.iter().map(convert).collect(),
+                        Some(arg) => vec![convert(arg)],
+                    })
+                    .collect()
+            }
+            _ => vec![self.control(msg)],
+        }
+    }
+
+    fn convert_arg(&self, desc: OscArgDescriptor, arg: &OscType) -> Option<AbsoluteValue> {
+        match arg {
+            OscType::String(s) if self.uses_string_values(desc) => {
+                let index = self.string_values.iter().position(|v| v == s)?;
+                let max = self.string_values.len() - 1;
+                Some(AbsoluteValue::Discrete(Fraction::new(
+                    index as u32,
+                    max as u32,
+                )))
+            }
+            _ => desc.convert_arg(arg),
+        }
+    }
+
+    fn feedback_string_value(&self, feedback_value: &FeedbackValue) -> Option<&str> {
+        let max = self.string_values.len().checked_sub(1)?;
+        let index = match feedback_value.to_numeric()?.value {
+            AbsoluteValue::Continuous(v) => (v.get() * max as f64).round() as usize,
+            AbsoluteValue::Discrete(f) => f.actual() as usize,
+        };
+        self.string_values.get(index.min(max)).map(|v| v.as_str())
+    }
+
+    fn uses_string_values(&self, desc: OscArgDescriptor) -> bool {
+        desc.type_tag == OscTypeTag::String && !self.string_values.is_empty()
+    }
+
+    fn interprets_address_as_pattern(&self) -> bool {
+        self.uses_address_pattern_matching || self.captures_address_value
+    }
+
+    fn address_matches(&self, addr: &str) -> bool {
+        if self.interprets_address_as_pattern() {
+            osc_address_matches(&self.address_pattern, addr)
         } else {
-            ControlValue::from_absolute(absolute_value)
+            self.address_pattern == addr
+        }
+    }
+
+    fn control_address_value(&self, addr: &str) -> Option<AbsoluteValue> {
+        let captured: f64 = capture_osc_address(&self.address_pattern, addr)?
+            .parse()
+            .ok()?;
+        let desc = self.arg_descriptor;
+        let value_range = desc
+            .map(|d| d.value_range)
+            .unwrap_or(DEFAULT_OSC_ARG_VALUE_RANGE);
+        let v = if desc.is_some_and(|d| d.type_tag.is_discrete()) {
+            AbsoluteValue::Discrete(map_discrete_from_range_to_positive(
+                captured.round() as i32,
+                value_range,
+            ))
+        } else {
+            AbsoluteValue::Continuous(map_continuous_from_range_to_unit(captured, value_range))
         };
-        Some(control_value)
+        Some(v)
     }
 
+    /// Formats the given value as number within the declared value range (if any), otherwise as
+    /// percentage.
     pub fn format_control_value(&self, value: ControlValue) -> Result<String, &'static str> {
-        let v = value.to_unit_value()?.get();
-        Ok(format_percentage_without_unit(v))
+        let v = value.to_unit_value()?;
+        let Some(desc) = self.arg_descriptor_with_custom_value_range() else {
+            return Ok(format_percentage_without_unit(v.get()));
+        };
+        let x = map_continuous_from_unit_to_range(v, desc.value_range);
+        let text = if desc.type_tag.is_discrete() {
+            format!("{}", x.round() as i64)
+        } else {
+            format!("{x:.3}")
+        };
+        Ok(text)
     }
 
+    /// Parses a number within the declared value range (if any), otherwise a percentage.
     pub fn parse_control_value(&self, text: &str) -> Result<UnitValue, &'static str> {
-        parse_percentage_without_unit(text)?.try_into()
+        let Some(desc) = self.arg_descriptor_with_custom_value_range() else {
+            return parse_percentage_without_unit(text)?.try_into();
+        };
+        let x: f64 = text.trim().parse().map_err(|_| "not a number")?;
+        if !desc.value_range.contains(x) {
+            return Err("value out of range");
+        }
+        Ok(map_continuous_from_range_to_unit(x, desc.value_range))
+    }
+
+    fn arg_descriptor_with_custom_value_range(&self) -> Option<OscArgDescriptor> {
+        self.arg_descriptor.filter(|d| {
+            d.type_tag.supports_value_range() && d.value_range != DEFAULT_OSC_ARG_VALUE_RANGE
+        })
     }
 
     pub fn character(&self) -> SourceCharacter {
@@ -414,6 +864,7 @@ impl OscSource {
             use OscTypeTag::*;
             match desc.type_tag {
                 Float | Double | Int | Long => RangeElement,
+                String if self.uses_string_values(desc) => RangeElement,
                 Bool | Nil | Inf => MomentaryButton,
                 _ => MomentaryButton,
             }
@@ -429,6 +880,9 @@ impl OscSource {
             } else {
                 use OscTypeTag::*;
                 match desc.type_tag {
+                    String if self.uses_string_values(desc) => {
+                        vec![DetailedSourceCharacter::RangeControl]
+                    }
                     Float | Double | Int | Long => vec![
                         DetailedSourceCharacter::RangeControl,
                         DetailedSourceCharacter::MomentaryVelocitySensitiveButton,
@@ -443,15 +897,65 @@ impl OscSource {
         }
     }
 
+    /// Returns the number of distinct values minus one, if the values are discrete.
+    pub fn max_discrete_value(&self) -> Option<u32> {
+        let desc = self.arg_descriptor?;
+        if desc.is_relative {
+            return None;
+        }
+        if self.uses_string_values(desc) {
+            return Some(self.string_values.len() as u32 - 1);
+        }
+        use OscTypeTag::*;
+        match desc.type_tag {
+            Int | Long => Some(clamp_to_positive(
+                round_value_range(desc.value_range).span(),
+            )),
+            Midi => Some(127),
+            _ => None,
+        }
+    }
+
+    /// Returns the expected character and value resolution of this source.
+    ///
+    /// Allocates!
+    pub fn character_info(&self) -> SourceCharacterInfo {
+        SourceCharacterInfo::new(
+            &self.possible_detailed_characters(),
+            self.max_discrete_value(),
+        )
+    }
+
     pub fn feedback(&self, feedback_value: FeedbackValue) -> Option<OscMessage> {
+        self.feedback_with_props(feedback_value, &|_: &str| None)
+    }
+
+    /// Like [`Self::feedback`] but uses the given prop provider for resolving text placeholders
+    /// in the feedback template.
+    pub fn feedback_with_props(
+        &self,
+        feedback_value: FeedbackValue,
+        prop_provider: &dyn PropProvider,
+    ) -> Option<OscMessage> {
+        if self.interprets_address_as_pattern() && is_osc_address_pattern(&self.address_pattern) {
+            // Feedback needs a concrete address
+            return None;
+        }
+        let value_range = self
+            .arg_descriptor
+            .map(|desc| desc.value_range)
+            .unwrap_or(DEFAULT_OSC_ARG_VALUE_RANGE);
         let msg = OscMessage {
             addr: self.address_pattern.clone(),
-            args: if !self.feedback_args.is_empty() {
+            args: if !self.feedback_template.is_empty() {
+                fill_feedback_template(
+                    &self.feedback_template,
+                    &feedback_value,
+                    value_range,
+                    prop_provider,
+                )
+            } else if !self.feedback_args.is_empty() {
                 // Explicit feedback args given.
-                let value_range = self
-                    .arg_descriptor
-                    .map(|desc| desc.value_range)
-                    .unwrap_or(DEFAULT_OSC_ARG_VALUE_RANGE);
                 self.feedback_args
                     .iter()
                     .map(|prop| {
@@ -461,7 +965,15 @@ impl OscSource {
                     .collect()
             } else if let Some(desc) = self.arg_descriptor {
                 // No explicit feedback args given. Just derive from argument descriptor.
-                desc.to_concrete_args(feedback_value)?
+                if self.uses_string_values(desc) {
+                    let value = self.feedback_string_value(&feedback_value)?;
+                    let arg = OscType::String(value.to_owned());
+                    let mut args = vec![OscType::Nil; desc.index as usize];
+                    args.resize(desc.index as usize + desc.arg_count as usize, arg);
+                    args
+                } else {
+                    desc.to_concrete_args(feedback_value)?
+                }
             } else {
                 // No arguments shall be sent.
                 vec![]
@@ -469,6 +981,103 @@ impl OscSource {
         };
         Some(msg)
     }
+
+    /// Like [`Self::feedback_with_props`] but returns a bundle which contains the main feedback
+    /// message followed by the additional feedback messages.
+    ///
+    /// Bundles are processed atomically by the receiver, so related messages (e.g. value, color
+    /// and label) can't interleave with other traffic.
+    pub fn feedback_bundle(
+        &self,
+        feedback_value: FeedbackValue,
+        prop_provider: &dyn PropProvider,
+    ) -> Option<OscBundle> {
+        let value_range = self
+            .arg_descriptor
+            .map(|desc| desc.value_range)
+            .unwrap_or(DEFAULT_OSC_ARG_VALUE_RANGE);
+        let additional_messages: Vec<_> = self
+            .additional_feedback_messages
+            .iter()
+            .filter(|t| {
+                !self.interprets_address_as_pattern() || !is_osc_address_pattern(&t.address)
+            })
+            .map(|t| OscMessage {
+                addr: t.address.clone(),
+                args: fill_feedback_template(&t.args, &feedback_value, value_range, prop_provider),
+            })
+            .collect();
+        let main_msg = self.feedback_with_props(feedback_value, prop_provider)?;
+        let bundle = OscBundle {
+            timetag: OSC_TIME_IMMEDIATELY,
+            content: iter::once(main_msg)
+                .chain(additional_messages)
+                .map(OscPacket::Message)
+                .collect(),
+        };
+        Some(bundle)
+    }
+}
+
+/// Special OSC time tag which means "process immediately".
+const OSC_TIME_IMMEDIATELY: OscTime = OscTime {
+    seconds: 0,
+    fractional: 1,
+};
+
+fn fill_feedback_template(
+    template: &[OscFeedbackArgTemplate],
+    feedback_value: &FeedbackValue,
+    value_range: Interval<f64>,
+    prop_provider: &dyn PropProvider,
+) -> Vec<OscType> {
+    template
+        .iter()
+        .map(|arg| match arg {
+            OscFeedbackArgTemplate::Prop(prop) => {
+                convert_feedback_prop_to_arg(*prop, feedback_value, value_range)
+                    .unwrap_or(OscType::Nil)
+            }
+            OscFeedbackArgTemplate::Constant(arg) => arg.clone(),
+            OscFeedbackArgTemplate::Text(text) => {
+                OscType::String(TextualFeedbackTemplate::parse(text).render(prop_provider))
+            }
+        })
+        .collect()
+}
+
+fn convert_arg_to_absolute_value(
+    arg: &OscType,
+    value_range: Interval<f64>,
+) -> Option<AbsoluteValue> {
+    use OscType::*;
+    let v = match arg {
+        Float(f) => {
+            AbsoluteValue::Continuous(map_continuous_from_range_to_unit(*f as f64, value_range))
+        }
+        Double(d) => AbsoluteValue::Continuous(map_continuous_from_range_to_unit(*d, value_range)),
+        Bool(on) => AbsoluteValue::Continuous(if *on { UnitValue::MAX } else { UnitValue::MIN }),
+        // Infinity/impulse or nil/null - act like a trigger.
+        Inf | Nil => AbsoluteValue::Continuous(UnitValue::MAX),
+        Int(i) => AbsoluteValue::Discrete(map_discrete_from_range_to_positive(*i, value_range)),
+        Long(l) => {
+            // TODO-low-discrete Maybe increase fraction integers to 64-bit? Right now
+            //  we don't really take advantage of fractions, so we emit continuous control
+            //  values as long as this doesn't change.
+            AbsoluteValue::Continuous(map_continuous_from_range_to_unit(*l as f64, value_range))
+        }
+        String(_) | Blob(_) | Time(_) | Char(_) | Color(_) | Midi(_) | Array(_) => return None,
+    };
+    Some(v)
+}
+
+fn convert_to_control_value(absolute_value: AbsoluteValue, is_relative: bool) -> ControlValue {
+    if is_relative {
+        let inc = if absolute_value.is_on() { 1 } else { -1 };
+        ControlValue::RelativeDiscrete(DiscreteIncrement::new(inc))
+    } else {
+        ControlValue::from_absolute(absolute_value)
+    }
 }
 
 fn convert_feedback_prop_to_arg(
@@ -522,6 +1131,11 @@ fn convert_color_to_rrggbb_string_arg(v: Option<RgbColor>) -> OscType {
     }
 }
 
+/// Converts an OSC color argument to an RGB color, ignoring the alpha channel.
+pub fn convert_osc_color_to_rgb_color(c: &OscColor) -> RgbColor {
+    RgbColor::new(c.red, c.green, c.blue)
+}
+
 fn convert_color_to_native_color_arg(v: Option<RgbColor>) -> OscType {
     match v {
         // Nil is hopefully interpreted as "Default color".
@@ -536,8 +1150,17 @@ fn convert_color_to_native_color_arg(v: Option<RgbColor>) -> OscType {
 }
 
 fn map_continuous_from_range_to_unit(x: f64, value_range: Interval<f64>) -> UnitValue {
+    let span = value_range.span();
+    if span == 0.0 {
+        // Degenerated range, everything at or above it is on
+        return if x < value_range.min_val() {
+            UnitValue::MIN
+        } else {
+            UnitValue::MAX
+        };
+    }
     // y = (x - min) / span
-    let y = (x - value_range.min_val()) / value_range.span();
+    let y = (x - value_range.min_val()) / span;
     UnitValue::new_clamped(y)
 }
 
@@ -571,3 +1194,455 @@ fn round_value_range(value_range: Interval<f64>) -> Interval<i32> {
 fn clamp_to_positive(v: i32) -> u32 {
     cmp::max(0, v) as u32
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FeedbackStyle, NumericFeedbackValue, PropValue};
+
+    #[test]
+    fn address_pattern() {
+        // Given
+        let source = OscSource::new(
+            "/fader/[1-4]".to_owned(),
+            Some(OscArgDescriptor::new(
+                0,
+                OscTypeTag::Float,
+                false,
+                DEFAULT_OSC_ARG_VALUE_RANGE,
+            )),
+            vec![],
+        );
+        let pattern_source = source.clone().with_address_pattern_matching(true);
+        let msg = |addr: &str| OscMessage {
+            addr: addr.to_owned(),
+            args: vec![OscType::Float(0.5)],
+        };
+        // When
+        // Then
+        assert_eq!(
+            pattern_source.control(&msg("/fader/2")),
+            Some(ControlValue::AbsoluteContinuous(UnitValue::new(0.5)))
+        );
+        assert_eq!(pattern_source.control(&msg("/fader/5")), None);
+        assert_eq!(pattern_source.feedback(FeedbackValue::Off), None);
+        // Without pattern matching, the address is matched literally
+        assert_eq!(source.control(&msg("/fader/2")), None);
+        assert_eq!(
+            source.control(&msg("/fader/[1-4]")),
+            Some(ControlValue::AbsoluteContinuous(UnitValue::new(0.5)))
+        );
+        assert!(source.feedback(FeedbackValue::Off).is_some());
+    }
+
+    #[test]
+    fn multiple_args() {
+        // Given
+        let desc = OscArgDescriptor::new(0, OscTypeTag::Float, false, DEFAULT_OSC_ARG_VALUE_RANGE)
+            .with_arg_count(2, OscArgCombination::Maximum);
+        let source = OscSource::new("/xy".to_owned(), Some(desc), vec![]);
+        let msg = OscMessage {
+            addr: "/xy".to_owned(),
+            args: vec![OscType::Float(0.25), OscType::Float(0.75)],
+        };
+        // When
+        // Then
+        assert_eq!(
+            source.control(&msg),
+            Some(ControlValue::AbsoluteContinuous(UnitValue::new(0.75)))
+        );
+        assert_eq!(
+            source.control_multiple(&msg),
+            vec![
+                Some(ControlValue::AbsoluteContinuous(UnitValue::new(0.25))),
+                Some(ControlValue::AbsoluteContinuous(UnitValue::new(0.75)))
+            ]
+        );
+        assert_eq!(
+            source.feedback(FeedbackValue::Off).unwrap().args,
+            vec![OscType::Float(0.0), OscType::Float(0.0)]
+        );
+    }
+
+    #[test]
+    fn color_midi_and_array_args() {
+        // Given
+        let source = |type_tag| {
+            let desc = OscArgDescriptor::new(0, type_tag, false, DEFAULT_OSC_ARG_VALUE_RANGE)
+                .with_midi_address(Some([0, 0xb0, 7]));
+            OscSource::new("/x".to_owned(), Some(desc), vec![])
+        };
+        let msg = |arg| OscMessage {
+            addr: "/x".to_owned(),
+            args: vec![arg],
+        };
+        let midi = |status, data1, data2| {
+            OscType::Midi(OscMidiMessage {
+                port: 0,
+                status,
+                data1,
+                data2,
+            })
+        };
+        let array = OscType::Array(OscArray {
+            content: vec![OscType::Float(0.25), OscType::Float(0.75)],
+        });
+        // When
+        // Then
+        let color = OscColor {
+            red: 0,
+            green: 51,
+            blue: 255,
+            alpha: 0,
+        };
+        assert_eq!(
+            convert_osc_color_to_rgb_color(&color),
+            RgbColor::new(0, 51, 255)
+        );
+        assert_eq!(
+            source(OscTypeTag::Color).control(&msg(OscType::Color(color))),
+            Some(ControlValue::AbsoluteContinuous(UnitValue::MAX))
+        );
+        assert_eq!(
+            source(OscTypeTag::Midi).control(&msg(midi(0xb0, 7, 127))),
+            Some(ControlValue::AbsoluteDiscrete(Fraction::new_max(127)))
+        );
+        assert_eq!(
+            source(OscTypeTag::Midi).control(&msg(midi(0xb0, 8, 127))),
+            None
+        );
+        assert_eq!(
+            source(OscTypeTag::Midi)
+                .feedback(FeedbackValue::Off)
+                .unwrap()
+                .args,
+            vec![midi(0xb0, 7, 0)]
+        );
+        assert_eq!(
+            source(OscTypeTag::Array).control(&msg(array.clone())),
+            Some(ControlValue::AbsoluteContinuous(UnitValue::new(0.5)))
+        );
+        assert_eq!(
+            source(OscTypeTag::Array).control_multiple(&msg(array)),
+            vec![
+                Some(ControlValue::AbsoluteContinuous(UnitValue::new(0.25))),
+                Some(ControlValue::AbsoluteContinuous(UnitValue::new(0.75)))
+            ]
+        );
+        assert_eq!(
+            source(OscTypeTag::Array)
+                .feedback(FeedbackValue::Off)
+                .unwrap()
+                .args,
+            vec![OscType::Array(OscArray {
+                content: vec![OscType::Float(0.0)]
+            })]
+        );
+    }
+
+    #[test]
+    fn string_values() {
+        // Given
+        let desc = OscArgDescriptor::new(0, OscTypeTag::String, false, DEFAULT_OSC_ARG_VALUE_RANGE);
+        let source = OscSource::new("/transport".to_owned(), Some(desc), vec![])
+            .with_string_values(vec![
+                "stopped".to_owned(),
+                "playing".to_owned(),
+                "recording".to_owned(),
+            ]);
+        let msg = |s: &str| OscMessage {
+            addr: "/transport".to_owned(),
+            args: vec![OscType::String(s.to_owned())],
+        };
+        let feedback = |value| {
+            let value =
+                FeedbackValue::Numeric(NumericFeedbackValue::new(Default::default(), value));
+            source.feedback(value).unwrap().args
+        };
+        // When
+        // Then
+        assert_eq!(
+            source.control(&msg("playing")),
+            Some(ControlValue::AbsoluteDiscrete(Fraction::new(1, 2)))
+        );
+        assert_eq!(source.control(&msg("paused")), None);
+        assert!(source.supports_control());
+        assert!(!OscSource::new("/transport".to_owned(), Some(desc), vec![]).supports_control());
+        assert_eq!(
+            feedback(AbsoluteValue::Discrete(Fraction::new(2, 2))),
+            vec![OscType::String("recording".to_owned())]
+        );
+        assert_eq!(
+            feedback(AbsoluteValue::Continuous(UnitValue::new(0.4))),
+            vec![OscType::String("playing".to_owned())]
+        );
+        assert_eq!(
+            feedback(AbsoluteValue::Discrete(Fraction::new(5, 5))),
+            vec![OscType::String("recording".to_owned())]
+        );
+    }
+
+    #[test]
+    fn feedback_template() {
+        // Given
+        let source =
+            OscSource::new("/track/1".to_owned(), None, vec![]).with_feedback_template(vec![
+                OscFeedbackArgTemplate::Constant(OscType::Int(1)),
+                OscFeedbackProp::ValueAsFloat.into(),
+                OscFeedbackArgTemplate::Text("{{ track.name | upper }}: {{ unknown }}".to_owned()),
+                OscFeedbackProp::Color.into(),
+            ]);
+        let value = FeedbackValue::Numeric(NumericFeedbackValue::new(
+            FeedbackStyle {
+                color: Some(RgbColor::new(0xff, 0x00, 0x80)),
+                background_color: None,
+            },
+            AbsoluteValue::Continuous(UnitValue::new(0.5)),
+        ));
+        let prop_provider =
+            |key: &str| (key == "track.name").then(|| PropValue::from("Bass".to_owned()));
+        // When
+        let msg = source.feedback_with_props(value, &prop_provider).unwrap();
+        // Then
+        assert_eq!(msg.addr, "/track/1");
+        assert_eq!(
+            msg.args,
+            vec![
+                OscType::Int(1),
+                OscType::Float(0.5),
+                OscType::String("BASS: ".to_owned()),
+                OscType::Color(OscColor {
+                    red: 0xff,
+                    green: 0x00,
+                    blue: 0x80,
+                    alpha: 255
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn feedback_bundle() {
+        // Given
+        let source = OscSource::new(
+            "/track/1/volume".to_owned(),
+            Some(OscArgDescriptor::new(
+                0,
+                OscTypeTag::Float,
+                false,
+                DEFAULT_OSC_ARG_VALUE_RANGE,
+            )),
+            vec![],
+        )
+        .with_additional_feedback_messages(vec![
+            OscFeedbackMessageTemplate {
+                address: "/track/1/color".to_owned(),
+                args: vec![OscFeedbackProp::ColorRrggbb.into()],
+            },
+            OscFeedbackMessageTemplate {
+                address: "/track/1/label".to_owned(),
+                args: vec![OscFeedbackArgTemplate::Text("{{ track.name }}".to_owned())],
+            },
+            OscFeedbackMessageTemplate {
+                address: "/track/*/label".to_owned(),
+                args: vec![],
+            },
+        ]);
+        let value = FeedbackValue::Numeric(NumericFeedbackValue::new(
+            FeedbackStyle {
+                color: Some(RgbColor::new(0xff, 0x00, 0x80)),
+                background_color: None,
+            },
+            AbsoluteValue::Continuous(UnitValue::new(0.5)),
+        ));
+        let prop_provider =
+            |key: &str| (key == "track.name").then(|| PropValue::from("Bass".to_owned()));
+        // When
+        let bundle = source.feedback_bundle(value, &prop_provider).unwrap();
+        // Then
+        let messages: Vec<_> = bundle
+            .content
+            .into_iter()
+            .map(|p| match p {
+                OscPacket::Message(msg) => msg,
+                OscPacket::Bundle(_) => panic!("expected message"),
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                OscMessage {
+                    addr: "/track/1/volume".to_owned(),
+                    args: vec![OscType::Float(0.5)],
+                },
+                OscMessage {
+                    addr: "/track/1/color".to_owned(),
+                    args: vec![OscType::String("FF0080".to_owned())],
+                },
+                OscMessage {
+                    addr: "/track/1/label".to_owned(),
+                    args: vec![OscType::String("Bass".to_owned())],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn address_value_capture() {
+        // Given
+        let source = OscSource::new(
+            "/scene/*".to_owned(),
+            Some(OscArgDescriptor::new(
+                0,
+                OscTypeTag::Int,
+                false,
+                Interval::new(0.0, 8.0),
+            )),
+            vec![],
+        )
+        .with_address_value_capture(true);
+        let msg = |addr: &str| OscMessage {
+            addr: addr.to_owned(),
+            args: vec![],
+        };
+        // When
+        // Then
+        assert_eq!(
+            source.control(&msg("/scene/3")),
+            Some(ControlValue::AbsoluteDiscrete(Fraction::new(3, 8)))
+        );
+        assert_eq!(source.control(&msg("/scene/x")), None);
+        assert_eq!(source.control(&msg("/track/3")), None);
+    }
+
+    #[test]
+    fn character_info() {
+        // Given
+        let source = |type_tag, is_relative, value_range| {
+            let desc = OscArgDescriptor::new(0, type_tag, is_relative, value_range);
+            OscSource::new("/value".to_owned(), Some(desc), vec![])
+        };
+        // When
+        let int_info = source(OscTypeTag::Int, false, Interval::new(0.0, 8.0)).character_info();
+        let float_info = source(OscTypeTag::Float, false, UNIT_INTERVAL).character_info();
+        let relative_info = source(OscTypeTag::Int, true, UNIT_INTERVAL).character_info();
+        let bool_info = source(OscTypeTag::Bool, false, UNIT_INTERVAL).character_info();
+        // Then
+        assert_eq!(int_info.character, DetailedSourceCharacter::RangeControl);
+        assert_eq!(int_info.max_discrete_value, Some(8));
+        assert_eq!(float_info.max_discrete_value, None);
+        assert_eq!(relative_info.character, DetailedSourceCharacter::Relative);
+        assert_eq!(relative_info.max_discrete_value, None);
+        assert_eq!(
+            bool_info.character,
+            DetailedSourceCharacter::MomentaryOnOffButton
+        );
+    }
+
+    #[test]
+    fn custom_value_ranges() {
+        // Given
+        let source = |type_tag, value_range| {
+            let desc = OscArgDescriptor::new(0, type_tag, false, value_range);
+            OscSource::new("/value".to_owned(), Some(desc), vec![])
+        };
+        let bipolar = source(OscTypeTag::Float, Interval::new(-1.0, 1.0));
+        let int = source(OscTypeTag::Int, Interval::new(0.0, 1024.0));
+        let msg = |arg| OscMessage {
+            addr: "/value".to_owned(),
+            args: vec![arg],
+        };
+        // When
+        // Then
+        assert_eq!(
+            bipolar.control(&msg(OscType::Float(0.0))),
+            Some(ControlValue::AbsoluteContinuous(UnitValue::new(0.5)))
+        );
+        assert_eq!(
+            bipolar.control(&msg(OscType::Float(-2.0))),
+            Some(ControlValue::AbsoluteContinuous(UnitValue::MIN))
+        );
+        assert_eq!(
+            bipolar
+                .feedback(FeedbackValue::Numeric(NumericFeedbackValue::new(
+                    Default::default(),
+                    AbsoluteValue::Continuous(UnitValue::new(0.75))
+                )))
+                .unwrap()
+                .args,
+            vec![OscType::Float(0.5)]
+        );
+        assert_eq!(
+            int.control(&msg(OscType::Int(256))),
+            Some(ControlValue::AbsoluteDiscrete(Fraction::new(256, 1024)))
+        );
+        assert_eq!(
+            int.feedback(FeedbackValue::Numeric(NumericFeedbackValue::new(
+                Default::default(),
+                AbsoluteValue::Discrete(Fraction::new(512, 1024))
+            )))
+            .unwrap()
+            .args,
+            vec![OscType::Int(512)]
+        );
+        assert_eq!(
+            bipolar
+                .format_control_value(ControlValue::AbsoluteContinuous(UnitValue::new(0.25)))
+                .unwrap(),
+            "-0.500"
+        );
+        assert_eq!(
+            int.format_control_value(ControlValue::AbsoluteContinuous(UnitValue::new(0.5)))
+                .unwrap(),
+            "512"
+        );
+        assert_eq!(int.parse_control_value("256"), Ok(UnitValue::new(0.25)));
+        assert!(int.parse_control_value("2000").is_err());
+        assert_eq!(
+            source(OscTypeTag::Float, Interval::new(5.0, 5.0)).control(&msg(OscType::Float(5.0))),
+            Some(ControlValue::AbsoluteContinuous(UnitValue::MAX))
+        );
+    }
+
+    #[test]
+    fn delta_values() {
+        // Given
+        let source = |type_tag, value_range| {
+            let desc =
+                OscArgDescriptor::new(0, type_tag, true, value_range).with_delta_values(true);
+            OscSource::new("/encoder".to_owned(), Some(desc), vec![])
+        };
+        let int = source(OscTypeTag::Int, DEFAULT_OSC_ARG_VALUE_RANGE);
+        let float = source(OscTypeTag::Float, Interval::new(-1.0, 1.0));
+        let msg = |arg| OscMessage {
+            addr: "/encoder".to_owned(),
+            args: vec![arg],
+        };
+        // When
+        // Then
+        assert_eq!(
+            int.control(&msg(OscType::Int(-3))),
+            Some(ControlValue::RelativeDiscrete(DiscreteIncrement::new(-3)))
+        );
+        assert_eq!(int.control(&msg(OscType::Int(0))), None);
+        assert_eq!(
+            int.control(&msg(OscType::Long(2))),
+            Some(ControlValue::RelativeDiscrete(DiscreteIncrement::new(2)))
+        );
+        assert_eq!(
+            float.control(&msg(OscType::Float(0.5))),
+            Some(ControlValue::RelativeContinuous(UnitIncrement::new(0.25)))
+        );
+        assert_eq!(
+            float.control_multiple(&msg(OscType::Double(-0.5))),
+            vec![Some(ControlValue::RelativeContinuous(UnitIncrement::new(
+                -0.25
+            )))]
+        );
+        assert_eq!(int.control(&msg(OscType::String("up".to_owned()))), None);
+        assert_eq!(float.control(&msg(OscType::Float(f32::NAN))), None);
+        assert_eq!(float.control(&msg(OscType::Double(f64::INFINITY))), None);
+        let empty_range = source(OscTypeTag::Float, Interval::new(0.5, 0.5));
+        assert_eq!(empty_range.control(&msg(OscType::Float(0.5))), None);
+    }
+}
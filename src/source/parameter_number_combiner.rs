@@ -0,0 +1,133 @@
+use crate::{AbstractTimestamp, ControlEvent, MidiSourceValue};
+use helgoboss_midi::{ParameterNumberMessageScanner, ShortMessage, StructuredShortMessage};
+
+const CHANNEL_COUNT: usize = 16;
+const DATA_ENTRY_MSB_CONTROLLER_NUMBER: u8 = 6;
+const DATA_ENTRY_LSB_CONTROLLER_NUMBER: u8 = 38;
+const DATA_INCREMENT_CONTROLLER_NUMBER: u8 = 96;
+const DATA_DECREMENT_CONTROLLER_NUMBER: u8 = 97;
+const PARAMETER_NUMBER_CONTROLLER_NUMBERS: [u8; 4] = [98, 99, 100, 101];
+
+/// Observes a stream of short MIDI messages and assembles (N)RPN messages (see
+/// [`ParameterNumberMessageScanner`]) into [`MidiSourceValue::ParameterNumber`] values.
+///
+/// Assembled values carry the timestamp of the first message which contributed to them. That's
+/// the parameter number select if it directly preceded the data entry, otherwise the data entry
+/// LSB (which precedes the MSB of 14-bit values) or MSB. Messages which don't complete a value
+/// are consumed.
+#[derive(Clone, Debug)]
+pub struct ParameterNumberCombiner<S: AbstractTimestamp> {
+    scanner: ParameterNumberMessageScanner,
+    slots: [Slot<S>; CHANNEL_COUNT],
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Slot<S> {
+    /// Set if a parameter number has been selected but not been followed by a data entry yet.
+    parameter_number_timestamp: Option<S>,
+    /// Set if a data entry LSB has been received but no MSB yet.
+    data_entry_lsb_timestamp: Option<S>,
+}
+
+impl<S> Default for Slot<S> {
+    fn default() -> Self {
+        Self {
+            parameter_number_timestamp: None,
+            data_entry_lsb_timestamp: None,
+        }
+    }
+}
+
+impl<S: AbstractTimestamp> Default for ParameterNumberCombiner<S> {
+    fn default() -> Self {
+        Self {
+            scanner: ParameterNumberMessageScanner::new(),
+            slots: [Default::default(); CHANNEL_COUNT],
+        }
+    }
+}
+
+impl<S: AbstractTimestamp> ParameterNumberCombiner<S> {
+    /// Feeds the given message into the combiner and returns the assembled values, if any.
+    pub fn feed<M: ShortMessage + Copy>(
+        &mut self,
+        msg: M,
+        timestamp: S,
+    ) -> [Option<ControlEvent<MidiSourceValue<'static, M>, S>>; 2] {
+        let results = self.scanner.feed(&msg);
+        let StructuredShortMessage::ControlChange {
+            channel,
+            controller_number,
+            ..
+        } = msg.to_structured()
+        else {
+            return [None, None];
+        };
+        let slot = &mut self.slots[channel.get() as usize];
+        let first_timestamp = match controller_number.get() {
+            n if PARAMETER_NUMBER_CONTROLLER_NUMBERS.contains(&n) => {
+                slot.parameter_number_timestamp.get_or_insert(timestamp);
+                slot.data_entry_lsb_timestamp = None;
+                timestamp
+            }
+            DATA_ENTRY_LSB_CONTROLLER_NUMBER => {
+                let t = slot.parameter_number_timestamp.take().unwrap_or(timestamp);
+                slot.data_entry_lsb_timestamp = Some(t);
+                t
+            }
+            DATA_ENTRY_MSB_CONTROLLER_NUMBER => slot
+                .data_entry_lsb_timestamp
+                .take()
+                .or(slot.parameter_number_timestamp.take())
+                .unwrap_or(timestamp),
+            DATA_INCREMENT_CONTROLLER_NUMBER | DATA_DECREMENT_CONTROLLER_NUMBER => {
+                slot.parameter_number_timestamp.take().unwrap_or(timestamp)
+            }
+            _ => timestamp,
+        };
+        results.map(|r| {
+            let value = MidiSourceValue::ParameterNumber(r?);
+            Some(ControlEvent::new(value, first_timestamp))
+        })
+    }
+
+    /// Forgets all received values.
+    pub fn reset(&mut self) {
+        self.scanner = ParameterNumberMessageScanner::new();
+        self.slots = [Default::default(); CHANNEL_COUNT];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_midi::test_util::*;
+    use helgoboss_midi::RawShortMessage;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn keep_timestamp_of_parameter_number_select() {
+        // Given
+        let mut combiner = ParameterNumberCombiner::default();
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let mut feed = |msg: RawShortMessage, millis| {
+            Vec::from_iter(combiner.feed(msg, at(millis)).into_iter().flatten())
+        };
+        // When
+        let number_msb = feed(control_change(1, 99, 1), 0);
+        let number_lsb = feed(control_change(1, 98, 2), 1);
+        let value_lsb = feed(control_change(1, 38, 5), 2);
+        let value = feed(control_change(1, 6, 100), 3);
+        let repeated_value = feed(control_change(1, 6, 101), 40);
+        // Then
+        assert!(number_msb.is_empty());
+        assert!(number_lsb.is_empty());
+        assert!(value_lsb.is_empty());
+        assert!(!value.is_empty());
+        assert!(value.iter().all(|e| e.timestamp() == at(0)
+            && matches!(e.payload_ref(), MidiSourceValue::ParameterNumber(_))));
+        assert!(!repeated_value.is_empty());
+        assert!(repeated_value.iter().all(|e| e.timestamp() == at(40)));
+    }
+}
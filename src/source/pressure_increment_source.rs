@@ -0,0 +1,152 @@
+use crate::{AbstractTimestamp, ControlValue, DiscreteIncrement};
+use helgoboss_midi::{Channel, ShortMessage, StructuredShortMessage, U7};
+
+/// Source which turns channel pressure (aftertouch) into a stream of relative increments, so it
+/// can act like an endless encoder: The harder the press, the faster the increments.
+///
+/// Pressure messages are fed via [`Self::feed`], increments are obtained via [`Self::poll`].
+/// Pressing emits one increment right away, so short presses are not lost.
+#[derive(Clone, Debug)]
+pub struct PressureIncrementSource<S: AbstractTimestamp> {
+    /// `None` means any channel.
+    channel: Option<Channel>,
+    max_rate: f64,
+    pressure: U7,
+    last_timestamp: Option<S>,
+    /// Increments which are due but haven't been emitted yet (including fractions).
+    pending_increments: f64,
+}
+
+impl<S: AbstractTimestamp> PressureIncrementSource<S> {
+    /// Creates a source which emits the given number of increments per second at full pressure.
+    pub fn new(channel: Option<Channel>, max_rate: f64) -> Result<Self, &'static str> {
+        if !max_rate.is_finite() || max_rate <= 0.0 {
+            return Err("rate must be positive");
+        }
+        let source = Self {
+            channel,
+            max_rate,
+            pressure: U7::MIN,
+            last_timestamp: None,
+            pending_increments: 0.0,
+        };
+        Ok(source)
+    }
+
+    pub fn channel(&self) -> Option<Channel> {
+        self.channel
+    }
+
+    /// Increments per second at full pressure.
+    pub fn max_rate(&self) -> f64 {
+        self.max_rate
+    }
+
+    /// Feeds the given message into the source.
+    ///
+    /// Returns whether the message has been consumed, that is, whether it's a channel pressure
+    /// message on the configured channel.
+    pub fn feed<M: ShortMessage>(&mut self, msg: M, timestamp: S) -> bool {
+        let StructuredShortMessage::ChannelPressure {
+            channel,
+            pressure_amount,
+        } = msg.to_structured()
+        else {
+            return false;
+        };
+        if self.channel.is_some_and(|ch| ch != channel) {
+            return false;
+        }
+        self.accumulate(timestamp);
+        if pressure_amount == U7::MIN {
+            self.pending_increments = 0.0;
+            self.last_timestamp = None;
+        } else if self.pressure == U7::MIN {
+            self.pending_increments = 1.0;
+            self.last_timestamp = Some(timestamp);
+        }
+        self.pressure = pressure_amount;
+        true
+    }
+
+    /// Returns the increments which became due since the last call.
+    ///
+    /// Should be called regularly while pressure is applied.
+    pub fn poll(&mut self, now: S) -> Option<ControlValue> {
+        self.accumulate(now);
+        let increment = self.pending_increments.trunc();
+        if increment < 1.0 {
+            return None;
+        }
+        self.pending_increments -= increment;
+        let increment = DiscreteIncrement::new_checked(increment as i32)?;
+        Some(ControlValue::RelativeDiscrete(increment))
+    }
+
+    /// Forgets the current pressure, e.g. after the device has been reconnected.
+    pub fn reset(&mut self) {
+        self.pressure = U7::MIN;
+        self.last_timestamp = None;
+        self.pending_increments = 0.0;
+    }
+
+    fn accumulate(&mut self, now: S) {
+        if self.pressure == U7::MIN {
+            return;
+        }
+        if let Some(last_timestamp) = self.last_timestamp {
+            let rate = self.max_rate * self.pressure.get() as f64 / U7::MAX.get() as f64;
+            self.pending_increments += (now - last_timestamp).as_secs_f64() * rate;
+        }
+        self.last_timestamp = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helgoboss_midi::test_util::{channel as ch, *};
+    use helgoboss_midi::RawShortMessage;
+    use std::time::{Duration, Instant};
+
+    fn inc(increment: i32) -> Option<ControlValue> {
+        Some(ControlValue::RelativeDiscrete(DiscreteIncrement::new(
+            increment,
+        )))
+    }
+
+    #[test]
+    fn rate_proportional_to_pressure() {
+        // Given
+        let mut source = PressureIncrementSource::new(Some(ch(0)), 10.0).unwrap();
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        // When
+        // Then
+        assert!(!source.feed::<RawShortMessage>(channel_pressure(1, 127), at(0)));
+        assert_eq!(source.poll(at(0)), None);
+        assert!(source.feed::<RawShortMessage>(channel_pressure(0, 127), at(0)));
+        assert_eq!(source.poll(at(0)), inc(1));
+        assert_eq!(source.poll(at(50)), None);
+        assert_eq!(source.poll(at(250)), inc(2));
+        assert_eq!(source.poll(at(500)), inc(3));
+        source.feed::<RawShortMessage>(channel_pressure(0, 0), at(600));
+        assert_eq!(source.poll(at(1000)), None);
+    }
+
+    #[test]
+    fn half_pressure() {
+        // Given
+        let mut source = PressureIncrementSource::new(None, 127.0).unwrap();
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        // When
+        source.feed::<RawShortMessage>(channel_pressure(3, 64), at(0));
+        let first = source.poll(at(0));
+        let after_one_second = source.poll(at(1000));
+        // Then
+        assert_eq!(first, inc(1));
+        assert_eq!(after_one_second, inc(64));
+        assert!(PressureIncrementSource::<Instant>::new(None, 0.0).is_err());
+    }
+}
@@ -1,15 +1,37 @@
-use crate::{AbsoluteValue, Fraction, PatternByte, RawMidiEvent, UnitValue};
+use crate::devices::DevicePalette;
+use crate::{AbsoluteValue, Fraction, PatternByte, RawMidiEvent, RgbColor, UnitValue};
 use logos::{Lexer, Logos};
 use std::fmt;
 use std::fmt::{Display, Formatter, Write};
 use std::num::ParseIntError;
-use std::ops::RangeInclusive;
+use std::ops::{Range, RangeInclusive};
 use std::str::FromStr;
 
+/// Maximum bit width of a multi-byte variable.
+const MAX_MULTI_BYTE_VARIABLE_WIDTH: u8 = 28;
+
+/// Padding byte of text placeholders if not specified (space).
+const DEFAULT_TEXT_PADDING: u8 = 0x20;
+
+/// Maximum number of optional groups within one pattern. Matching tries all combinations of
+/// present and omitted groups, so this must stay small.
+const MAX_OPTIONAL_GROUP_COUNT: usize = 8;
+
+/// Maximum number of entries a pattern may have after expanding repeated groups. Nested
+/// repetitions multiply, so this guards against patterns which would exhaust memory.
+const MAX_EXPANDED_ENTRY_COUNT: usize = RawMidiEvent::MAX_LENGTH;
+
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
 pub struct RawMidiPattern {
     entries: Vec<RawMidiPatternEntry>,
     resolution: u8,
+    /// If `true`, incoming messages which don't correspond exactly to this pattern are matched
+    /// tolerantly as well.
+    tolerant: bool,
+    /// Ranges of entries which may be missing in incoming messages, e.g. `(01 02)?`.
+    ///
+    /// Don't overlap. Feedback always includes them.
+    optional_groups: Vec<Range<usize>>,
 }
 
 impl RawMidiPattern {
@@ -25,6 +47,8 @@ impl RawMidiPattern {
             } else {
                 0
             },
+            tolerant: false,
+            optional_groups: vec![],
         }
     }
 
@@ -36,9 +60,53 @@ impl RawMidiPattern {
         Self {
             entries,
             resolution: 0,
+            tolerant: false,
+            optional_groups: vec![],
         }
     }
 
+    /// Makes [`Self::match_and_capture`] fall back to tolerant matching if incoming messages don't
+    /// correspond exactly to this pattern.
+    pub fn with_tolerant_matching(mut self, tolerant: bool) -> Self {
+        self.tolerant = tolerant;
+        self
+    }
+
+    pub fn is_tolerant(&self) -> bool {
+        self.tolerant
+    }
+
+    /// Marks the given ranges of entries as optional, so incoming messages also match if they
+    /// lack some of them.
+    pub fn with_optional_groups(
+        mut self,
+        optional_groups: Vec<Range<usize>>,
+    ) -> Result<Self, &'static str> {
+        if optional_groups.len() > MAX_OPTIONAL_GROUP_COUNT {
+            return Err("too many optional groups");
+        }
+        if optional_groups
+            .iter()
+            .any(|g| g.is_empty() || g.end > self.entries.len())
+        {
+            return Err("invalid optional group");
+        }
+        let overlaps = optional_groups.iter().enumerate().any(|(i, a)| {
+            optional_groups[i + 1..]
+                .iter()
+                .any(|b| a.start < b.end && b.start < a.end)
+        });
+        if overlaps {
+            return Err("optional groups must not overlap");
+        }
+        self.optional_groups = optional_groups;
+        Ok(self)
+    }
+
+    pub fn optional_groups(&self) -> &[Range<usize>] {
+        &self.optional_groups
+    }
+
     pub fn variable_range(&self) -> Option<RangeInclusive<usize>> {
         let left = self.entries().iter().position(|e| !e.is_fixed())?;
         let right = self.entries().iter().rposition(|e| !e.is_fixed())?;
@@ -62,7 +130,7 @@ impl RawMidiPattern {
         &self.entries
     }
 
-    /// Resolution in bit (maximum 16 bit).
+    /// Resolution in bit (maximum 16 bit for bit patterns, 28 bit for multi-byte variables).
     ///
     /// If no variable bytes, this returns 0.
     pub fn resolution(&self) -> u8 {
@@ -70,8 +138,8 @@ impl RawMidiPattern {
     }
 
     /// If no variable bytes, this returns 0.
-    pub fn max_discrete_value(&self) -> u16 {
-        (2u32.pow(self.resolution as _) - 1) as u16
+    pub fn max_discrete_value(&self) -> u32 {
+        2u32.pow(self.resolution as _) - 1
     }
 
     pub fn step_size(&self) -> Option<UnitValue> {
@@ -84,23 +152,126 @@ impl RawMidiPattern {
 
     /// If it matches and there are no variable bytes in the pattern, this returns
     /// `Some(Fraction(0, 0))`.
+    ///
+    /// First tries to match exactly. If that fails and this pattern is tolerant, it falls back
+    /// to tolerant matching (see [`Self::match_and_capture_tolerantly`]).
     pub fn match_and_capture(&self, bytes: &[u8]) -> Option<Fraction> {
-        if bytes.len() != self.entries.len() {
-            return None;
+        let exact_match = self.match_and_capture_exactly(bytes);
+        if !self.tolerant {
+            return exact_match;
         }
-        let mut current_value: u16 = 0;
-        for (i, b) in bytes.iter().enumerate() {
-            let pattern_entry = self.entries[i];
-            if let Some(v) = pattern_entry.match_and_capture(*b, current_value) {
-                current_value = v;
-            } else {
+        exact_match.or_else(|| self.match_and_capture_tolerantly(bytes))
+    }
+
+    /// Matches only if the given bytes correspond exactly to this pattern (with or without its
+    /// optional groups).
+    pub fn match_and_capture_exactly(&self, bytes: &[u8]) -> Option<Fraction> {
+        self.omission_masks().find_map(|mask| {
+            if bytes.len() != self.entries.len() - self.omitted_entry_count(mask) {
                 return None;
             }
+            self.match_and_capture_internal(self.entries_without(mask), bytes.iter().copied())
+        })
+    }
+
+    /// Matches the given bytes after normalizing them.
+    ///
+    /// - System real-time bytes (0xF8 - 0xFF) interleaved with the actual message are ignored.
+    /// - If the message lacks a status byte (running status) and this pattern starts with a fixed
+    ///   channel status byte, that status byte is assumed.
+    ///
+    /// Several older devices send messages this way.
+    pub fn match_and_capture_tolerantly(&self, bytes: &[u8]) -> Option<Fraction> {
+        let relevant_bytes = || {
+            bytes
+                .iter()
+                .copied()
+                .filter(|b| !is_system_real_time_byte(*b))
+        };
+        let missing_status_byte = match relevant_bytes().next() {
+            Some(b) if b < 0x80 => Some(self.expected_channel_status_byte()?),
+            _ => None,
+        };
+        self.omission_masks().find_map(|mask| {
+            self.match_and_capture_internal(
+                self.entries_without(mask),
+                missing_status_byte.into_iter().chain(relevant_bytes()),
+            )
+        })
+    }
+
+    /// Returns all combinations of omitted optional groups, starting with the one which omits
+    /// nothing. Bit i set means that optional group i is omitted.
+    fn omission_masks(&self) -> Range<u32> {
+        0..(1 << self.optional_groups.len())
+    }
+
+    fn omitted_entry_count(&self, omission_mask: u32) -> usize {
+        self.optional_groups
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| omission_mask & (1 << i) != 0)
+            .map(|(_, g)| g.len())
+            .sum()
+    }
+
+    fn entries_without(
+        &self,
+        omission_mask: u32,
+    ) -> impl Iterator<Item = &RawMidiPatternEntry> + '_ {
+        self.entries.iter().enumerate().filter_map(move |(i, e)| {
+            let is_omitted = self
+                .optional_groups
+                .iter()
+                .enumerate()
+                .any(|(g, range)| omission_mask & (1 << g) != 0 && range.contains(&i));
+            (!is_omitted).then_some(e)
+        })
+    }
+
+    fn match_and_capture_internal<'a>(
+        &self,
+        mut entries: impl Iterator<Item = &'a RawMidiPatternEntry>,
+        bytes: impl Iterator<Item = u8>,
+    ) -> Option<Fraction> {
+        let mut current_value: u32 = 0;
+        let mut count = 0;
+        // Wrapping sums of all bytes before index i, stored at i % 256. Enough for checksums
+        // because they cover at most 255 bytes.
+        let mut prefix_sums = [0u8; 256];
+        let mut sum: u8 = 0;
+        for b in bytes {
+            let pattern_entry = entries.next()?;
+            prefix_sums[count % 256] = sum;
+            if let RawMidiPatternEntry::Checksum {
+                algorithm,
+                covered_byte_count,
+            } = pattern_entry
+            {
+                let start = count.saturating_sub(*covered_byte_count as usize);
+                let covered_sum = sum.wrapping_sub(prefix_sums[start % 256]);
+                if b != algorithm.compute(covered_sum) {
+                    return None;
+                }
+            } else {
+                current_value = pattern_entry.match_and_capture(b, current_value)?;
+            }
+            sum = sum.wrapping_add(b);
+            count += 1;
+        }
+        if entries.next().is_some() {
+            return None;
         }
-        let fraction = Fraction::new(current_value as _, self.max_discrete_value() as _);
+        let fraction = Fraction::new(current_value, self.max_discrete_value());
         Some(fraction)
     }
 
+    /// Returns the status byte of this pattern if it's a fixed channel voice/mode status byte.
+    fn expected_channel_status_byte(&self) -> Option<u8> {
+        let status_byte = self.entries.first()?.byte_if_fixed()?;
+        (0x80..0xF0).contains(&status_byte).then_some(status_byte)
+    }
+
     pub fn to_bytes(&self, variable_value: AbsoluteValue) -> Vec<u8> {
         self.byte_iter(variable_value).collect()
     }
@@ -109,25 +280,80 @@ impl RawMidiPattern {
         &self,
         variable_value: AbsoluteValue,
     ) -> impl ExactSizeIterator<Item = u8> + '_ {
+        self.byte_iter_with_placeholder_values(variable_value, Default::default())
+    }
+
+    /// Like [`Self::byte_iter`] but resolves color and text placeholders with the given values.
+    pub fn byte_iter_with_placeholder_values<'a>(
+        &'a self,
+        variable_value: AbsoluteValue,
+        values: RawMidiPatternPlaceholderValues<'a>,
+    ) -> impl ExactSizeIterator<Item = u8> + 'a {
         let discrete_value = match variable_value {
             AbsoluteValue::Continuous(v) => v.to_discrete(self.max_discrete_value()),
-            AbsoluteValue::Discrete(f) => {
-                std::cmp::min(f.actual(), self.max_discrete_value() as u32) as u16
-            }
+            AbsoluteValue::Discrete(f) => std::cmp::min(f.actual(), self.max_discrete_value()),
         };
-        self.entries.iter().map(move |e| e.to_byte(discrete_value))
+        (0..self.entries.len()).map(move |i| self.byte_at(i, discrete_value, &values))
+    }
+
+    /// Returns whether this pattern contains at least one text placeholder.
+    pub fn contains_text_placeholders(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|e| matches!(e, RawMidiPatternEntry::TextPlaceholderPortion { .. }))
+    }
+
+    fn byte_at(
+        &self,
+        index: usize,
+        discrete_value: u32,
+        values: &RawMidiPatternPlaceholderValues,
+    ) -> u8 {
+        match self.entries[index] {
+            RawMidiPatternEntry::Checksum {
+                algorithm,
+                covered_byte_count,
+            } => {
+                let start = index.saturating_sub(covered_byte_count as usize);
+                let covered_sum = (start..index)
+                    .map(|i| self.byte_at(i, discrete_value, values))
+                    .fold(0u8, |sum, b| sum.wrapping_add(b));
+                algorithm.compute(covered_sum)
+            }
+            RawMidiPatternEntry::ColorPlaceholder(placeholder) => values.color.resolve(placeholder),
+            RawMidiPatternEntry::TextPlaceholderPortion {
+                placeholder,
+                char_index,
+            } => placeholder.resolve(values.text, char_index),
+            entry => entry.to_byte(discrete_value),
+        }
     }
 
     pub fn to_concrete_midi_event(
         &self,
         frame_offset: u32,
         variable_value: AbsoluteValue,
+    ) -> RawMidiEvent {
+        self.to_concrete_midi_event_with_placeholder_values(
+            frame_offset,
+            variable_value,
+            Default::default(),
+        )
+    }
+
+    /// Like [`Self::to_concrete_midi_event`] but resolves color and text placeholders with the
+    /// given values.
+    pub fn to_concrete_midi_event_with_placeholder_values(
+        &self,
+        frame_offset: u32,
+        variable_value: AbsoluteValue,
+        values: RawMidiPatternPlaceholderValues,
     ) -> RawMidiEvent {
         // TODO-medium Use RawMidiEvent::try_from_iter
         let mut array = [0; RawMidiEvent::MAX_LENGTH];
         let mut i = 0u32;
         for byte in self
-            .byte_iter(variable_value)
+            .byte_iter_with_placeholder_values(variable_value, values)
             .take(RawMidiEvent::MAX_LENGTH)
         {
             array[i as usize] = byte;
@@ -141,6 +367,219 @@ impl RawMidiPattern {
 pub enum RawMidiPatternEntry {
     FixedByte(u8),
     PotentiallyVariableByte(BitPattern),
+    /// One byte of a variable which spans multiple bytes.
+    MultiByteVariablePortion {
+        variable: MultiByteVariable,
+        /// Index of this byte within the bytes of the variable, in transmission order.
+        byte_index: u8,
+    },
+    /// Checksum over the given number of directly preceding bytes, e.g. `{checksum:roland:6}`.
+    ///
+    /// Computed when sending feedback, validated when matching.
+    Checksum {
+        algorithm: ChecksumAlgorithm,
+        covered_byte_count: u8,
+    },
+    /// Placeholder for the feedback color, e.g. `{color:r}`.
+    ///
+    /// Resolved when sending feedback, matches any byte when matching.
+    ColorPlaceholder(ColorPlaceholder),
+    /// One character of a text placeholder, e.g. `{text:8}`.
+    ///
+    /// Resolved when sending feedback, matches any byte when matching.
+    TextPlaceholderPortion {
+        placeholder: TextPlaceholder,
+        /// Index of the character within the text.
+        char_index: u8,
+    },
+}
+
+/// Which aspect of the feedback color a color placeholder stands for.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ColorPlaceholder {
+    /// Red component, reduced to 7 bit (`{color:r}`).
+    Red,
+    /// Green component, reduced to 7 bit (`{color:g}`).
+    Green,
+    /// Blue component, reduced to 7 bit (`{color:b}`).
+    Blue,
+    /// Index of the closest color in the palette of the device (`{color:index}`).
+    PaletteIndex,
+}
+
+/// The feedback values which are used to resolve placeholders.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct RawMidiPatternPlaceholderValues<'a> {
+    pub color: RawMidiPatternColor,
+    pub text: &'a str,
+}
+
+/// Placeholder for the textual feedback value, encoded as 7-bit ASCII, e.g. `{text:8}`.
+///
+/// Always occupies exactly `max_length` bytes. Longer texts are truncated, shorter ones padded
+/// with the padding byte. Non-ASCII characters are left out.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TextPlaceholder {
+    max_length: u8,
+    padding: u8,
+}
+
+impl TextPlaceholder {
+    /// Creates a text placeholder.
+    ///
+    /// The padding byte must be a 7-bit value.
+    pub fn new(max_length: u8, padding: u8) -> Result<Self, &'static str> {
+        if max_length == 0 {
+            return Err("text placeholder must have a length of at least 1");
+        }
+        if padding > 0x7f {
+            return Err("text placeholder padding must be a 7-bit value");
+        }
+        let placeholder = Self {
+            max_length,
+            padding,
+        };
+        Ok(placeholder)
+    }
+
+    pub fn max_length(&self) -> u8 {
+        self.max_length
+    }
+
+    pub fn padding(&self) -> u8 {
+        self.padding
+    }
+
+    fn resolve(&self, text: &str, char_index: u8) -> u8 {
+        text.chars()
+            .filter(|ch| ch.is_ascii())
+            .nth(char_index as usize)
+            .map(|ch| ch as u8)
+            .unwrap_or(self.padding)
+    }
+}
+
+/// The feedback color which is used to resolve color placeholders.
+///
+/// Placeholders resolve to zero if there's no color (or no palette index).
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct RawMidiPatternColor {
+    pub rgb: Option<RgbColor>,
+    pub palette_index: Option<u8>,
+}
+
+impl RawMidiPatternColor {
+    /// Looks up the palette index of the given color in the given device palette, if any.
+    pub fn new(rgb: Option<RgbColor>, palette: Option<&DevicePalette>) -> Self {
+        Self {
+            rgb,
+            palette_index: rgb
+                .zip(palette)
+                .and_then(|(c, p)| p.velocity_for_color(c))
+                .map(|v| v.get()),
+        }
+    }
+
+    fn resolve(&self, placeholder: ColorPlaceholder) -> u8 {
+        use ColorPlaceholder::*;
+        let value = match placeholder {
+            Red => self.rgb.map(|c| c.r() >> 1),
+            Green => self.rgb.map(|c| c.g() >> 1),
+            Blue => self.rgb.map(|c| c.b() >> 1),
+            PaletteIndex => self.palette_index,
+        };
+        value.unwrap_or(0)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ChecksumAlgorithm {
+    /// 7-bit sum which makes the covered bytes plus checksum add up to a multiple of 128.
+    Roland,
+}
+
+impl ChecksumAlgorithm {
+    /// Computes the checksum from the wrapping sum of the covered bytes.
+    pub fn compute(&self, covered_sum: u8) -> u8 {
+        match self {
+            ChecksumAlgorithm::Roland => 0x80u8.wrapping_sub(covered_sum & 0x7f) & 0x7f,
+        }
+    }
+}
+
+/// A variable which spans multiple consecutive bytes, e.g. `{14:be:7}`.
+///
+/// The value is split into chunks of `bits_per_byte` bits, each one occupying the lower bits of
+/// one byte.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct MultiByteVariable {
+    width: u8,
+    byte_order: VariableByteOrder,
+    bits_per_byte: u8,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum VariableByteOrder {
+    /// Most significant chunk first.
+    BigEndian,
+    /// Least significant chunk first.
+    LittleEndian,
+}
+
+impl MultiByteVariable {
+    /// Creates a multi-byte variable.
+    ///
+    /// Supported values for `bits_per_byte` are 4 (nibbles), 7 (sys-ex data bytes) and 8.
+    pub fn new(
+        width: u8,
+        byte_order: VariableByteOrder,
+        bits_per_byte: u8,
+    ) -> Result<Self, &'static str> {
+        if width == 0 || width > MAX_MULTI_BYTE_VARIABLE_WIDTH {
+            return Err("multi-byte variable width must be between 1 and 28 bit");
+        }
+        if !matches!(bits_per_byte, 4 | 7 | 8) {
+            return Err("multi-byte variable must use 4, 7 or 8 bits per byte");
+        }
+        let variable = Self {
+            width,
+            byte_order,
+            bits_per_byte,
+        };
+        Ok(variable)
+    }
+
+    pub fn width(&self) -> u8 {
+        self.width
+    }
+
+    pub fn byte_order(&self) -> VariableByteOrder {
+        self.byte_order
+    }
+
+    pub fn bits_per_byte(&self) -> u8 {
+        self.bits_per_byte
+    }
+
+    pub fn byte_count(&self) -> u8 {
+        self.width.div_ceil(self.bits_per_byte)
+    }
+
+    /// Returns the bit pattern of the byte at the given index (in transmission order).
+    fn bit_pattern(&self, byte_index: u8) -> BitPattern {
+        let chunk_index = match self.byte_order {
+            VariableByteOrder::BigEndian => self.byte_count() - 1 - byte_index,
+            VariableByteOrder::LittleEndian => byte_index,
+        };
+        let mut entries: [BitPatternEntry; 8] = Default::default();
+        for bit in 0..self.bits_per_byte {
+            let value_bit_index = chunk_index * self.bits_per_byte + bit;
+            if value_bit_index < self.width {
+                entries[7 - bit as usize] = BitPatternEntry::VariableBit(value_bit_index);
+            }
+        }
+        BitPattern { entries }
+    }
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
@@ -163,13 +602,13 @@ impl BitPattern {
             .max()
     }
 
-    pub fn to_byte(self, discrete_value: u16) -> u8 {
+    pub fn to_byte(self, discrete_value: u32) -> u8 {
         let mut final_byte: u8 = 0;
         for i in 0..8 {
             use BitPatternEntry::*;
             let final_bit = match self.entries[i] {
                 FixedBit(bit) => bit,
-                VariableBit(bit_index) => (discrete_value & (1 << bit_index) as u16) > 0,
+                VariableBit(bit_index) => (discrete_value & (1 << bit_index)) > 0,
             };
             if final_bit {
                 final_byte |= 1 << (7 - i);
@@ -178,7 +617,7 @@ impl BitPattern {
         final_byte
     }
 
-    fn match_and_capture(&self, actual_byte: u8, current_value: u16) -> Option<u16> {
+    fn match_and_capture(&self, actual_byte: u8, current_value: u32) -> Option<u32> {
         let mut new_value = current_value;
         for i in 0..8 {
             let actual_bit = (actual_byte >> (7 - i)) & 1 == 1;
@@ -244,10 +683,14 @@ impl RawMidiPatternEntry {
                     Some(p.to_byte(0))
                 }
             }
+            MultiByteVariablePortion { .. }
+            | Checksum { .. }
+            | ColorPlaceholder(_)
+            | TextPlaceholderPortion { .. } => None,
         }
     }
 
-    fn match_and_capture(&self, actual_byte: u8, current_value: u16) -> Option<u16> {
+    fn match_and_capture(&self, actual_byte: u8, current_value: u32) -> Option<u32> {
         use RawMidiPatternEntry::*;
         match self {
             FixedByte(b) => {
@@ -260,6 +703,16 @@ impl RawMidiPatternEntry {
             PotentiallyVariableByte(pattern) => {
                 pattern.match_and_capture(actual_byte, current_value)
             }
+            MultiByteVariablePortion {
+                variable,
+                byte_index,
+            } => variable
+                .bit_pattern(*byte_index)
+                .match_and_capture(actual_byte, current_value),
+            // Validated by the pattern because it depends on the preceding bytes
+            Checksum { .. } => Some(current_value),
+            // Colors and texts are not captured
+            ColorPlaceholder(_) | TextPlaceholderPortion { .. } => Some(current_value),
         }
     }
 
@@ -268,21 +721,61 @@ impl RawMidiPatternEntry {
         match self {
             FixedByte(_) => None,
             PotentiallyVariableByte(bit_pattern) => bit_pattern.max_variable_bit_index(),
+            MultiByteVariablePortion {
+                variable,
+                byte_index,
+            } => variable.bit_pattern(*byte_index).max_variable_bit_index(),
+            Checksum { .. } | ColorPlaceholder(_) | TextPlaceholderPortion { .. } => None,
         }
     }
 
-    fn to_byte(self, discrete_value: u16) -> u8 {
+    fn to_byte(self, discrete_value: u32) -> u8 {
         use RawMidiPatternEntry::*;
         match self {
             FixedByte(byte) => byte,
             PotentiallyVariableByte(bit_pattern) => bit_pattern.to_byte(discrete_value),
+            MultiByteVariablePortion {
+                variable,
+                byte_index,
+            } => variable.bit_pattern(byte_index).to_byte(discrete_value),
+            // Computed by the pattern because it depends on the preceding bytes, color or text
+            Checksum { .. } | ColorPlaceholder(_) | TextPlaceholderPortion { .. } => 0,
         }
     }
 }
 
 impl Display for RawMidiPattern {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        let string_vec: Vec<_> = self.entries.iter().map(|e| e.to_string()).collect();
+        let mut indexed_strings: Vec<_> = self
+            .entries
+            .iter()
+            .enumerate()
+            // A multi-byte variable or text placeholder is displayed only once
+            .filter(|(_, e)| {
+                !matches!(
+                    e,
+                    RawMidiPatternEntry::MultiByteVariablePortion { byte_index, .. } if *byte_index > 0
+                )
+                    && !matches!(
+                        e,
+                        RawMidiPatternEntry::TextPlaceholderPortion { char_index, .. } if *char_index > 0
+                    )
+            })
+            .map(|(i, e)| (i, e.to_string()))
+            .collect();
+        for group in &self.optional_groups {
+            if let Some((_, s)) = indexed_strings.iter_mut().find(|(i, _)| *i == group.start) {
+                s.insert(0, '(');
+            }
+            if let Some((_, s)) = indexed_strings.iter_mut().rfind(|(i, _)| *i < group.end) {
+                s.push_str(")?");
+            }
+        }
+        let string_vec: Vec<_> = indexed_strings.into_iter().map(|(_, s)| s).collect();
+        // A leading tilde marks tolerant matching
+        if self.tolerant {
+            f.write_str("~ ")?;
+        }
         f.write_str(&string_vec.join(" "))
     }
 }
@@ -293,10 +786,54 @@ impl Display for RawMidiPatternEntry {
         match self {
             FixedByte(byte) => write!(f, "{:02X}", *byte),
             PotentiallyVariableByte(pattern) => write!(f, "[{pattern}]"),
+            MultiByteVariablePortion { variable, .. } => variable.fmt(f),
+            Checksum {
+                algorithm,
+                covered_byte_count,
+            } => {
+                let algorithm = match algorithm {
+                    ChecksumAlgorithm::Roland => "roland",
+                };
+                write!(f, "{{checksum:{algorithm}:{covered_byte_count}}}")
+            }
+            ColorPlaceholder(placeholder) => {
+                let component = match placeholder {
+                    self::ColorPlaceholder::Red => "r",
+                    self::ColorPlaceholder::Green => "g",
+                    self::ColorPlaceholder::Blue => "b",
+                    self::ColorPlaceholder::PaletteIndex => "index",
+                };
+                write!(f, "{{color:{component}}}")
+            }
+            TextPlaceholderPortion { placeholder, .. } => placeholder.fmt(f),
         }
     }
 }
 
+impl Display for TextPlaceholder {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.padding == DEFAULT_TEXT_PADDING {
+            write!(f, "{{text:{}}}", self.max_length)
+        } else {
+            write!(f, "{{text:{}:{:02X}}}", self.max_length, self.padding)
+        }
+    }
+}
+
+impl Display for MultiByteVariable {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let byte_order = match self.byte_order {
+            VariableByteOrder::BigEndian => "be",
+            VariableByteOrder::LittleEndian => "le",
+        };
+        write!(
+            f,
+            "{{{}:{}:{}}}",
+            self.width, byte_order, self.bits_per_byte
+        )
+    }
+}
+
 impl Display for BitPattern {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         for entry in &self.entries[..4] {
@@ -324,21 +861,93 @@ impl FromStr for RawMidiPattern {
     type Err = ParseRawMidiPatternError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (tolerant, s) = match s.trim_start().strip_prefix('~') {
+            None => (false, s),
+            Some(rest) => (true, rest),
+        };
         let lex: Lexer<RawMidiPatternToken> = RawMidiPatternToken::lexer(s);
         use RawMidiPatternToken::*;
-        let entries: Result<Vec<_>, ParseRawMidiPatternError> = lex
-            .map(|token| {
-                let entry = match token? {
-                    FixedByte(byte) => RawMidiPatternEntry::FixedByte(byte),
-                    PotentiallyVariableByte(pattern) => {
-                        RawMidiPatternEntry::PotentiallyVariableByte(pattern)
+        let mut entries = vec![];
+        let mut optional_groups: Vec<Range<usize>> = vec![];
+        // Entry indexes at which the currently open groups start
+        let mut open_groups = vec![];
+        for token in lex {
+            match token.map_err(|_| "couldn't parse raw MIDI pattern")? {
+                GroupStart => open_groups.push(entries.len()),
+                RepeatedGroupEnd(count) => {
+                    let start = open_groups.pop().ok_or("unexpected end of group")?;
+                    if count == 0 {
+                        return Err("repetition count must be at least 1".into());
                     }
-                };
-                Ok(entry)
-            })
-            .collect();
-        let entries = entries.map_err(|_| "couldn't parse raw MIDI pattern")?;
-        Ok(RawMidiPattern::from_entries(entries))
+                    let segment = entries[start..].to_vec();
+                    let expanded_entry_count = start + segment.len() * count as usize;
+                    if expanded_entry_count > MAX_EXPANDED_ENTRY_COUNT {
+                        return Err("repeated groups expand to too many bytes".into());
+                    }
+                    let segment_optional_groups: Vec<_> = optional_groups
+                        .iter()
+                        .filter(|g| g.start >= start)
+                        .cloned()
+                        .collect();
+                    for i in 1..count as usize {
+                        let offset = i * segment.len();
+                        let repeated_optional_groups = segment_optional_groups
+                            .iter()
+                            .map(|g| g.start + offset..g.end + offset);
+                        optional_groups.extend(repeated_optional_groups);
+                        entries.extend_from_slice(&segment);
+                    }
+                }
+                OptionalGroupEnd => {
+                    let start = open_groups.pop().ok_or("unexpected end of group")?;
+                    if start == entries.len() {
+                        return Err("optional group must not be empty".into());
+                    }
+                    if optional_groups.iter().any(|g| g.start >= start) {
+                        return Err("optional groups must not be nested".into());
+                    }
+                    optional_groups.push(start..entries.len());
+                }
+                FixedByte(byte) => entries.push(RawMidiPatternEntry::FixedByte(byte)),
+                PotentiallyVariableByte(pattern) => {
+                    entries.push(RawMidiPatternEntry::PotentiallyVariableByte(pattern))
+                }
+                Checksum((algorithm, covered_byte_count)) => {
+                    entries.push(RawMidiPatternEntry::Checksum {
+                        algorithm,
+                        covered_byte_count,
+                    })
+                }
+                ColorPlaceholder(placeholder) => {
+                    entries.push(RawMidiPatternEntry::ColorPlaceholder(placeholder))
+                }
+                TextPlaceholder(placeholder) => {
+                    let portions = (0..placeholder.max_length()).map(|char_index| {
+                        RawMidiPatternEntry::TextPlaceholderPortion {
+                            placeholder,
+                            char_index,
+                        }
+                    });
+                    entries.extend(portions);
+                }
+                MultiByteVariable(variable) => {
+                    let portions = (0..variable.byte_count()).map(|byte_index| {
+                        RawMidiPatternEntry::MultiByteVariablePortion {
+                            variable,
+                            byte_index,
+                        }
+                    });
+                    entries.extend(portions);
+                }
+            }
+        }
+        if !open_groups.is_empty() {
+            return Err("unclosed group".into());
+        }
+        let pattern = RawMidiPattern::from_entries(entries)
+            .with_optional_groups(optional_groups)?
+            .with_tolerant_matching(tolerant);
+        Ok(pattern)
     }
 }
 
@@ -348,8 +957,27 @@ impl FromStr for RawMidiPattern {
 enum RawMidiPatternToken {
     #[regex(r"\[[01abcdefghijklmnop ]*\]", parse_as_bit_pattern)]
     PotentiallyVariableByte(BitPattern),
+    #[regex(r"\{[0-9]+(:(be|le))?(:[0-9])?\}", parse_as_multi_byte_variable)]
+    MultiByteVariable(MultiByteVariable),
+    #[regex(r"\{checksum:[a-z]+:[0-9]+\}", parse_as_checksum)]
+    Checksum((ChecksumAlgorithm, u8)),
+    #[regex(r"\{color:[a-z]+\}", parse_as_color_placeholder)]
+    ColorPlaceholder(ColorPlaceholder),
+    #[regex(
+        r"\{text:[0-9]+(:[0-9a-fA-F][0-9a-fA-F]?)?\}",
+        parse_as_text_placeholder
+    )]
+    TextPlaceholder(TextPlaceholder),
     #[regex(r"[0-9a-fA-F][0-9a-fA-F]?", parse_as_byte)]
     FixedByte(u8),
+    #[token("(")]
+    GroupStart,
+    /// End of a group which is repeated the given number of times, e.g. `(00 7F)*16`.
+    #[regex(r"\)\*[0-9]+", parse_as_repetition_count)]
+    RepeatedGroupEnd(u8),
+    /// End of a group which may be missing in incoming messages, e.g. `(00 7F)?`.
+    #[token(")?")]
+    OptionalGroupEnd,
 }
 
 #[derive(Clone, PartialEq, Debug, Default, thiserror::Error)]
@@ -398,6 +1026,88 @@ fn parse_as_bit_pattern(lex: &mut Lexer<RawMidiPatternToken>) -> Result<BitPatte
     Ok(pattern)
 }
 
+/// Parses `)*count`.
+fn parse_as_repetition_count(lex: &mut Lexer<RawMidiPatternToken>) -> Result<u8, &'static str> {
+    lex.slice()[2..]
+        .parse()
+        .map_err(|_| "invalid repetition count")
+}
+
+/// Parses `{width[:be|le][:bits_per_byte]}`. Defaults to big endian with 7 bits per byte.
+fn parse_as_multi_byte_variable(
+    lex: &mut Lexer<RawMidiPatternToken>,
+) -> Result<MultiByteVariable, &'static str> {
+    let slice: &str = lex.slice();
+    let mut parts = slice[1..slice.len() - 1].split(':');
+    let width = parts
+        .next()
+        .and_then(|p| p.parse().ok())
+        .ok_or("invalid multi-byte variable width")?;
+    let mut byte_order = VariableByteOrder::BigEndian;
+    let mut bits_per_byte = 7;
+    for part in parts {
+        match part {
+            "be" => byte_order = VariableByteOrder::BigEndian,
+            "le" => byte_order = VariableByteOrder::LittleEndian,
+            _ => bits_per_byte = part.parse().map_err(|_| "invalid bits per byte")?,
+        }
+    }
+    MultiByteVariable::new(width, byte_order, bits_per_byte)
+}
+
+/// Parses `{checksum:algorithm:covered_byte_count}`.
+fn parse_as_checksum(
+    lex: &mut Lexer<RawMidiPatternToken>,
+) -> Result<(ChecksumAlgorithm, u8), &'static str> {
+    let slice: &str = lex.slice();
+    let mut parts = slice[1..slice.len() - 1].split(':').skip(1);
+    let algorithm = match parts.next() {
+        Some("roland") => ChecksumAlgorithm::Roland,
+        _ => return Err("unknown checksum algorithm"),
+    };
+    let covered_byte_count = parts
+        .next()
+        .and_then(|p| p.parse().ok())
+        .ok_or("invalid number of bytes covered by checksum")?;
+    Ok((algorithm, covered_byte_count))
+}
+
+/// Parses `{color:r|g|b|index}`.
+fn parse_as_color_placeholder(
+    lex: &mut Lexer<RawMidiPatternToken>,
+) -> Result<ColorPlaceholder, &'static str> {
+    let slice: &str = lex.slice();
+    let placeholder = match &slice[7..slice.len() - 1] {
+        "r" => ColorPlaceholder::Red,
+        "g" => ColorPlaceholder::Green,
+        "b" => ColorPlaceholder::Blue,
+        "index" => ColorPlaceholder::PaletteIndex,
+        _ => return Err("unknown color placeholder"),
+    };
+    Ok(placeholder)
+}
+
+/// Parses `{text:max_length[:padding]}`. Pads with spaces by default.
+fn parse_as_text_placeholder(
+    lex: &mut Lexer<RawMidiPatternToken>,
+) -> Result<TextPlaceholder, &'static str> {
+    let slice: &str = lex.slice();
+    let mut parts = slice[1..slice.len() - 1].split(':').skip(1);
+    let max_length = parts
+        .next()
+        .and_then(|p| p.parse().ok())
+        .ok_or("invalid text placeholder length")?;
+    let padding = match parts.next() {
+        None => DEFAULT_TEXT_PADDING,
+        Some(p) => u8::from_str_radix(p, 16).map_err(|_| "invalid text placeholder padding")?,
+    };
+    TextPlaceholder::new(max_length, padding)
+}
+
+fn is_system_real_time_byte(byte: u8) -> bool {
+    byte >= 0xF8
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -490,6 +1200,101 @@ mod tests {
         assert_eq!(&pattern.to_string(), "F0 [1111 dcba] F7");
     }
 
+    #[test]
+    fn multi_byte_variable_7_bit() {
+        // Given
+        let pattern: RawMidiPattern = "F0 43 {14} F7".parse().unwrap();
+        // When
+        // Then
+        assert_eq!(pattern.resolution(), 14);
+        assert_eq!(pattern.entries().len(), 4);
+        assert_eq!(
+            pattern.to_bytes(AbsoluteValue::Discrete(Fraction::new(0x1234, 16383))),
+            vec![0xf0, 0x43, 0x24, 0x34, 0xf7]
+        );
+        assert_eq!(
+            pattern.match_and_capture(&[0xf0, 0x43, 0x24, 0x34, 0xf7]),
+            Some(Fraction::new(0x1234, 16383))
+        );
+        assert_eq!(pattern.variable_range(), Some(2..=3));
+        assert_eq!(&pattern.to_string(), "F0 43 {14:be:7} F7");
+    }
+
+    #[test]
+    fn multi_byte_variable_nibbles() {
+        // Given
+        let pattern: RawMidiPattern = "F0 {16:le:4} F7".parse().unwrap();
+        // When
+        // Then
+        assert_eq!(pattern.resolution(), 16);
+        assert_eq!(
+            pattern.to_bytes(AbsoluteValue::Discrete(Fraction::new(0xabcd, 0xffff))),
+            vec![0xf0, 0x0d, 0x0c, 0x0b, 0x0a, 0xf7]
+        );
+        assert_eq!(
+            pattern.match_and_capture(&[0xf0, 0x0d, 0x0c, 0x0b, 0x0a, 0xf7]),
+            Some(Fraction::new(0xabcd, 0xffff))
+        );
+        assert_eq!(
+            pattern.match_and_capture(&[0xf0, 0x1d, 0x0c, 0x0b, 0x0a, 0xf7]),
+            None
+        );
+        assert_eq!(&pattern.to_string(), "F0 {16:le:4} F7");
+    }
+
+    #[test]
+    fn multi_byte_variable_28_bit() {
+        // Given
+        let pattern: RawMidiPattern = "F0 {28:be:7} F7".parse().unwrap();
+        // When
+        // Then
+        assert_eq!(pattern.max_discrete_value(), 0x0fff_ffff);
+        assert_eq!(
+            pattern.to_bytes(AbsoluteValue::Continuous(UnitValue::MAX)),
+            vec![0xf0, 0x7f, 0x7f, 0x7f, 0x7f, 0xf7]
+        );
+        assert!("F0 {29} F7".parse::<RawMidiPattern>().is_err());
+        assert!("F0 {14:be:5} F7".parse::<RawMidiPattern>().is_err());
+    }
+
+    #[test]
+    fn roland_checksum() {
+        // Given
+        let pattern: RawMidiPattern = "F0 41 10 42 12 40 00 7F [0gfe dcba] {checksum:roland:4} F7"
+            .parse()
+            .unwrap();
+        // When
+        // Then
+        assert_eq!(pattern.resolution(), 7);
+        assert_eq!(
+            pattern.to_bytes(AbsoluteValue::Discrete(Fraction::new(0, 127))),
+            vec![0xf0, 0x41, 0x10, 0x42, 0x12, 0x40, 0x00, 0x7f, 0x00, 0x41, 0xf7]
+        );
+        assert_eq!(
+            pattern.to_bytes(AbsoluteValue::Discrete(Fraction::new(5, 127))),
+            vec![0xf0, 0x41, 0x10, 0x42, 0x12, 0x40, 0x00, 0x7f, 0x05, 0x3c, 0xf7]
+        );
+        assert_eq!(
+            pattern.match_and_capture(&[
+                0xf0, 0x41, 0x10, 0x42, 0x12, 0x40, 0x00, 0x7f, 0x05, 0x3c, 0xf7
+            ]),
+            Some(Fraction::new(5, 127))
+        );
+        assert_eq!(
+            pattern.match_and_capture(&[
+                0xf0, 0x41, 0x10, 0x42, 0x12, 0x40, 0x00, 0x7f, 0x05, 0x41, 0xf7
+            ]),
+            None
+        );
+        assert_eq!(
+            &pattern.to_string(),
+            "F0 41 10 42 12 40 00 7F [0gfe dcba] {checksum:roland:4} F7"
+        );
+        assert!("F0 {checksum:yamaha:4} F7"
+            .parse::<RawMidiPattern>()
+            .is_err());
+    }
+
     #[test]
     fn wrong_variable_pattern() {
         let result = "F0[0000dcbaa]F7".parse::<RawMidiPattern>();
@@ -551,4 +1356,242 @@ mod tests {
             Some(Fraction::new(0, 0))
         );
     }
+
+    #[test]
+    fn color_placeholders() {
+        // Given
+        let pattern: RawMidiPattern =
+            "F0 00 20 29 02 0C 03 03 [0gfe dcba] {color:r} {color:g} {color:b} F7"
+                .parse()
+                .unwrap();
+        let index_pattern: RawMidiPattern = "90 [0gfe dcba] {color:index}".parse().unwrap();
+        let color = RawMidiPatternColor {
+            rgb: Some(RgbColor::new(255, 128, 0)),
+            palette_index: Some(5),
+        };
+        // When
+        let values = RawMidiPatternPlaceholderValues {
+            color,
+            ..Default::default()
+        };
+        let event = pattern.to_concrete_midi_event_with_placeholder_values(
+            0,
+            AbsoluteValue::Discrete(Fraction::new(11, 127)),
+            values,
+        );
+        // Then
+        assert_eq!(
+            event.bytes(),
+            &[0xF0, 0x00, 0x20, 0x29, 0x02, 0x0C, 0x03, 0x03, 11, 0x7F, 0x40, 0x00, 0xF7]
+        );
+        assert_eq!(
+            index_pattern
+                .byte_iter_with_placeholder_values(
+                    AbsoluteValue::Discrete(Fraction::new(36, 127)),
+                    values
+                )
+                .collect::<Vec<_>>(),
+            vec![0x90, 36, 5]
+        );
+        assert_eq!(
+            index_pattern.to_bytes(AbsoluteValue::Discrete(Fraction::new(36, 127))),
+            vec![0x90, 36, 0]
+        );
+        assert_eq!(
+            index_pattern.match_and_capture(&[0x90, 36, 0x42]),
+            Some(Fraction::new(36, 127))
+        );
+        assert_eq!(index_pattern.to_string(), "90 [0gfe dcba] {color:index}");
+        assert!("90 {color:alpha}".parse::<RawMidiPattern>().is_err());
+    }
+
+    #[test]
+    fn text_placeholders() {
+        // Given
+        let pattern: RawMidiPattern = "F0 00 66 14 12 00 {text:6} F7".parse().unwrap();
+        let zero_padded_pattern: RawMidiPattern = "F0 {text:4:00} F7".parse().unwrap();
+        let values = RawMidiPatternPlaceholderValues {
+            color: Default::default(),
+            text: "Vol\u{e4}",
+        };
+        // When
+        // Then
+        assert!(pattern.contains_text_placeholders());
+        assert_eq!(pattern.resolution(), 0);
+        assert_eq!(
+            pattern
+                .byte_iter_with_placeholder_values(AbsoluteValue::default(), values)
+                .collect::<Vec<_>>(),
+            vec![0xF0, 0x00, 0x66, 0x14, 0x12, 0x00, b'V', b'o', b'l', b' ', b' ', b' ', 0xF7]
+        );
+        assert_eq!(
+            zero_padded_pattern
+                .byte_iter_with_placeholder_values(
+                    AbsoluteValue::default(),
+                    RawMidiPatternPlaceholderValues {
+                        color: Default::default(),
+                        text: "Reverb",
+                    }
+                )
+                .collect::<Vec<_>>(),
+            vec![0xF0, b'R', b'e', b'v', b'e', 0xF7]
+        );
+        assert_eq!(
+            zero_padded_pattern.to_bytes(AbsoluteValue::default()),
+            vec![0xF0, 0, 0, 0, 0, 0xF7]
+        );
+        assert_eq!(
+            zero_padded_pattern.match_and_capture(&[0xF0, b'a', b'b', b'c', b'd', 0xF7]),
+            Some(Fraction::new(0, 0))
+        );
+        assert_eq!(pattern.to_string(), "F0 00 66 14 12 00 {text:6} F7");
+        assert_eq!(zero_padded_pattern.to_string(), "F0 {text:4:00} F7");
+        assert!("F0 {text:0} F7".parse::<RawMidiPattern>().is_err());
+        assert!("F0 {text:4:80} F7".parse::<RawMidiPattern>().is_err());
+    }
+
+    #[test]
+    fn interleaved_real_time_bytes() {
+        // Given
+        let pattern = "F0 00 20 [0000 dcba] F7"
+            .parse::<RawMidiPattern>()
+            .unwrap()
+            .with_tolerant_matching(true);
+        // When
+        // Then
+        assert_eq!(
+            pattern.match_and_capture_exactly(&[0xF0, 0x00, 0xF8, 0x20, 0x05, 0xF7]),
+            None
+        );
+        assert_eq!(
+            pattern.match_and_capture(&[0xF0, 0x00, 0xF8, 0x20, 0x05, 0xFE, 0xF7]),
+            Some(Fraction::new(5, 15))
+        );
+    }
+
+    #[test]
+    fn running_status() {
+        // Given
+        let pattern = "B0 07 [0gfe dcba]"
+            .parse::<RawMidiPattern>()
+            .unwrap()
+            .with_tolerant_matching(true);
+        // When
+        // Then
+        assert_eq!(pattern.match_and_capture_exactly(&[0x07, 0x40]), None);
+        assert_eq!(
+            pattern.match_and_capture(&[0x07, 0x40]),
+            Some(Fraction::new(64, 127))
+        );
+        assert_eq!(
+            pattern.match_and_capture(&[0x07, 0xF8, 0x40]),
+            Some(Fraction::new(64, 127))
+        );
+        assert_eq!(pattern.match_and_capture(&[0x08, 0x40]), None);
+    }
+
+    #[test]
+    fn tolerant_matching() {
+        // Given
+        let pattern: RawMidiPattern = "F0 00 20 [0000 dcba] F7".parse().unwrap();
+        let tolerant_pattern = pattern.clone().with_tolerant_matching(true);
+        let running_status_pattern: RawMidiPattern = "B0 07 [0gfe dcba]".parse().unwrap();
+        // When
+        // Then
+        assert!(!pattern.is_tolerant());
+        assert!(tolerant_pattern.is_tolerant());
+        assert_eq!(
+            pattern.match_and_capture(&[0xF0, 0x00, 0x20, 0x05, 0xF7]),
+            Some(Fraction::new(5, 15))
+        );
+        assert_eq!(
+            pattern.match_and_capture(&[0xF0, 0x00, 0xF8, 0x20, 0x05, 0xF7]),
+            None
+        );
+        assert_eq!(
+            tolerant_pattern.match_and_capture(&[0xF0, 0x00, 0xF8, 0x20, 0x05, 0xF7]),
+            Some(Fraction::new(5, 15))
+        );
+        assert_eq!(
+            running_status_pattern.match_and_capture(&[0x07, 0x40]),
+            None
+        );
+    }
+
+    #[test]
+    fn tolerant_matching_to_string_and_back() {
+        // Given
+        let pattern: RawMidiPattern = "~ F0 00 20 [0000 dcba] F7".parse().unwrap();
+        let compact_pattern: RawMidiPattern = "~F0 00 20 [0000 dcba] F7".parse().unwrap();
+        // When
+        let string = pattern.to_string();
+        // Then
+        assert!(pattern.is_tolerant());
+        assert_eq!(compact_pattern, pattern);
+        assert_eq!(string, "~ F0 00 20 [0000 dcba] F7");
+        assert_eq!(string.parse::<RawMidiPattern>().unwrap(), pattern);
+        assert_eq!(
+            pattern.match_and_capture(&[0xF0, 0x00, 0xF8, 0x20, 0x05, 0xF7]),
+            Some(Fraction::new(5, 15))
+        );
+        assert!("F0 ~ 00 F7".parse::<RawMidiPattern>().is_err());
+    }
+
+    #[test]
+    fn repetition() {
+        // Given
+        let pattern: RawMidiPattern = "F0 43 (00 7F)*3 [0gfe dcba] F7".parse().unwrap();
+        // When
+        // Then
+        assert_eq!(pattern.entries().len(), 10);
+        assert_eq!(
+            pattern.to_bytes(AbsoluteValue::Continuous(UnitValue::MAX)),
+            vec![0xF0, 0x43, 0x00, 0x7F, 0x00, 0x7F, 0x00, 0x7F, 0x7F, 0xF7]
+        );
+        assert_eq!(
+            pattern
+                .match_and_capture(&[0xF0, 0x43, 0x00, 0x7F, 0x00, 0x7F, 0x00, 0x7F, 0x40, 0xF7]),
+            Some(Fraction::new(64, 127))
+        );
+        assert_eq!(
+            pattern.to_string(),
+            "F0 43 00 7F 00 7F 00 7F [0gfe dcba] F7"
+        );
+        assert!("F0 (00)*0 F7".parse::<RawMidiPattern>().is_err());
+        assert!("F0 (00 F7".parse::<RawMidiPattern>().is_err());
+        assert!("F0 00)*2 F7".parse::<RawMidiPattern>().is_err());
+        assert!("((((00)*255)*255)*255)*255"
+            .parse::<RawMidiPattern>()
+            .is_err());
+    }
+
+    #[test]
+    fn optional_groups() {
+        // Given
+        let pattern: RawMidiPattern = "F0 43 (10 11)? [0gfe dcba] (12)? F7".parse().unwrap();
+        // When
+        // Then
+        assert_eq!(pattern.optional_groups(), &[2..4, 5..6]);
+        assert_eq!(
+            pattern.to_bytes(AbsoluteValue::Continuous(UnitValue::MAX)),
+            vec![0xF0, 0x43, 0x10, 0x11, 0x7F, 0x12, 0xF7]
+        );
+        let value = Some(Fraction::new(64, 127));
+        assert_eq!(
+            pattern.match_and_capture(&[0xF0, 0x43, 0x10, 0x11, 0x40, 0x12, 0xF7]),
+            value
+        );
+        assert_eq!(pattern.match_and_capture(&[0xF0, 0x43, 0x40, 0xF7]), value);
+        assert_eq!(
+            pattern.match_and_capture(&[0xF0, 0x43, 0x40, 0x12, 0xF7]),
+            value
+        );
+        assert_eq!(
+            pattern.match_and_capture(&[0xF0, 0x43, 0x10, 0x40, 0xF7]),
+            None
+        );
+        assert_eq!(pattern.to_string(), "F0 43 (10 11)? [0gfe dcba] (12)? F7");
+        assert!("F0 ((00)? 01)? F7".parse::<RawMidiPattern>().is_err());
+        assert!("F0 ()? F7".parse::<RawMidiPattern>().is_err());
+    }
 }
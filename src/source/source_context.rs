@@ -1,5 +1,66 @@
+use crate::devices::akai::AkaiLedAnimation;
+use crate::devices::{DevicePalette, DeviceQuirks};
+
 /// Context for source-related functions.
-#[derive(Copy, Clone, Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct SourceContext<A> {
     pub additional_script_input: A,
+    /// If set, colors of numeric feedback values are translated into velocities (or CC values)
+    /// of the given device palette. If the device supports exact RGB colors, messages which set
+    /// the exact color are sent instead.
+    pub device_palette: Option<DevicePalette>,
+    /// How LEDs should light up on devices which support animations via palette conventions
+    /// (e.g. Akai APC pads and buttons).
+    pub led_animation: AkaiLedAnimation,
+    /// If set, feedback is adjusted to what the connected device is able to process. If not set,
+    /// the device is assumed to process everything.
+    pub device_capabilities: Option<DeviceCapabilities>,
+    /// Deviations of the connected device from the expected behavior which should be
+    /// compensated when processing control input.
+    pub device_quirks: DeviceQuirks,
+}
+
+/// Describes which kinds of feedback the connected device is able to process.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct DeviceCapabilities {
+    /// Whether the device interprets RGB color components (e.g. in raw MIDI patterns).
+    pub supports_rgb_color: bool,
+    /// Number of characters the device is able to display, `None` if unknown or unlimited.
+    pub text_display_length: Option<u32>,
+    /// Whether the device processes 14-bit control change feedback. If not, only the MSB is sent.
+    pub supports_14_bit_feedback: bool,
+}
+
+impl Default for DeviceCapabilities {
+    fn default() -> Self {
+        Self {
+            supports_rgb_color: true,
+            text_display_length: None,
+            supports_14_bit_feedback: true,
+        }
+    }
+}
+
+impl<A> SourceContext<A> {
+    pub fn supports_rgb_color(&self) -> bool {
+        self.device_capabilities
+            .map_or(true, |c| c.supports_rgb_color)
+    }
+
+    pub fn supports_14_bit_feedback(&self) -> bool {
+        self.device_capabilities
+            .map_or(true, |c| c.supports_14_bit_feedback)
+    }
+
+    /// Cuts the given text so that it fits on the device's text display, if the length of the
+    /// display is known.
+    pub fn fit_text<'a>(&self, text: &'a str) -> &'a str {
+        let Some(max_length) = self.device_capabilities.and_then(|c| c.text_display_length) else {
+            return text;
+        };
+        match text.char_indices().nth(max_length as usize) {
+            None => text,
+            Some((i, _)) => &text[..i],
+        }
+    }
 }
@@ -0,0 +1,281 @@
+use crate::{
+    guess_source_character, AbstractTimestamp, MidiSource, MidiSourceScript, MidiSourceValue,
+    SourceCharacter,
+};
+use helgoboss_midi::{DataType, ShortMessage, StructuredShortMessage, U7};
+use std::time::Duration;
+
+/// Proposes the most likely source configuration for a control element, given the timestamped
+/// values it emitted while the user was interacting with it (e.g. during learning).
+///
+/// - Composite values (14-bit CC, (N)RPN, bank/program change, ...) win over the plain
+///   messages they consist of, so the values should have been passed through the usual
+///   preprocessors (e.g. [`crate::ControlChange14BitPairer`]) before.
+/// - If the values belong to different sources, the most frequent one wins.
+/// - The character is guessed from the values: Relative encodings (see
+///   [`guess_source_character`]), buttons (just off and one on value) and ranges. A button whose
+///   "off" follows its "on" about as late as the next "on" follows the "off" is considered a
+///   toggle-only button. Without meaningful timestamps, buttons are considered momentary.
+///
+/// Returns `None` if none of the values can be used as source.
+pub fn guess_midi_source<S, M, T>(values: &[(T, MidiSourceValue<M>)]) -> Option<MidiSource<S>>
+where
+    S: for<'a> MidiSourceScript<'a>,
+    M: ShortMessage + Copy,
+    T: AbstractTimestamp,
+{
+    let has_composite_values = values.iter().any(|(_, v)| is_composite(v));
+    let candidates: Vec<_> = values
+        .iter()
+        .filter(|(_, v)| !has_composite_values || is_composite(v))
+        .filter_map(|(t, v)| {
+            let source = MidiSource::<S>::from_source_value(v.clone(), None)?;
+            Some((*t, v, source))
+        })
+        .collect();
+    // Sources don't implement `Hash`, so we count by comparing them with each other.
+    let (winner_index, _) = candidates
+        .iter()
+        .enumerate()
+        .map(|(i, (_, _, source))| {
+            let count = candidates.iter().filter(|(_, _, s)| s == source).count();
+            (i, count)
+        })
+        .max_by_key(|(i, count)| (*count, std::cmp::Reverse(*i)))?;
+    let (_, winner_value, winner) = &candidates[winner_index];
+    let events: Vec<_> = candidates
+        .iter()
+        .filter(|(_, _, source)| source == winner)
+        .filter_map(|(t, v, _)| Some((*t, discrete_value(v)?)))
+        .collect();
+    let mut source = MidiSource::from_source_value((*winner_value).clone(), None)?;
+    if let Some(character) = guess_character(&events) {
+        set_custom_character(&mut source, character);
+    }
+    Some(source)
+}
+
+fn is_composite<M: ShortMessage>(value: &MidiSourceValue<M>) -> bool {
+    use MidiSourceValue::*;
+    matches!(
+        value,
+        ParameterNumber(_)
+            | ControlChange14Bit(_)
+            | ControlChangePair(_)
+            | BankProgramChange(_)
+            | Ump(_)
+            | Mpe { .. }
+            | TimeCode(_)
+    )
+}
+
+/// Returns the actual and maximum value of values which can be emitted by sources with custom
+/// character.
+fn discrete_value<M: ShortMessage>(value: &MidiSourceValue<M>) -> Option<(u32, u32)> {
+    use MidiSourceValue::*;
+    let res = match value {
+        Plain(msg) => match msg.to_structured() {
+            StructuredShortMessage::ControlChange { control_value, .. } => {
+                (control_value.get() as u32, 127)
+            }
+            _ => return None,
+        },
+        ControlChange14Bit(msg) => (msg.value().get() as u32, 16383),
+        ControlChangePair(msg) => (msg.value().get() as u32, 16383),
+        ParameterNumber(msg) if msg.data_type() == DataType::DataEntry => {
+            let max = if msg.is_14_bit() { 16383 } else { 127 };
+            (msg.value().get() as u32, max)
+        }
+        _ => return None,
+    };
+    Some(res)
+}
+
+fn guess_character<T: AbstractTimestamp>(events: &[(T, (u32, u32))]) -> Option<SourceCharacter> {
+    let (_, (_, max)) = events.first()?;
+    let mut distinct_values: Vec<_> = events.iter().map(|(_, (v, _))| *v).collect();
+    distinct_values.sort_unstable();
+    distinct_values.dedup();
+    match distinct_values.as_slice() {
+        [0, _] => {
+            let character = if is_toggle_button(events) {
+                SourceCharacter::ToggleButton
+            } else {
+                SourceCharacter::MomentaryButton
+            };
+            return Some(character);
+        }
+        // Button which sends "on" only
+        [v] if *v == *max => return Some(SourceCharacter::MomentaryButton),
+        _ => {}
+    }
+    if *max != 127 {
+        // Relative encoders only exist in 7-bit
+        return Some(SourceCharacter::RangeElement);
+    }
+    let values: Vec<_> = events.iter().map(|(_, (v, _))| U7::new(*v as u8)).collect();
+    guess_source_character(&values)
+}
+
+/// A momentary button is usually released much faster than it's pressed again. With a
+/// toggle-only button, both "off" and "on" are caused by a press, so they take about equally
+/// long.
+fn is_toggle_button<T: AbstractTimestamp>(events: &[(T, (u32, u32))]) -> bool {
+    let mut on_durations = vec![];
+    let mut off_durations = vec![];
+    for w in events.windows(2) {
+        let ((t1, (v1, _)), (t2, (v2, _))) = (w[0], w[1]);
+        let duration = t2 - t1;
+        match (v1 > 0, v2 > 0) {
+            (true, false) => on_durations.push(duration),
+            (false, true) => off_durations.push(duration),
+            _ => {}
+        }
+    }
+    let (Some(on), Some(off)) = (average(&on_durations), average(&off_durations)) else {
+        return false;
+    };
+    on * 2 > off
+}
+
+fn average(durations: &[Duration]) -> Option<Duration> {
+    if durations.is_empty() {
+        return None;
+    }
+    Some(durations.iter().sum::<Duration>() / durations.len() as u32)
+}
+
+fn set_custom_character<S: for<'a> MidiSourceScript<'a>>(
+    source: &mut MidiSource<S>,
+    character: SourceCharacter,
+) {
+    use MidiSource::*;
+    match source {
+        ControlChangeValue {
+            custom_character, ..
+        }
+        | ControlChange14BitValue {
+            custom_character, ..
+        }
+        | ControlChangePairValue {
+            custom_character, ..
+        }
+        | ParameterNumberValue {
+            custom_character, ..
+        }
+        | UmpValue {
+            custom_character, ..
+        }
+        | Raw {
+            custom_character, ..
+        } => *custom_character = character,
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::full_7_bit_interval;
+    use crate::source::test_util::TestMidiSourceScript;
+    use helgoboss_midi::test_util::{channel as ch, controller_number as cn, *};
+    use helgoboss_midi::RawShortMessage;
+    use std::time::Instant;
+
+    type TestMidiSource = MidiSource<TestMidiSourceScript>;
+
+    fn guess(
+        messages: &[(u64, MidiSourceValue<'static, RawShortMessage>)],
+    ) -> Option<TestMidiSource> {
+        let start = Instant::now();
+        let values: Vec<_> = messages
+            .iter()
+            .map(|(millis, v)| (start + Duration::from_millis(*millis), v.clone()))
+            .collect();
+        guess_midi_source(&values)
+    }
+
+    fn plain(msg: RawShortMessage) -> MidiSourceValue<'static, RawShortMessage> {
+        MidiSourceValue::Plain(msg)
+    }
+
+    fn cc_source(character: SourceCharacter) -> Option<TestMidiSource> {
+        Some(TestMidiSource::ControlChangeValue {
+            channel: Some(ch(0)),
+            controller_number: Some(cn(7)),
+            custom_character: character,
+            value_interval: full_7_bit_interval(),
+        })
+    }
+
+    #[test]
+    fn guess_source() {
+        // Given
+        // When
+        // Then
+        assert_eq!(guess(&[]), None);
+        // Knob
+        assert_eq!(
+            guess(&[
+                (0, plain(control_change(0, 7, 10))),
+                (10, plain(control_change(0, 7, 11))),
+                (20, plain(control_change(0, 7, 13))),
+                (30, plain(control_change(0, 7, 12))),
+                (40, plain(control_change(0, 7, 9))),
+            ]),
+            cc_source(SourceCharacter::RangeElement)
+        );
+        // Encoder
+        assert_eq!(
+            guess(&[
+                (0, plain(control_change(0, 7, 1))),
+                (10, plain(control_change(0, 7, 1))),
+                (20, plain(control_change(0, 7, 2))),
+                (30, plain(control_change(0, 7, 127))),
+                (40, plain(control_change(0, 7, 127))),
+            ]),
+            cc_source(SourceCharacter::Encoder1)
+        );
+        // Momentary button, with a stray message of another control element
+        assert_eq!(
+            guess(&[
+                (0, plain(control_change(0, 7, 127))),
+                (100, plain(control_change(0, 7, 0))),
+                (150, plain(control_change(0, 8, 0))),
+                (1000, plain(control_change(0, 7, 127))),
+                (1100, plain(control_change(0, 7, 0))),
+            ]),
+            cc_source(SourceCharacter::MomentaryButton)
+        );
+        // Toggle-only button
+        assert_eq!(
+            guess(&[
+                (0, plain(control_change(0, 7, 127))),
+                (1000, plain(control_change(0, 7, 0))),
+                (2000, plain(control_change(0, 7, 127))),
+                (3000, plain(control_change(0, 7, 0))),
+            ]),
+            cc_source(SourceCharacter::ToggleButton)
+        );
+        // 14-bit fader whose first MSB has been passed through
+        assert_eq!(
+            guess(&[
+                (0, plain(control_change(0, 7, 93))),
+                (
+                    1,
+                    MidiSourceValue::ControlChange14Bit(control_change_14_bit(0, 7, 12000))
+                ),
+                (
+                    10,
+                    MidiSourceValue::ControlChange14Bit(control_change_14_bit(0, 7, 12100))
+                ),
+            ]),
+            Some(TestMidiSource::ControlChange14BitValue {
+                channel: Some(ch(0)),
+                msb_controller_number: Some(cn(7)),
+                custom_character: SourceCharacter::RangeElement,
+                feedback_mode: Default::default(),
+            })
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::AbstractTimestamp;
+use std::time::Duration;
+
+/// Hook for sending sys-ex requests to the device.
+pub trait SysExRequestSender {
+    fn send_sys_ex_request(&mut self, bytes: &[u8]);
+}
+
+impl<F: FnMut(&[u8])> SysExRequestSender for F {
+    fn send_sys_ex_request(&mut self, bytes: &[u8]) {
+        self(bytes)
+    }
+}
+
+/// Request which is sent periodically to devices which don't push parameter changes by
+/// themselves.
+///
+/// Part of [`crate::MidiSource::Raw`]. The device's response is matched against the source's
+/// pattern like any other incoming message. Sending the request is driven by [`SysExPoller`].
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SysExPollRequest {
+    bytes: Vec<u8>,
+    interval: Duration,
+}
+
+impl SysExPollRequest {
+    /// Creates a request which should be sent every time the given interval has elapsed.
+    ///
+    /// The request must be a complete sys-ex message.
+    pub fn new(bytes: Vec<u8>, interval: Duration) -> Result<Self, &'static str> {
+        if bytes.len() < 2 || bytes.first() != Some(&0xF0) || bytes.last() != Some(&0xF7) {
+            return Err("request is not a sys-ex message");
+        }
+        if interval.is_zero() {
+            return Err("polling interval must not be zero");
+        }
+        let request = Self { bytes, interval };
+        Ok(request)
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+}
+
+/// Keeps track of when a [`SysExPollRequest`] has been sent the last time.
+#[derive(Clone, Debug)]
+pub struct SysExPoller<S: AbstractTimestamp> {
+    last_request_timestamp: Option<S>,
+}
+
+impl<S: AbstractTimestamp> Default for SysExPoller<S> {
+    fn default() -> Self {
+        Self {
+            last_request_timestamp: None,
+        }
+    }
+}
+
+impl<S: AbstractTimestamp> SysExPoller<S> {
+    /// Sends the given request via the given sender if it's due.
+    ///
+    /// Should be called regularly. The first call always sends the request. Returns whether the
+    /// request has been sent.
+    pub fn poll(
+        &mut self,
+        request: &SysExPollRequest,
+        now: S,
+        sender: &mut impl SysExRequestSender,
+    ) -> bool {
+        let is_due = self
+            .last_request_timestamp
+            .map_or(true, |t| now - t >= request.interval);
+        if !is_due {
+            return false;
+        }
+        sender.send_sys_ex_request(&request.bytes);
+        self.last_request_timestamp = Some(now);
+        true
+    }
+
+    /// Makes the next call of [`Self::poll`] send the request immediately, e.g. after the device
+    /// has been reconnected.
+    pub fn reset(&mut self) {
+        self.last_request_timestamp = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn poll() {
+        // Given
+        let request = SysExPollRequest::new(
+            vec![0xF0, 0x43, 0x20, 0x01, 0xF7],
+            Duration::from_millis(100),
+        )
+        .unwrap();
+        let mut poller = SysExPoller::default();
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let mut sent_requests: Vec<Vec<u8>> = vec![];
+        let mut sender = |bytes: &[u8]| sent_requests.push(bytes.to_vec());
+        // When
+        let polled = [
+            poller.poll(&request, at(0), &mut sender),
+            poller.poll(&request, at(50), &mut sender),
+            poller.poll(&request, at(100), &mut sender),
+            poller.poll(&request, at(150), &mut sender),
+        ];
+        poller.reset();
+        let polled_after_reset = poller.poll(&request, at(160), &mut sender);
+        // Then
+        assert_eq!(polled, [true, false, true, false]);
+        assert!(polled_after_reset);
+        assert_eq!(sent_requests.len(), 3);
+        assert!(sent_requests
+            .iter()
+            .all(|r| r == &[0xF0, 0x43, 0x20, 0x01, 0xF7]));
+    }
+
+    #[test]
+    fn invalid_request() {
+        // Given
+        let interval = Duration::from_millis(100);
+        // When
+        // Then
+        assert!(SysExPollRequest::new(vec![0xB0, 0x07], interval).is_err());
+        assert!(SysExPollRequest::new(vec![0xF0, 0xF7], Duration::ZERO).is_err());
+    }
+}
@@ -0,0 +1,252 @@
+use crate::Fraction;
+use derive_more::Display;
+use helgoboss_midi::Channel;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use strum::EnumIter;
+
+/// UMP message type of MIDI 2.0 channel voice messages (64-bit).
+const MIDI_2_CHANNEL_VOICE_MESSAGE_TYPE: u8 = 0x4;
+
+/// Kind of MIDI 2.0 channel voice message which carries a high-resolution value.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    EnumIter,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Display,
+    Serialize_repr,
+    Deserialize_repr,
+)]
+#[repr(usize)]
+pub enum UmpMessageKind {
+    #[display(fmt = "Note off")]
+    NoteOff = 0,
+    #[display(fmt = "Note on")]
+    NoteOn = 1,
+    #[display(fmt = "Poly pressure")]
+    PolyPressure = 2,
+    #[default]
+    #[display(fmt = "Control change")]
+    ControlChange = 3,
+    #[display(fmt = "Registered controller (RPN)")]
+    RegisteredController = 4,
+    #[display(fmt = "Assignable controller (NRPN)")]
+    AssignableController = 5,
+    #[display(fmt = "Channel pressure")]
+    ChannelPressure = 6,
+    #[display(fmt = "Pitch bend")]
+    PitchBend = 7,
+}
+
+impl UmpMessageKind {
+    fn from_status(status: u8) -> Option<Self> {
+        use UmpMessageKind::*;
+        let kind = match status {
+            0x8 => NoteOff,
+            0x9 => NoteOn,
+            0xA => PolyPressure,
+            0x2 => RegisteredController,
+            0x3 => AssignableController,
+            0xB => ControlChange,
+            0xD => ChannelPressure,
+            0xE => PitchBend,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    fn status(&self) -> u8 {
+        use UmpMessageKind::*;
+        match self {
+            NoteOff => 0x8,
+            NoteOn => 0x9,
+            PolyPressure => 0xA,
+            RegisteredController => 0x2,
+            AssignableController => 0x3,
+            ControlChange => 0xB,
+            ChannelPressure => 0xD,
+            PitchBend => 0xE,
+        }
+    }
+
+    /// Returns whether messages of this kind are addressed by an index (note number, controller
+    /// number or bank/index).
+    pub fn has_index(&self) -> bool {
+        use UmpMessageKind::*;
+        !matches!(self, ChannelPressure | PitchBend)
+    }
+
+    /// Returns the maximum value. Note velocities have 16 bit, everything else 32 bit.
+    pub fn max_value(&self) -> u32 {
+        use UmpMessageKind::*;
+        match self {
+            NoteOff | NoteOn => u16::MAX as u32,
+            _ => u32::MAX,
+        }
+    }
+}
+
+/// Error which can occur when parsing a Universal MIDI Packet.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, thiserror::Error)]
+pub enum UmpParseError {
+    #[error("UMP too short")]
+    TooShort,
+    #[error("UMP message type {0:#x} not supported")]
+    UnsupportedMessageType(u8),
+    #[error("UMP channel voice status {0:#x} not supported")]
+    UnsupportedStatus(u8),
+}
+
+/// A MIDI 2.0 channel voice message with a high-resolution value, transported as Universal MIDI
+/// Packet (UMP).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct UmpChannelVoiceMessage {
+    group: u8,
+    channel: Channel,
+    kind: UmpMessageKind,
+    index: u16,
+    value: u32,
+}
+
+impl UmpChannelVoiceMessage {
+    /// Creates a message.
+    ///
+    /// The index is the note number, the controller number or - for registered and assignable
+    /// controllers - the bank in the upper 7 bits and the index in the lower 7 bits. It's ignored
+    /// for kinds which don't have an index. The value is clamped to the maximum value of the
+    /// given kind.
+    pub fn new(group: u8, channel: Channel, kind: UmpMessageKind, index: u16, value: u32) -> Self {
+        Self {
+            group: group & 0x0f,
+            channel,
+            kind,
+            index: if kind.has_index() { index & 0x3fff } else { 0 },
+            value: value.min(kind.max_value()),
+        }
+    }
+
+    /// Parses the given UMP words.
+    pub fn from_words(words: &[u32]) -> Result<Self, UmpParseError> {
+        let [word_0, word_1, ..] = *words else {
+            return Err(UmpParseError::TooShort);
+        };
+        let message_type = (word_0 >> 28) as u8;
+        if message_type != MIDI_2_CHANNEL_VOICE_MESSAGE_TYPE {
+            return Err(UmpParseError::UnsupportedMessageType(message_type));
+        }
+        let group = ((word_0 >> 24) & 0x0f) as u8;
+        let status = ((word_0 >> 20) & 0x0f) as u8;
+        let channel = Channel::new(((word_0 >> 16) & 0x0f) as u8);
+        let byte_3 = ((word_0 >> 8) & 0x7f) as u16;
+        let byte_4 = (word_0 & 0x7f) as u16;
+        let kind =
+            UmpMessageKind::from_status(status).ok_or(UmpParseError::UnsupportedStatus(status))?;
+        use UmpMessageKind::*;
+        let (index, value) = match kind {
+            NoteOff | NoteOn => (byte_3, word_1 >> 16),
+            PolyPressure | ControlChange => (byte_3, word_1),
+            RegisteredController | AssignableController => ((byte_3 << 7) | byte_4, word_1),
+            ChannelPressure | PitchBend => (0, word_1),
+        };
+        Ok(Self::new(group, channel, kind, index, value))
+    }
+
+    /// Parses the given bytes (big-endian UMP words).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, UmpParseError> {
+        let word = |i: usize| {
+            let chunk = bytes.get(i * 4..(i + 1) * 4)?;
+            Some(u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        };
+        let (Some(word_0), Some(word_1)) = (word(0), word(1)) else {
+            return Err(UmpParseError::TooShort);
+        };
+        Self::from_words(&[word_0, word_1])
+    }
+
+    /// Returns the UMP words representing this message.
+    pub fn to_words(&self) -> [u32; 2] {
+        use UmpMessageKind::*;
+        let (byte_3, byte_4) = match self.kind {
+            RegisteredController | AssignableController => (self.index >> 7, self.index & 0x7f),
+            _ => (self.index & 0x7f, 0),
+        };
+        let word_0 = (MIDI_2_CHANNEL_VOICE_MESSAGE_TYPE as u32) << 28
+            | (self.group as u32) << 24
+            | (self.kind.status() as u32) << 20
+            | (self.channel.get() as u32) << 16
+            | (byte_3 as u32) << 8
+            | byte_4 as u32;
+        let word_1 = match self.kind {
+            NoteOff | NoteOn => self.value << 16,
+            _ => self.value,
+        };
+        [word_0, word_1]
+    }
+
+    pub fn group(&self) -> u8 {
+        self.group
+    }
+
+    pub fn channel(&self) -> Channel {
+        self.channel
+    }
+
+    pub fn kind(&self) -> UmpMessageKind {
+        self.kind
+    }
+
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
+    /// Returns the value as fraction of the maximum value, without losing any precision.
+    pub fn value_fraction(&self) -> Fraction {
+        Fraction::new(self.value, self.kind.max_value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        // Given
+        let words = [0x40b5_0700, 0x8000_0001];
+        // When
+        let msg = UmpChannelVoiceMessage::from_words(&words).unwrap();
+        // Then
+        assert_eq!(msg.group(), 0);
+        assert_eq!(msg.channel(), Channel::new(5));
+        assert_eq!(msg.kind(), UmpMessageKind::ControlChange);
+        assert_eq!(msg.index(), 7);
+        assert_eq!(msg.value(), 0x8000_0001);
+        assert_eq!(msg.value_fraction(), Fraction::new(0x8000_0001, u32::MAX));
+        assert_eq!(msg.to_words(), words);
+        let bytes = [0x41, 0x92, 0x3c, 0x00, 0xff, 0xff, 0x00, 0x00];
+        let note_on = UmpChannelVoiceMessage::from_bytes(&bytes).unwrap();
+        assert_eq!(note_on.group(), 1);
+        assert_eq!(note_on.kind(), UmpMessageKind::NoteOn);
+        assert_eq!(note_on.index(), 60);
+        assert_eq!(note_on.value_fraction(), Fraction::new_max(u16::MAX as u32));
+        assert_eq!(
+            UmpChannelVoiceMessage::from_words(&[0x2090_3c7f]),
+            Err(UmpParseError::TooShort)
+        );
+        assert_eq!(
+            UmpChannelVoiceMessage::from_words(&[0x2090_3c7f, 0]),
+            Err(UmpParseError::UnsupportedMessageType(0x2))
+        );
+    }
+}